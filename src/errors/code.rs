@@ -0,0 +1,102 @@
+//! Module to define the codes of the diagnostics
+//!
+//! This module implements the [`ErrorCode`] enum, that identifies a
+//! diagnostic independently from its message.
+
+use core::fmt;
+
+/// Stable code of a diagnostic
+///
+/// The messages of the diagnostics contain the names and values found in the
+/// source code, so they can't be used to recognise a diagnostic. The code
+/// identifies the kind of the diagnostic, and can be used to suppress or
+/// filter specific diagnostics. It is displayed like `E0015`.
+///
+/// The number of a code never changes: new codes are given the next number.
+/// Not all the diagnostics have a code yet.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::{ErrorCode, Location, lex_file};
+///
+/// let (_, errors) = lex_file("int m@in() { }", &mut Location::from("")).into_parts();
+/// assert!(errors[0].get_code() == Some(ErrorCode::UnsupportedCharacter));
+/// assert!(ErrorCode::UnsupportedCharacter.to_string() == "E0015");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// Hexadecimal digit after a full hexadecimal escape sequence, like the
+    /// `c` of `"\xabc"`.
+    AmbiguousHexEscape,
+    /// Line continuation `\` on the last line of the file.
+    BackslashAtEndOfFile,
+    /// Char literal with more than one character, like `'ab'`.
+    CharTooLong,
+    /// Escape character `\` outside a string or a char.
+    EscapeOutsideString,
+    /// Escape sequence with too few digits, like `\u12`.
+    EscapeTooShort,
+    /// Escape sequence `\e`, that is a GNU extension.
+    GnuEscape,
+    /// Character that can't be escaped, like `\k`.
+    InvalidEscape,
+    /// Octal escape sequence with a non-octal digit.
+    InvalidOctalDigit,
+    /// Unicode escape sequence that isn't a valid unicode character.
+    InvalidUnicodeEscape,
+    /// Universal character name in an identifier, that designates a character
+    /// that isn't allowed there, like `\u0020`.
+    InvalidUniversalChar,
+    /// Line comment `//` before C99.
+    LineCommentBeforeC99,
+    /// Octal escape sequence whose value doesn't fit in a char, like `\777`.
+    OctalEscapeOutOfRange,
+    /// Encoding prefix that was introduced after the standard, like `u` in
+    /// C99.
+    PrefixNotInStandard,
+    /// White space between a line continuation `\` and the end of the line.
+    SpaceAfterBackslash,
+    /// Trigraph, like `??=`, that was translated.
+    TranslatedTrigraph,
+    /// Character that isn't allowed in the source code, like `@`.
+    UnsupportedCharacter,
+    /// Prefix `u8` on a char before C23.
+    Utf8CharPrefix,
+}
+
+impl ErrorCode {
+    /// Returns the number of the code.
+    #[inline]
+    #[must_use]
+    pub const fn number(self) -> u32 {
+        match self {
+            Self::AmbiguousHexEscape => 1,
+            Self::BackslashAtEndOfFile => 2,
+            Self::CharTooLong => 3,
+            Self::EscapeOutsideString => 4,
+            Self::EscapeTooShort => 5,
+            Self::GnuEscape => 6,
+            Self::InvalidEscape => 7,
+            Self::InvalidOctalDigit => 8,
+            Self::InvalidUnicodeEscape => 9,
+            Self::InvalidUniversalChar => 17,
+            Self::LineCommentBeforeC99 => 10,
+            Self::OctalEscapeOutOfRange => 11,
+            Self::PrefixNotInStandard => 12,
+            Self::SpaceAfterBackslash => 13,
+            Self::TranslatedTrigraph => 14,
+            Self::UnsupportedCharacter => 15,
+            Self::Utf8CharPrefix => 16,
+        }
+    }
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for ErrorCode {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{:04}", self.number())
+    }
+}
@@ -4,7 +4,9 @@
 
 use core::fmt;
 
+use super::code::ErrorCode;
 use crate::errors::api::Location;
+use crate::lexer::api::Token;
 
 /// Struct to store the error information
 ///
@@ -26,39 +28,292 @@ use crate::errors::api::Location;
 /// [`Res`](super::result::Res).
 #[derive(Debug)]
 pub struct CompileError {
+    /// Code of the error, to recognise the kind of the error without parsing
+    /// its message
+    code: Option<ErrorCode>,
     /// Severity of the error
     err_lvl: ErrorLevel,
     /// Location of the error in the C source file
     location: Location,
     /// Error message to be displayed to the user
     message: String,
+    /// Secondary location that helps to understand the error, like the
+    /// opening bracket of a mismatched closing bracket
+    note: Option<Box<Self>>,
+    /// Index of the token the error points at, in the list of tokens given to
+    /// the parser, if the error originates from a token
+    token: Option<usize>,
 }
 
 impl CompileError {
-    /// Returns the referenced data of a `CompileError`.
-    pub(super) fn get_values(&self) -> (&Location, &str, String) {
-        (&self.location, &self.message, self.err_lvl.to_string())
+    /// Returns the code of the error, if it has one.
+    ///
+    /// See [`ErrorCode`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{ErrorCode, Location, lex_file};
+    ///
+    /// let (_, errors) = lex_file("x = 'ab';", &mut Location::from("")).into_parts();
+    /// assert!(errors[0].get_code() == Some(ErrorCode::CharTooLong));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn get_code(&self) -> Option<ErrorCode> {
+        self.code
+    }
+
+    /// Returns the severity of the error.
+    pub(super) const fn get_level(&self) -> ErrorLevel {
+        self.err_lvl
+    }
+
+    /// Returns the location of the error.
+    pub(crate) const fn get_location(&self) -> &Location {
+        &self.location
+    }
+
+    /// Returns a mutable reference to the location of the error.
+    pub(crate) const fn get_location_mut(&mut self) -> &mut Location {
+        &mut self.location
+    }
+
+    /// Returns the message of the error.
+    pub(super) fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the note attached to the error, if any.
+    pub(super) fn get_note(&self) -> Option<&Self> {
+        self.note.as_deref()
+    }
+
+    /// Returns the index of the token the error points at, if the error
+    /// originates from a token.
+    ///
+    /// The index refers to the list of tokens that was given to the parser.
+    /// Errors that don't originate from a token, like the lexer errors, don't
+    /// have an index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, lex_file, parse_tokens};
+    ///
+    /// let (tokens, _) = lex_file("x = 1 2;", &mut Location::from("")).into_parts();
+    /// let (_, errors) = parse_tokens(tokens).into_parts();
+    /// assert!(errors[0].get_token_index() == Some(3));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn get_token_index(&self) -> Option<usize> {
+        self.token
     }
 
-    /// Checks if the error is of severity [`ErrorLevel::Failure`].
-    pub(crate) fn is_failure(&self) -> bool {
+    /// Checks if the error is of severity [`ErrorLevel::Failure`], displayed as
+    /// `error`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, lex_file};
+    ///
+    /// let (_, errors) = lex_file("int m@in() { }", &mut Location::from("")).into_parts();
+    /// assert!(errors[0].is_error());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_error(&self) -> bool {
         self.err_lvl == ErrorLevel::Failure
     }
+
+    /// Promotes the error to a more severe level.
+    ///
+    /// This is used in pedantic mode, to turn the warnings about non-standard
+    /// code into errors. The level is never lowered: promoting an error to a
+    /// warning keeps it an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{ErrorLevel, Location, lex_file};
+    ///
+    /// let (_, errors) = lex_file("_Bool b;", &mut Location::from("")).into_parts();
+    /// let warning = errors.into_iter().next().unwrap();
+    /// assert!(!warning.is_error());
+    /// let error = warning.promote_to(ErrorLevel::Failure);
+    /// assert!(error.is_error());
+    /// assert!(error.promote_to(ErrorLevel::Warning).is_error());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn promote_to(mut self, level: ErrorLevel) -> Self {
+        if level.severity() > self.err_lvl.severity() {
+            self.err_lvl = level;
+        }
+        self
+    }
+
+    /// Renders the error into the lines displayed to the user.
+    ///
+    /// The lines are the header with the location and the message, the line of
+    /// source code, and the carets under the erroneous token. This is used by
+    /// [`Res::get_displayed_errors`](super::result::Res::get_displayed_errors),
+    /// but can be used to display the errors in another format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, lex_file};
+    ///
+    /// let content = "int m@in() { }";
+    /// let (_, errors) = lex_file(content, &mut Location::from("filename.c")).into_parts();
+    /// let lines = errors[0].render_lines(&[("filename.c".to_owned(), content)], "lexer");
+    /// assert!(
+    ///     lines
+    ///         == [
+    ///             "filename.c:1:6: lexer error[E0015]: Character '@' not supported.",
+    ///             "    1 | int m@in() { }",
+    ///             "             ^",
+    ///         ]
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the file or the line of the error doesn't exist in `files`.
+    #[inline]
+    #[must_use]
+    pub fn render_lines(&self, files: &[(String, &str)], err_type: &str) -> Vec<String> {
+        let (filename, line_nb, column_nb, _) = self.location.get_values();
+        let content = files
+            .iter()
+            .find(|(name, _)| name == filename)
+            .map(|(_, content)| content)
+            .expect("Never happens: File of error doesn't exist");
+        let code_line = content.lines().nth(safe_decrement(line_nb)).unwrap_or_else(|| {
+            panic!("Never happens: given line of file that doesn't exist: {filename}:{line_nb}:{column_nb} (for {err_type})")
+        });
+        let mut lines = self.render_with_code_line(code_line, err_type);
+        if let Some(note) = &self.note {
+            lines.extend(note.render_lines(files, err_type));
+        }
+        lines
+    }
+
+    /// Renders the error, with the line of code of the error already fetched
+    /// from the file.
+    ///
+    /// The note isn't rendered. See [`CompileError::render_lines`].
+    pub(super) fn render_with_code_line(&self, code_line: &str, err_type: &str) -> Vec<String> {
+        let (filename, line_nb, column_nb, length) = self.location.get_values();
+        let err_lvl = &self.err_lvl;
+        let code = self
+            .code
+            .map_or_else(String::new, |code| format!("[{code}]"));
+        let message = &self.message;
+        let mut too_long = false;
+        let col = safe_decrement(column_nb);
+        let under_spaces = " ".repeat(8usize.checked_add(col).unwrap_or_else(|| {
+            too_long = true;
+            col
+        }));
+        let under_tilde = "~".repeat(safe_decrement(length));
+        let mut lines = vec![
+            format!("{filename}:{line_nb}:{column_nb}: {err_type} {err_lvl}{code}: {message}"),
+            format!("{line_nb:5} | {code_line}"),
+            format!("{under_spaces}^{under_tilde}"),
+        ];
+        if too_long {
+            lines.push(format!(
+                "{filename}:{line_nb}:{column_nb}: format warning: This line of code exceeds the maximum size of {}. Consider refactoring your code. {line_nb:5} | {code_line}",
+                usize::MAX
+            ));
+            lines.push(format!("{under_spaces}^{under_tilde}"));
+        }
+        lines
+    }
+
+    /// Returns the token the error points at, from the list of tokens that was
+    /// given to the parser.
+    ///
+    /// This is useful to apply fixes that replace the erroneous token. See
+    /// [`CompileError::get_token_index`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, TokenValue, lex_file, parse_tokens};
+    ///
+    /// let (tokens, _) = lex_file("x = 1 2;", &mut Location::from("")).into_parts();
+    /// let (_, errors) = parse_tokens(tokens.clone()).into_parts();
+    /// let token = errors[0].resolve_token(&tokens).unwrap();
+    /// assert!(token.get_value().to_source() == "2");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn resolve_token<'tokens>(&self, tokens: &'tokens [Token]) -> Option<&'tokens Token> {
+        tokens.get(self.token?)
+    }
+
+    /// Stores the index of the token the error points at, if there is one.
+    ///
+    /// `locations` are the locations of the tokens given to the parser, in the
+    /// same order.
+    pub(crate) fn set_token_index(&mut self, locations: &[Location]) {
+        self.token = locations
+            .iter()
+            .position(|location| location.starts_like(&self.location));
+    }
+
+    /// Sets the code of the error.
+    pub(crate) const fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Attaches a note to the error, to point to a secondary location.
+    pub(crate) fn with_note(mut self, location: Location, message: String) -> Self {
+        self.note = Some(Box::new(Self::from((location, message, ErrorLevel::Note))));
+        self
+    }
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for CompileError {
+    /// Displays the error on one line, without the source code.
+    ///
+    /// Use [`Res::get_displayed_errors`](super::result::Res::get_displayed_errors)
+    /// or [`CompileError::render_lines`] for the full error.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (filename, line_nb, column_nb, _) = self.location.get_values();
+        write!(f, "{filename}:{line_nb}:{column_nb}: {}", self.err_lvl)?;
+        if let Some(code) = self.code {
+            write!(f, "[{code}]")?;
+        }
+        write!(f, ": {}", self.message)
+    }
 }
 
 impl From<(Location, String, ErrorLevel)> for CompileError {
     #[inline]
     fn from((location, message, err_lvl): (Location, String, ErrorLevel)) -> Self {
         Self {
+            code: None,
             err_lvl,
             location,
             message,
+            note: None,
+            token: None,
         }
     }
 }
 
 /// Different levels of errors
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
 pub enum ErrorLevel {
     /// The compiler stops compiling the current block and fails.
     ///
@@ -69,6 +324,12 @@ pub enum ErrorLevel {
     /// are independent from the original location of the error. Not all of the
     /// independent parts are compiled though.
     Failure,
+    /// Additional information on another error.
+    ///
+    /// # Examples
+    ///
+    /// - the location of the opening bracket of a mismatched closing bracket
+    Note,
     /// Found a bad practice.
     ///
     /// # Examples
@@ -88,13 +349,33 @@ pub enum ErrorLevel {
     Warning,
 }
 
+impl ErrorLevel {
+    /// Returns the severity of the level, from the notes to the failures.
+    const fn severity(self) -> u32 {
+        match self {
+            Self::Note => 0,
+            Self::Suggestion => 1,
+            Self::Warning => 2,
+            Self::Failure => 3,
+        }
+    }
+}
+
 #[expect(clippy::min_ident_chars)]
 impl fmt::Display for ErrorLevel {
+    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Failure => "error".fmt(f),
+            Self::Note => "note".fmt(f),
             Self::Suggestion => "suggestion".fmt(f),
             Self::Warning => "warning".fmt(f),
         }
     }
 }
+
+/// Decrements a value of 1
+pub(super) const fn safe_decrement(val: usize) -> usize {
+    val.checked_sub(1)
+        .expect("line, col, len are initialised at 1, then incremented")
+}
@@ -1,12 +1,14 @@
 //! Module to display the errors
 //!
 //! Implements the [`display_errors`] function that converts the
-//! [`CompileError`] to a user-readable error string.
+//! [`CompileError`] to a user-readable error string, and the
+//! [`display_errors_json`] function that converts them to JSON for other
+//! tools.
 
 use core::fmt::Write as _;
-use std::collections::HashMap;
+use core::iter;
 
-use super::compile::CompileError;
+use super::compile::{CompileError, safe_decrement};
 
 /// Transforms [`CompileError`] into a human-readable string
 ///
@@ -17,49 +19,87 @@ use super::compile::CompileError;
 ///
 /// Returns an error when the writing on the string buffer fails.
 pub(super) fn display_errors(
-    errors: &Vec<CompileError>,
+    errors: &[CompileError],
     files: &[(String, &str)],
     err_type: &str,
 ) -> Result<String, ()> {
-    let mut files_state: HashMap<String, Vec<&str>> = HashMap::new();
     let mut res = String::new();
-    for (filename, content) in files {
-        files_state.insert(filename.to_owned(), content.lines().collect());
+    // lines of each file, split only once for all the errors of that file
+    let mut line_indexes: Vec<(&str, Vec<&str>)> = vec![];
+    for error in errors
+        .iter()
+        .flat_map(|error| iter::successors(Some(error), |err| err.get_note()))
+    {
+        let (filename, line_nb, column_nb, _) = error.get_location().get_values();
+        let index = line_indexes
+            .iter()
+            .position(|(name, _)| *name == filename)
+            .unwrap_or_else(|| {
+                let content = files
+                    .iter()
+                    .find(|(name, _)| name == filename)
+                    .map(|(_, content)| content)
+                    .expect("Never happens: File of error doesn't exist");
+                line_indexes.push((filename, content.lines().collect()));
+                safe_decrement(line_indexes.len())
+            });
+        let code_line = line_indexes
+            .get(index)
+            .and_then(|(_, lines)| lines.get(safe_decrement(line_nb)))
+            .unwrap_or_else(|| {
+                panic!("Never happens: given line of file that doesn't exist: {filename}:{line_nb}:{column_nb} (for {err_type})")
+            });
+        for line in error.render_with_code_line(code_line, err_type) {
+            writeln!(res, "{line}").map_err(|_| ())?;
+        }
     }
-    for error in errors {
-        let (location, message, err_lvl) = error.get_values();
-        let (filename, line_nb, column_nb, length) = location.get_values();
-        let code_lines = files_state
-            .get(filename)
-            .expect("Never happens: File of error doesn't exist");
-        let code_line = code_lines.get(safe_decrement(line_nb)).unwrap_or_else(|| {
-            panic!("Never happens: given line of file that doesn't exist: {filename}:{line_nb}:{column_nb} (for {err_type})")
-        });
-        let mut too_long = false;
-        let col = safe_decrement(column_nb);
-        let under_spaces = " ".repeat(8usize.checked_add(col).unwrap_or_else(|| {
-            too_long = true;
-            col
-        }));
-        let under_tilde = "~".repeat(safe_decrement(length));
+    Ok(res)
+}
+
+/// Transforms [`CompileError`] into JSON diagnostics, one object per line.
+///
+/// The notes are displayed as separate diagnostics, after their error, with
+/// the `note` level. See
+/// [`Res::get_json_errors`](super::result::Res::get_json_errors) for extra
+/// information and examples.
+pub(super) fn display_errors_json(errors: &[CompileError]) -> String {
+    let mut res = String::new();
+    for error in errors
+        .iter()
+        .flat_map(|error| iter::successors(Some(error), |err| err.get_note()))
+    {
+        let (filename, line_nb, column_nb, length) = error.get_location().get_values();
         writeln!(
             res,
-            "{filename}:{line_nb}:{column_nb}: {err_type} {err_lvl}: {message}\n{line_nb:5} | {code_line}\n{under_spaces}^{under_tilde}"
-        ).map_err(|_| ())?;
-        if too_long {
-            writeln!(
-                res,
-                "{filename}:{line_nb}:{column_nb}: format warning: This line of code exceeds the maximum size of {}. Consider refactoring your code. {line_nb:5} | {code_line}\n{under_spaces}^{under_tilde}",
-                usize::MAX
-            )
-            .map_err(|_| ())?;
-        }
+            r#"{{"file":{},"line":{line_nb},"col":{column_nb},"length":{length},"level":"{}","code":{},"message":{}}}"#,
+            json_string(filename),
+            error.get_level(),
+            error
+                .get_code()
+                .map_or_else(|| "null".to_owned(), |code| format!(r#""{code}""#)),
+            json_string(error.get_message())
+        )
+        .expect("writing to a string never fails");
     }
-    Ok(res)
+    res
 }
 
-/// Decrements a value of 1
-const fn safe_decrement(val: usize) -> usize {
-    val.checked_sub(1)
-        .expect("line, col, len are initialised at 1, then incremented")
+/// Returns a string as a JSON string literal, with its quotes.
+fn json_string(value: &str) -> String {
+    let mut res = String::from('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            _ if ch.is_control() => {
+                write!(res, "\\u{:04x}", u32::from(ch)).expect("writing to a string never fails");
+            }
+            _ => res.push(ch),
+        }
+    }
+    res.push('"');
+    res
 }
@@ -16,6 +16,7 @@ use super::compile::{CompileError, ErrorLevel};
 /// In order to respect the click links from terminals, the line and column of
 /// a file start at 1 and not 0.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Location {
     /// Abscissa of the begging of the erroneous token.
     col: usize,
@@ -28,6 +29,41 @@ pub struct Location {
 }
 
 impl Location {
+    /// Extends the location until the end of another location.
+    ///
+    /// This is used to merge two tokens into one. If the locations aren't on
+    /// the same line, the location is left unchanged.
+    pub(crate) fn extend_to(&mut self, end: &Self) {
+        if self.file == end.file
+            && self.line == end.line
+            && let Some(length) = end
+                .col
+                .checked_add(end.length)
+                .and_then(|end_col| end_col.checked_sub(self.col))
+        {
+            self.length = length;
+        }
+    }
+
+    /// Returns the source file of a `Location`.
+    pub(crate) fn get_file(&self) -> &str {
+        &self.file
+    }
+
+    /// Returns the line of a `Location`.
+    pub(crate) const fn get_line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the characters of a line of the source that are covered by the
+    /// `Location`.
+    pub(crate) fn get_span(&self, line: &str) -> String {
+        line.chars()
+            .skip(self.col.saturating_sub(1))
+            .take(self.length)
+            .collect()
+    }
+
     /// Returns the referenced data of a `Location`.
     pub(super) fn get_values(&self) -> (&str, usize, usize, usize) {
         (&self.file, self.line, self.col, self.length)
@@ -36,30 +72,42 @@ impl Location {
     /// Increments column of location by 1
     ///
     /// This is used by lexer when parsing every character of the C file.
-    pub(crate) fn incr_col(&mut self) -> CompileRes<()> {
-        self.col = self.col.checked_add(1).ok_or_else(|| {
-            self.to_failure(format!(
-                "This line of code exceeds the maximum numbers of columns ({}).
-        Consider refactoring your code.",
-                usize::MAX
-            ))
-        })?;
-        Ok(())
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the location unchanged, if the line has
+    /// more than `max_columns` characters.
+    pub(crate) fn incr_col(&mut self, max_columns: usize) -> CompileRes<()> {
+        match self.col.checked_add(1) {
+            Some(col) if col.saturating_sub(1) <= max_columns => {
+                self.col = col;
+                Ok(())
+            }
+            Some(_) | None => Err(self.to_failure(format!(
+                "This line of code exceeds the maximum number of columns ({max_columns}). Consider refactoring your code."
+            ))),
+        }
     }
 
     /// Increments line of location by 1
     ///
     /// This is used by lexer when parsing every line of the C file.
-    pub(crate) fn incr_line(&mut self) -> CompileRes<()> {
-        self.line = self.line.checked_add(1).ok_or_else(|| {
-            self.to_failure(format!(
-                "The file exceeds the maximum number lines ({}). Consider refactoring
-        your code.",
-                usize::MAX
-            ))
-        })?;
-        self.col = 1;
-        Ok(())
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the location unchanged, if the file has
+    /// more than `max_lines` lines.
+    pub(crate) fn incr_line(&mut self, max_lines: usize) -> CompileRes<()> {
+        match self.line.checked_add(1) {
+            Some(line) if line <= max_lines => {
+                self.line = line;
+                self.col = 1;
+                Ok(())
+            }
+            Some(_) | None => Err(self.to_failure(format!(
+                "The file exceeds the maximum number of lines ({max_lines}). Consider refactoring your code."
+            ))),
+        }
     }
 
     /// Creates an error from a location without cloning
@@ -79,11 +127,38 @@ impl Location {
         }
     }
 
+    /// Checks if another location starts right after the end of this one,
+    /// on the same line, without any character in between.
+    pub(crate) fn is_followed_by(&self, next: &Self) -> bool {
+        self.file == next.file
+            && self.line == next.line
+            && self.col.checked_add(self.length) == Some(next.col)
+    }
+
+    /// Moves the location to another line, after lines were removed and
+    /// added before it.
+    ///
+    /// This is used when re-lexing a part of a file, to update the locations
+    /// of the tokens after the edited part.
+    pub(crate) const fn shift_lines(&mut self, removed: usize, added: usize) {
+        self.line = self.line.saturating_add(added).saturating_sub(removed);
+    }
+
+    /// Checks if two locations start at the same character of the same file.
+    pub(crate) fn starts_like(&self, other: &Self) -> bool {
+        self.file == other.file && self.line == other.line && self.col == other.col
+    }
+
     /// Creates an error by cloning the location.
     pub(crate) fn to_failure(&self, msg: String) -> CompileError {
         CompileError::from((self.to_owned(), msg, ErrorLevel::Failure))
     }
 
+    /// Returns the line and the column of the location, as `line:col`.
+    pub(crate) fn to_line_col(&self) -> String {
+        format!("{}:{}", self.line, self.col)
+    }
+
     /// Creates an suggestion by cloning the location.
     pub(crate) fn to_suggestion(&self, msg: String) -> CompileError {
         CompileError::from((self.to_owned(), msg, ErrorLevel::Suggestion))
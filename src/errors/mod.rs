@@ -9,11 +9,13 @@ pub mod api {
 
     #![allow(clippy::pub_use)]
 
-    pub use super::compile::CompileError;
+    pub use super::code::ErrorCode;
+    pub use super::compile::{CompileError, ErrorLevel};
     pub use super::location::Location;
     pub use super::result::{CompileRes, Res, SingleRes};
 }
 
+mod code;
 mod compile;
 mod display;
 mod location;
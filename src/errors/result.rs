@@ -7,7 +7,7 @@ use alloc::vec;
 use core::{convert, ops};
 
 use super::compile::CompileError;
-use super::display::display_errors;
+use super::display::{display_errors, display_errors_json};
 
 /// [`Result`] alias for [`CompileError`]
 pub type CompileRes<T> = Result<T, CompileError>;
@@ -37,6 +37,26 @@ impl<T> Res<T> {
         mutable
     }
 
+    /// Returns the errors of a [`Res`], without consuming it.
+    ///
+    /// The result can still be used afterwards, with [`Res::result`] or
+    /// [`Res::into_parts`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, lex_file};
+    ///
+    /// let res = lex_file("x = 3 @", &mut Location::from(""));
+    /// assert!(res.errors().len() == 1);
+    /// assert!(res.result().len() == 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn errors(&self) -> &[CompileError] {
+        self.errors.as_slice()
+    }
+
     /// Checks if the ``errors`` field is empty
     ///
     /// # Examples
@@ -70,7 +90,7 @@ impl<T> Res<T> {
     /// let content = "int m@in() { }";
     /// let res = lex_file(&content, &mut Location::from("filename.c"));
     /// let errors = res.get_displayed_errors(&[("filename.c".to_owned(), content)], "lexer");
-    /// let expected = "filename.c:1:6: lexer error: Character '@' not supported.
+    /// let expected = "filename.c:1:6: lexer error[E0015]: Character '@' not supported.
     ///     1 | int m@in() { }
     ///              ^
     /// ";
@@ -87,9 +107,36 @@ impl<T> Res<T> {
             .expect("Buffer overflow, failed to fetch errors")
     }
 
+    /// Returns all the errors as JSON diagnostics, for IDEs and other tools.
+    ///
+    /// # Returns
+    ///
+    /// A [`String`] with one JSON object per line, for each error and each
+    /// note, with the fields `file`, `line`, `col`, `length`, `level`, `code`
+    /// and `message`. The `level` is one of `error`, `warning`, `suggestion`
+    /// and `note`, and the `code` is the
+    /// [`ErrorCode`](super::code::ErrorCode) of the error, like `"E0015"`, or
+    /// `null` if it has none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, lex_file};
+    ///
+    /// let res = lex_file("int m@in() { }", &mut Location::from("filename.c"));
+    /// let expected = r#"{"file":"filename.c","line":1,"col":6,"length":1,"level":"error","code":"E0015","message":"Character '@' not supported."}
+    /// "#;
+    /// assert!(res.get_json_errors() == expected);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get_json_errors(&self) -> String {
+        display_errors_json(&self.errors)
+    }
+
     /// Checks if the [`Res`] contains critical failures.
     pub(crate) fn has_failures(&self) -> bool {
-        self.errors.iter().any(CompileError::is_failure)
+        self.errors.iter().any(CompileError::is_error)
     }
 
     /// Returns the errors of a [`Res`]
@@ -99,6 +146,36 @@ impl<T> Res<T> {
         self.errors
     }
 
+    /// Returns the value and the errors of a [`Res`].
+    ///
+    /// This is the non-panicking counterpart of
+    /// [`Res::unwrap_or_display`]: the errors are given to the caller, who can
+    /// choose how to handle them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, TokenValue, lex_file};
+    ///
+    /// let (tokens, errors) = lex_file("x = 3 @", &mut Location::from("")).into_parts();
+    /// assert!(tokens.len() == 3);
+    /// assert!(tokens[0].get_value() == &TokenValue::Ident("x".to_owned()));
+    /// assert!(errors.len() == 1);
+    /// ```
+    #[inline]
+    pub fn into_parts(self) -> (T, Vec<CompileError>) {
+        (self.result, self.errors)
+    }
+
+    /// Returns the value of a [`Res`], without consuming it.
+    ///
+    /// See [`Res::errors`] to borrow the errors.
+    #[inline]
+    #[must_use]
+    pub const fn result(&self) -> &T {
+        &self.result
+    }
+
     /// Prints all the errors to the user.
     ///
     /// # Returns
@@ -183,16 +260,31 @@ impl<T: Default> ops::FromResidual<Result<convert::Infallible, CompileError>> fo
     }
 }
 
+/// Compat residual wrapper.
+#[derive(Debug)]
+pub struct ResResidual(Vec<CompileError>);
+
+impl<T: Default> ops::Residual<T> for ResResidual {
+    type TryType = Res<T>;
+}
+
+impl<T: Default> ops::FromResidual<ResResidual> for Res<T> {
+    #[inline]
+    fn from_residual(residual: ResResidual) -> Self {
+        Self::from_errors(residual.0)
+    }
+}
+
 impl<T: Default> ops::Try for Res<T> {
     type Output = T;
-    type Residual = Vec<CompileError>;
+    type Residual = ResResidual;
 
     #[inline]
     fn branch(self) -> ops::ControlFlow<Self::Residual, Self::Output> {
         if self.errors.is_empty() {
             ops::ControlFlow::Continue(self.result)
         } else {
-            ops::ControlFlow::Break(self.errors)
+            ops::ControlFlow::Break(ResResidual(self.errors))
         }
     }
 
@@ -288,6 +380,10 @@ impl<T: Default> ops::FromResidual<CompileError> for SingleRes<T> {
     }
 }
 
+impl<T: Default> ops::Residual<T> for CompileError {
+    type TryType = SingleRes<T>;
+}
+
 impl<T: Default> ops::Try for SingleRes<T> {
     type Output = T;
     type Residual = CompileError;
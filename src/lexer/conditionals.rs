@@ -0,0 +1,784 @@
+//! Module to handle the conditional inclusion directives: `#if`, `#ifdef`,
+//! `#ifndef`, `#elif`, `#else` and `#endif`.
+//!
+//! The lines of the branches that are not taken are skipped, and are never
+//! lexed. The conditions of `#if` and `#elif` are parsed as expressions, and
+//! evaluated as integer constant expressions.
+//!
+//! The macros defined with `#define` are not expanded in the code: they are
+//! only used to evaluate the conditions, and raise a warning when they are
+//! used in the code. The function-like macros can be
+//! variadic, with `__VA_ARGS__` and the GNU comma elision of
+//! `, ## __VA_ARGS__`. Only the predefined macros, like
+//! `__LINE__`, are expanded in the code. The table of the defined macros can
+//! be queried after lexing with [`lex_file_with_macros`].
+//!
+//! [`lex_file_with_macros`]: super::lex_content::lex_file_with_macros
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+
+use super::numbers::api::Number;
+use super::types::api::{Encoding, LexingData, Token, TokenValue, is_ident_continue};
+use crate::errors::api::{CompileError, Location, Res};
+use crate::lexer::api::Symbol;
+use crate::options::api::Options;
+use crate::parser::api::{ConstantError, parse_tokens_with_options, try_constant_value};
+
+/// Macros that are predefined by the preprocessor.
+///
+/// See [`predefined_value`] for their values.
+const PREDEFINED_MACROS: [&str; 4] = ["__DATE__", "__FILE__", "__LINE__", "__TIME__"];
+
+/// Block of conditional inclusion, opened by an `#if`, `#ifdef` or `#ifndef`.
+#[derive(Debug)]
+#[expect(clippy::struct_excessive_bools)]
+struct Block {
+    /// The lines of the current branch are lexed.
+    active: bool,
+    /// An `#else` was found for this block.
+    has_else: bool,
+    /// Location of the directive that opened the block.
+    location: Location,
+    /// The block is inside a branch that is lexed.
+    parent_active: bool,
+    /// One of the branches of the block was already taken.
+    taken: bool,
+}
+
+/// Stores the opened conditional blocks and the defined macros.
+#[derive(Debug, Default)]
+pub struct Conditionals {
+    /// Opened conditional blocks, from the outermost to the innermost.
+    blocks: Vec<Block>,
+    /// Macros defined with `#define`, in the order of their definitions.
+    macros: Vec<Macro>,
+}
+
+impl Conditionals {
+    /// Warns about the defined macros that are used in the code on the line
+    /// of `location`, as they are read as identifiers instead of being
+    /// expanded.
+    pub fn check_macro_uses(&self, location: &Location, lex_data: &mut LexingData) {
+        let mut warnings = vec![];
+        for token in lex_data.line_tokens(location) {
+            if let TokenValue::Ident(name) = token.get_value()
+                && self.macros.iter().any(|found| found.name == *name)
+            {
+                warnings.push(token.get_location().to_warning(format!(
+                    "Macros are not expanded in the code: '{name}' can only be used in conditional directives."
+                )));
+            }
+        }
+        // the tokens were read from the last to the first
+        while let Some(warning) = warnings.pop() {
+            lex_data.push_err(warning);
+        }
+    }
+
+    /// Pushes an error for every block that wasn't closed by an `#endif`,
+    /// and returns the table of the macros, with the predefined ones.
+    pub fn close(self, lex_data: &mut LexingData) -> Vec<Macro> {
+        for block in self.blocks {
+            lex_data.push_err(block.location.into_failure(
+                "Found conditional directive without a matching '#endif'.".to_owned(),
+            ));
+        }
+        PREDEFINED_MACROS
+            .iter()
+            .map(|name| Macro {
+                name: (*name).to_owned(),
+                params: None,
+                predefined: true,
+                value: vec![],
+            })
+            .chain(self.macros)
+            .collect()
+    }
+
+    /// Handles a `#define` directive, and stores the macro.
+    ///
+    /// A `(` directly after the name, without spaces, opens the parameters of
+    /// a function-like macro.
+    fn define(&mut self, value: Res<Vec<Token>>, location: &Location, lex_data: &mut LexingData) {
+        let mut tokens = value.into_parts().0.into_iter();
+        let Some((first, name_location)) = tokens.next().map(Token::into_value_location) else {
+            lex_data.push_err(location.to_failure(
+                "Expected a macro name after '#define', but found nothing.".to_owned(),
+            ));
+            return;
+        };
+        let TokenValue::Ident(name) = first else {
+            lex_data.push_err(name_location.into_failure(format!(
+                "Expected a macro name after '#define', but found {first}."
+            )));
+            return;
+        };
+        if PREDEFINED_MACROS.contains(&name.as_str()) {
+            lex_data.push_err(name_location.into_failure(format!(
+                "Found '#define' of the predefined macro '{name}', but it can't be redefined."
+            )));
+            return;
+        }
+        let params = if tokens.as_slice().first().is_some_and(|token| {
+            *token.get_value() == TokenValue::Symbol(Symbol::ParenthesisOpen)
+                && name_location.is_followed_by(token.get_location())
+        }) {
+            tokens.next();
+            let Some(params) = get_macro_params(&mut tokens, &name_location, lex_data) else {
+                return;
+            };
+            Some(params)
+        } else {
+            None
+        };
+        self.macros.retain(|old| old.name != name);
+        self.macros.push(Macro {
+            name,
+            params,
+            predefined: false,
+            value: tokens.collect(),
+        });
+    }
+
+    /// Evaluates the condition of an `#if`, `#elif`, `#ifdef` or `#ifndef`.
+    ///
+    /// An invalid condition raises an error, and evaluates to false.
+    fn evaluate(
+        &self,
+        directive: &str,
+        value: Res<Vec<Token>>,
+        location: &Location,
+        lex_data: &mut LexingData,
+    ) -> bool {
+        if directive == "ifdef" {
+            return get_macro_name(value, location, lex_data)
+                .is_some_and(|name| self.is_defined(&name));
+        }
+        if directive == "ifndef" {
+            return get_macro_name(value, location, lex_data)
+                .is_some_and(|name| !self.is_defined(&name));
+        }
+        let (tokens, errors) = value.into_parts();
+        if push_errors(errors, lex_data) {
+            return false;
+        }
+        let expanded = self.expand(tokens, &mut vec![], location, lex_data);
+        if expanded.is_empty() {
+            lex_data.push_err(location.to_failure(format!(
+                "Expected a condition after '#{directive}', but found nothing."
+            )));
+            return false;
+        }
+        let (node, parse_errors) =
+            parse_tokens_with_options(expanded, *lex_data.options()).into_parts();
+        if push_errors(parse_errors, lex_data) {
+            return false;
+        }
+        match try_constant_value(&node, &|_| None) {
+            Ok(result) => result != 0,
+            Err(err) => {
+                let msg = match err {
+                    ConstantError::DivisionByZero => {
+                        format!("Found a division by zero in the condition of '#{directive}'.")
+                    }
+                    ConstantError::NotConstant => {
+                        format!("Expected an integer constant expression after '#{directive}'.")
+                    }
+                    ConstantError::Overflow => format!(
+                        "Found an integer overflow in the condition of '#{directive}': the result doesn't fit in 'intmax_t'."
+                    ),
+                };
+                lex_data.push_err(location.to_failure(msg));
+                false
+            }
+        }
+    }
+
+    /// Replaces the `defined` operators and the macros by their values.
+    ///
+    /// The identifiers that are not macros evaluate to 0, with a warning if
+    /// they are reported in the [`Options`].
+    /// `expanding` contains the macros that are being expanded, to prevent
+    /// infinite recursions. The predefined macros are expanded at `location`,
+    /// the location of the directive, even inside the value of another macro.
+    fn expand(
+        &self,
+        tokens: Vec<Token>,
+        expanding: &mut Vec<String>,
+        location: &Location,
+        lex_data: &mut LexingData,
+    ) -> Vec<Token> {
+        let mut expanded = vec![];
+        let mut iter = tokens.into_iter();
+        while let Some(mut token) = iter.next() {
+            let TokenValue::Ident(name) = token.get_value() else {
+                expanded.push(token);
+                continue;
+            };
+            if name == "defined" {
+                let is_parens = iter.as_slice().first().is_some_and(|next| {
+                    *next.get_value() == TokenValue::Symbol(Symbol::ParenthesisOpen)
+                });
+                if is_parens {
+                    iter.next();
+                }
+                let macro_name = iter
+                    .next()
+                    .and_then(|next| match next.into_value_location().0 {
+                        TokenValue::Ident(macro_name) => Some(macro_name),
+                        TokenValue::Char(..)
+                        | TokenValue::Comment(_)
+                        | TokenValue::Keyword(_)
+                        | TokenValue::Newline
+                        | TokenValue::Number(_)
+                        | TokenValue::Str(..)
+                        | TokenValue::Symbol(_)
+                        | TokenValue::Whitespace(_) => None,
+                    });
+                if is_parens
+                    && !iter.next().is_some_and(|next| {
+                        *next.get_value() == TokenValue::Symbol(Symbol::ParenthesisClose)
+                    })
+                {
+                    lex_data.push_err(token.get_location().to_failure(
+                        "Expected a macro name between parenthesis after 'defined'.".to_owned(),
+                    ));
+                }
+                let Some(defined_name) = macro_name else {
+                    lex_data.push_err(
+                        token
+                            .get_location()
+                            .to_failure("Expected a macro name after 'defined'.".to_owned()),
+                    );
+                    continue;
+                };
+                *token.get_value_mut() =
+                    TokenValue::Number(Number::Int(self.is_defined(&defined_name).into()));
+                expanded.push(token);
+            } else if let Some(value) = predefined_value(name, location) {
+                *token.get_value_mut() = value;
+                expanded.push(token);
+            } else if let Some(found) = self.macros.iter().find(|found| found.name == *name)
+                && !expanding.contains(name)
+            {
+                if found.params.is_some()
+                    && !iter.as_slice().first().is_some_and(|next| {
+                        *next.get_value() == TokenValue::Symbol(Symbol::ParenthesisOpen)
+                    })
+                {
+                    lex_data.push_err(token.get_location().to_warning(format!(
+                        "Function-like macro '{name}' is not followed by '(', so it isn't expanded, and evaluates to 0."
+                    )));
+                    *token.get_value_mut() = TokenValue::Number(Number::Int(0));
+                    expanded.push(token);
+                    continue;
+                }
+                expanded.extend(self.expand_macro(
+                    found,
+                    token.get_location(),
+                    &mut iter,
+                    expanding,
+                    location,
+                    lex_data,
+                ));
+            } else {
+                if lex_data.options().reports_undefined_macros() {
+                    lex_data.push_err(token.get_location().to_warning(format!(
+                        "Identifier '{name}' is not defined, and evaluates to 0."
+                    )));
+                }
+                *token.get_value_mut() = TokenValue::Number(Number::Int(0));
+                expanded.push(token);
+            }
+        }
+        expanded
+    }
+
+    /// Expands a macro, found at `name_location`.
+    ///
+    /// The arguments of a function-like macro are read from `tokens`, and
+    /// expanded before being substituted to the parameters. The result is then
+    /// expanded again, without the macro itself, to prevent infinite
+    /// recursions.
+    fn expand_macro(
+        &self,
+        found: &Macro,
+        name_location: &Location,
+        tokens: &mut IntoIter<Token>,
+        expanding: &mut Vec<String>,
+        location: &Location,
+        lex_data: &mut LexingData,
+    ) -> Vec<Token> {
+        let value = if found.params.is_some() {
+            tokens.next();
+            let Some(args) = get_macro_args(tokens, found, name_location, lex_data) else {
+                return vec![];
+            };
+            let expanded_args = args
+                .into_iter()
+                .map(|arg| self.expand(arg, expanding, location, lex_data))
+                .collect();
+            match found.substitute(expanded_args, name_location) {
+                Ok(value) => value,
+                Err(err) => {
+                    lex_data.push_err(err);
+                    return vec![];
+                }
+            }
+        } else {
+            found.value.clone()
+        };
+        expanding.push(found.name.clone());
+        let expanded = self.expand(value, expanding, location, lex_data);
+        expanding.pop();
+        expanded
+    }
+
+    /// Handles a directive, found at the beginning of a line.
+    ///
+    /// `value` contains the tokens after the name of the directive. They are
+    /// only used if the directive needs them, so the errors of a directive in
+    /// a skipped branch are ignored.
+    pub fn handle_directive(
+        &mut self,
+        directive: &str,
+        value: Res<Vec<Token>>,
+        location: &Location,
+        lex_data: &mut LexingData,
+    ) {
+        let active = self.is_active();
+        match directive {
+            "if" | "ifdef" | "ifndef" => {
+                let condition = active && self.evaluate(directive, value, location, lex_data);
+                self.blocks.push(Block {
+                    active: condition,
+                    has_else: false,
+                    location: location.to_owned(),
+                    parent_active: active,
+                    taken: condition,
+                });
+            }
+            "elif" | "else" => {
+                let Some(block) = self.blocks.last() else {
+                    lex_data.push_err(location.to_failure(format!(
+                        "Found '#{directive}' without a matching '#if'."
+                    )));
+                    return;
+                };
+                if block.has_else {
+                    lex_data.push_err(
+                        location.to_failure(format!("Found '#{directive}' after '#else'.")),
+                    );
+                    return;
+                }
+                let condition = block.parent_active
+                    && !block.taken
+                    && (directive == "else" || self.evaluate(directive, value, location, lex_data));
+                if let Some(last) = self.blocks.last_mut() {
+                    last.active = condition;
+                    last.taken |= condition;
+                    last.has_else = directive == "else";
+                }
+            }
+            "endif" => {
+                if self.blocks.pop().is_none() {
+                    lex_data.push_err(
+                        location.to_failure("Found '#endif' without a matching '#if'.".to_owned()),
+                    );
+                }
+            }
+            _ if !active => (),
+            "define" => self.define(value, location, lex_data),
+            "undef" => {
+                if let Some(name) = get_macro_name(value, location, lex_data) {
+                    if PREDEFINED_MACROS.contains(&name.as_str()) {
+                        lex_data.push_err(location.to_failure(format!(
+                            "Found '#undef' of the predefined macro '{name}', but it can't be undefined."
+                        )));
+                    }
+                    self.macros.retain(|old| old.name != name);
+                }
+            }
+            "" => (),
+            _ => lex_data.push_err(location.to_failure(format!(
+                "Found unsupported preprocessor directive '#{directive}'. Please run the preprocessor first (e.g. `cpp` or `gcc -E`)."
+            ))),
+        }
+    }
+
+    /// Checks if the lines are lexed, or skipped because they are in a branch
+    /// that isn't taken.
+    pub fn is_active(&self) -> bool {
+        self.blocks.last().is_none_or(|block| block.active)
+    }
+
+    /// Checks if a macro was defined with `#define`, or is predefined.
+    fn is_defined(&self, name: &str) -> bool {
+        PREDEFINED_MACROS.contains(&name) || self.macros.iter().any(|found| found.name == name)
+    }
+}
+
+/// Macro of the preprocessor, defined with `#define` or predefined.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::{Location, Options, display_tokens, lex_file_with_macros};
+///
+/// let (_, macros) = lex_file_with_macros(
+///     "#define MAX(a, b) a > b ? a : b\n",
+///     &mut Location::from(""),
+///     Options::default(),
+/// );
+/// let max = macros
+///     .iter()
+///     .find(|found| found.get_name() == "MAX")
+///     .unwrap();
+/// assert!(max.get_params() == Some(["a".to_owned(), "b".to_owned()].as_slice()));
+/// assert!(
+///     display_tokens(max.get_value())
+///         == "[Ident(a), Gt, Ident(b), Interrogation, Ident(a), Colon, Ident(b)]"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Macro {
+    /// Name of the macro.
+    name: String,
+    /// Names of the parameters, if the macro is function-like.
+    ///
+    /// The last parameter of a variadic macro is `...`.
+    params: Option<Vec<String>>,
+    /// The macro is predefined by the preprocessor, like `__LINE__`.
+    predefined: bool,
+    /// Replacement list of the macro.
+    value: Vec<Token>,
+}
+
+impl Macro {
+    /// Returns the name of the macro.
+    #[inline]
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the names of the parameters of a function-like macro, like
+    /// `a` and `b` in `#define MAX(a, b) ...`, or [`None`] for an
+    /// object-like macro.
+    ///
+    /// The last parameter of a variadic macro, like
+    /// `#define LOG(fmt, ...) ...`, is `...`.
+    #[inline]
+    #[must_use]
+    pub fn get_params(&self) -> Option<&[String]> {
+        self.params.as_deref()
+    }
+
+    /// Returns the tokens of the replacement list of the macro.
+    ///
+    /// The value of a predefined macro depends on where it is expanded, so
+    /// its replacement list is empty.
+    #[inline]
+    #[must_use]
+    pub fn get_value(&self) -> &[Token] {
+        &self.value
+    }
+
+    /// Checks if the macro is predefined by the preprocessor, like
+    /// `__LINE__` or `__FILE__`.
+    #[inline]
+    #[must_use]
+    pub const fn is_predefined(&self) -> bool {
+        self.predefined
+    }
+
+    /// Returns the names of the fixed parameters, and whether the macro is
+    /// variadic.
+    fn split_params(&self) -> (&[String], bool) {
+        let params = self.params.as_deref().unwrap_or_default();
+        match params.split_last() {
+            Some((last, fixed)) if last == "..." => (fixed, true),
+            Some(_) | None => (params, false),
+        }
+    }
+
+    /// Replaces the parameters of a function-like macro by the arguments of
+    /// an invocation found at `location`.
+    ///
+    /// The variadic arguments replace `__VA_ARGS__`. In `, ## __VA_ARGS__`,
+    /// the comma is removed if there are no variadic arguments, like in GNU C.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of arguments doesn't match the number of
+    /// parameters, or if `##` is used elsewhere than before `__VA_ARGS__`.
+    fn substitute(
+        &self,
+        mut args: Vec<Vec<Token>>,
+        location: &Location,
+    ) -> Result<Vec<Token>, CompileError> {
+        let (fixed, variadic) = self.split_params();
+        if fixed.is_empty() && args.len() == 1 && args.first().is_some_and(Vec::is_empty) {
+            // `F()` has one empty argument, but no argument is expected
+            args.clear();
+        }
+        if args.len() < fixed.len() || (!variadic && args.len() > fixed.len()) {
+            return Err(location.to_failure(format!(
+                "Macro '{}' expects {}{} argument{}, but found {}.",
+                self.name,
+                if variadic { "at least " } else { "" },
+                fixed.len(),
+                if fixed.len() == 1 { "" } else { "s" },
+                args.len()
+            )));
+        }
+        let va_args = if args.len() > fixed.len() {
+            args.pop().unwrap_or_default()
+        } else {
+            vec![]
+        };
+        let mut value = vec![];
+        let mut rest = self.value.as_slice();
+        while let Some((token, tail)) = rest.split_first() {
+            rest = tail;
+            match token.get_value() {
+                TokenValue::Symbol(Symbol::Comma)
+                    if variadic
+                        && let [paste, va_name, after @ ..] = tail
+                        && *paste.get_value() == TokenValue::Symbol(Symbol::HashHash)
+                        && *va_name.get_value() == TokenValue::Ident("__VA_ARGS__".to_owned()) =>
+                {
+                    rest = after;
+                    if !va_args.is_empty() {
+                        value.push(token.clone());
+                        value.extend(va_args.iter().cloned());
+                    }
+                }
+                TokenValue::Symbol(Symbol::HashHash) => {
+                    return Err(token.get_location().to_failure(format!(
+                        "Found '##' in the macro '{}', but token pasting is only supported in ', ## __VA_ARGS__'.",
+                        self.name
+                    )));
+                }
+                TokenValue::Ident(ident) if variadic && ident == "__VA_ARGS__" => {
+                    value.extend(va_args.iter().cloned());
+                }
+                TokenValue::Ident(ident) => {
+                    if let Some(idx) = fixed.iter().position(|param| param == ident) {
+                        value.extend(args.get(idx).into_iter().flatten().cloned());
+                    } else {
+                        value.push(token.clone());
+                    }
+                }
+                TokenValue::Char(..)
+                | TokenValue::Comment(_)
+                | TokenValue::Keyword(_)
+                | TokenValue::Newline
+                | TokenValue::Number(_)
+                | TokenValue::Str(..)
+                | TokenValue::Symbol(_)
+                | TokenValue::Whitespace(_) => value.push(token.clone()),
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// Returns the parameters of a function-like macro, after the `(` that
+/// follows its name.
+fn get_macro_params(
+    tokens: &mut IntoIter<Token>,
+    location: &Location,
+    lex_data: &mut LexingData,
+) -> Option<Vec<String>> {
+    let mut params = vec![];
+    loop {
+        match tokens.next().map(Token::into_value_location) {
+            Some((TokenValue::Symbol(Symbol::ParenthesisClose), _)) if params.is_empty() => {
+                return Some(params);
+            }
+            Some((TokenValue::Ident(param), _)) => params.push(param),
+            Some((TokenValue::Symbol(Symbol::Ellipsis), ellipsis_location)) => {
+                if tokens.next().is_some_and(|close| {
+                    *close.get_value() == TokenValue::Symbol(Symbol::ParenthesisClose)
+                }) {
+                    params.push("...".to_owned());
+                    return Some(params);
+                }
+                lex_data.push_err(ellipsis_location.into_failure(
+                    "Expected '...' as last parameter of the macro definition.".to_owned(),
+                ));
+                return None;
+            }
+            Some((other, other_location)) => {
+                lex_data.push_err(other_location.into_failure(format!(
+                    "Expected a parameter name in the macro definition, but found {other}."
+                )));
+                return None;
+            }
+            None => break,
+        }
+        match tokens.next().map(Token::into_value_location) {
+            Some((TokenValue::Symbol(Symbol::ParenthesisClose), _)) => return Some(params),
+            Some((TokenValue::Symbol(Symbol::Comma), _)) => (),
+            Some((other, other_location)) => {
+                lex_data.push_err(other_location.into_failure(format!(
+                    "Expected ',' or ')' after a parameter of the macro definition, but found {other}."
+                )));
+                return None;
+            }
+            None => break,
+        }
+    }
+    lex_data
+        .push_err(location.to_failure(
+            "Expected ')' to close the parameters of the macro definition.".to_owned(),
+        ));
+    None
+}
+
+/// Returns the arguments of an invocation of a function-like macro, after
+/// the `(` that follows its name.
+///
+/// The arguments are separated by the commas that are not nested in
+/// parenthesis. The variadic arguments are kept together, with their commas,
+/// to replace `__VA_ARGS__`.
+fn get_macro_args(
+    tokens: &mut IntoIter<Token>,
+    found: &Macro,
+    location: &Location,
+    lex_data: &mut LexingData,
+) -> Option<Vec<Vec<Token>>> {
+    let (fixed, variadic) = found.split_params();
+    let mut args: Vec<Vec<Token>> = vec![vec![]];
+    let mut depth = 0usize;
+    for token in tokens.by_ref() {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match token.get_value() {
+            TokenValue::Symbol(Symbol::ParenthesisClose) if depth == 0 => return Some(args),
+            TokenValue::Symbol(Symbol::Comma)
+                if depth == 0 && (!variadic || args.len() <= fixed.len()) =>
+            {
+                args.push(vec![]);
+                continue;
+            }
+            TokenValue::Symbol(Symbol::ParenthesisOpen) => depth = depth.saturating_add(1),
+            TokenValue::Symbol(Symbol::ParenthesisClose) => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+        if let Some(arg) = args.last_mut() {
+            arg.push(token);
+        }
+    }
+    lex_data.push_err(location.to_failure(format!(
+        "Expected ')' to close the arguments of the macro '{}'.",
+        found.name
+    )));
+    None
+}
+
+/// Returns the macro name of an `#ifdef`, `#ifndef` or `#undef` directive.
+fn get_macro_name(
+    value: Res<Vec<Token>>,
+    location: &Location,
+    lex_data: &mut LexingData,
+) -> Option<String> {
+    let (tokens, errors) = value.into_parts();
+    if push_errors(errors, lex_data) {
+        return None;
+    }
+    let mut iter = tokens.into_iter();
+    match iter.next().map(Token::into_value_location) {
+        Some((TokenValue::Ident(name), _)) => {
+            if let Some(extra) = iter.next() {
+                lex_data.push_err(extra.get_location().to_warning(
+                    "Found extra tokens after the macro name. Please remove them.".to_owned(),
+                ));
+            }
+            Some(name)
+        }
+        Some((other, other_location)) => {
+            lex_data.push_err(
+                other_location.into_failure(format!("Expected a macro name, but found {other}.")),
+            );
+            None
+        }
+        None => {
+            lex_data.push_err(
+                location.to_failure("Expected a macro name, but found nothing.".to_owned()),
+            );
+            None
+        }
+    }
+}
+
+/// Returns the value of a predefined macro, expanded at `location`, or
+/// [`None`] if the name isn't a predefined macro.
+///
+/// `__LINE__` expands to the line of `location`, and `__FILE__` to its file.
+/// The date and the time of translation are not available without the
+/// standard library, so `__DATE__` and `__TIME__` expand to the placeholders
+/// `"??? ?? ????"` and `"??:??:??"`, like in GCC.
+pub fn predefined_value(name: &str, location: &Location) -> Option<TokenValue> {
+    match name {
+        "__DATE__" => Some(TokenValue::Str("??? ?? ????".to_owned(), Encoding::Plain)),
+        "__FILE__" => Some(TokenValue::Str(
+            location.get_file().to_owned(),
+            Encoding::Plain,
+        )),
+        "__LINE__" => Some(TokenValue::Number(Number::Int(
+            i32::try_from(location.get_line()).unwrap_or(i32::MAX),
+        ))),
+        "__TIME__" => Some(TokenValue::Str("??:??:??".to_owned(), Encoding::Plain)),
+        _ => None,
+    }
+}
+
+/// Pushes errors to the lexing data, and returns `true` if one of them is a
+/// failure.
+fn push_errors(errors: Vec<CompileError>, lex_data: &mut LexingData) -> bool {
+    let mut failure = false;
+    for error in errors {
+        failure |= error.is_error();
+        lex_data.push_err(error);
+    }
+    failure
+}
+
+/// Splits a directive line into the name of the directive, and a line where
+/// the `#` and the name are replaced by spaces.
+///
+/// The `#` can also be written as the digraph `%:` and as the trigraph `??=`,
+/// if they are enabled in the [`Options`].
+///
+/// The replacement keeps the columns of the tokens after the name, so they
+/// can be lexed with the right locations.
+pub fn split_directive(line: &str, options: &Options) -> Option<(String, String)> {
+    let start = line.len().checked_sub(line.trim_start().len())?;
+    let trimmed = line.get(start..)?;
+    let after_hash = trimmed
+        .strip_prefix('#')
+        .or_else(|| {
+            trimmed
+                .strip_prefix("%:")
+                .filter(|_| options.translates_digraphs())
+        })
+        .or_else(|| {
+            trimmed
+                .strip_prefix("??=")
+                .filter(|_| options.translates_trigraphs())
+        })?;
+    let name = after_hash
+        .trim_start()
+        .chars()
+        .take_while(|ch| is_ident_continue(*ch, options))
+        .collect::<String>();
+    let end = line
+        .len()
+        .checked_sub(after_hash.trim_start().len())?
+        .checked_add(name.len())?;
+    let mut value = line.to_owned();
+    value.replace_range(..end, &" ".repeat(line.get(..end)?.chars().count()));
+    Some((name, value))
+}
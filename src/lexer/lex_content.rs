@@ -2,11 +2,35 @@
 //!
 //! See [`lex_file`] for more information.
 
+use super::conditionals::{Conditionals, Macro, split_directive};
 use super::state::api::{
-    CommentState, EscapeState, LexingState as LS, SymbolState, end_current, handle_escape
+    CommentState, EscapeState, LexingState as LS, SymbolState, end_current, end_pending_escape, handle_escape
 };
-use super::types::api::{LexingData, Token};
-use crate::errors::api::{Location, Res};
+use super::stream::Lexer;
+use super::types::api::{Encoding, Ident, LexingData, Token, is_ident_continue, is_ident_start};
+use crate::errors::api::{CompileError, ErrorCode, Location, Res};
+use crate::options::api::{CStandard, Options};
+
+/// Checks that line comments (`//`) are allowed by the C standard.
+///
+/// Line comments were only introduced in C99. Under C89, they raise a warning,
+/// or an error in pedantic mode.
+fn check_line_comment(lex_data: &mut LexingData, location: &Location) {
+    let options = lex_data.options();
+    if options.standard() < CStandard::C99 {
+        let mut comment_location = location.to_owned().into_past_with_length(1);
+        comment_location.extend_to(location);
+        let msg = format!(
+            "Line comments were introduced in C99, but the standard is {}. Consider using '/* */' instead.",
+            options.standard()
+        );
+        lex_data.push_pedantic(
+            comment_location
+                .to_warning(msg)
+                .with_code(ErrorCode::LineCommentBeforeC99),
+        );
+    }
+}
 
 /// Function to manage one character.
 ///
@@ -22,82 +46,97 @@ fn lex_char(
     eol: bool,
 ) {
     match (ch, lex_state, escape_state) {
-        (_, LS::StartOfLine, _) if ch.is_whitespace() => (),
+        (_, LS::StartOfLine, EscapeState::False) if ch.is_whitespace() => {
+            lex_data.push_whitespace(ch, location);
+        }
         /* Inside comment */
         ('/', state @ LS::Comment(CommentState::Star), _) => {
             *state = LS::Comment(CommentState::False);
+            lex_data.push_comment_char(ch);
+            lex_data.close_comment(location);
         }
         ('*', state @ LS::Comment(CommentState::True), _) => {
             *state = LS::Comment(CommentState::Star);
+            lex_data.push_comment_char(ch);
         }
-        (_, LS::Comment(CommentState::True), _) => (),
+        (_, LS::Comment(CommentState::True), _) => lex_data.push_comment_char(ch),
         (_, state @ LS::Comment(CommentState::Star), _) => {
             *state = LS::Comment(CommentState::True);
+            lex_data.push_comment_char(ch);
         }
         /* Escaped character */
         (
             _,
-            state @ (LS::Char(None) | LS::Str(_)),
+            state @ (LS::Char(None, ..) | LS::Str(..)),
             escape @ (EscapeState::Single | EscapeState::Sequence(_)),
         ) => {
-            if let Some(escaped) = handle_escape(ch, lex_data, escape, location) {
+            if let Some((escaped, trailing)) = handle_escape(ch, lex_data, escape, location) {
                 *escape = EscapeState::False;
                 #[expect(clippy::wildcard_enum_match_arm)]
                 match state {
-                    LS::Char(None) => *state = LS::Char(Some(escaped)),
-                    LS::Str(val) => val.push(escaped),
+                    LS::Char(escaped_char @ None, ..) => *escaped_char = Some(escaped),
+                    LS::Str(val, ..) => val.push(escaped),
                     _ => panic!("this can't happen, see match above"),
                 }
+                if let Some(next) = trailing {
+                    lex_char(next, location, lex_data, state, escape, eol);
+                }
             }
         }
 
-        (_, _, EscapeState::Single | EscapeState::Sequence(_)) => {
-            panic!("Can't happen because error raised on escape creation if wrong state.")
+        (_, state, escape @ (EscapeState::Single | EscapeState::Sequence(_))) => {
+            lex_universal_char(ch, location, lex_data, state, escape, eol);
         }
         /* Create comment */
         ('*', state, _) if state.symbol().and_then(SymbolState::last) == Some('/') => {
             state.clear_last_symbol();
             end_current(state, lex_data, location);
+            lex_data.open_comment("/*", location);
             *state = LS::Comment(CommentState::True);
         }
 
         /* Escape character */
-        ('\\', LS::Char(None) | LS::Str(_), escape) => *escape = EscapeState::Single,
-        ('\\', _, escape) if eol => *escape = EscapeState::Single,
-        ('\\', state, _) => lex_data.push_err(location.to_failure(format!(
-            "Escape characters are only authorised in strings or chars, not in '{}' context.",
-            state.repr(),
-        ))),
+        // in a literal, line continuation, or universal character name
+        ('\\', state, escape) if !matches!(state, LS::Char(Some(_), ..)) => {
+            *escape = EscapeState::Single;
+        }
 
         /* Static strings and chars */
+        // digit separator, checked when the number ends
+        ('\'', LS::Ident(ident), _) if ident.is_number() => ident.push(ch),
         // open/close
-        ('\'', LS::Symbols(symbol_state), _) if symbol_state.is_trigraph() => {
-            if let Some((size, symbol)) = symbol_state.push(ch, lex_data, location) {
-                lex_data.push_token(Token::from_symbol(symbol, size, location));
-            }
-        }
-        ('\'', state @ LS::Char(_), _) => end_current(state, lex_data, location),
-        ('\'', state, _) if !matches!(state, LS::Str(_)) => {
+        ('\'', state @ LS::Char(..), _) => end_current(state, lex_data, location),
+        ('\'', state, _) if !matches!(state, LS::Str(..)) => {
+            let encoding = take_prefix(state, ch, lex_data, location);
             end_current(state, lex_data, location);
-            *state = LS::Char(None);
+            // the rest of the line is skipped after an invalid prefix
+            if !lex_data.is_end_line() {
+                *state = LS::Char(None, 0, encoding);
+            }
         }
-        ('\"', state @ LS::Str(_), _) => {
+        ('\"', state @ LS::Str(..), _) => {
             end_current(state, lex_data, location);
         }
-        ('\"', state, _) if !matches!(state, LS::Char(_)) => {
+        ('\"', state, _) if !matches!(state, LS::Char(..)) => {
+            let encoding = take_prefix(state, ch, lex_data, location);
             end_current(state, lex_data, location);
-            *state = LS::Str(String::new());
+            *state = LS::Str(String::new(), 0, encoding);
         }
         // middle
-        (_, LS::Char(Some(_)), _) => lex_data
-            .push_err(location.to_failure("A char must contain only one character.".to_owned())),
-        (_, state @ LS::Char(None), _) => *state = LS::Char(Some(ch)),
-        (_, LS::Str(val), _) => val.push(ch),
+        (_, LS::Char(Some(_), ..), _) => lex_data.push_err(
+            location
+                .to_failure("A char must contain only one character.".to_owned())
+                .with_code(ErrorCode::CharTooLong),
+        ),
+        (_, LS::Char(value @ None, ..), _) => *value = Some(ch),
+        (_, LS::Str(val, ..), _) => val.push(ch),
 
         /* Operator symbols */
         ('/', state, _) if state.symbol().and_then(SymbolState::last) == Some('/') => {
             state.clear_last_symbol();
             end_current(state, lex_data, location);
+            check_line_comment(lex_data, location);
+            lex_data.open_comment("//", location);
             lex_data.set_end_line();
         }
         ('.', LS::Ident(ident), _) if !ident.contains('.') && ident.is_number() => {
@@ -110,13 +149,17 @@ fn lex_char(
         }
         (
             '(' | ')' | '[' | ']' | '{' | '}' | '~' | '!' | '*' | '&' | '%' | '/' | '>' | '<' | '='
-            | '|' | '^' | ',' | '?' | ':' | ';' | '.' | '+' | '-',
+            | '|' | '^' | ',' | '?' | ':' | ';' | '.' | '+' | '-' | '#',
             state,
             _,
         ) => {
             if let LS::Symbols(symbol_state) = state {
                 if let Some((size, symbol)) = symbol_state.push(ch, lex_data, location) {
-                    lex_data.push_token(Token::from_symbol(symbol, size, location));
+                    // the symbol is followed by the other chars of the state
+                    let end = location
+                        .to_owned()
+                        .into_past_with_length(symbol_state.len().saturating_sub(1));
+                    lex_data.push_token(Token::from_symbol(symbol, size, &end));
                 }
             } else {
                 end_current(state, lex_data, location);
@@ -127,29 +170,37 @@ fn lex_char(
         /* Whitespace: end of everyone */
         (_, state, _) if ch.is_whitespace() => {
             end_current(state, lex_data, location);
+            lex_data.push_whitespace(ch, location);
         }
 
         // Whitespace: end of everyone
-        (_, LS::Ident(val), _) if ch.is_alphanumeric() || matches!(ch, '_' | '.' | '+' | '-') => {
+        (_, LS::Ident(val), _)
+            if is_ident_continue(ch, lex_data.options()) || matches!(ch, '.' | '+' | '-') =>
+        {
             // dbg!("here", &val, ch);
             val.push(ch);
             // dbg!("there", &val);
         }
-        (_, state, _) if ch.is_alphanumeric() || matches!(ch, '_') => {
+        // a number constant is lexed like an identifier
+        (_, state, _) if is_ident_start(ch, lex_data.options()) || ch.is_ascii_digit() => {
             if let LS::Symbols(symbol) = state
                 && symbol.last() == Some('.')
                 && ch.is_ascii_digit()
             {
                 symbol.clear_last();
                 end_current(state, lex_data, location);
-                state.new_ident_str(format!("0.{ch}"));
+                state.new_ident_str(format!(".{ch}"));
             } else {
                 end_current(state, lex_data, location);
                 state.new_ident(ch);
             }
         }
         (_, _, _) => {
-            lex_data.push_err(location.to_failure(format!("Character '{ch}' not supported.")));
+            lex_data.push_err(
+                location
+                    .to_failure(format!("Character '{ch}' not supported."))
+                    .with_code(ErrorCode::UnsupportedCharacter),
+            );
         }
     }
 }
@@ -160,34 +211,366 @@ fn lex_char(
 /// functions. Every character is parsed one by one, and the state is modified
 /// accordingly. When the state changes, the buffers of the state are empty into
 /// the data.
+///
+/// The file is lexed with the default [`Options`]. See
+/// [`lex_file_with_options`] to choose them.
 #[inline]
 pub fn lex_file(content: &str, location: &mut Location) -> Res<Vec<Token>> {
-    let mut lex_data = LexingData::default();
+    lex_file_with_options(content, location, Options::default())
+}
+
+/// Function that lexes a whole source file, with the given [`Options`].
+///
+/// See [`lex_file`] for more information.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::{CStandard, Location, Options, lex_file_with_options};
+///
+/// let options = Options::default().with_standard(CStandard::C89);
+/// let res = lex_file_with_options("int x; // comment", &mut Location::from(""), options);
+/// assert!(!res.errors_empty());
+/// ```
+#[inline]
+pub fn lex_file_with_options(
+    content: &str,
+    location: &mut Location,
+    options: Options,
+) -> Res<Vec<Token>> {
+    lex_file_with_macros(content, location, options).0
+}
+
+/// Function that lexes a whole source file, with the given [`Options`], and
+/// groups the tokens by line.
+///
+/// This is meant for tools that render the source line by line, like syntax
+/// highlighters. The `n`-th element contains the tokens of the `n`-th line,
+/// counted from the line of `location`. Each token is given with the index of
+/// the last line it covers. A token that spans over several lines, like a
+/// block comment continued with a `\\` or string literals merged across
+/// lines, is in the group of the line where it starts, and the other lines it
+/// covers have no tokens.
+///
+/// See [`lex_file`] for more information on the lexing.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::{Location, Options, TokenValue, lex_file_by_lines};
+///
+/// let (lines, _) = lex_file_by_lines(
+///     "int x;\n\nx = \"a\"\n    \"b\";",
+///     &mut Location::from(""),
+///     Options::default(),
+/// )
+/// .into_parts();
+/// assert!(lines.len() == 4);
+/// assert!(lines[0].len() == 3);
+/// assert!(lines[1].is_empty());
+/// let (first, end_line) = &lines[2][0];
+/// assert!(first.get_value() == &TokenValue::Ident("x".to_owned()));
+/// assert!(*end_line == 2);
+/// // the merged string literal ends on the last line
+/// assert!(lines[2][2].1 == 3);
+/// assert!(lines[3].len() == 1);
+/// ```
+#[inline]
+pub fn lex_file_by_lines(
+    content: &str,
+    location: &mut Location,
+    options: Options,
+) -> Res<Vec<Vec<(Token, usize)>>> {
+    let first_line = location.get_line();
+    let (tokens, errors) = lex_file_with_options(content, location, options).into_parts();
+    let mut lines: Vec<Vec<(Token, usize)>> = content.lines().map(|_| vec![]).collect();
+    for token in tokens {
+        let idx = token.get_location().get_line().saturating_sub(first_line);
+        if lines.len() <= idx {
+            lines.resize_with(idx.saturating_add(1), Vec::new);
+        }
+        if let Some(line) = lines.get_mut(idx) {
+            let end_line = idx.saturating_add(token.get_extra_lines());
+            line.push((token, end_line));
+        }
+    }
+    Res::from((lines, errors))
+}
+
+/// Function that lexes a whole source file, with the given [`Options`], and
+/// returns the table of the macros defined at the end of the file.
+///
+/// The table contains the macros defined with `#define` and not undefined
+/// with `#undef`, in the order of their definitions, after the predefined
+/// macros `__DATE__`, `__FILE__`, `__LINE__` and `__TIME__`. See [`Macro`] for
+/// more information.
+///
+/// See [`lex_file`] for more information on the lexing.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::{Location, Options, lex_file_with_macros};
+///
+/// let (_, macros) = lex_file_with_macros(
+///     "#define N 10\n#define M 2\n#undef M\n",
+///     &mut Location::from(""),
+///     Options::default(),
+/// );
+/// let names = macros
+///     .iter()
+///     .map(|found| found.get_name())
+///     .collect::<Vec<_>>();
+/// assert!(names == ["__DATE__", "__FILE__", "__LINE__", "__TIME__", "N"]);
+/// ```
+#[inline]
+pub fn lex_file_with_macros(
+    content: &str,
+    location: &mut Location,
+    options: Options,
+) -> (Res<Vec<Token>>, Vec<Macro>) {
+    let mut lexer = Lexer::new(content, location.to_owned(), options);
+    let mut tokens = vec![];
+    let mut errors = vec![];
+    for item in lexer.by_ref() {
+        match item {
+            Ok(token) => tokens.push(token),
+            Err(err) => errors.push(err),
+        }
+    }
+    location.clone_from(lexer.get_location());
+    (Res::from((tokens, errors)), lexer.into_macros())
+}
+
+/// Function that lexes successive lines of a file, starting at the line of
+/// `location`.
+///
+/// `end_of_file` contains two booleans: the first indicates that the last of
+/// the `lines` is the last line of the file, and the second that the file
+/// ends with a newline character. When the first one is false, a newline is
+/// pushed after every line.
+pub(super) fn lex_lines<'lines>(
+    lines: impl Iterator<Item = &'lines str>,
+    end_of_file: (bool, bool),
+    location: &mut Location,
+    lex_data: &mut LexingData,
+    conditionals: &mut Conditionals,
+) {
     let mut lex_state = LS::default();
+    let mut lines_iter = lines.peekable();
+    while let Some(line) = lines_iter.next() {
+        let last_line = end_of_file.0 && lines_iter.peek().is_none();
+        if !lex_next_line(
+            line,
+            (last_line, !last_line || end_of_file.1),
+            location,
+            lex_data,
+            conditionals,
+            &mut lex_state,
+        ) {
+            break;
+        }
+    }
+}
 
-    for line in content.lines() {
-        lex_line(line, location, &mut lex_data, &mut lex_state);
-        if let Err(err) = location.incr_line() {
+/// Function that lexes the line of `location`, or evaluates it if it is a
+/// directive, and moves `location` to the next line.
+///
+/// `end_of_line` contains two booleans: the first indicates that the line is
+/// the last line of the file, and the second that it is followed by a newline
+/// character.
+///
+/// Returns `false` if the lexing must stop, because the maximum number of
+/// lines was reached.
+pub(super) fn lex_next_line(
+    line: &str,
+    end_of_line: (bool, bool),
+    location: &mut Location,
+    lex_data: &mut LexingData,
+    conditionals: &mut Conditionals,
+    lex_state: &mut LS,
+) -> bool {
+    let options = *lex_data.options();
+    let (last_line, newline) = end_of_line;
+    if *lex_state == LS::StartOfLine
+        && let Some((directive, value_line)) = split_directive(line, &options)
+    {
+        if line.trim_start().starts_with("??=") {
+            let mut trigraph_location = location.to_owned();
+            let indent = line.len().saturating_sub(line.trim_start().len());
+            for _ in 0..indent.saturating_add(3) {
+                if trigraph_location.incr_col(usize::MAX).is_err() {
+                    break;
+                }
+            }
+            lex_data.push_err(trigraph_warning(&trigraph_location, '#', '='));
+        }
+        let value = lex_directive_value(&value_line, location, options);
+        conditionals.handle_directive(&directive, value, location, lex_data);
+    } else if conditionals.is_active() {
+        lex_line(line, location, lex_data, lex_state, last_line);
+        lex_data.attach_lexemes(line, location);
+        conditionals.check_macro_uses(location, lex_data);
+    }
+    if newline {
+        lex_data.push_newline(location);
+    }
+    if let Err(err) = location.incr_line(options.max_lines()) {
+        if !last_line {
             lex_data.push_err(err);
         }
+        return false;
     }
+    true
+}
 
-    lex_data.into_res()
+/// Lexes the value of a directive, that is the end of the line after the name
+/// of the directive.
+///
+/// The value is lexed on its own, with the whitespace tokens disabled.
+fn lex_directive_value(value_line: &str, location: &Location, options: Options) -> Res<Vec<Token>> {
+    let mut value_data = LexingData::from(options.with_whitespace(false));
+    value_data.set_directive();
+    let mut value_location = location.to_owned();
+    lex_line(
+        value_line,
+        &mut value_location,
+        &mut value_data,
+        &mut LS::default(),
+        true,
+    );
+    value_data.into_res()
+}
+
+/// Lexes a character after a `\\` outside of a char or a string literal.
+///
+/// Outside of the literals, the escapes are only allowed for the universal
+/// character names, like `\\u00C0`, that can be used in identifiers. The
+/// escaped character is pushed to the identifier that is being lexed, or
+/// starts a new one, if it is allowed in an identifier (cf.
+/// [`is_ident_start`] and [`is_ident_continue`]). As in the standard, the
+/// characters of the basic character set, like `A`, can't be written with a
+/// universal character name, except `$`.
+fn lex_universal_char(
+    ch: char,
+    location: &Location,
+    lex_data: &mut LexingData,
+    lex_state: &mut LS,
+    escape_state: &mut EscapeState,
+    eol: bool,
+) {
+    if *escape_state == EscapeState::Single && !matches!(ch, 'u' | 'U') {
+        *escape_state = EscapeState::False;
+        lex_data.push_err(
+            location
+                .to_owned()
+                .into_past_with_length(1)
+                .into_failure(format!(
+                    "Escape characters are only authorised in strings or chars, not in '{}' context.",
+                    lex_state.repr(),
+                ))
+                .with_code(ErrorCode::EscapeOutsideString),
+        );
+        return;
+    }
+    // the `\\` and the prefix are part of the universal character name
+    let width = match escape_state {
+        EscapeState::Sequence(sequence) => sequence.max_len().saturating_add(2),
+        EscapeState::False | EscapeState::Single => 2,
+    };
+    let Some((escaped, trailing)) = handle_escape(ch, lex_data, escape_state, location) else {
+        return;
+    };
+    *escape_state = EscapeState::False;
+    let options = lex_data.options();
+    let continues = is_ident_continue(escaped, options);
+    let allowed = if let LS::Ident(_) = lex_state {
+        continues
+    } else {
+        is_ident_start(escaped, options)
+    };
+    if !allowed || (u32::from(escaped) < 0xA0 && escaped != '$') {
+        let mut ucn_location = location
+            .to_owned()
+            .into_past_with_length(width.saturating_sub(1));
+        ucn_location.extend_to(location);
+        let msg = if allowed {
+            format!(
+                "Found a universal character name for '{escaped}', but the characters of the basic character set can't be written with universal character names. Consider writing '{escaped}' instead."
+            )
+        } else if escaped.is_ascii_digit() {
+            format!(
+                "Found a universal character name for '{escaped}', but a digit can't start an identifier."
+            )
+        } else if continues {
+            // like the combining marks
+            format!(
+                "Found a universal character name for U+{:04X}, but this character can't start an identifier.",
+                u32::from(escaped)
+            )
+        } else {
+            format!(
+                "Found a universal character name for U+{:04X}, but this character isn't allowed in an identifier.",
+                u32::from(escaped)
+            )
+        };
+        lex_data.push_err(
+            ucn_location
+                .into_failure(msg)
+                .with_code(ErrorCode::InvalidUniversalChar),
+        );
+        return;
+    }
+    if let LS::Ident(ident) = lex_state {
+        ident.push_escaped(escaped, width);
+    } else {
+        end_current(lex_state, lex_data, location);
+        let mut ident = Ident::default();
+        ident.push_escaped(escaped, width);
+        *lex_state = LS::Ident(ident);
+    }
+    if let Some(next) = trailing {
+        lex_char(next, location, lex_data, lex_state, escape_state, eol);
+    }
 }
 
 /// Function that lexes one line.
 ///
 /// It stops at the first erroneous character, or at the end of the line if
 /// everything was ok.
-fn lex_line(line: &str, location: &mut Location, lex_data: &mut LexingData, lex_state: &mut LS) {
+///
+/// `last_line` indicates that this line is the last of the file: a `\` at the
+/// end of this line can't be a line continuation, as there is no line after.
+///
+/// If they are enabled in the [`Options`], the trigraphs of the line are
+/// translated before lexing (cf. [`translate_trigraphs`]).
+fn lex_line(
+    line: &str,
+    location: &mut Location,
+    lex_data: &mut LexingData,
+    lex_state: &mut LS,
+    last_line: bool,
+) {
     lex_data.newline();
     let mut escape_state = EscapeState::False;
     let trimmed = line.trim_end();
+    let trailing = line.get(trimmed.len()..).unwrap_or_default();
     if trimmed.is_empty() {
+        if *lex_state == LS::StartOfLine {
+            lex_trailing_whitespace(trailing, location, lex_data);
+        }
         return;
     }
-    let last = trimmed.len().checked_sub(1).expect("trimmed is not empty");
-    for (idx, ch) in trimmed.chars().enumerate() {
+    let chars = if lex_data.options().translates_trigraphs() {
+        translate_trigraphs(trimmed)
+    } else {
+        trimmed.chars().map(|ch| (ch, None)).collect()
+    };
+    let last = chars.len().checked_sub(1).expect("trimmed is not empty");
+    let continuation = chars.last().is_some_and(|&(ch, _)| ch == '\\');
+    let mut chars_iter = chars.into_iter().enumerate();
+    for (idx, (ch, trigraph)) in chars_iter.by_ref() {
+        let in_literal = matches!(lex_state, LS::Char(..) | LS::Str(..));
         lex_char(
             ch,
             location,
@@ -196,23 +579,182 @@ fn lex_line(line: &str, location: &mut Location, lex_data: &mut LexingData, lex_
             &mut escape_state,
             idx == last,
         );
-        if let Err(err) = location.incr_col() {
-            lex_data.push_err(err);
+        let width = if trigraph.is_some() { 3usize } else { 1usize };
+        if in_literal && let LS::Char(_, len, _) | LS::Str(_, len, _) = lex_state {
+            // the quotes are not counted in the length of the literal
+            *len = len.saturating_add(width);
+        }
+        for _ in 0usize..width {
+            if let Err(err) = location.incr_col(lex_data.options().max_columns()) {
+                lex_data.push_err(err);
+                break;
+            }
+        }
+        if let Some(third) = trigraph {
+            lex_data.push_err(trigraph_warning(location, ch, third));
         }
         if lex_data.is_end_line() {
             break;
         }
     }
+    // a universal character name can't be continued on the next line
+    end_pending_escape(lex_data, lex_state, &mut escape_state, location);
     if escape_state != EscapeState::Single {
         end_current(lex_state, lex_data, location);
     }
-    if line.trim_end().ends_with('\\') {
+    // rest of a line comment, only kept with the whitespace tokens
+    if lex_data.is_in_comment() && lex_data.is_end_line() {
+        for (_, (ch, trigraph)) in chars_iter {
+            lex_data.push_comment_char(ch);
+            let width = if trigraph.is_some() { 3usize } else { 1usize };
+            for _ in 0usize..width {
+                if location.incr_col(usize::MAX).is_err() {
+                    break;
+                }
+            }
+        }
+        lex_data.close_comment(&location.to_owned().into_past_with_length(1));
+        lex_trailing_whitespace(trailing, location, lex_data);
+    }
+    if !continuation && !lex_data.is_end_line() && !matches!(lex_state, LS::Comment(_)) {
+        lex_trailing_whitespace(trailing, location, lex_data);
+    }
+    if continuation {
         if line.ends_with(char::is_whitespace) {
-            lex_data.push_err(location.to_suggestion(
-                "found white space after '\\' at EOL. Please remove the space.".to_owned(),
-            ));
+            // the space is trimmed and the line is continued, but in standard
+            // C the '\' must be directly followed by the newline
+            let err = if lex_data.options().is_pedantic() {
+                location.to_failure("Found white space after '\\' at end of line: this is not a line continuation, as the '\\' must be directly followed by the newline. Please remove the space.".to_owned())
+            } else {
+                location.to_suggestion("Found white space after '\\' at end of line: it was treated as a line continuation, but in standard C the '\\' must be directly followed by the newline. Please remove the space.".to_owned())
+            };
+            lex_data.push_err(err.with_code(ErrorCode::SpaceAfterBackslash));
+        }
+        if last_line {
+            lex_data.push_err(
+                location
+                    .to_owned()
+                    .into_past_with_length(1)
+                    .into_failure(
+                        "Found '\\' at end of file: backslash-newline at end of file is illegal, as there is no next line to continue on.".to_owned(),
+                    )
+                    .with_code(ErrorCode::BackslashAtEndOfFile),
+            );
+            end_current(lex_state, lex_data, location);
         }
     } else {
+        lex_data.close_comment(&location.to_owned().into_past_with_length(1));
         *lex_state = LS::default();
     }
 }
+
+/// Pushes the whitespace at the end of a line, that is trimmed before lexing
+/// the line.
+///
+/// This does nothing if the whitespace tokens aren't enabled in the
+/// [`Options`].
+fn lex_trailing_whitespace(trailing: &str, location: &mut Location, lex_data: &mut LexingData) {
+    for ch in trailing.chars() {
+        lex_data.push_whitespace(ch, location);
+        if let Err(err) = location.incr_col(lex_data.options().max_columns()) {
+            lex_data.push_err(err);
+            break;
+        }
+    }
+}
+
+/// Takes the encoding prefix of a char or a string literal, like the `L` of
+/// `L"str"`, from the identifier that is being lexed before the `quote`.
+///
+/// Like the keywords, the prefixes introduced after the standard are read as
+/// identifiers. The `u8` prefix is only allowed for chars since C23.
+fn take_prefix(
+    state: &mut LS,
+    quote: char,
+    lex_data: &mut LexingData,
+    location: &Location,
+) -> Encoding {
+    let LS::Ident(ident) = state else {
+        return Encoding::Plain;
+    };
+    let Some(encoding) = Encoding::from_prefix(ident.value()) else {
+        return Encoding::Plain;
+    };
+    let standard = lex_data.options().standard();
+    let prefix_location = location.to_owned().into_past_with_length(ident.len());
+    if encoding.since() > standard {
+        lex_data.push_err(
+            prefix_location
+                .to_suggestion(format!(
+                    "'{encoding}' is an encoding prefix since {}, but the standard is {standard}: it is read as an identifier.",
+                    encoding.since()
+                ))
+                .with_code(ErrorCode::PrefixNotInStandard),
+        );
+        return Encoding::Plain;
+    }
+    if quote == '\'' && encoding == Encoding::Utf8 && standard < CStandard::C23 {
+        lex_data.push_err(
+            prefix_location
+                .into_failure(format!(
+                    "Found a char with the prefix 'u8', but this prefix is only allowed for chars since C23, and the standard is {standard}. Consider removing the prefix."
+                ))
+                .with_code(ErrorCode::Utf8CharPrefix),
+        );
+    }
+    *state = LS::Unset;
+    encoding
+}
+
+/// Returns the warning for a trigraph that was translated.
+///
+/// `location` is the location right after the trigraph.
+fn trigraph_warning(location: &Location, ch: char, third: char) -> CompileError {
+    location
+        .to_owned()
+        .into_past_with_length(3)
+        .to_warning(format!(
+            "Trigraph '??{third}' was translated to '{ch}', but trigraphs were removed in C23. Consider writing '{ch}' instead."
+        ))
+        .with_code(ErrorCode::TranslatedTrigraph)
+}
+
+/// Translates the trigraphs of a line into the characters they represent.
+///
+/// Every character is returned with the last character of the trigraph it was
+/// translated from, if it was a trigraph. For instance, `??=` is translated
+/// into `('#', Some('='))`.
+fn translate_trigraphs(line: &str) -> Vec<(char, Option<char>)> {
+    let mut translated = Vec::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(ch) = rest.chars().next() {
+        let trigraph = rest
+            .strip_prefix("??")
+            .and_then(|after| after.chars().next())
+            .and_then(|third| Some((trigraph_value(third)?, third)));
+        if let Some((value, third)) = trigraph {
+            translated.push((value, Some(third)));
+            rest = rest.get(3..).unwrap_or_default();
+        } else {
+            translated.push((ch, None));
+            rest = rest.get(ch.len_utf8()..).unwrap_or_default();
+        }
+    }
+    translated
+}
+
+/// Returns the character represented by the trigraph `??third`, if it exists.
+const fn trigraph_value(third: char) -> Option<char> {
+    match third {
+        '=' => Some('#'),
+        '/' => Some('\\'),
+        '\'' => Some('^'),
+        '(' => Some('['),
+        ')' => Some(']'),
+        '!' => Some('|'),
+        '<' => Some('{'),
+        '>' => Some('}'),
+        '-' => Some('~'),
+        _ => None,
+    }
+}
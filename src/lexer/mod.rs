@@ -7,12 +7,22 @@ pub mod api {
 
     #![allow(clippy::pub_use)]
 
-    pub use super::lex_content::lex_file;
-    pub use super::numbers::api::Number;
-    pub use super::types::api::{Keyword, Symbol, Token, TokenValue, display_tokens};
+    pub use super::conditionals::Macro;
+    pub use super::lex_content::{
+        lex_file, lex_file_by_lines, lex_file_with_macros, lex_file_with_options
+    };
+    pub use super::numbers::api::{Number, NumberType};
+    pub use super::relex::relex_file;
+    pub use super::stream::Lexer;
+    pub use super::types::api::{
+        Encoding, Keyword, Symbol, Token, TokenValue, display_tokens, modernise_keywords
+    };
 }
 
+mod conditionals;
 mod lex_content;
 mod numbers;
+mod relex;
 mod state;
+mod stream;
 mod types;
@@ -8,7 +8,8 @@ use crate::errors::api::Location;
 
 /// Parses a binary value.
 ///
-/// The input doesn't contain the prefix ('0b') or the suffix (e.g. 'ULL').
+/// The input doesn't contain the prefix ('0b' or '0B') or the suffix (e.g.
+/// 'ULL').
 ///
 /// # Returns
 ///
@@ -54,7 +55,7 @@ pub fn to_bin_value(
     } else {
         let first = literal
             .chars()
-            .find(|ch| matches!(ch, '0' | '1'))
+            .find(|ch| !matches!(ch, '0' | '1'))
             .expect("Exists according to line above");
         OverParseRes::from(location.to_failure(format!(
             "{ERR_PREFIX}a binary constant must only contain '0's and '1's. Found invalid character '{first}'."
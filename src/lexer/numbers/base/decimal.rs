@@ -3,9 +3,13 @@
 use core::num::ParseFloatError;
 use core::str::FromStr;
 
-use super::super::parse::OverParseRes;
-use super::super::types::arch_types::{Double, Float, Int, Long, LongLong, UInt, ULong, ULongLong};
+use super::super::big_uint::BigUint;
+use super::super::parse::{OutOfRange, OverParseRes};
+use super::super::types::arch_types::{
+    Double, Float, Int, Long, LongDouble, LongLong, UInt, ULong, ULongLong
+};
 use super::super::types::{ERR_PREFIX, Number, NumberType};
+use super::hexadecimal::FloatLayout;
 use crate::errors::api::{CompileRes, Location};
 
 /// Parses the stringifies version of a decimal number in a specific integer
@@ -13,13 +17,31 @@ use crate::errors::api::{CompileRes, Location};
 macro_rules! parse_number {
     ($location:ident, $nb_type:ident, $literal:tt, $($int:ident)*, $($float:ident)*) => {
         match $nb_type {
-            NumberType::LongDouble => OverParseRes::from($location.to_failure(format!("{ERR_PREFIX}`long double` not supported yet."))), //TODO: f128 not implemented
+            NumberType::LongDouble => to_long_double_value($literal, $location),
             $(NumberType::$int => $crate::lexer::numbers::macros::safe_parse_int!(ERR_PREFIX, $int, $location, $literal.parse::<$int>()).map(|nb| Number::$int(nb)),)*
-            $(NumberType::$float => OverParseRes::from(parse_and_error::<$float>($literal, $location).map(|nb| Number::$float(nb))?),)*
+            $(NumberType::$float => OverParseRes::from_value(parse_and_error::<$float>($literal, $location).map(|nb| Number::$float(nb))?),)*
         }
     };
 }
 
+/// Checks that a decimal float doesn't contain letters, other than the
+/// exponent.
+///
+/// The parser of Rust also accepts `inf`, `infinity` and `nan`, but they
+/// aren't float constants in C: they come from the `INFINITY` and `NAN`
+/// macros.
+fn check_float_letters(literal: &str, location: &Location) -> CompileRes<()> {
+    if literal
+        .chars()
+        .any(|ch| ch.is_ascii_alphabetic() && !matches!(ch, 'e' | 'E'))
+    {
+        return Err(location.to_failure(format!(
+            "{ERR_PREFIX}found '{literal}', but a decimal float can only contain digits, a full stop and an exponent. Use the 'INFINITY' and 'NAN' macros for the infinity and NaN."
+        )));
+    }
+    Ok(())
+}
+
 /// Parses the stringifies version of decimal number in a specific floating
 /// point type.
 fn parse_and_error<T>(literal: &str, location: &Location) -> CompileRes<T>
@@ -27,11 +49,140 @@ where
     T: FromStr,
     <T as FromStr>::Err: Into<ParseFloatError>,
 {
+    check_float_letters(literal, location)?;
     literal
         .parse::<T>()
         .map_err(|_err| location.to_failure(format!("{ERR_PREFIX}invalid decimal float number.")))
 }
 
+/// Parses a decimal float in a `long double`, rounded to the nearest value,
+/// ties to even.
+///
+/// Returns [`None`] if the literal isn't a decimal float, like `1.5e-3`. The
+/// second value tells if the number was rounded to the infinity or to zero.
+pub fn parse_long_double(literal: &str) -> Option<(LongDouble, Option<OutOfRange>)> {
+    let (mantissa, exponent) = match literal.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, parse_exponent(exponent)?),
+        None => (literal, 0),
+    };
+    let (int_part, decimal_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if (int_part.is_empty() && decimal_part.is_empty())
+        || !int_part
+            .chars()
+            .chain(decimal_part.chars())
+            .all(|ch| ch.is_ascii_digit())
+    {
+        return None;
+    }
+    let decimal_len = i64::try_from(decimal_part.len()).ok()?;
+    let (binary_mantissa, binary_exponent, sticky) = to_binary_parts(
+        &format!("{int_part}{decimal_part}"),
+        exponent.checked_sub(decimal_len)?,
+    );
+    let (bits, out_of_range) =
+        FloatLayout::LONG_DOUBLE.to_bits(binary_mantissa, binary_exponent, sticky);
+    Some((LongDouble::from_bits(bits), out_of_range))
+}
+
+/// Parses the exponent of a decimal float, after the `e`.
+///
+/// The exponents that are too big are bounded, as the number is then always
+/// infinite or 0.
+fn parse_exponent(exponent: &str) -> Option<i64> {
+    // beyond this bound, the number is always 0 or infinite
+    const MAX_EXPONENT: i64 = 1 << 32;
+    let negative = exponent.starts_with('-');
+    let digits = exponent.trim_start_matches(['-', '+']);
+    if digits.is_empty() {
+        return None;
+    }
+    let mut value = 0i64;
+    for ch in digits.chars() {
+        value = value
+            .saturating_mul(10)
+            .saturating_add(i64::from(ch.to_digit(10)?))
+            .min(MAX_EXPONENT);
+    }
+    if negative {
+        value.checked_neg()
+    } else {
+        Some(value)
+    }
+}
+
+/// Returns the number `digits * 10^exponent` as `mantissa * 2^exponent`, with
+/// a flag to know if the mantissa was truncated.
+///
+/// The number is written as a fraction of big integers, and the quotient is
+/// computed with 126 bits, more than the 113 bits of a `long double`, so that
+/// it is rounded only once.
+#[expect(clippy::arithmetic_side_effects, reason = "exponents are bounded")]
+fn to_binary_parts(digits: &str, exponent: i64) -> (u128, i64, bool) {
+    // the `long double` values are between 10^-4966 and 10^4933
+    const MAX_MAGNITUDE: i64 = 5000;
+    // with a binary exponent beyond this bound, the number is 0 or infinite
+    const OUT_OF_RANGE: i64 = 1 << 32;
+    // index of the highest bit of the quotient
+    const QUOTIENT_BITS: u64 = 125;
+    let significant = digits.trim_matches('0');
+    if significant.is_empty() {
+        return (0, 0, false);
+    }
+    let significant_len = i64::try_from(significant.len()).expect("the literal is small");
+    let trailing_zeros = i64::try_from(digits.trim_start_matches('0').len() - significant.len())
+        .expect("the literal is small");
+    let decimal_exponent = exponent + trailing_zeros;
+    let magnitude = decimal_exponent + significant_len;
+    if magnitude > MAX_MAGNITUDE {
+        return (1, OUT_OF_RANGE, false);
+    }
+    if magnitude < -MAX_MAGNITUDE {
+        return (1, -OUT_OF_RANGE, false);
+    }
+    let mut numerator = BigUint::from_digits(significant);
+    let mut denominator = BigUint::from(1);
+    if decimal_exponent >= 0 {
+        numerator.mul_pow(10, decimal_exponent.unsigned_abs());
+    } else {
+        denominator.mul_pow(10, decimal_exponent.unsigned_abs());
+    }
+    let shift = i64::try_from(numerator.bit_len()).expect("the number is bounded")
+        - i64::try_from(denominator.bit_len()).expect("the number is bounded")
+        - i64::try_from(QUOTIENT_BITS).expect("small constant");
+    if shift < 0 {
+        numerator = numerator.shl(shift.unsigned_abs());
+    } else {
+        denominator = denominator.shl(shift.unsigned_abs());
+    }
+    let mut quotient = 0u128;
+    let mut part = denominator.shl(QUOTIENT_BITS);
+    for bit in (0..=QUOTIENT_BITS).rev() {
+        if part <= numerator {
+            numerator.sub_assign(&part);
+            quotient |= 1 << bit;
+        }
+        part.shr_one();
+    }
+    (quotient, shift, !numerator.is_zero())
+}
+
+/// Parses the stringified version of a decimal number in a `long double`.
+///
+/// Like for the hexadecimal floats, the numbers that are rounded to the
+/// infinity are reported as overflows, and those that are rounded to zero as
+/// underflows.
+fn to_long_double_value(literal: &str, location: &Location) -> OverParseRes<Number> {
+    if let Err(err) = check_float_letters(literal, location) {
+        return OverParseRes::from(err);
+    }
+    let Some((value, out_of_range)) = parse_long_double(literal) else {
+        return OverParseRes::from(
+            location.to_failure(format!("{ERR_PREFIX}invalid decimal float number.")),
+        );
+    };
+    OverParseRes::from_value(Number::LongDouble(value)).add_out_of_range(out_of_range)
+}
+
 /// Parses a binary value.
 ///
 /// The input doesn't contain the suffix (e.g. 'ULL').
@@ -3,92 +3,102 @@
 #![allow(clippy::arbitrary_source_item_ordering)]
 
 use super::super::macros::parse_int_from_radix;
-use super::super::parse::OverParseRes;
+use super::super::parse::{OutOfRange, OverParseRes};
 use super::super::types::arch_types::{
-    Double, DoubleIntPart, Float, FloatIntPart, Int, Long, LongDouble, LongDoubleIntPart, LongLong, UInt, ULong, ULongLong
+    Double, Float, Int, Long, LongDouble, LongLong, UInt, ULong, ULongLong
 };
 use super::super::types::{ERR_PREFIX, Number, NumberType};
 use crate::errors::api::{CompileRes, Location};
 
-/// Implements the [`FloatingPoint`] for the floating-point types.
-macro_rules! impl_floating_point {
-    ($x:expr, $($type:ident)*) => {
-        $(#[expect(clippy::as_conversions, clippy::cast_precision_loss)]
-        impl FloatingPoint<concat_idents!($type, IntPart)> for $type {
-            const MANTISSA_SIZE: u32 = $x;
-
-            type Unsigned = concat_idents!($type, IntPart);
-
-
-            fn from_unsigned(
-                val: Self::Unsigned,
-                overflow: &mut bool,
-            ) -> Self {
-                if val >= (2 as Self::Unsigned).pow(Self::MANTISSA_SIZE) {
-                    *overflow = true;
-                }
-                val as Self
-            }
-
-            fn from_usize(
-                val: usize,
-                overflow: &mut bool,
-            ) -> Self {
-                if val >= 2usize.pow(Self::MANTISSA_SIZE) {
-                    *overflow = true;
-                }
-                val as Self
-            }
-        })*
-    };
-}
-
-/// Parses the stringified version of a number into a [`HexFloatData`].
-macro_rules! parse_hexadecimal_float {
-    ($overflow:expr, $nb_type:ident, $float_parse:ident, $($t:ident)*) => {{
-        #[expect(clippy::float_arithmetic, clippy::arithmetic_side_effects)]
-        #[expect(clippy::as_conversions)]
-        match $nb_type {
-            $(NumberType::$t => {
-                let int_part = $t::from_unsigned(
-                    <concat_idents!($t, IntPart)>::from_str_radix(&$float_parse.int_part, 16).expect("2 <= <= 36"),
-                    $overflow);
-                let exponent = $t::from_unsigned((2 as concat_idents!($t, IntPart)).pow($float_parse.get_exp()), $overflow);
-                let mut decimal_part: $t = 0.;
-                for (idx, ch) in $float_parse.decimal_part.chars().enumerate() {
-                    let digit_value = $t::from_unsigned(hex_char_to_int(ch).into(), $overflow);
-                    let exponent_pow = $t::from(16.).powf($t::from_usize(idx, $overflow) + 1.);
-                    decimal_part += digit_value / exponent_pow;
-                }
-                if $float_parse.exponent_neg.unwrap_or(false) {
-                   OverParseRes::from(Number::$t((int_part + decimal_part) / exponent))
-                } else {
-                    OverParseRes::from(Number::$t((int_part + decimal_part) * exponent))
-                }
-            },)*
-            _ => panic!("Never happens: nb_type is float"),
-        }
-    }};
+/// Layout of the bits of a floating-point type, in the IEEE 754 format.
+pub struct FloatLayout {
+    /// Bias of the exponent: the stored exponent is the real exponent plus
+    /// the bias.
+    bias: i64,
+    /// Number of bits of the mantissa, without the implicit leading `1`.
+    mantissa_size: u32,
 }
 
-impl_floating_point!(23, Double Float LongDouble);
+impl FloatLayout {
+    /// Layout of a `double`
+    const DOUBLE: Self = Self {
+        bias: 1023,
+        mantissa_size: 52,
+    };
+    /// Layout of a `float`
+    const FLOAT: Self = Self {
+        bias: 127,
+        mantissa_size: 23,
+    };
+    /// Layout of a `long double`
+    pub const LONG_DOUBLE: Self = Self {
+        bias: 16383,
+        mantissa_size: 112,
+    };
 
-/// Trait to try and convert the integer and decimal part inside the mantissa.
-///
-/// ``overflow`` is set to true if the value doesn't fix in the mantissa.
-trait FloatingPoint<T> {
-    /// Size of the mantissa
+    /// Returns the bits of the number `mantissa * 2^exponent`, rounded to
+    /// the nearest representable value, ties to even.
+    ///
+    /// `sticky` is true if the mantissa was truncated, and the truncated bits
+    /// weren't all zeros.
     ///
-    /// In the binary representation of the floating-point
-    /// values, there is one part for the exponent, and one point for the
-    /// digits, the latter is called 'mantissa'.
-    const MANTISSA_SIZE: u32;
-    /// The biggest unsigned integer type that can contain the mantissa.
-    type Unsigned;
-    /// Convert the integer-parsed value into the current floating-point type.
-    fn from_unsigned(val: T, overflow: &mut bool) -> Self;
-    /// Convert the usize-parsed value into the current floating-point type.
-    fn from_usize(val: usize, overflow: &mut bool) -> Self;
+    /// The second value tells if the number is too big and was rounded to the
+    /// infinity, or too small and was rounded to zero. The subnormal numbers
+    /// are not considered as out of range.
+    #[expect(clippy::arithmetic_side_effects, reason = "exponents are bounded")]
+    pub fn to_bits(
+        &self,
+        mantissa: u128,
+        exponent: i64,
+        sticky: bool,
+    ) -> (u128, Option<OutOfRange>) {
+        if mantissa == 0 {
+            return (0, None);
+        }
+        let size = i64::from(self.mantissa_size);
+        let highest = i64::from(mantissa.ilog2()) + exponent;
+        // exponent of the last bit that is kept, the subnormal numbers having
+        // less bits
+        let mut lowest = (highest - size).max(1 - self.bias - size);
+        let shift = lowest - exponent;
+        let mut kept = if shift <= 0 {
+            mantissa << shift.unsigned_abs()
+        } else if shift > 128 {
+            0
+        } else {
+            let shift_u32 = u32::try_from(shift).expect("0 < shift <= 128");
+            let kept = mantissa.checked_shr(shift_u32).unwrap_or(0);
+            let half = 1u128 << (shift_u32 - 1);
+            let rest = mantissa & ((half << 1u32).wrapping_sub(1));
+            if rest > half || (rest == half && (sticky || kept & 1 == 1)) {
+                kept + 1
+            } else {
+                kept
+            }
+        };
+        if kept >> (self.mantissa_size + 1) != 0 {
+            kept >>= 1u32;
+            lowest += 1;
+        }
+        let implicit_one = 1u128 << self.mantissa_size;
+        if kept < implicit_one {
+            // subnormal number, with an exponent of 0
+            return (kept, (kept == 0).then_some(OutOfRange::Underflow));
+        }
+        let biased = lowest + size + self.bias;
+        // the biggest exponent is reserved for the infinity and NaN
+        let max_biased = 2 * self.bias + 1;
+        let infinity = u128::try_from(max_biased).expect("bias > 0") << self.mantissa_size;
+        if biased >= max_biased {
+            (infinity, Some(OutOfRange::Overflow))
+        } else {
+            let biased_u128 = u128::try_from(biased).expect("normal numbers have exponents > 0");
+            (
+                (biased_u128 << self.mantissa_size) | (kept - implicit_one),
+                None,
+            )
+        }
+    }
 }
 
 /// Stores the data of an hexadecimal constant
@@ -123,15 +133,44 @@ impl HexFloatData {
         }
     }
 
-    /// Returns the exponent of the number constant.
-    fn get_exp(&self) -> u32 {
-        if self.exponent.is_empty() {
-            0
-        } else {
-            self.exponent
-                .parse()
-                .expect("Never fails: contains only ascii digits and not empty")
+    /// Returns the number as `mantissa * 2^exponent`, with a flag to know if
+    /// the mantissa was truncated.
+    ///
+    /// The digits that don't fit in the mantissa are dropped: they only
+    /// increase the exponent if they are before the full stop, and set the
+    /// flag if they aren't zeros.
+    #[expect(clippy::arithmetic_side_effects, reason = "exponents are bounded")]
+    fn to_binary_parts(&self) -> (u128, i64, bool) {
+        // beyond this bound, the number is always 0 or infinite
+        const MAX_EXPONENT: i64 = 1 << 32;
+        let mut exponent = self.exponent.chars().fold(0i64, |acc, ch| {
+            (acc * 10 + i64::from(hex_char_to_int(ch))).min(MAX_EXPONENT)
+        });
+        if self.exponent_neg == Some(true) {
+            exponent = -exponent;
         }
+        let mut mantissa = 0u128;
+        let mut sticky = false;
+        for (ch, is_decimal) in self
+            .int_part
+            .chars()
+            .map(|ch| (ch, false))
+            .chain(self.decimal_part.chars().map(|ch| (ch, true)))
+        {
+            let digit = u128::from(hex_char_to_int(ch));
+            if mantissa >> 124u32 == 0 {
+                mantissa = (mantissa << 4u32) | digit;
+                if is_decimal {
+                    exponent = (exponent - 4).max(-MAX_EXPONENT);
+                }
+            } else {
+                sticky |= digit != 0;
+                if !is_decimal {
+                    exponent = (exponent + 4).min(MAX_EXPONENT);
+                }
+            }
+        }
+        (mantissa, exponent, sticky)
     }
 }
 
@@ -324,13 +363,41 @@ pub fn to_hex_value(
         );
     }
     if nb_type.is_int() {
-        parse_int_from_radix!(location,
+        return parse_int_from_radix!(location,
            nb_type, literal, "never fails", 16, Int Long LongLong UInt ULong ULongLong
-        )
-    } else {
-        let mut overflow = false;
-        let res =
-            parse_hexadecimal_float!(&mut overflow, nb_type, float_data, Float Double LongDouble);
-        if overflow { res.add_overflow() } else { res }
+        );
+    }
+    if float_data.state != HexFloatParseState::Exponent {
+        return OverParseRes::from(location.to_failure(format!(
+            "{ERR_PREFIX}a hexadecimal float must have a 'p' exponent, like in '0x1.8p0'."
+        )));
     }
+    if float_data.int_part.is_empty() && float_data.decimal_part.is_empty() {
+        return OverParseRes::from(location.to_failure(format!(
+            "{ERR_PREFIX}found no digits before the 'p' exponent of the hexadecimal float."
+        )));
+    }
+    let (mantissa, exponent, sticky) = float_data.to_binary_parts();
+    #[expect(clippy::wildcard_enum_match_arm)]
+    let (number, out_of_range) = match nb_type {
+        NumberType::Float => {
+            let (bits, out_of_range) = FloatLayout::FLOAT.to_bits(mantissa, exponent, sticky);
+            let float_bits = u32::try_from(bits).expect("a float has 32 bits");
+            (Number::Float(Float::from_bits(float_bits)), out_of_range)
+        }
+        NumberType::Double => {
+            let (bits, out_of_range) = FloatLayout::DOUBLE.to_bits(mantissa, exponent, sticky);
+            let double_bits = u64::try_from(bits).expect("a double has 64 bits");
+            (Number::Double(Double::from_bits(double_bits)), out_of_range)
+        }
+        NumberType::LongDouble => {
+            let (bits, out_of_range) = FloatLayout::LONG_DOUBLE.to_bits(mantissa, exponent, sticky);
+            (
+                Number::LongDouble(LongDouble::from_bits(bits)),
+                out_of_range,
+            )
+        }
+        _ => panic!("Never happens: nb_type is float"),
+    };
+    OverParseRes::from_value(number).add_out_of_range(out_of_range)
 }
@@ -0,0 +1,208 @@
+//! Module that defines big unsigned integers.
+//!
+//! The parser of Rust doesn't support [`f128`], so the decimal `long double`
+//! constants are converted to binary, and back to decimal, with exact integer
+//! arithmetic.
+
+use core::cmp::Ordering;
+use core::fmt::Write as _;
+
+/// Base of the digits of a [`BigUint`]
+const DIGIT_BITS: u64 = 32;
+
+/// Unsigned integer of any size
+///
+/// The integer is stored in base `2^32`, from the least significant digit to
+/// the most significant one, without leading zeros.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigUint(Vec<u32>);
+
+impl BigUint {
+    /// Returns the number of bits of the integer, without the leading zeros.
+    #[expect(clippy::arithmetic_side_effects, reason = "the length is small")]
+    pub fn bit_len(&self) -> u64 {
+        self.0.last().map_or(0, |last| {
+            let len = u64::try_from(self.0.len()).expect("the length is small");
+            (len - 1) * DIGIT_BITS + u64::from(u32::BITS - last.leading_zeros())
+        })
+    }
+
+    /// Divides the integer by `divisor`, and returns the remainder.
+    #[expect(
+        clippy::arithmetic_side_effects,
+        clippy::integer_division,
+        clippy::integer_division_remainder_used,
+        reason = "the remainder is smaller than the divisor"
+    )]
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for digit in self.0.iter_mut().rev() {
+            let current = (remainder << DIGIT_BITS) | u64::from(*digit);
+            *digit = u32::try_from(current / u64::from(divisor)).expect("remainder < divisor");
+            remainder = current % u64::from(divisor);
+        }
+        self.normalise();
+        u32::try_from(remainder).expect("remainder < divisor")
+    }
+
+    /// Makes an integer from its decimal digits.
+    ///
+    /// The characters that aren't digits are ignored.
+    pub fn from_digits(digits: &str) -> Self {
+        let mut value = Self(vec![]);
+        for digit in digits.chars().filter_map(|ch| ch.to_digit(10)) {
+            value.mul_add(10, digit);
+        }
+        value
+    }
+
+    /// Checks if the integer is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Multiplies the integer by `factor`, and adds `term`.
+    #[expect(clippy::arithmetic_side_effects, reason = "the result fits in 64 bits")]
+    fn mul_add(&mut self, factor: u32, term: u32) {
+        let mut carry = u64::from(term);
+        for digit in &mut self.0 {
+            let current = u64::from(*digit) * u64::from(factor) + carry;
+            *digit = truncate(current);
+            carry = current >> DIGIT_BITS;
+        }
+        if carry != 0 {
+            self.0.push(truncate(carry));
+        }
+        self.normalise();
+    }
+
+    /// Multiplies the integer by `base^exponent`.
+    ///
+    /// The integer is multiplied by the biggest powers of `base` that fit in
+    /// a digit.
+    #[expect(clippy::arithmetic_side_effects, reason = "remaining > 0")]
+    pub fn mul_pow(&mut self, base: u32, exponent: u64) {
+        let mut remaining = exponent;
+        while remaining > 0 {
+            let mut factor = base;
+            remaining -= 1;
+            while remaining > 0
+                && let Some(next) = factor.checked_mul(base)
+            {
+                factor = next;
+                remaining -= 1;
+            }
+            self.mul_add(factor, 0);
+        }
+    }
+
+    /// Removes the leading zeros.
+    fn normalise(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+
+    /// Returns the integer shifted to the left by `shift` bits.
+    #[expect(
+        clippy::integer_division,
+        clippy::integer_division_remainder_used,
+        reason = "the shift is small"
+    )]
+    pub fn shl(&self, shift: u64) -> Self {
+        let zeros = usize::try_from(shift / DIGIT_BITS).expect("the shift is small");
+        let bits = shift % DIGIT_BITS;
+        let mut digits = vec![0; zeros];
+        let mut carry = 0u32;
+        for digit in &self.0 {
+            let current = u64::from(*digit) << bits;
+            digits.push(truncate(current) | carry);
+            carry = truncate(current >> DIGIT_BITS);
+        }
+        digits.push(carry);
+        let mut value = Self(digits);
+        value.normalise();
+        value
+    }
+
+    /// Divides the integer by 2.
+    pub fn shr_one(&mut self) {
+        let mut carry = 0u32;
+        for digit in self.0.iter_mut().rev() {
+            let low_bit = *digit & 1;
+            *digit = (*digit >> 1u32) | (carry << (u32::BITS - 1));
+            carry = low_bit;
+        }
+        self.normalise();
+    }
+
+    /// Subtracts `other` from the integer, that must be greater or equal.
+    pub fn sub_assign(&mut self, other: &Self) {
+        let mut borrow = false;
+        let mut other_digits = other.0.iter();
+        for digit in &mut self.0 {
+            let (partial, first_borrow) =
+                digit.overflowing_sub(other_digits.next().copied().unwrap_or(0));
+            let (result, second_borrow) = partial.overflowing_sub(u32::from(borrow));
+            *digit = result;
+            borrow = first_borrow || second_borrow;
+        }
+        debug_assert!(!borrow, "self >= other");
+        self.normalise();
+    }
+
+    /// Returns the decimal digits of the integer.
+    pub fn to_decimal(&self) -> String {
+        let mut value = self.clone();
+        let mut chunks = vec![];
+        while !value.is_zero() {
+            chunks.push(value.div_rem_small(1_000_000_000));
+        }
+        let mut digits = chunks
+            .pop()
+            .map_or_else(|| "0".to_owned(), |chunk| chunk.to_string());
+        for chunk in chunks.iter().rev() {
+            write!(digits, "{chunk:09}").expect("writing to a string never fails");
+        }
+        digits
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        let mut digits = vec![];
+        let mut rest = value;
+        while rest != 0 {
+            digits.push(truncate_u128(rest));
+            rest >>= DIGIT_BITS;
+        }
+        Self(digits)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .len()
+            .cmp(&other.0.len())
+            .then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns the lowest 32 bits of a number.
+#[expect(clippy::as_conversions, clippy::cast_possible_truncation)]
+const fn truncate(value: u64) -> u32 {
+    value as u32
+}
+
+/// Returns the lowest 32 bits of a number.
+#[expect(clippy::as_conversions, clippy::cast_possible_truncation)]
+const fn truncate_u128(value: u128) -> u32 {
+    value as u32
+}
@@ -9,14 +9,60 @@ use super::types::arch_types::Int;
 use super::types::{Base, ERR_PREFIX, Number, NumberType};
 use crate::errors::api::{CompileRes, Location, SingleRes};
 
+/// Checks that the digit separators of a number constant, like in `1'000`,
+/// are each between two digits.
+///
+/// A separator can't be doubled, nor be at the end of the constant, nor be
+/// next to the prefix (`0x'1`), to a suffix (`1'u`) or to a full stop (`1'.5`).
+fn check_digit_separators(literal: &str, location: &Location) -> CompileRes<()> {
+    let lower = literal.to_ascii_lowercase();
+    let is_hex = lower.starts_with("0x");
+    let prefix_size = if is_hex || lower.starts_with("0b") {
+        2
+    } else {
+        0
+    };
+    let is_digit = |ch: char| {
+        if is_hex {
+            ch.is_ascii_hexdigit()
+        } else {
+            ch.is_ascii_digit()
+        }
+    };
+    let chars = literal.chars().collect::<Vec<_>>();
+    for (idx, ch) in chars.iter().enumerate() {
+        if *ch != '\'' {
+            continue;
+        }
+        let previous = idx.checked_sub(1).and_then(|prev_idx| chars.get(prev_idx));
+        let next = chars.get(idx.saturating_add(1));
+        let separator = match (previous, next) {
+            (_, Some('\'')) => "a doubled digit separator".to_owned(),
+            (_, None) => "a trailing digit separator".to_owned(),
+            (Some(prefix), _) if prefix_size != 0 && idx == prefix_size => {
+                format!("a digit separator right after the '0{prefix}' prefix")
+            }
+            (Some(prev), _) if !is_digit(*prev) => format!("a digit separator after '{prev}'"),
+            (_, Some(next_ch)) if !is_digit(*next_ch) => {
+                format!("a digit separator before '{next_ch}'")
+            }
+            _ => continue,
+        };
+        return Err(location.to_failure(format!(
+            "Found {separator} in the number constant '{literal}', but digit separators must be between two digits."
+        )));
+    }
+    Ok(())
+}
+
 /// Finds the base of the number constant by looking at the prefix
 ///
 /// # Returns
 ///
 /// This function returns
 ///
-/// - [`Base::Binary`] if the literal starts with "0b";
-/// - [`Base::Hexadecimal`] if the literal starts with "0x";
+/// - [`Base::Binary`] if the literal starts with "0b" or "0B";
+/// - [`Base::Hexadecimal`] if the literal starts with "0x" or "0X";
 /// - [`Base::Octal`] if the literal starts with "0";
 /// - [`Base::Decimal`] in every other case.
 fn get_base(literal: &str, nb_type: &NumberType, location: &Location) -> CompileRes<Base> {
@@ -24,25 +70,29 @@ fn get_base(literal: &str, nb_type: &NumberType, location: &Location) -> Compile
     let first = chars.next().expect("len >= 1");
     let second = chars.next().expect("len >= 2");
 
-    let one_char = literal
+    let value_len = literal
         .len()
         .checked_sub(nb_type.suffix_size())
-        .expect("literal contains the suffix")
-        == 1;
+        .expect("literal contains the suffix");
+    let one_char = value_len == 1;
+    // only the prefix, like `0b` or `0xu`
+    let prefix_only = value_len == 2;
 
     match (first, second) {
-        ('0', 'x') if one_char => {
-            Err(location.to_failure(format!("{ERR_PREFIX}no digits found after 0x prefix")))
-        }
-        ('0', 'b') if one_char => {
-            Err(location.to_failure(format!("{ERR_PREFIX}no digits found after 0b prefix")))
-        }
-        ('0', 'x') => Ok(Base::Hexadecimal),
-        ('0', 'b') if nb_type.is_int() => Ok(Base::Binary),
-        ('0', 'b') if matches!(nb_type, NumberType::Float) => {
+        ('0', prefix @ ('x' | 'X')) if prefix_only => Err(location.to_failure(format!(
+            "{ERR_PREFIX}no digits found after 0{prefix} prefix"
+        ))),
+        ('0', prefix @ ('b' | 'B')) if prefix_only => Err(location.to_failure(format!(
+            "{ERR_PREFIX}no digits found after 0{prefix} prefix. Please add at least one '0' or '1'."
+        ))),
+        ('0', 'x' | 'X') => Ok(Base::Hexadecimal),
+        ('0', 'b' | 'B') if nb_type.is_int() => Ok(Base::Binary),
+        ('0', 'b' | 'B') if matches!(nb_type, NumberType::Float) => {
             Err(location.to_failure(format!("{ERR_PREFIX}a binary can't be a `float`")))
         }
-        ('0', 'b') => Err(location.to_failure(format!("{ERR_PREFIX}a binary can't be a `double`"))),
+        ('0', 'b' | 'B') => {
+            Err(location.to_failure(format!("{ERR_PREFIX}a binary can't be a `double`")))
+        }
         ('0', '0'..='9') if nb_type.is_int() => Ok(Base::Octal),
         ('0', _) if nb_type.is_int() && one_char => Ok(Base::Decimal),
         ('0', ch) if nb_type.is_int() => Err(location.to_failure(format!(
@@ -73,6 +123,30 @@ fn get_first_invalid_char(literal: &str, base: &Base) -> Option<char> {
     }
 }
 
+/// Returns the invalid suffix of the literal, starting at the first invalid
+/// character.
+///
+/// A suffix is only returned if the invalid character is a letter or an
+/// underscore, as in `123abc`, and not a digit, as in `0b12`.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert!(get_invalid_suffix("123abc", "123abc", 'a') == Some("abc"));
+/// assert!(get_invalid_suffix("0b12", "12", '2') == None);
+/// ```
+fn get_invalid_suffix<'lit>(literal: &'lit str, value: &str, ch: char) -> Option<&'lit str> {
+    if !ch.is_ascii_alphabetic() && ch != '_' {
+        return None;
+    }
+    let value_start = literal.len().checked_sub(value.len())?;
+    let suffix_start = literal
+        .get(value_start..)?
+        .find(ch)?
+        .checked_add(value_start)?;
+    literal.get(suffix_start..)
+}
+
 /// Gets the type of the number constant by looking at the suffix.
 ///
 /// # Returns
@@ -93,7 +167,7 @@ fn get_first_invalid_char(literal: &str, base: &Base) -> Option<char> {
 /// - if there is a 'i' suffix (for complex numbers);
 /// - there are more than 2 'l's in the suffix.
 fn get_number_type(literal: &str, location: &Location) -> CompileRes<NumberType> {
-    let is_hex = literal.starts_with("0x");
+    let is_hex = literal.starts_with("0x") || literal.starts_with("0X");
     /* literal characteristics */
     let double_or_float = literal.contains('.')
         || (is_hex && (literal.contains(['p', 'P'])))
@@ -157,6 +231,9 @@ fn get_number_type(literal: &str, location: &Location) -> CompileRes<NumberType>
 
 /// Functions to try parse a literal into a number.
 ///
+/// The digit separators, like in `1'000`, are checked and removed before the
+/// conversion.
+///
 /// # Returns
 ///
 /// - `Some(number)` if literal is a number
@@ -184,9 +261,13 @@ pub fn literal_to_number(
 
     let begin_location = location.to_owned().into_past_with_length(literal.len());
 
+    if let Err(err) = check_digit_separators(literal.value(), &begin_location) {
+        lex_data.push_err(err);
+        return None;
+    }
+    let digits = literal.value().replace('\'', "");
     let (val, error) =
-        literal_to_number_err(literal.value(), begin_location, lex_data.last_is_minus())
-            .into_value_err();
+        literal_to_number_err(&digits, begin_location, lex_data.last_is_minus()).into_value_err();
     if let Some(err) = error {
         lex_data.push_err(err);
     }
@@ -203,6 +284,7 @@ fn literal_to_number_err(
     signed: bool,
 ) -> SingleRes<Option<Number>> {
     let mut nb_type = get_number_type(literal, &location)?;
+    let inferred_type = nb_type.to_string();
     let base = get_base(literal, &nb_type, &location)?;
     let value = literal
         .get(base.prefix_size()..literal.len().checked_sub(nb_type.suffix_size()).expect("literal contains the suffix"))
@@ -215,12 +297,24 @@ fn literal_to_number_err(
     }
 
     if let Some(ch) = get_first_invalid_char(value, &base) {
+        if let Some(suffix) = get_invalid_suffix(literal, value, ch) {
+            return SingleRes::from(location.into_failure(format!(
+                "Invalid suffix \"{suffix}\" on {} constant.",
+                if nb_type.is_int() {
+                    "integer"
+                } else {
+                    "floating"
+                }
+            )));
+        }
         return SingleRes::from(location.into_failure(format!(
             "{ERR_PREFIX}found invalid character '{ch}' in {} base.",
             base.repr(),
         )));
     }
 
+    // unsuffixed decimal constants are never given an unsigned type
+    let signed_only = signed || matches!(base, Base::Decimal);
     loop {
         let parse_res = match base {
             Base::Binary => binary::to_bin_value(value, &nb_type, &location),
@@ -229,11 +323,18 @@ fn literal_to_number_err(
             Base::Octal => octal::to_oct_value(value, &nb_type, &location),
         };
         if parse_res.overflowed()
-            && let Some(new_type) = nb_type.incr_size(signed)
+            && let Some(new_type) = nb_type.incr_size(signed_only)
         {
             nb_type = new_type;
         } else {
-            return parse_res.ignore_overflow(literal, &location);
+            // the message names the inferred type, and the biggest type that
+            // was attempted
+            let target = if nb_type.to_string() == inferred_type {
+                format!("`{nb_type}`")
+            } else {
+                format!("`{inferred_type}`, nor in the bigger types up to `{nb_type}`")
+            };
+            return parse_res.ignore_overflow(literal, &target, &location);
         }
     }
 }
@@ -0,0 +1,43 @@
+//! Module to serialise the `long double` constants.
+//!
+//! Serde can't serialise a [`f128`], so a `long double` is serialised as its
+//! spelling in C source code, without the suffix, like `"0.1"`.
+
+use serde::de::Error as _;
+use serde::{Deserialize as _, Deserializer, Serializer};
+
+use super::base::decimal::parse_long_double;
+use super::types::arch_types::LongDouble;
+use super::types::long_double_to_source;
+
+/// Deserialises a `long double` from its spelling in C source code.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<LongDouble, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let source = String::deserialize(deserializer)?;
+    let (negative, abs) = source
+        .strip_prefix('-')
+        .map_or((false, source.as_str()), |abs| (true, abs));
+    let value = if abs == "(0.0 / 0.0)" {
+        LongDouble::NAN
+    } else {
+        parse_long_double(abs)
+            .ok_or_else(|| D::Error::custom(format!("invalid long double '{source}'")))?
+            .0
+    };
+    // the sign bit is set for the negative numbers
+    Ok(if negative {
+        LongDouble::from_bits(value.to_bits() | (1 << 127))
+    } else {
+        value
+    })
+}
+
+/// Serialises a `long double` as its spelling in C source code.
+pub fn serialize<S>(value: &LongDouble, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&long_double_to_source(*value))
+}
@@ -25,7 +25,7 @@ macro_rules! safe_parse_int {
         use $crate::lexer::numbers::api::OverParseRes;
         let parsed: Result<$dest_type, core::num::ParseIntError> = $function_call.map_err(|err| err.into());
         match parsed {
-            Ok(nb) => OverParseRes::from(nb),
+            Ok(nb) => OverParseRes::from_value(nb),
             Err(err) => match *err.kind() {
                 core::num::IntErrorKind::Empty => panic!("Never happens. Checks for non empty."),
                 core::num::IntErrorKind::InvalidDigit => OverParseRes::from($location.to_failure(format!(
@@ -22,11 +22,14 @@ pub mod api {
     pub use super::from_literal::literal_to_number;
     pub(crate) use super::macros::safe_parse_int;
     pub use super::parse::OverParseRes;
-    pub use super::types::Number;
+    pub use super::types::{Number, NumberType};
 }
 
 mod base;
+mod big_uint;
 mod from_literal;
+#[cfg(feature = "serde")]
+mod long_double_serde;
 mod macros;
 mod parse;
 mod types;
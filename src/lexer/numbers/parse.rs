@@ -1,11 +1,20 @@
 //! Module that defines the result and error types used for parsing a number
 //! constant.
 
-use core::{convert, fmt, ops};
+use core::{convert, ops};
 
 use super::types::Number;
 use crate::errors::api::{CompileError, CompileRes, Location, SingleRes};
 
+/// Number that doesn't fit in the range of a floating-point type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutOfRange {
+    /// The number is too big, and was rounded to the infinity.
+    Overflow,
+    /// The number is too small, and was rounded to zero.
+    Underflow,
+}
+
 /// Number parse result with overflow
 ///
 /// It can contain errors and values at the same time.
@@ -25,9 +34,21 @@ pub enum OverParseRes<T> {
     ValueErr(T, CompileError),
     /// Number parsing succeeded; but with an overflow
     ValueOverflow(T),
+    /// Number parsing succeeded; but the number was rounded to zero.
+    ValueUnderflow(T),
 }
 
 impl<T> OverParseRes<T> {
+    /// Adds the warning of a number that is out of the range of a
+    /// floating-point type, if there is one.
+    pub fn add_out_of_range(self, out_of_range: Option<OutOfRange>) -> Self {
+        match out_of_range {
+            Some(OutOfRange::Overflow) => self.add_overflow(),
+            Some(OutOfRange::Underflow) => self.add_underflow(),
+            None => self,
+        }
+    }
+
     /// Adds an overflow warning to the current result
     ///
     /// # Note
@@ -37,7 +58,28 @@ impl<T> OverParseRes<T> {
     pub fn add_overflow(self) -> Self {
         match self {
             Self::Value(val) => Self::ValueOverflow(val),
-            Self::Err(_) | Self::ValueErr(..) | Self::ValueOverflow(..) | Self::Overflow => self,
+            Self::Err(_)
+            | Self::ValueErr(..)
+            | Self::ValueOverflow(..)
+            | Self::ValueUnderflow(..)
+            | Self::Overflow => self,
+        }
+    }
+
+    /// Adds an underflow warning to the current result.
+    ///
+    /// # Note
+    ///
+    /// The warning is not added if the result is already an error and doesn't
+    /// contain any value.
+    pub fn add_underflow(self) -> Self {
+        match self {
+            Self::Value(val) => Self::ValueUnderflow(val),
+            Self::Err(_)
+            | Self::ValueErr(..)
+            | Self::ValueOverflow(..)
+            | Self::ValueUnderflow(..)
+            | Self::Overflow => self,
         }
     }
 
@@ -61,18 +103,35 @@ impl<T> OverParseRes<T> {
         Self::Overflow
     }
 
+    /// Creates a [`OverParseRes`] from a successfully parsed value.
+    pub const fn from_value(value: T) -> Self {
+        Self::Value(value)
+    }
+
     /// Clamps to value if there is an overflow.
-    pub fn ignore_overflow(self, value: &str, location: &Location) -> SingleRes<Option<T>> {
+    ///
+    /// `target` describes the type that was attempted, like `` `int` ``, to
+    /// name it in the overflow message.
+    pub fn ignore_overflow(
+        self,
+        value: &str,
+        target: &str,
+        location: &Location,
+    ) -> SingleRes<Option<T>> {
         match self {
             Self::ValueOverflow(val) => SingleRes::from((
+                Some(val),
+                location.to_warning(format!("Overflow: {value} doesn't fit in {target}.")),
+            )),
+            Self::Overflow => SingleRes::from(
+                location.to_failure(format!("Overflow: {value} doesn't fit in {target}.")),
+            ),
+            Self::ValueUnderflow(val) => SingleRes::from((
                 Some(val),
                 location.to_warning(format!(
-                    "Overflow: {value} is too big in traditional number"
+                    "Underflow: {value} is too small for {target}, and was rounded to 0."
                 )),
             )),
-            Self::Overflow => SingleRes::from(location.to_failure(format!(
-                "Overflow: {value} is too big in traditional number"
-            ))),
             Self::Value(val) => SingleRes::from(Some(val)),
             Self::Err(compile_error) => SingleRes::from(compile_error),
             Self::ValueErr(val, compile_error) => SingleRes::from((Some(val), compile_error)),
@@ -90,6 +149,7 @@ impl<T> OverParseRes<T> {
             Self::Overflow => OverParseRes::Overflow,
             Self::Err(err) => OverParseRes::Err(err),
             Self::ValueOverflow(val) => OverParseRes::ValueOverflow(f(val)),
+            Self::ValueUnderflow(val) => OverParseRes::ValueUnderflow(f(val)),
             Self::ValueErr(val, err) => OverParseRes::ValueErr(f(val), err),
         }
     }
@@ -106,12 +166,6 @@ impl<T> From<CompileError> for OverParseRes<T> {
     }
 }
 
-impl<T: fmt::Display> From<T> for OverParseRes<T> {
-    fn from(value: T) -> Self {
-        Self::Value(value)
-    }
-}
-
 impl ops::FromResidual<CompileRes<convert::Infallible>> for OverParseRes<Number> {
     fn from_residual(residual: CompileRes<convert::Infallible>) -> Self {
         match residual {
@@ -21,30 +21,39 @@ pub mod arch_types {
     #[cfg(target_pointer_width = "64")]
     pub type ULong = ULongLong;
     pub type ULongLong = u64;
-
-    pub type FloatIntPart = u32;
-    pub type DoubleIntPart = u64;
-    pub type LongDoubleIntPart = u128;
 }
 
 use core::fmt;
+use core::ops::RangeInclusive;
 
 use arch_types::{Double, Float, Int, Long, LongDouble, LongLong, UInt, ULong, ULongLong};
 
+use super::base::decimal::parse_long_double;
+use super::big_uint::BigUint;
+
 /// Defines the [`Number`] and [`NumberType`] enums
+///
+/// The attributes before a type are added to its variant of [`Number`].
 macro_rules! define_nb_types {
-    ($($t:ident)*) => {
+    ($($(#[$attr:meta])* $t:ident)*) => {
         /// Token value for a number constant
-        #[derive(Debug, PartialEq)]
+        #[derive(Debug, PartialEq, Clone)]
+        #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
         pub enum Number {
             $(
                 /// $t C type
+                $(#[$attr])*
                 $t($t),
             )*
         }
 
+        /// Type of a number constant
+        #[derive(Debug, PartialEq, Eq, Clone)]
         pub enum NumberType {
-            $($t,)*
+            $(
+                /// $t C type
+                $t,
+            )*
         }
 
     };
@@ -93,13 +102,291 @@ impl Base {
     }
 }
 
-define_nb_types!(Int Long LongLong Float Double LongDouble UInt ULong ULongLong);
+define_nb_types!(
+    Int Long LongLong Float Double
+    #[cfg_attr(feature = "serde", serde(with = "super::long_double_serde"))]
+    LongDouble
+    UInt ULong ULongLong
+);
+
+impl Number {
+    /// Returns the value of the number, as a floating-point number.
+    ///
+    /// All the values of the integer types are exactly represented by a
+    /// [`f128`].
+    #[expect(clippy::as_conversions, clippy::match_same_arms)]
+    const fn as_float(&self) -> f128 {
+        match self {
+            Self::Int(x) => *x as f128,
+            Self::Long(x) => *x as f128,
+            Self::LongLong(x) => *x as f128,
+            Self::Float(x) => *x as f128,
+            Self::Double(x) => *x as f128,
+            Self::LongDouble(x) => *x,
+            Self::UInt(x) => *x as f128,
+            Self::ULong(x) => *x as f128,
+            Self::ULongLong(x) => *x as f128,
+        }
+    }
+
+    /// Returns the value of the number, if it is an integer.
+    #[expect(clippy::match_same_arms)]
+    fn as_integer(&self) -> Option<i128> {
+        match self {
+            Self::Int(x) => Some(i128::from(*x)),
+            Self::Long(x) => Some(i128::from(*x)),
+            Self::LongLong(x) => Some(i128::from(*x)),
+            Self::UInt(x) => Some(i128::from(*x)),
+            Self::ULong(x) => Some(i128::from(*x)),
+            Self::ULongLong(x) => Some(i128::from(*x)),
+            Self::Float(_) | Self::Double(_) | Self::LongDouble(_) => None,
+        }
+    }
+
+    /// Checks if the value of the number is exactly representable in the given
+    /// type.
+    ///
+    /// An integer fits in an integer type if it is within its bounds. A
+    /// floating-point number fits in an integer type if it has no fractional
+    /// part and is within its bounds. A number fits in a floating-point type
+    /// if it isn't rounded by the conversion: `0.5` fits in a `float`, but
+    /// `0.1` doesn't. This can be used to report overflows and lossy implicit
+    /// conversions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Number, NumberType};
+    ///
+    /// assert!(Number::Long(300).fits_in(&NumberType::Int));
+    /// assert!(!Number::Long(1 << 40).fits_in(&NumberType::Int));
+    /// assert!(!Number::Int(-1).fits_in(&NumberType::UInt));
+    /// assert!(Number::Double(3.0).fits_in(&NumberType::Int));
+    /// assert!(!Number::Double(0.1).fits_in(&NumberType::Float));
+    /// ```
+    #[inline]
+    #[must_use]
+    #[expect(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        clippy::float_cmp
+    )]
+    pub fn fits_in(&self, ty: &NumberType) -> bool {
+        let value = self.as_float();
+        match ty.integer_range() {
+            Some(range) => self.as_integer().map_or_else(
+                || {
+                    let integer = value as i128;
+                    integer as f128 == value && range.contains(&integer)
+                },
+                |integer| range.contains(&integer),
+            ),
+            None if value.is_nan() => true,
+            None if *ty == NumberType::Float => value as Float as f128 == value,
+            None if *ty == NumberType::Double => value as Double as f128 == value,
+            None => true,
+        }
+    }
+
+    /// Returns the type of the number.
+    pub(crate) const fn number_type(&self) -> NumberType {
+        match self {
+            Self::Int(_) => NumberType::Int,
+            Self::Long(_) => NumberType::Long,
+            Self::LongLong(_) => NumberType::LongLong,
+            Self::Float(_) => NumberType::Float,
+            Self::Double(_) => NumberType::Double,
+            Self::LongDouble(_) => NumberType::LongDouble,
+            Self::UInt(_) => NumberType::UInt,
+            Self::ULong(_) => NumberType::ULong,
+            Self::ULongLong(_) => NumberType::ULongLong,
+        }
+    }
+
+    /// Returns the spelling of the number in C source code.
+    ///
+    /// The suffix of the type is written, like `1UL` or `1.5f`. Unlike the
+    /// [`Display`](fmt::Display) implementation, the floating-point numbers
+    /// always contain a decimal point or an exponent, so that `1.0f` is not
+    /// written `1f`, which isn't a valid C constant. The spelling is the
+    /// shortest one that evaluates to the same value: `1e10` is written
+    /// `10000000000.0`. To keep the original spelling, the lexemes of the
+    /// tokens can be kept with
+    /// [`Options::with_lexemes`](crate::Options::with_lexemes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::Number;
+    ///
+    /// assert!(Number::Float(1.0).to_source() == "1.0f");
+    /// assert!(Number::Double(1e20).to_source() == "1e20");
+    /// assert!(Number::Double(-0.0).to_source() == "-0.0");
+    /// assert!(Number::ULong(3).to_source() == "3UL");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_source(&self) -> String {
+        match self {
+            Self::Int(x) => x.to_string(),
+            Self::Long(x) => format!("{x}L"),
+            Self::LongLong(x) => format!("{x}LL"),
+            Self::Float(x) => format!("{}f", float_to_source(f64::from(*x))),
+            Self::Double(x) => float_to_source(*x),
+            Self::LongDouble(x) => format!("{}L", long_double_to_source(*x)),
+            Self::UInt(x) => format!("{x}U"),
+            Self::ULong(x) => format!("{x}UL"),
+            Self::ULongLong(x) => format!("{x}ULL"),
+        }
+    }
+}
+
+/// Returns the spelling of a floating-point number in C source code, without
+/// the suffix.
+///
+/// The [`Debug`](fmt::Debug) formatting of Rust always contains a decimal
+/// point or an exponent, like `1.0` or `1e20`, which are also valid in C. The
+/// infinity is written with an exponent that is too big to be represented.
+fn float_to_source(value: f64) -> String {
+    if value.is_nan() {
+        "(0.0 / 0.0)".to_owned()
+    } else if value.is_infinite() {
+        if value.is_sign_negative() {
+            "-1e999"
+        } else {
+            "1e999"
+        }
+        .to_owned()
+    } else {
+        format!("{value:?}")
+    }
+}
+
+/// Returns the spelling of a `long double` in C source code, without the
+/// suffix.
+///
+/// Rust can't format a [`f128`], so the spelling is computed from the exact
+/// decimal value of the number, and follows the [`Debug`](fmt::Debug)
+/// formatting of the other floating-point numbers: `1e400` or `0.1`. The
+/// infinity is written with an exponent that is too big to be represented.
+#[expect(clippy::arithmetic_side_effects, reason = "exponents are bounded")]
+pub fn long_double_to_source(value: LongDouble) -> String {
+    let sign = if value.is_sign_negative() { "-" } else { "" };
+    if value.is_nan() {
+        return "(0.0 / 0.0)".to_owned();
+    }
+    if value.is_infinite() {
+        return format!("{sign}1e99999");
+    }
+    if value.abs().to_bits() == 0 {
+        return format!("{sign}0.0");
+    }
+    let (digits, exponent) = shortest_digits(value.abs());
+    let digits_len = i64::try_from(digits.len()).expect("at most 36 digits");
+    if !(-4..16).contains(&exponent) {
+        let (first, rest) = digits.split_at(1);
+        let dot = if rest.is_empty() { "" } else { "." };
+        format!("{sign}{first}{dot}{rest}e{exponent}")
+    } else if exponent < 0 {
+        let zeros = "0".repeat(usize::try_from(-1 - exponent).expect("exponent < 0"));
+        format!("{sign}0.{zeros}{digits}")
+    } else if exponent < digits_len - 1 {
+        let (int_part, decimal_part) =
+            digits.split_at(usize::try_from(exponent + 1).expect("exponent >= 0"));
+        format!("{sign}{int_part}.{decimal_part}")
+    } else {
+        let zeros =
+            "0".repeat(usize::try_from(exponent + 1 - digits_len).expect("exponent >= len - 1"));
+        format!("{sign}{digits}{zeros}.0")
+    }
+}
 
+/// Rounds decimal digits to the given number of significant digits.
+///
+/// The exponent is the exponent of the first digit, and is incremented if
+/// the rounding adds a digit, like when `999` is rounded to `1000`. The
+/// trailing zeros are removed.
 #[expect(
-    clippy::min_ident_chars,
-    clippy::match_same_arms,
-    clippy::as_conversions
+    clippy::arithmetic_side_effects,
+    reason = "digits are not 9 and exponents are bounded"
 )]
+fn round_digits(digits: &str, precision: usize, exponent: i64) -> (String, i64) {
+    let (kept, dropped) = digits.split_at(precision.min(digits.len()));
+    let mut rounded = kept.as_bytes().to_vec();
+    let mut rounded_exponent = exponent;
+    if dropped
+        .as_bytes()
+        .first()
+        .is_some_and(|first| *first >= b'5')
+    {
+        let mut carry = true;
+        for digit in rounded.iter_mut().rev() {
+            if *digit == b'9' {
+                *digit = b'0';
+            } else {
+                *digit += 1;
+                carry = false;
+                break;
+            }
+        }
+        if carry {
+            rounded.insert(0, b'1');
+            rounded.pop();
+            rounded_exponent += 1;
+        }
+    }
+    let rounded_digits = String::from_utf8(rounded).expect("digits are ASCII");
+    (
+        rounded_digits.trim_end_matches('0').to_owned(),
+        rounded_exponent,
+    )
+}
+
+/// Returns the shortest decimal digits of a positive `long double`, that are
+/// parsed to the same value, with the exponent of the first digit.
+///
+/// A `long double` has 113 bits of precision, so 36 digits are always enough.
+#[expect(clippy::arithmetic_side_effects, reason = "exponents are bounded")]
+fn shortest_digits(value: LongDouble) -> (String, i64) {
+    const MANTISSA_SIZE: u32 = 112;
+    const BIAS: i64 = 16383;
+    let bits = value.to_bits();
+    let fraction = bits & ((1 << MANTISSA_SIZE) - 1);
+    let biased = i64::try_from(bits >> MANTISSA_SIZE).expect("the sign is positive");
+    let (mantissa, exponent) = if biased == 0 {
+        (fraction, 1 - BIAS - i64::from(MANTISSA_SIZE))
+    } else {
+        (
+            fraction | (1 << MANTISSA_SIZE),
+            biased - BIAS - i64::from(MANTISSA_SIZE),
+        )
+    };
+    // the exact value is `exact * 10^-scale`, as `2^-n = 5^n * 10^-n`
+    let mut exact = BigUint::from(mantissa);
+    let scale = if exponent >= 0 {
+        exact = exact.shl(exponent.unsigned_abs());
+        0
+    } else {
+        exact.mul_pow(5, exponent.unsigned_abs());
+        -exponent
+    };
+    let exact_digits = exact.to_decimal();
+    let first_exponent =
+        i64::try_from(exact_digits.len()).expect("the number is bounded") - 1 - scale;
+    for precision in 1..=36 {
+        let (digits, digits_exponent) = round_digits(&exact_digits, precision, first_exponent);
+        let last_exponent =
+            digits_exponent + 1 - i64::try_from(digits.len()).expect("at most 36 digits");
+        if parse_long_double(&format!("{digits}e{last_exponent}"))
+            .is_some_and(|(parsed, _)| parsed.to_bits() == bits)
+        {
+            return (digits, digits_exponent);
+        }
+    }
+    round_digits(&exact_digits, 36, first_exponent)
+}
+
+#[expect(clippy::min_ident_chars, clippy::match_same_arms)]
 impl fmt::Display for Number {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -109,7 +396,7 @@ impl fmt::Display for Number {
             Self::LongLong(x) => x.to_string(),
             Self::Float(x) => x.to_string(),
             Self::Double(x) => x.to_string(),
-            Self::LongDouble(x) => format!("'{}'", *x as f64),
+            Self::LongDouble(x) => format!("'{}'", long_double_to_source(*x)),
             Self::UInt(x) => x.to_string(),
             Self::ULong(x) => x.to_string(),
             Self::ULongLong(x) => x.to_string(),
@@ -118,6 +405,29 @@ impl fmt::Display for Number {
 }
 
 impl NumberType {
+    /// Returns the common type of two arithmetic types, given by the usual
+    /// arithmetic conversions.
+    ///
+    /// The type of bigger rank is chosen, the floating-point types being
+    /// bigger than the integer types. If it is a signed integer type that
+    /// can't represent all the values of the other type, its unsigned version
+    /// is chosen instead, like `unsigned long long` for `long long` and
+    /// `unsigned long` on 64-bit targets.
+    pub(crate) fn common_type(&self, other: &Self) -> Self {
+        let (big, small) = if self.rank() >= other.rank() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if let (Some(big_range), Some(small_range)) = (big.integer_range(), small.integer_range())
+            && !big_range.contains(small_range.end())
+        {
+            big.to_unsigned()
+        } else {
+            big.clone()
+        }
+    }
+
     /// Tries to increment the size of a type, by taking a bigger type.
     ///
     /// It works with the following (where M(x) means the size of the type x):
@@ -146,11 +456,45 @@ impl NumberType {
         })
     }
 
+    /// Returns the bounds of the values of an integer type.
+    ///
+    /// Returns [`None`] for the floating-point types.
+    #[expect(clippy::as_conversions)]
+    const fn integer_range(&self) -> Option<RangeInclusive<i128>> {
+        Some(match self {
+            Self::Int => Int::MIN as i128..=Int::MAX as i128,
+            Self::Long => Long::MIN as i128..=Long::MAX as i128,
+            Self::LongLong => LongLong::MIN as i128..=LongLong::MAX as i128,
+            Self::UInt => 0..=UInt::MAX as i128,
+            Self::ULong => 0..=ULong::MAX as i128,
+            Self::ULongLong => 0..=ULongLong::MAX as i128,
+            Self::Float | Self::Double | Self::LongDouble => return None,
+        })
+    }
+
     /// Checks that the type is an integer type
     pub(crate) const fn is_int(&self) -> bool {
         !matches!(self, Self::Double | Self::Float | Self::LongDouble)
     }
 
+    /// Returns the rank of the type for the usual arithmetic conversions.
+    ///
+    /// An unsigned type is considered bigger than the signed type of the same
+    /// size.
+    const fn rank(&self) -> u32 {
+        match self {
+            Self::Int => 0,
+            Self::UInt => 1,
+            Self::Long => 2,
+            Self::ULong => 3,
+            Self::LongLong => 4,
+            Self::ULongLong => 5,
+            Self::Float => 6,
+            Self::Double => 7,
+            Self::LongDouble => 8,
+        }
+    }
+
     /// Returns the size of the suffix of the type.
     ///
     /// # Examples
@@ -171,6 +515,20 @@ impl NumberType {
             Self::ULongLong => 3,
         }
     }
+
+    /// Returns the unsigned version of an integer type.
+    ///
+    /// The unsigned and floating-point types are returned unchanged.
+    const fn to_unsigned(&self) -> Self {
+        match self {
+            Self::Int | Self::UInt => Self::UInt,
+            Self::Long | Self::ULong => Self::ULong,
+            Self::LongLong | Self::ULongLong => Self::ULongLong,
+            Self::Float => Self::Float,
+            Self::Double => Self::Double,
+            Self::LongDouble => Self::LongDouble,
+        }
+    }
 }
 
 #[expect(clippy::min_ident_chars)]
@@ -0,0 +1,291 @@
+//! Module to re-lex only the part of a file that was edited.
+//!
+//! See [`relex_file`] for more information.
+
+use core::ops::Range;
+
+use super::conditionals::{Conditionals, split_directive};
+use super::lex_content::{lex_file_with_options, lex_lines};
+use super::types::api::{LexingData, Token, TokenValue};
+use crate::errors::api::{Location, Res};
+use crate::options::api::Options;
+
+/// Lines of the file before and after the edit.
+struct Lines<'text> {
+    /// Line of the beginning of the file.
+    base: usize,
+    /// Lines of the file after the edit.
+    new: Vec<&'text str>,
+    /// Lines of the file before the edit.
+    old: Vec<&'text str>,
+    /// The trigraphs are translated, so `??/` is a line continuation.
+    trigraphs: bool,
+}
+
+impl Lines<'_> {
+    /// Checks if the last line of the window continues on the next line,
+    /// before or after the edit.
+    fn is_continued(&self, last: usize) -> bool {
+        self.old
+            .get(last)
+            .is_some_and(|line| is_continued(line, self.trigraphs))
+            || self
+                .new
+                .get(self.to_new_line(last))
+                .is_some_and(|line| is_continued(line, self.trigraphs))
+    }
+
+    /// Lexes the lines of the window, after the edit.
+    fn lex_window(
+        &self,
+        window: (usize, Option<usize>),
+        location: &Location,
+        options: Options,
+        ends_with_newline: bool,
+    ) -> Res<Vec<Token>> {
+        let (start, end) = window;
+        let mut window_location = location.to_owned();
+        for _ in 0..start {
+            if window_location.incr_line(usize::MAX).is_err() {
+                break;
+            }
+        }
+        let window_lines = end
+            .map_or_else(
+                || self.new.get(start..),
+                |last| self.new.get(start..=self.to_new_line(last)),
+            )
+            .unwrap_or_default();
+        let mut lex_data = LexingData::from(options);
+        lex_lines(
+            window_lines.iter().copied(),
+            (end.is_none(), ends_with_newline),
+            &mut window_location,
+            &mut lex_data,
+            &mut Conditionals::default(),
+        );
+        lex_data.into_res()
+    }
+
+    /// Returns the index of the line of a location, in the lines of the file.
+    const fn line_of(&self, location: &Location) -> usize {
+        location.get_line().saturating_sub(self.base)
+    }
+
+    /// Returns the first token, that isn't a layout token, after a line.
+    fn next_token<'tokens>(&self, tokens: &'tokens [Token], line: usize) -> Option<&'tokens Token> {
+        tokens
+            .iter()
+            .find(|token| !token.is_layout() && self.line_of(token.get_location()) > line)
+    }
+
+    /// Moves a location after the window to its line after the edit.
+    const fn shift(&self, location: &mut Location) {
+        location.shift_lines(self.old.len(), self.new.len());
+    }
+
+    /// Returns the index of a line after the window, after the edit.
+    const fn to_new_line(&self, line: usize) -> usize {
+        line.saturating_add(self.new.len())
+            .saturating_sub(self.old.len())
+    }
+
+    /// Widens the window of lines to re-lex, when the old tokens may cross its
+    /// boundaries.
+    ///
+    /// The window contains the first line, and the last line if it doesn't go
+    /// until the end of the file.
+    fn widen(&self, tokens: &[Token], window: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let (mut start, mut end) = window;
+        let last_old = self.old.len().saturating_sub(1);
+        if let Some(previous_line) = start.checked_sub(1)
+            && self
+                .old
+                .get(previous_line)
+                .is_some_and(|line| is_continued(line, self.trigraphs))
+        {
+            start = previous_line;
+        }
+        if let Some(token) = tokens
+            .iter()
+            .rev()
+            .find(|token| !token.is_layout() && self.line_of(token.get_location()) < start)
+            && is_str(token)
+        {
+            start = self.line_of(token.get_location());
+        }
+        if let Some(last) = end {
+            if self.is_continued(last) {
+                end = Some(last.saturating_add(1)).filter(|next| *next < last_old);
+            } else if tokens
+                .iter()
+                .rev()
+                .find(|token| !token.is_layout() && self.line_of(token.get_location()) <= last)
+                .is_some_and(is_str)
+            {
+                // the literals after the string may have been merged into it,
+                // until the next token
+                end = self
+                    .next_token(tokens, last)
+                    .map(|token| self.line_of(token.get_location()))
+                    .filter(|next_last| *next_last < last_old);
+            }
+        }
+        (start, end)
+    }
+}
+
+/// Checks if a line ends with a `\`, and continues on the next line.
+fn is_continued(line: &str, trigraphs: bool) -> bool {
+    let trimmed = line.trim_end();
+    trimmed.ends_with('\\') || (trigraphs && trimmed.ends_with("??/"))
+}
+
+/// Checks if the [`Token`] is a string literal.
+///
+/// Successive string literals are merged by the lexer, even if they are on
+/// different lines, so the lines of a string literal must be re-lexed
+/// together.
+const fn is_str(token: &Token) -> bool {
+    matches!(token.get_value(), TokenValue::Str(..))
+}
+
+/// Function that re-lexes a file after an edit, with the tokens of the file
+/// before the edit.
+///
+/// `previous` is the result of the lexing of `old`, for example by
+/// [`lex_file_with_options`], with the same `location` and [`Options`].
+/// `location` is the location at the beginning of the file. The edit replaces
+/// the bytes of `old` in `range` by `replacement`. The result is the same as
+/// the lexing of the whole edited file.
+///
+/// Only the lines of the edit are lexed again. The window of lines is widened
+/// when the tokens may cross its boundaries: around line continuations, and
+/// around string literals, that are merged with the next ones. The tokens
+/// and errors after the window are moved to their new lines.
+///
+/// The whole file is lexed again if it contains preprocessor directives, as
+/// an edit can change the branches of the conditional directives, or if it
+/// contains `__LINE__`, as its value changes when lines are added or removed.
+///
+/// # Errors
+///
+/// Returns an error if `range` is not a valid range of `old`.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::{Location, Options, display_tokens, lex_file, relex_file};
+///
+/// let old = "int x = a * b;\nint y;";
+/// let previous = lex_file(old, &mut Location::from(""));
+/// // `/` is typed before the `*`, which opens a comment
+/// let tokens = relex_file(
+///     previous,
+///     old,
+///     10..10,
+///     "/",
+///     &Location::from(""),
+///     Options::default(),
+/// )
+/// .unwrap_or_display(&[], "lexer");
+/// let full = lex_file("int x = a /* b;\nint y;", &mut Location::from(""))
+///     .unwrap_or_display(&[], "lexer");
+/// assert!(display_tokens(&tokens) == display_tokens(&full));
+/// ```
+#[inline]
+pub fn relex_file(
+    previous: Res<Vec<Token>>,
+    old: &str,
+    range: Range<usize>,
+    replacement: &str,
+    location: &Location,
+    options: Options,
+) -> Res<Vec<Token>> {
+    let (Some(before), Some(edited), Some(after)) = (
+        old.get(..range.start).filter(|_| range.start <= range.end),
+        old.get(..range.end),
+        old.get(range.end..),
+    ) else {
+        return Res::from(location.to_failure(format!(
+            "Invalid edit: {}..{} is not a valid range of the file.",
+            range.start, range.end
+        )));
+    };
+    let new = format!("{before}{replacement}{after}");
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    let trigraphs = options.translates_trigraphs();
+    if old_lines
+        .iter()
+        .chain(&new_lines)
+        .any(|line| split_directive(line, &options).is_some())
+        || old.contains("__LINE__")
+        || new_lines.len().saturating_add(location.get_line()) > options.max_lines()
+    {
+        return lex_file_with_options(&new, &mut location.to_owned(), options);
+    }
+    let lines = Lines {
+        base: location.get_line(),
+        new: new_lines,
+        old: old_lines,
+        trigraphs,
+    };
+    let (mut tokens, mut errors) = previous.into_parts();
+    let last_old = lines.old.len().saturating_sub(1);
+    // first line, and last line if the window doesn't go until the end of the
+    // file
+    let mut window = (
+        before.matches('\n').count().min(last_old),
+        Some(edited.matches('\n').count()).filter(|last| *last < last_old),
+    );
+    loop {
+        let widened = lines.widen(&tokens, window);
+        if widened != window {
+            window = widened;
+            continue;
+        }
+        let (start, end) = window;
+        let (window_tokens, window_errors) = lines
+            .lex_window(window, location, options, new.ends_with('\n'))
+            .into_parts();
+        if let Some(last) = end
+            && window_tokens
+                .iter()
+                .rev()
+                .find(|token| !token.is_layout())
+                .is_some_and(is_str)
+            && let Some(next) = lines.next_token(&tokens, last)
+            && is_str(next)
+        {
+            // the new string literal is merged with the next one
+            window.1 =
+                Some(lines.line_of(next.get_location())).filter(|next_last| *next_last < last_old);
+            continue;
+        }
+        let is_after =
+            |old_location: &Location| end.is_some_and(|last| lines.line_of(old_location) > last);
+        let mut suffix = tokens.split_off(
+            tokens
+                .iter()
+                .position(|token| lines.line_of(token.get_location()) >= start)
+                .unwrap_or(tokens.len()),
+        );
+        suffix.retain(|token| is_after(token.get_location()));
+        let mut suffix_errors = errors
+            .extract_if(.., |error| lines.line_of(error.get_location()) >= start)
+            .filter(|error| is_after(error.get_location()))
+            .collect::<Vec<_>>();
+        for token in &mut suffix {
+            lines.shift(token.get_location_mut());
+        }
+        for error in &mut suffix_errors {
+            lines.shift(error.get_location_mut());
+        }
+        tokens.extend(window_tokens);
+        tokens.extend(suffix);
+        errors.extend(window_errors);
+        errors.extend(suffix_errors);
+        return Res::from((tokens, errors));
+    }
+}
@@ -17,16 +17,25 @@ pub fn end_current(state: &mut LexingState, lex_data: &mut LexingData, location:
         LexingState::Comment(_) | LexingState::Unset | LexingState::StartOfLine => return,
         LexingState::Symbols(symbol_state) => end_symbols(symbol_state, lex_data, location),
         LexingState::Ident(ident) => end_ident(ident, lex_data, location),
-        LexingState::Char(None) => {
+        LexingState::Char(None, ..) => {
             lex_data.push_err(
                 location.to_failure(
                     "Found an empty char, but chars must contain one character. Did you mean '\\''?".to_owned(),
                 ),
             );
         }
-        LexingState::Char(Some(ch)) => lex_data.push_token(Token::from_char(*ch, location)),
-        LexingState::Str(val) => {
-            lex_data.push_token(Token::from_str(mem::take(val), location));
+        LexingState::Char(Some(ch), len, encoding) => {
+            if let Some(max) = encoding.max_char()
+                && u32::from(*ch) > max
+            {
+                lex_data.push_err(location.to_owned().into_past_with_length(*len).into_failure(format!(
+                    "Found '{ch}' in a char with the prefix '{encoding}', but it doesn't fit in a single code unit. Consider using a string literal instead."
+                )));
+            }
+            lex_data.push_token(Token::from_char(*ch, *encoding, *len, location));
+        }
+        LexingState::Str(val, len, encoding) => {
+            lex_data.push_token(Token::from_str(mem::take(val), *encoding, *len, location));
         }
     };
     *state = LexingState::Unset;
@@ -42,13 +51,13 @@ fn end_ident(literal: &mut Ident, lex_data: &mut LexingData, location: &Location
         let possible_number = literal_to_number(lex_data, literal, location);
         match possible_number {
             None => {
-                if !literal.first().unwrap_or('0').is_ascii_digit() {
+                if !literal.is_number() {
                     let token = Token::from_identifier(lex_data, literal, location);
                     lex_data.push_token(token);
                 }
             }
             Some(nb) => {
-                let token = Token::from_number(nb, location);
+                let token = Token::from_number(nb, literal.len(), location);
                 lex_data.push_token(token);
             }
         }
@@ -62,15 +71,10 @@ pub fn end_symbols(symbols: &mut SymbolState, lex_data: &mut LexingData, locatio
             break;
         }
         if let Some((size, symbol)) = symbols.try_to_operator(lex_data, location) {
-            let token = Token::from_symbol(symbol, size, location);
+            // the symbol is followed by the other chars of the state
+            let end = location.to_owned().into_past_with_length(symbols.len());
+            let token = Token::from_symbol(symbol, size, &end);
             lex_data.push_token(token);
-        } else {
-            /* This happens when the 3 characters formed a trigraph. If this
-             * is the case, they were ignored. It only happens in this case
-             * because symbols is not empty. */
-            //TODO: the characters are meant to be printed as they are
-            //TODO: it is only for a case not yet implemented: trigraphs inside
-            // string literals.
         }
     }
 }
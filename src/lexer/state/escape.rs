@@ -3,7 +3,8 @@
 
 use super::super::numbers::api::safe_parse_int;
 use super::super::types::api::LexingData;
-use crate::errors::api::Location;
+use super::lex_state::LexingState;
+use crate::errors::api::{ErrorCode, Location};
 use crate::lexer::types::api::EscapeSequence;
 
 /// Used to store the current escape state and the escape sequence values if
@@ -22,72 +23,96 @@ pub enum EscapeState {
 ///
 /// This function also checks that the right number of digits were given after
 /// the prefix, and that the value is correct.
+///
+/// The second char is only returned for an octal sequence that overflows a
+/// char: the last digit is then not part of the escape sequence, and is
+/// returned as a literal char.
 fn end_escape_sequence(
     lex_data: &mut LexingData,
     location: &Location,
     sequence: &EscapeSequence,
-) -> Result<char, ()> {
+) -> Result<(char, Option<char>), ()> {
     match sequence {
         EscapeSequence::ShortUnicode(value) => {
             expect_max_length(4, value);
             expect_min_length(lex_data, 4, value, location, sequence)?;
-            end_unicode_sequence(lex_data, value, location)
+            end_unicode_sequence(lex_data, value, location).map(|ch| (ch, None))
         }
         EscapeSequence::Unicode(value) => {
             if value.len() <= 4 {
-                lex_data.push_err(location.to_failure(format!(
-                    "Invalid escaped unicode number: An escaped big unicode must contain 8 hexadecimal digits, found only {}. Did you mean to use lowercase \\u?",
-                    value.len()
-                )));
+                lex_data.push_err(
+                    location
+                        .to_failure(format!(
+                            "Invalid escaped unicode number: An escaped big unicode must contain 8 hexadecimal digits, found only {}. Did you mean to use lowercase \\u?",
+                            value.len()
+                        ))
+                        .with_code(ErrorCode::EscapeTooShort),
+                );
                 return Err(());
             }
             expect_max_length(8, value);
             expect_min_length(lex_data, 8, value, location, sequence)?;
-            end_unicode_sequence(lex_data, value, location)
+            end_unicode_sequence(lex_data, value, location).map(|ch| (ch, None))
         }
         EscapeSequence::Hexadecimal(value) => {
             expect_max_length(3, value);
-            expect_min_length(lex_data, 2, value, location, sequence)?;
+            expect_min_length(lex_data, 1, value, location, sequence)?;
             let int =
                 u8::from_str_radix(value, 16).expect("We push only numeric so this doesn't happen");
-            Ok(int.into())
+            Ok((int.into(), None))
         }
         EscapeSequence::Octal(value) => {
             expect_max_length(3, value);
             expect_min_length(lex_data, 1, value, location, sequence)?;
-            let (int, small) = safe_parse_int!(
-                "Invalid octal escape sequence :",
-                u32,
-                location,
-                u32::from_str_radix(value, 8)
-            )
-            .ignore_overflow(value, location)
-            .map_or_else(
-                |err| lex_data.push_err(err),
-                |int| (int, value.len() < 3 || int <= 0o377),
-            )?;
-            if small {
-                #[expect(
-                    clippy::as_conversions,
-                    clippy::cast_possible_truncation,
-                    reason = "int <= 255"
-                )]
-                Ok(char::from(int as u8))
+            let Some(int) = octal_value(value) else {
+                lex_data.push_err(
+                    location
+                        .to_failure(format!(
+                            "Invalid octal escape sequence: {value} must contain only octal digits."
+                        ))
+                        .with_code(ErrorCode::InvalidOctalDigit),
+                );
+                return Err(());
+            };
+            if let Ok(byte) = u8::try_from(int) {
+                return Ok((char::from(byte), None));
+            }
+            if let Some((head, tail)) = value.split_at_checked(2)
+                && let Some(byte) = octal_value(head).and_then(|nb| u8::try_from(nb).ok())
+                && let Some(trailing) = tail.chars().next()
+            {
+                lex_data.push_err(
+                    location
+                        .to_warning(format!(
+                            "Octal escape sequence out of range: \\{value} is {int}, but a char can't exceed 255. It was interpreted as \\{head} followed by the character '{trailing}'."
+                        ))
+                        .with_code(ErrorCode::OctalEscapeOutOfRange),
+                );
+                Ok((char::from(byte), Some(trailing)))
             } else {
-                #[expect(clippy::string_slice, reason = "len = 3")]
-                safe_parse_int!(
-                    "Invalid octal escape sequence: ",
-                    u8,
-                    location,
-                    u8::from_str_radix(&value[0..2], 8)
-                )
-                .ignore_overflow(&value[0..2], location)
-                .map_or_else(|err| lex_data.push_err(err), char::from)
+                lex_data.push_err(
+                    location
+                        .to_failure(format!(
+                            "Invalid octal escape sequence: \\{value} is {int}, but a char can't exceed 255."
+                        ))
+                        .with_code(ErrorCode::OctalEscapeOutOfRange),
+                );
+                Err(())
             }
         }
     }
 }
 
+/// Computes the value of a string of octal digits.
+///
+/// Returns `None` if a character isn't an octal digit, or if the value
+/// overflows.
+fn octal_value(digits: &str) -> Option<u32> {
+    digits.chars().try_fold(0u32, |acc, ch| {
+        acc.checked_mul(8)?.checked_add(ch.to_digit(8)?)
+    })
+}
+
 /// Converts a hexadecimal unicode sequence into a char.
 fn end_unicode_sequence(
     lex_data: &mut LexingData,
@@ -101,18 +126,22 @@ fn end_unicode_sequence(
         u32::from_str_radix(value, 16)
     )
     .map(char::from_u32)
-    .ignore_overflow(value, location)
+    .ignore_overflow(value, "a unicode character", location)
     .map_or_else(
         |err| {
-            lex_data.push_err(err);
+            lex_data.push_err(err.with_code(ErrorCode::InvalidUnicodeEscape));
         },
         |val| val,
     )?
     .map_or_else(
         || {
-            lex_data.push_err(location.to_failure(format!(
-                "Invalid escaped unicode number: {value} is not a valid unicode character.",
-            )));
+            lex_data.push_err(
+                location
+                    .to_failure(format!(
+                        "Invalid escaped unicode number: {value} is not a valid unicode character.",
+                    ))
+                    .with_code(ErrorCode::InvalidUnicodeEscape),
+            );
             Err(())
         },
         Ok,
@@ -136,28 +165,62 @@ fn expect_min_length(
 ) -> Result<(), ()> {
     let len = value.len();
     if len < size {
-        lex_data.push_err(location.to_failure(format!(
-            "Invalid escaped {} number: must contain 4 digits, but found only {}",
-            sequence.repr(),
-            len,
-        )));
+        lex_data.push_err(
+            location
+                .to_failure(format!(
+                    "Invalid escaped {} number: must contain {size} digits, but found only {len}",
+                    sequence.repr(),
+                ))
+                .with_code(ErrorCode::EscapeTooShort),
+        );
         return Err(());
     }
     Ok(())
 }
 
+/// Ends a universal character name that is still pending at the end of a
+/// line, like `\u00` in `int \u00`.
+///
+/// A universal character name ends as soon as it has all its digits, so a
+/// pending one is always too short. A pending `\` alone is a line
+/// continuation, and the escapes of the literals are left to the literals.
+pub fn end_pending_escape(
+    lex_data: &mut LexingData,
+    lex_state: &LexingState,
+    escape_state: &mut EscapeState,
+    location: &Location,
+) {
+    if lex_data.is_end_line() || matches!(lex_state, LexingState::Char(..) | LexingState::Str(..)) {
+        return;
+    }
+    if let EscapeState::Sequence(sequence) = escape_state {
+        // the `\` and the prefix are part of the universal character name
+        let width = sequence.value_mut().len().saturating_add(2);
+        let sequence_location = location.to_owned().into_past_with_length(width);
+        assert!(
+            end_escape_sequence(lex_data, &sequence_location, sequence).is_err(),
+            "Never happens: a full universal character name is already ended"
+        );
+        *escape_state = EscapeState::False;
+    }
+}
+
 /// Pushed a character into an escape state, whatever the escape state.
+///
+/// The second returned char is a character that isn't part of the escape
+/// sequence, and that must be lexed again, as a normal character.
 pub fn handle_escape(
     ch: char,
     lex_data: &mut LexingData,
     escape_state: &mut EscapeState,
     location: &Location,
-) -> Option<char> {
+) -> Option<(char, Option<char>)> {
     match escape_state {
         EscapeState::Sequence(escape_sequence) => {
             handle_escaped_sequence(ch, escape_sequence, lex_data, location)
         }
-        EscapeState::Single => handle_escape_one_char(ch, lex_data, escape_state, location),
+        EscapeState::Single => handle_escape_one_char(ch, lex_data, escape_state, location)
+            .map(|escaped| (escaped, None)),
         EscapeState::False => panic!("never called"),
     }
 }
@@ -175,14 +238,23 @@ fn handle_escape_one_char(
     *escape_state = EscapeState::False;
     match ch {
         '\0' => Some('\0'),
-        'a' => Some('\u{0007}'),  // alert (bepp, bell)
-        'b' => Some('\u{0008}'),  // backspace
-        't' => Some('\u{0009}'),  // horizontal tab
-        'n' => Some('\u{000A}'),  // newline (line feed)
-        'v' => Some('\u{000B}'),  // vertical tab
-        'f' => Some('\u{000C}'),  // formfeed page break
-        'r' => Some('\u{000D}'),  // carriage return
-        'e' => Some('\u{001B}'),  // escape character
+        'a' => Some('\u{0007}'), // alert (bepp, bell)
+        'b' => Some('\u{0008}'), // backspace
+        't' => Some('\u{0009}'), // horizontal tab
+        'n' => Some('\u{000A}'), // newline (line feed)
+        'v' => Some('\u{000B}'), // vertical tab
+        'f' => Some('\u{000C}'), // formfeed page break
+        'r' => Some('\u{000D}'), // carriage return
+        'e' => {
+            lex_data.push_pedantic(
+                location
+                    .to_warning(
+                        "Found '\\e', but this escape sequence is a GNU extension. Consider using '\\x1B' instead.".to_owned(),
+                    )
+                    .with_code(ErrorCode::GnuEscape),
+            );
+            Some('\u{001B}') // escape character
+        }
         '"' => Some('\u{0022}'),  // double quotation mark
         '\'' => Some('\u{0027}'), // apostrophe or single quotation mark
         '?' => Some('\u{003F}'),  // question mark (used to avoid trigraphs)
@@ -204,27 +276,61 @@ fn handle_escape_one_char(
             None
         }
         _ => {
-            lex_data.push_err(location.to_failure(format!(
-                "Character '{ch}' can not be escaped, even inside a string or a char.",
-            )));
+            lex_data.push_err(
+                location
+                    .to_failure(format!(
+                        "Character '{ch}' can not be escaped, even inside a string or a char.",
+                    ))
+                    .with_code(ErrorCode::InvalidEscape),
+            );
             None
         }
     }
 }
 
 /// See [`end_escape_sequence`].
+///
+/// A full hexadecimal sequence is only ended by the next character, to
+/// report the hexadecimal digits that directly follow it, if they are
+/// reported in the [`Options`](crate::Options).
+///
+/// If `ch` ends the sequence without being part of it, like the `b` in `\0b`,
+/// it is returned as the second char to be lexed again.
+///
+/// The digit separators don't apply inside escape sequences: a `'` always
+/// ends the sequence, so `\x1'2` is `\x1` followed by the characters `'2`.
 fn handle_escaped_sequence(
     ch: char,
     escape_sequence: &mut EscapeSequence,
     lex_data: &mut LexingData,
     location: &Location,
-) -> Option<char> {
-    if !ch.is_ascii_hexdigit() || (escape_sequence.is_octal() && !ch.is_ascii_octdigit()) {
-        end_escape_sequence(lex_data, location, escape_sequence).ok()
+) -> Option<(char, Option<char>)> {
+    let full_hexadecimal = escape_sequence.is_hexadecimal()
+        && escape_sequence.value_mut().len() == escape_sequence.max_len();
+    if !ch.is_ascii_hexdigit()
+        || (escape_sequence.is_octal() && !ch.is_ascii_octdigit())
+        || full_hexadecimal
+    {
+        if full_hexadecimal
+            && ch.is_ascii_hexdigit()
+            && lex_data.options().reports_ambiguous_escapes()
+        {
+            let value = escape_sequence.value_mut();
+            lex_data.push_err(
+                location
+                    .to_suggestion(format!(
+                        "Found hexadecimal digit '{ch}' after the escape sequence '\\x{value}': it is read as a character, as the escape sequence contains at most 2 digits, but in standard C it would be part of the escape sequence. Consider splitting the string, like \"\\x{value}\" \"{ch}\", or using '\\u00{value}'."
+                    ))
+                    .with_code(ErrorCode::AmbiguousHexEscape),
+            );
+        }
+        end_escape_sequence(lex_data, location, escape_sequence)
+            .ok()
+            .map(|(escaped, _)| (escaped, Some(ch)))
     } else {
         let value = escape_sequence.value_mut();
         value.push(ch);
-        if value.len() == escape_sequence.max_len() {
+        if value.len() == escape_sequence.max_len() && !escape_sequence.is_hexadecimal() {
             end_escape_sequence(lex_data, location, escape_sequence).ok()
         } else {
             None
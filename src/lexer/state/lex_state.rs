@@ -1,7 +1,7 @@
 //! Module that defines and implements the [`LexingState`] automaton.
 
 use super::super::state::api::SymbolState;
-use crate::lexer::types::api::Ident;
+use crate::lexer::types::api::{Encoding, Ident};
 
 /// State of the comments
 ///
@@ -27,9 +27,13 @@ pub enum CommentState {
 pub enum LexingState {
     /// Reading a char
     ///
-    /// - When `'` is read, the state becomes `Char(None)`.
-    /// - The next character is stored inside `Char(_)`.
-    Char(Option<char>),
+    /// - When `'` is read, the state becomes `Char(None, 0, _)`.
+    /// - The next character is stored inside `Char(_, _, _)`.
+    ///
+    /// The `usize` is the length of the char in the source file, that differs
+    /// from 1 when it contains an escape. The [`Encoding`] is given by the
+    /// prefix of the char, like `L'a'`.
+    Char(Option<char>, usize, Encoding),
     /// Reading a block comment.
     Comment(CommentState),
     /// Reading an identifier.
@@ -38,7 +42,11 @@ pub enum LexingState {
     #[default]
     StartOfLine,
     /// Reading a string literal, between double quotes.
-    Str(String),
+    ///
+    /// The `usize` is the length of the literal in the source file, that
+    /// differs from the length of the string when it contains escapes. The
+    /// [`Encoding`] is given by the prefix of the string, like `L"str"`.
+    Str(String, usize, Encoding),
     /// Reading symbols.
     Symbols(SymbolState),
     /// Default variant for when all the buffers are cleared.
@@ -72,8 +80,8 @@ impl LexingState {
             Self::Unset => "no context",
             Self::Symbols(_) => "symbols",
             Self::Ident(_) => "identifier",
-            Self::Char(_) => "char",
-            Self::Str(_) => "string",
+            Self::Char(..) => "char",
+            Self::Str(..) => "string",
             Self::Comment(_) => "comment",
         }
     }
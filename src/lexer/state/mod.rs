@@ -8,7 +8,7 @@ pub mod api {
     #![allow(clippy::pub_use)]
 
     pub use super::end_state::end_current;
-    pub use super::escape::{EscapeState, handle_escape};
+    pub use super::escape::{EscapeState, end_pending_escape, handle_escape};
     pub use super::lex_state::{CommentState, LexingState};
     pub use super::symbol::SymbolState;
 }
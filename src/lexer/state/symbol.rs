@@ -41,48 +41,27 @@ impl SymbolState {
         }
     }
 
-    /// Handler for digraphs and trigraphs.
-    fn handle_digraphs_trigraphs(&mut self) -> Option<(String, usize, bool)> {
-        let symbols = (self.first, self.second, self.third);
-        let (graph, is_trigraph) = match symbols {
-            ('?', '?', '=') => (Some('#'), true),
-            ('?', '?', '/') => (Some('\\'), true),
-            ('?', '?', '\'') => (Some('^'), true),
-            ('?', '?', '(') => (Some('['), true),
-            ('?', '?', ')') => (Some(']'), true),
-            ('?', '?', '!') => (Some('|'), true),
-            ('?', '?', '<') => (Some('{'), true),
-            ('?', '?', '>') => (Some('}'), true),
-            ('?', '?', '-') => (Some('~'), true),
-            ('<', ':', _) => (Some('['), false),
-            (':', '>', _) => (Some(']'), false),
-            ('<', '%', _) => (Some('{'), false),
-            ('%', '>', _) => (Some('}'), false),
-            ('%', ':', _) => {
-                return Some((
-                    "Found invalid character '#', found by replacing digraph '%:'.".to_owned(),
-                    2,
-                    true,
-                ));
-            }
-            _ => (None, false),
+    /// Handler for digraphs.
+    ///
+    /// The digraphs are alternative spellings of some symbols, still valid in
+    /// C23: `<:` for `[`, `:>` for `]`, `<%` for `{`, `%>` for `}` and `%:`
+    /// for `#`. They are replaced by the symbol they stand for.
+    ///
+    /// By the maximal munch rule, `<:` is always a digraph, so `a<::b` is
+    /// lexed as `a [ : b`. Unlike C++, that keeps `<` before `::` for
+    /// templates, C has no special case for `<::`.
+    const fn handle_digraphs(&mut self) {
+        let graph = match (self.first, self.second) {
+            ('<', ':') => '[',
+            (':', '>') => ']',
+            ('<', '%') => '{',
+            ('%', '>') => '}',
+            ('%', ':') => '#',
+            _ => return,
         };
-        if let Some(symbol) = graph {
-            if is_trigraph {
-                let msg = format!(
-                    "Trigraphs are deprecated in C23. Please remove them: replace '{}{}{}' by '{symbol}'.",
-                    self.first, self.second, self.third
-                );
-                self.first = NULL;
-                self.second = NULL;
-                self.third = NULL;
-                return Some((msg, 3, false));
-            }
-            self.first = symbol;
-            self.second = self.third;
-            self.third = NULL;
-        }
-        None
+        self.first = graph;
+        self.second = self.third;
+        self.third = NULL;
     }
 
     /// Checks if the state contains a value or not.
@@ -90,14 +69,6 @@ impl SymbolState {
         self.first == NULL && self.second == NULL && self.third == NULL
     }
 
-    /// Checks if the state is a valid trigraph or not.
-    pub const fn is_trigraph(&self) -> bool {
-        matches!(
-            (self.first, self.second, self.third),
-            ('?', '?', NULL) | (_, '?', '?')
-        )
-    }
-
     /// Returns the last element of the state by copying it: it is not removed
     /// from the state.
     pub const fn last(&self) -> Option<char> {
@@ -116,6 +87,14 @@ impl SymbolState {
         }
     }
 
+    /// Returns the number of `char` in the state.
+    pub fn len(&self) -> usize {
+        [self.first, self.second, self.third]
+            .iter()
+            .filter(|ch| **ch != NULL)
+            .count()
+    }
+
     /// Pushes a `char` into the state.
     ///
     /// # Returns
@@ -160,16 +139,25 @@ impl SymbolState {
         lex_data: &mut LexingData,
         location: &Location,
     ) -> Option<(usize, Symbol)> {
-        if let Some((msg, len, error)) = self.handle_digraphs_trigraphs() {
-            let new_location = location.to_owned().into_past_with_length(len);
-            if error {
-                lex_data.push_err(new_location.to_failure(msg));
-            } else {
-                lex_data.push_err(new_location.to_warning(msg));
-            }
+        if lex_data.options().translates_digraphs() {
+            self.handle_digraphs();
         }
         let result = match (self.first, self.second, self.third) {
             ('<', '<', '=') => Some((3, Symbol::ShiftLeftAssign)),
+            ('.', '.', '.') => Some((3, Symbol::Ellipsis)),
+            ('.', '.', _) => {
+                // `..` isn't a token: it is split into two `.`
+                lex_data.push_err(
+                    location
+                        .to_owned()
+                        .into_past_with_length(self.len())
+                        .into_failure(
+                        "Found unexpected '..', which isn't a valid symbol. Did you mean '...'?"
+                            .to_owned(),
+                    ),
+                );
+                Some((1, Symbol::Dot))
+            }
             ('>', '>', '=') => Some((3, Symbol::ShiftRightAssign)),
             ('-', '>', _) => Some((2, Symbol::Arrow)),
             ('+', '+', _) => Some((2, Symbol::Increment)),
@@ -190,6 +178,8 @@ impl SymbolState {
             ('&', '=', _) => Some((2, Symbol::AndAssign)),
             ('|', '=', _) => Some((2, Symbol::OrAssign)),
             ('^', '=', _) => Some((2, Symbol::XorAssign)),
+            (':', ':', _) => Some((2, Symbol::DoubleColon)),
+            ('#', '#', _) => Some((2, Symbol::HashHash)),
             ('+', _, _) => Some((1, Symbol::Plus)),
             ('-', _, _) => Some((1, Symbol::Minus)),
             ('(', _, _) => Some((1, Symbol::ParenthesisOpen)),
@@ -214,6 +204,7 @@ impl SymbolState {
             ('?', _, _) => Some((1, Symbol::Interrogation)),
             (':', _, _) => Some((1, Symbol::Colon)),
             (';', _, _) => Some((1, Symbol::SemiColon)),
+            ('#', _, _) => Some((1, Symbol::Hash)),
             (NULL, NULL, NULL) => None,
             _ => panic!(
                 "This is not meant to happen. Some unsupported symbols were found in the operator part of the lex_data. LexingData: {self:?}"
@@ -0,0 +1,144 @@
+//! Module to lex a file lazily, line by line.
+//!
+//! See [`Lexer`] for more information.
+
+extern crate alloc;
+use alloc::collections::VecDeque;
+use core::iter::Peekable;
+use core::mem;
+use core::str::Lines;
+
+use super::conditionals::{Conditionals, Macro};
+use super::lex_content::lex_next_line;
+use super::state::api::LexingState;
+use super::types::api::{LexingData, Token};
+use crate::errors::api::{CompileError, Location};
+use crate::options::api::Options;
+
+/// Lexer that lexes a source file lazily, one line at a time.
+///
+/// This is meant for tools that stream the tokens, like syntax highlighters.
+/// The [`Lexer`] is an iterator on the tokens of the file, and on the errors
+/// and warnings found while lexing, in the order they are found. A line is
+/// only lexed when the tokens of the previous lines were all returned.
+///
+/// A token is only returned when the next tokens can't modify it anymore: a
+/// string literal is returned after the next token that isn't a layout token,
+/// as it may be merged with the next literal.
+///
+/// See [`lex_file`](super::lex_content::lex_file) for more information on the
+/// lexing.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::{Keyword, Lexer, Location, Options, TokenValue};
+///
+/// let mut lexer = Lexer::new("int x;\nx = 1;", Location::from(""), Options::default());
+/// let first = lexer.next().unwrap().unwrap();
+/// assert!(first.get_value() == &TokenValue::Keyword(Keyword::Int));
+/// assert!(lexer.count() == 6);
+/// ```
+#[derive(Debug)]
+pub struct Lexer<'content> {
+    /// Opened conditional blocks and defined macros, until the end of the
+    /// file.
+    conditionals: Option<Conditionals>,
+    /// The content of the file ends with a newline character.
+    ends_with_newline: bool,
+    /// Data of the lines that were lexed.
+    lex_data: LexingData,
+    /// State of the lexer at the end of the last lexed line.
+    lex_state: LexingState,
+    /// Lines of the file that weren't lexed yet.
+    lines: Peekable<Lines<'content>>,
+    /// Location of the next line to lex.
+    location: Location,
+    /// Macros defined at the end of the file, once it was lexed.
+    macros: Vec<Macro>,
+    /// Tokens and errors that were lexed, but not returned yet.
+    queue: VecDeque<Result<Token, CompileError>>,
+}
+
+impl<'content> Lexer<'content> {
+    /// Returns the location of the next line to lex.
+    ///
+    /// Once the whole file was lexed, this is the location after the last
+    /// line.
+    #[inline]
+    #[must_use]
+    pub const fn get_location(&self) -> &Location {
+        &self.location
+    }
+
+    /// Returns the table of the macros defined at the end of the file.
+    ///
+    /// The table is empty if the file wasn't lexed until the end. See
+    /// [`lex_file_with_macros`](super::lex_content::lex_file_with_macros) for
+    /// more information.
+    #[inline]
+    #[must_use]
+    pub fn into_macros(self) -> Vec<Macro> {
+        self.macros
+    }
+
+    /// Lexes the next line, and queues the tokens and errors that are ready.
+    ///
+    /// At the end of the file, the conditional blocks are closed and all the
+    /// remaining tokens are queued.
+    fn lex_next(&mut self) {
+        if let Some(conditionals) = &mut self.conditionals
+            && let Some(line) = self.lines.next()
+        {
+            let last_line = self.lines.peek().is_none();
+            if !lex_next_line(
+                line,
+                (last_line, !last_line || self.ends_with_newline),
+                &mut self.location,
+                &mut self.lex_data,
+                conditionals,
+                &mut self.lex_state,
+            ) {
+                // the maximum number of lines was reached
+                self.lines = "".lines().peekable();
+            }
+            let (tokens, errors) = self.lex_data.drain_ready();
+            self.queue.extend(tokens.into_iter().map(Ok));
+            self.queue.extend(errors.into_iter().map(Err));
+        } else if let Some(conditionals) = self.conditionals.take() {
+            self.macros = conditionals.close(&mut self.lex_data);
+            let (tokens, errors) = mem::take(&mut self.lex_data).into_res().into_parts();
+            self.queue.extend(tokens.into_iter().map(Ok));
+            self.queue.extend(errors.into_iter().map(Err));
+        }
+    }
+
+    /// Creates a [`Lexer`] for the content of a file, that starts at
+    /// `location`, with the given [`Options`].
+    #[inline]
+    #[must_use]
+    pub fn new(content: &'content str, location: Location, options: Options) -> Self {
+        Self {
+            conditionals: Some(Conditionals::default()),
+            ends_with_newline: content.ends_with('\n'),
+            lex_data: LexingData::from(options),
+            lex_state: LexingState::default(),
+            lines: content.lines().peekable(),
+            location,
+            macros: vec![],
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Result<Token, CompileError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.queue.is_empty() && self.conditionals.is_some() {
+            self.lex_next();
+        }
+        self.queue.pop_front()
+    }
+}
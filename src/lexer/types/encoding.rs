@@ -0,0 +1,88 @@
+//! Module to define the [`Encoding`] type.
+
+use core::fmt;
+
+use crate::options::api::CStandard;
+
+/// Encoding of a char or a string literal, chosen by its prefix.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::*;
+///
+/// let (tokens, _) = lex_file("L'a' u8\"b\"", &mut Location::from("")).into_parts();
+/// assert!(tokens[0].get_value() == &TokenValue::Char('a', Encoding::Wide));
+/// assert!(tokens[1].get_value() == &TokenValue::Str("b".to_owned(), Encoding::Utf8));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Encoding {
+    /// No prefix: `'a'` and `"str"`
+    #[default]
+    Plain,
+    /// `u` prefix: `u'a'` and `u"str"`, of type `char16_t`
+    Utf16,
+    /// `U` prefix: `U'a'` and `U"str"`, of type `char32_t`
+    Utf32,
+    /// `u8` prefix: `u8'a'` and `u8"str"`, of type `char8_t`
+    Utf8,
+    /// `L` prefix: `L'a'` and `L"str"`, of type `wchar_t`
+    Wide,
+}
+
+impl Encoding {
+    /// Returns the encoding of a prefix, if it is one.
+    pub(crate) fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "u" => Some(Self::Utf16),
+            "U" => Some(Self::Utf32),
+            "u8" => Some(Self::Utf8),
+            "L" => Some(Self::Wide),
+            _ => None,
+        }
+    }
+
+    /// Returns the maximum value of a char with this encoding, if it is
+    /// limited by the size of a code unit.
+    pub(crate) const fn max_char(self) -> Option<u32> {
+        match self {
+            Self::Utf8 => Some(0x7F),
+            Self::Utf16 => Some(0xFFFF),
+            Self::Plain | Self::Utf32 | Self::Wide => None,
+        }
+    }
+
+    /// Returns the prefix of the literals with this encoding.
+    #[inline]
+    #[must_use]
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::Plain => "",
+            Self::Utf16 => "u",
+            Self::Utf32 => "U",
+            Self::Utf8 => "u8",
+            Self::Wide => "L",
+        }
+    }
+
+    /// Returns the version of the C standard that introduced the prefix for
+    /// string literals.
+    ///
+    /// The `u8` prefix was only introduced in C23 for chars.
+    #[inline]
+    #[must_use]
+    pub const fn since(self) -> CStandard {
+        match self {
+            Self::Plain | Self::Wide => CStandard::C89,
+            Self::Utf16 | Self::Utf32 | Self::Utf8 => CStandard::C11,
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.prefix().fmt(f)
+    }
+}
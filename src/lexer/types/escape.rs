@@ -25,6 +25,11 @@ pub enum EscapeSequence {
 }
 
 impl EscapeSequence {
+    /// Checks if the escape sequence is hexadecimal.
+    pub const fn is_hexadecimal(&self) -> bool {
+        matches!(self, Self::Hexadecimal(_))
+    }
+
     /// Checks if the escape sequence is octal.
     pub const fn is_octal(&self) -> bool {
         matches!(self, Self::Octal(_))
@@ -2,20 +2,27 @@
 
 use core::fmt;
 
+use crate::options::api::CStandard;
+
 /// Defines the keyword type and its methods
 macro_rules! impl_keywords {
-    ($($pascal:ident $type:ident $str:expr ,)*) => {
+    ($($pascal:ident $type:ident $std:ident $str:expr ,)*) => {
 
         /// Keywords of the language
         ///
         /// See [CppReference](https://en.cppreference.com/w/c/keyword) for the list of C keywords.
-        #[derive(Debug, PartialEq, Eq)]
+        #[derive(Debug, PartialEq, Eq, Clone)]
+        #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
         pub enum Keyword {
-            $($pascal,)*
+            $(
+                #[doc = concat!("`", $str, "` keyword")]
+                $pascal,
+            )*
         }
 
         impl Keyword {
             /// Tries to make a keyword from a literal.
+            #[inline]
             pub fn from_value_or_res(value: &str) -> TryKeyword {
                 match value {
                     $($str => TryKeyword::from(Self::$pascal),)*
@@ -24,16 +31,36 @@ macro_rules! impl_keywords {
             }
 
             /// Returns the type of a keyword.
+            #[inline]
             pub const fn keyword_type(&self) -> KeywordType {
                 match self {
                     $(Self::$pascal => KeywordType::$type,)*
                 }
             }
 
+            /// Returns the C standard that introduced the keyword.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use c_parser::*;
+            ///
+            /// assert!(Keyword::Typeof.since() == CStandard::C23);
+            /// assert!(Keyword::UBool.since() == CStandard::C99);
+            /// ```
+            #[inline]
+            #[must_use]
+            pub const fn since(&self) -> CStandard {
+                match self {
+                    $(Self::$pascal => CStandard::$std,)*
+                }
+            }
+
         }
 
         #[expect(clippy::min_ident_chars)]
         impl fmt::Display for Keyword {
+            #[inline]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 match self {
                     $(Self::$pascal => $str.fmt(f),)*
@@ -44,68 +71,89 @@ macro_rules! impl_keywords {
 }
 
 impl_keywords!(
-    Alignof Operator "alignof",
-    Alignas Storage "alignas",
-    Auto Storage "auto",
-    Bool Type "bool",
-    Break Control "break",
-    Case Control "case",
-    Char Type "char",
-    Const Storage "const",
-    Constexpr Storage "constexpr",
-    Continue Control "continue",
-    Default Control "default",
-    Do Control "do",
-    Double Type "double",
-    Else Control "else",
-    Enum Type "enum",
-    Extern Storage "extern",
-    False Literal "false",
-    Float Type "float",
-    For Control "for",
-    Goto Control "goto",
-    If Control "if",
-    Inline Storage "inline",
-    Int Type "int",
-    Long Type "long",
-    Null Literal "NULL",
-    Nullptr Literal "nullptr",
-    Register Storage "register",
-    Restrict Storage "restrict",
-    Return Control "return",
-    Short Type "short",
-    Signed Type "signed",
-    Sizeof Operator "sizeof",
-    Static Storage "static",
-    StaticAssert Control "static_assert",
-    Struct Type "struct",
-    Switch Control "switch",
-    ThreadLocal Storage "thread_local",
-    True Literal "true",
-    Typedef Storage "typedef",
-    Typeof Operator "typeof",
-    TypeofUnqual Operator "typeof_unqual",
-    Union Type "union",
-    Unsigned Type "unsigned",
-    Void Type "void",
-    Volatile Storage "volatile",
-    While Control "while",
-    UAlignas Storage "_Alignas",
-    UAlignof Operator "_Alignof",
-    UAtomic Storage "_Atomic",
-    UBigInt Type "_BigInt",
-    UBool Type "_Bool",
-    UComplex Type "_Complex",
-    UDecimal128 Type "_Decimal128",
-    UDecimal32 Type "_Decimal32",
-    UDecimal64 Type "_Decimal64",
-    UGeneric Operator "_Generic",
-    UImaginary Type "_Imaginary",
-    UNoreturn Storage "_Noreturn",
-    UStaticAssert Control "_Static_assert",
-    UThreadLocal Storage "_Thread_local",
+    Alignof Operator C23 "alignof",
+    Alignas Storage C23 "alignas",
+    Auto Storage C89 "auto",
+    Bool Type C23 "bool",
+    Break Control C89 "break",
+    Case Control C89 "case",
+    Char Type C89 "char",
+    Const Storage C89 "const",
+    Constexpr Storage C23 "constexpr",
+    Continue Control C89 "continue",
+    Default Control C89 "default",
+    Do Control C89 "do",
+    Double Type C89 "double",
+    Else Control C89 "else",
+    Enum Type C89 "enum",
+    Extern Storage C89 "extern",
+    False Literal C23 "false",
+    Float Type C89 "float",
+    For Control C89 "for",
+    Goto Control C89 "goto",
+    If Control C89 "if",
+    Inline Storage C99 "inline",
+    Int Type C89 "int",
+    Long Type C89 "long",
+    Null Literal C89 "NULL",
+    Nullptr Literal C23 "nullptr",
+    Register Storage C89 "register",
+    Restrict Storage C99 "restrict",
+    Return Control C89 "return",
+    Short Type C89 "short",
+    Signed Type C89 "signed",
+    Sizeof Operator C89 "sizeof",
+    Static Storage C89 "static",
+    StaticAssert Control C23 "static_assert",
+    Struct Type C89 "struct",
+    Switch Control C89 "switch",
+    ThreadLocal Storage C23 "thread_local",
+    True Literal C23 "true",
+    Typedef Storage C89 "typedef",
+    Typeof Operator C23 "typeof",
+    TypeofUnqual Operator C23 "typeof_unqual",
+    Union Type C89 "union",
+    Unsigned Type C89 "unsigned",
+    Void Type C89 "void",
+    Volatile Storage C89 "volatile",
+    While Control C89 "while",
+    UAlignas Storage C11 "_Alignas",
+    UAlignof Operator C11 "_Alignof",
+    UAtomic Storage C11 "_Atomic",
+    UBigInt Type C23 "_BigInt",
+    UBool Type C99 "_Bool",
+    UComplex Type C99 "_Complex",
+    UDecimal128 Type C23 "_Decimal128",
+    UDecimal32 Type C23 "_Decimal32",
+    UDecimal64 Type C23 "_Decimal64",
+    UGeneric Operator C11 "_Generic",
+    UImaginary Type C99 "_Imaginary",
+    UNoreturn Storage C11 "_Noreturn",
+    UStaticAssert Control C11 "_Static_assert",
+    UThreadLocal Storage C11 "_Thread_local",
 );
 
+impl Keyword {
+    /// Returns the modern spelling of a keyword deprecated in C23, like
+    /// `bool` for `_Bool`.
+    ///
+    /// Returns [`None`] if the keyword isn't deprecated, or if it has no
+    /// keyword replacement (`_Noreturn` is replaced by an attribute).
+    #[inline]
+    #[must_use]
+    pub const fn modern(&self) -> Option<Self> {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match self {
+            Self::UAlignas => Some(Self::Alignas),
+            Self::UAlignof => Some(Self::Alignof),
+            Self::UBool => Some(Self::Bool),
+            Self::UStaticAssert => Some(Self::StaticAssert),
+            Self::UThreadLocal => Some(Self::ThreadLocal),
+            _ => None,
+        }
+    }
+}
+
 /// Type of keywords
 #[derive(Debug, PartialEq, Eq)]
 pub enum KeywordType {
@@ -1,9 +1,11 @@
 //! Module to define the [`LexingData`] type.
 
-use super::super::types::api::{Token, TokenValue};
+use super::super::conditionals::predefined_value;
+use super::super::types::api::{Encoding, Token, TokenValue};
 use super::symbols::Symbol;
 use crate::Res;
-use crate::errors::api::CompileError;
+use crate::errors::api::{CompileError, ErrorLevel, Location};
+use crate::options::api::Options;
 
 /// Lexing data
 ///
@@ -11,6 +13,16 @@ use crate::errors::api::CompileError;
 /// needed to be stored.
 #[derive(Debug, Default)]
 pub struct LexingData {
+    /// Comment that is being lexed, if the whitespace tokens are enabled in
+    /// the [`Options`].
+    comment: Option<Token>,
+    /// The data contains the value of a directive.
+    ///
+    /// The predefined macros, like `__LINE__`, are expanded in the code when
+    /// they are lexed, but in a directive they are expanded when the
+    /// directive is evaluated, as the value of a `#define` is expanded where
+    /// the macro is used.
+    directive: bool,
     /// Boolean to indicate if the lexer needs to fail this line and try the
     /// next.
     ///
@@ -22,11 +34,63 @@ pub struct LexingData {
     end_line: bool,
     /// Errors that have occurred while lexing.
     errors: Vec<CompileError>,
+    /// Options chosen by the user to lex the file.
+    options: Options,
     /// Tokens that have been lexed
     tokens: Vec<Token>,
+    /// The last string literal was truncated, so the literals merged into it
+    /// are dropped without another warning.
+    truncated: bool,
 }
 
 impl LexingData {
+    /// Stores the lexemes of the tokens that were lexed on the current line,
+    /// if they are kept in the [`Options`].
+    ///
+    /// `line` is the content of the line, and `location` is on this line.
+    pub fn attach_lexemes(&mut self, line: &str, location: &Location) {
+        if !self.options.keeps_lexemes() {
+            return;
+        }
+        for token in self.tokens.iter_mut().rev() {
+            if token.get_location().get_line() != location.get_line() {
+                break;
+            }
+            if token.get_lexeme().is_none() {
+                token.set_lexeme(line);
+            }
+        }
+    }
+
+    /// Pushes the comment that is being lexed, if there is one.
+    ///
+    /// `location` is the location of the last character of the comment.
+    pub fn close_comment(&mut self, location: &Location) {
+        if let Some(mut comment) = self.comment.take() {
+            comment.extend_location(location);
+            self.tokens.push(comment);
+        }
+    }
+
+    /// Takes the errors, and the tokens that can't be modified by the next
+    /// tokens.
+    ///
+    /// The last token that isn't a layout token, and the layout tokens after
+    /// it, are kept: a string literal can still be merged with the next one,
+    /// whitespace with the next whitespace, and a `-` changes the overflow
+    /// of the next number.
+    pub fn drain_ready(&mut self) -> (Vec<Token>, Vec<CompileError>) {
+        let kept = self
+            .tokens
+            .iter()
+            .rposition(|token| !token.is_layout())
+            .unwrap_or_else(|| self.tokens.len().saturating_sub(1));
+        (
+            self.tokens.drain(..kept).collect(),
+            self.errors.drain(..).collect(),
+        )
+    }
+
     /// Makes a [`Res`] from the lexing data.
     pub fn into_res(self) -> Res<Vec<Token>> {
         Res::from((self.tokens, self.errors))
@@ -42,6 +106,11 @@ impl LexingData {
         self.end_line
     }
 
+    /// Checks if a comment is being lexed, and will be pushed as a token.
+    pub const fn is_in_comment(&self) -> bool {
+        self.comment.is_some()
+    }
+
     /// Checks if the last parsed token was a minus sign.
     ///
     /// This is useful to know wether the last accepted token was `-`. This is
@@ -53,37 +122,168 @@ impl LexingData {
         )
     }
 
+    /// Returns the tokens that were lexed on the line of `location`, from the
+    /// last to the first.
+    pub fn line_tokens(&self, location: &Location) -> impl Iterator<Item = &Token> {
+        self.tokens
+            .iter()
+            .rev()
+            .take_while(|token| token.get_location().get_line() == location.get_line())
+    }
+
     /// Resets the lexing data for a new line.
     pub const fn newline(&mut self) {
         self.end_line = false;
     }
 
+    /// Starts a comment, if the whitespace tokens are enabled in the
+    /// [`Options`].
+    ///
+    /// `opening` is the delimiter of the comment, `/*` or `//`, and
+    /// `location` is the location of its last character.
+    pub fn open_comment(&mut self, opening: &str, location: &Location) {
+        if self.options.keeps_whitespace() {
+            self.comment = Some(Token::from_comment(opening, location));
+        }
+    }
+
+    /// Returns the options chosen by the user to lex the file.
+    pub const fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Pushes a character in the comment that is being lexed, if there is one.
+    pub fn push_comment_char(&mut self, ch: char) {
+        if let Some(comment) = &mut self.comment
+            && let TokenValue::Comment(value) = comment.get_value_mut()
+        {
+            value.push(ch);
+        }
+    }
+
     /// Pushes an error to the lexing data.
     pub fn push_err(&mut self, err: CompileError) {
-        let is_error = err.is_failure();
+        let is_error = err.is_error();
         self.errors.push(err);
         if is_error {
             self.end_line = true;
         }
     }
 
-    /// Pushes a token to the lexing data.
-    pub fn push_token(&mut self, token: Token) {
-        if let TokenValue::Str(val) = token.get_value()
-            && let Some(TokenValue::Str(old)) = self.tokens.last_mut().map(Token::get_value_mut)
+    /// Pushes a newline token to the lexing data, if the whitespace tokens are
+    /// enabled in the [`Options`].
+    pub fn push_newline(&mut self, location: &Location) {
+        if self.options.keeps_whitespace() {
+            self.tokens.push(Token::from_newline(location));
+        }
+    }
+
+    /// Pushes a warning about non-standard code to the lexing data.
+    ///
+    /// In pedantic mode, the warning is promoted to an error.
+    pub fn push_pedantic(&mut self, warning: CompileError) {
+        self.push_err(if self.options.is_pedantic() {
+            warning.promote_to(ErrorLevel::Failure)
+        } else {
+            warning
+        });
+    }
+
+    ///
+    /// Successive string literals are merged into one token, whose location
+    /// spans over all the literals. The whitespace, newlines and comments
+    /// between the literals are dropped. Successive whitespace is merged in
+    /// the same way. The literals are appended in place, so merging `n`
+    /// literals is linear in their total length, and a string longer than
+    /// [`Options::max_string_length`] is truncated with a warning. The
+    /// merged literal takes the prefix of the literals, like `L` for
+    /// `"a" L"b"`, and literals with different prefixes raise an error.
+    ///
+    /// The predefined macros, like `__LINE__`, are expanded before the
+    /// literals are merged, unless the data contains the value of a
+    /// directive.
+    pub fn push_token(&mut self, mut token: Token) {
+        if !self.directive
+            && let TokenValue::Ident(name) = token.get_value()
+            && let Some(value) = predefined_value(name, token.get_location())
+        {
+            *token.get_value_mut() = value;
+        }
+        let max_length = self.options.max_string_length();
+        if let TokenValue::Str(val, encoding) = token.get_value()
+            && let Some(idx) = self.tokens.iter().rposition(|old| !old.is_layout())
+            && let Some(last) = self.tokens.get_mut(idx)
+            && let TokenValue::Str(old, old_encoding) = last.get_value_mut()
+        {
+            if *old_encoding == Encoding::Plain {
+                *old_encoding = *encoding;
+            } else if *encoding != Encoding::Plain && encoding != old_encoding {
+                self.errors.push(token.get_location().to_failure(format!(
+                    "Found adjacent string literals with different prefixes '{old_encoding}' and '{encoding}': they can't be merged. Consider using the same prefix."
+                )));
+            }
+            if !self.truncated {
+                old.push_str(val);
+                self.truncated = truncate_string(old, max_length);
+                if self.truncated {
+                    self.errors.push(
+                        token
+                            .get_location()
+                            .to_warning(string_length_msg(max_length)),
+                    );
+                }
+            }
+            last.extend_location(token.get_location());
+            self.tokens.truncate(idx.saturating_add(1));
+        } else if let Some(last) = self.tokens.last_mut()
+            && let (TokenValue::Whitespace(old), TokenValue::Whitespace(val)) =
+                (last.get_value_mut(), token.get_value())
         {
             old.push_str(val);
+            last.extend_location(token.get_location());
         } else {
+            if let TokenValue::Str(val, _) = token.get_value_mut() {
+                self.truncated = truncate_string(val, max_length);
+                if self.truncated {
+                    self.errors.push(
+                        token
+                            .get_location()
+                            .to_warning(string_length_msg(max_length)),
+                    );
+                }
+            }
             self.tokens.push(token);
         }
     }
 
+    /// Pushes a whitespace character to the lexing data, if the whitespace
+    /// tokens are enabled in the [`Options`].
+    pub fn push_whitespace(&mut self, ch: char, location: &Location) {
+        if self.options.keeps_whitespace() {
+            self.push_token(Token::from_whitespace(ch, location));
+        }
+    }
+
+    /// Sets the lexing data as containing the value of a directive.
+    pub const fn set_directive(&mut self) {
+        self.directive = true;
+    }
+
     /// Sets the lexing data in end-of-line
     pub const fn set_end_line(&mut self) {
         self.end_line = true;
     }
 }
 
+impl From<Options> for LexingData {
+    fn from(options: Options) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+}
+
 /// Function to display tokens in a user-readable format.
 ///
 /// # Examples
@@ -110,3 +310,48 @@ pub fn display_tokens(tokens: &[Token]) -> String {
             .join(", ")
     )
 }
+
+/// Rewrites the keywords deprecated in C23 into their modern spelling.
+///
+/// For instance, `_Bool` is replaced by `bool`, and `_Static_assert` by
+/// `static_assert`. The locations of the tokens are left unchanged, so they
+/// still point to the original spelling. This can be used to modernise a
+/// source file, but the new keywords are only valid since C23.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::*;
+///
+/// let (mut tokens, _) = lex_file("_Bool x", &mut Location::from("")).into_parts();
+/// modernise_keywords(&mut tokens);
+/// assert!(display_tokens(&tokens) == "[Keyword(bool), Ident(x)]");
+/// ```
+#[inline]
+pub fn modernise_keywords(tokens: &mut [Token]) {
+    for token in tokens {
+        if let TokenValue::Keyword(keyword) = token.get_value_mut()
+            && let Some(modern) = keyword.modern()
+        {
+            *keyword = modern;
+        }
+    }
+}
+
+/// Returns the warning message for a string literal that was truncated.
+fn string_length_msg(max_length: usize) -> String {
+    format!(
+        "String literal is longer than the maximum length of {max_length} bytes, so the rest of the string was dropped."
+    )
+}
+
+/// Truncates a string to its maximum length, on a character boundary.
+///
+/// Returns `true` if the string was truncated.
+fn truncate_string(string: &mut String, max_length: usize) -> bool {
+    if string.len() <= max_length {
+        return false;
+    }
+    string.truncate(string.floor_char_boundary(max_length));
+    true
+}
@@ -8,13 +8,15 @@ pub mod api {
 
     #![allow(clippy::pub_use)]
 
+    pub use super::encoding::Encoding;
     pub use super::escape::EscapeSequence;
     pub use super::keywords::Keyword;
-    pub use super::lex_data::{LexingData, display_tokens};
+    pub use super::lex_data::{LexingData, display_tokens, modernise_keywords};
     pub use super::symbols::Symbol;
-    pub use super::tokens::{Ident, Token, TokenValue};
+    pub use super::tokens::{Ident, Token, TokenValue, is_ident_continue, is_ident_start};
 }
 
+mod encoding;
 mod escape;
 mod keywords;
 mod lex_data;
@@ -5,7 +5,8 @@
 /// See [`SymbolState`](super::super::state::api::SymbolState) for more
 /// information.
 #[expect(clippy::arbitrary_source_item_ordering)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Symbol {
     // one character
     /// &
@@ -34,8 +35,12 @@ pub enum Symbol {
     Divide,
     /// .
     Dot,
+    /// ...
+    Ellipsis,
     /// >
     Gt,
+    /// #
+    Hash,
     /// ?
     Interrogation,
     /// !
@@ -69,10 +74,14 @@ pub enum Symbol {
     Different,
     /// /=
     DivAssign,
+    /// ::
+    DoubleColon,
     /// ==
     Equal,
     /// >=
     Ge,
+    /// ##
+    HashHash,
     /// ++
     Increment,
     /// <=
@@ -101,3 +110,60 @@ pub enum Symbol {
     /// >>=
     ShiftRightAssign,
 }
+
+impl Symbol {
+    /// Returns the characters of the symbol, as written in the source.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ampersand => "&",
+            Self::Assign => "=",
+            Self::BitwiseNot => "~",
+            Self::BitwiseOr => "|",
+            Self::BitwiseXor => "^",
+            Self::BraceClose => "}",
+            Self::BraceOpen => "{",
+            Self::BracketClose => "]",
+            Self::BracketOpen => "[",
+            Self::Colon => ":",
+            Self::Comma => ",",
+            Self::Divide => "/",
+            Self::Dot => ".",
+            Self::Ellipsis => "...",
+            Self::Gt => ">",
+            Self::Hash => "#",
+            Self::Interrogation => "?",
+            Self::LogicalNot => "!",
+            Self::Lt => "<",
+            Self::Minus => "-",
+            Self::Modulo => "%",
+            Self::ParenthesisClose => ")",
+            Self::ParenthesisOpen => "(",
+            Self::Plus => "+",
+            Self::SemiColon => ";",
+            Self::Star => "*",
+            Self::AddAssign => "+=",
+            Self::AndAssign => "&=",
+            Self::Arrow => "->",
+            Self::Decrement => "--",
+            Self::Different => "!=",
+            Self::DivAssign => "/=",
+            Self::DoubleColon => "::",
+            Self::Equal => "==",
+            Self::Ge => ">=",
+            Self::HashHash => "##",
+            Self::Increment => "++",
+            Self::Le => "<=",
+            Self::LogicalAnd => "&&",
+            Self::LogicalOr => "||",
+            Self::ModAssign => "%=",
+            Self::MulAssign => "*=",
+            Self::OrAssign => "|=",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+            Self::SubAssign => "-=",
+            Self::XorAssign => "^=",
+            Self::ShiftLeftAssign => "<<=",
+            Self::ShiftRightAssign => ">>=",
+        }
+    }
+}
@@ -4,85 +4,199 @@
 //! store and pass on the values of the token that were lexed. They are stored
 //! in [`LexingData`] during lexing and then returned.
 
+use core::ops::RangeInclusive;
 use core::str::pattern;
 use core::{fmt, mem};
 
 use super::super::numbers::api::Number;
 use super::super::types::api::LexingData;
+use super::encoding::Encoding;
 use super::keywords::{Keyword, TryKeyword};
 use super::symbols::Symbol;
 use crate::errors::api::Location;
+use crate::options::api::{CStandard, Options};
+
+/// Ranges of the characters that aren't ASCII but are allowed in identifiers
+/// before C23.
+///
+/// See Annex D.1 of the C11 standard.
+const C11_IDENT_RANGES: [RangeInclusive<char>; 45] = [
+    '\u{a8}'..='\u{a8}',
+    '\u{aa}'..='\u{aa}',
+    '\u{ad}'..='\u{ad}',
+    '\u{af}'..='\u{af}',
+    '\u{b2}'..='\u{b5}',
+    '\u{b7}'..='\u{ba}',
+    '\u{bc}'..='\u{be}',
+    '\u{c0}'..='\u{d6}',
+    '\u{d8}'..='\u{f6}',
+    '\u{f8}'..='\u{ff}',
+    '\u{100}'..='\u{167f}',
+    '\u{1681}'..='\u{180d}',
+    '\u{180f}'..='\u{1fff}',
+    '\u{200b}'..='\u{200d}',
+    '\u{202a}'..='\u{202e}',
+    '\u{203f}'..='\u{2040}',
+    '\u{2054}'..='\u{2054}',
+    '\u{2060}'..='\u{206f}',
+    '\u{2070}'..='\u{218f}',
+    '\u{2460}'..='\u{24ff}',
+    '\u{2776}'..='\u{2793}',
+    '\u{2c00}'..='\u{2dff}',
+    '\u{2e80}'..='\u{2fff}',
+    '\u{3004}'..='\u{3007}',
+    '\u{3021}'..='\u{302f}',
+    '\u{3031}'..='\u{303f}',
+    '\u{3040}'..='\u{d7ff}',
+    '\u{f900}'..='\u{fd3d}',
+    '\u{fd40}'..='\u{fdcf}',
+    '\u{fdf0}'..='\u{fe44}',
+    '\u{fe47}'..='\u{fffd}',
+    '\u{10000}'..='\u{1fffd}',
+    '\u{20000}'..='\u{2fffd}',
+    '\u{30000}'..='\u{3fffd}',
+    '\u{40000}'..='\u{4fffd}',
+    '\u{50000}'..='\u{5fffd}',
+    '\u{60000}'..='\u{6fffd}',
+    '\u{70000}'..='\u{7fffd}',
+    '\u{80000}'..='\u{8fffd}',
+    '\u{90000}'..='\u{9fffd}',
+    '\u{a0000}'..='\u{afffd}',
+    '\u{b0000}'..='\u{bfffd}',
+    '\u{c0000}'..='\u{cfffd}',
+    '\u{d0000}'..='\u{dfffd}',
+    '\u{e0000}'..='\u{efffd}',
+];
+
+/// Ranges of the characters of [`C11_IDENT_RANGES`] that can't start an
+/// identifier.
+///
+/// See Annex D.2 of the C11 standard.
+const C11_NON_START_RANGES: [RangeInclusive<char>; 4] = [
+    '\u{300}'..='\u{36f}',
+    '\u{1dc0}'..='\u{1dff}',
+    '\u{20d0}'..='\u{20ff}',
+    '\u{fe20}'..='\u{fe2f}',
+];
 
 /// Represents an identifier
 ///
-/// An identifier is a token that contains a succession of alphanumeric digits
-/// (or underscores).
+/// An identifier is a token that starts with a character accepted by
+/// [`is_ident_start`], followed by characters accepted by
+/// [`is_ident_continue`].
 ///
 /// Identifiers are used as variable names, custom types, number constants etc.
 #[derive(Debug, Default, PartialEq, Eq)]
-pub struct Ident(String);
+pub struct Ident {
+    /// Characters of the identifier, with the universal character names
+    /// decoded
+    value: String,
+    /// Number of characters of the identifier in the source file
+    ///
+    /// This differs from the number of characters of the value when the
+    /// identifier contains universal character names, like `\u00C0`.
+    width: usize,
+}
 
 impl Ident {
     /// Checks if the underlying string contains a pattern
     pub fn contains<P: pattern::Pattern>(&self, pat: P) -> bool {
-        self.0.contains(pat)
+        self.value.contains(pat)
     }
 
     /// Returns the first character of the underlying string
     pub fn first(&self) -> Option<char> {
-        self.0.chars().next()
+        self.value.chars().next()
     }
 
     /// Checks if the underlying string is empty
     pub const fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.value.is_empty()
+    }
+
+    /// Checks if the identifier is a hexadecimal number constant: it starts
+    /// with `0x` or `0X`.
+    pub fn is_hexadecimal(&self) -> bool {
+        self.value.starts_with("0x") || self.value.starts_with("0X")
     }
 
-    /// Checks if the first character is a valid ascii digit (`[0-9]`).
+    /// Checks if the identifier is a number constant: it starts with an ascii
+    /// digit (`[0-9]`), or with a full stop followed by a digit, like `.5`.
     pub fn is_number(&self) -> bool {
-        self.first().unwrap_or('x').is_ascii_digit()
+        match self.first() {
+            Some('.') => self
+                .value
+                .chars()
+                .nth(1)
+                .is_some_and(|ch| ch.is_ascii_digit()),
+            Some(first) => first.is_ascii_digit(),
+            None => false,
+        }
     }
 
     /// Checks if last character of the string
     pub fn last_is_exp(&self) -> bool {
         self.is_number()
-            && match self.0.chars().last() {
-                Some('p' | 'P') => self.0.starts_with("0x"),
-                Some('e' | 'E') => !self.0.starts_with("0x"), /* if the number expression starts with 0 and contains an exponent, the number is considered decimal, not octal. */
+            && match self.value.chars().last() {
+                Some('p' | 'P') => self.is_hexadecimal(),
+                // if the number expression starts with 0 and contains an exponent, the
+                // number is considered decimal, not octal.
+                Some('e' | 'E') => !self.is_hexadecimal(),
                 Some(_) | None => false,
             }
     }
 
-    /// Returns the length of the underlying string
+    /// Returns the number of characters of the identifier in the source file
     pub const fn len(&self) -> usize {
-        self.0.len()
+        self.width
     }
 
     /// Pushes a character to the underlying string
     pub fn push(&mut self, ch: char) {
-        self.0.push(ch);
+        self.value.push(ch);
+        self.width = self.width.saturating_add(1);
+    }
+
+    /// Pushes a character that was written with a universal character name,
+    /// like `\u00C0`, to the underlying string.
+    ///
+    /// `width` is the number of characters of the universal character name.
+    pub fn push_escaped(&mut self, ch: char, width: usize) {
+        self.value.push(ch);
+        self.width = self.width.saturating_add(width);
     }
 
     /// Takes the value of the underlying string
     pub fn take_value(&mut self) -> String {
-        mem::take(&mut self.0)
+        self.width = 0;
+        mem::take(&mut self.value)
     }
 
     /// Returns a reference to the underlying string
     pub fn value(&self) -> &str {
-        &self.0
+        &self.value
     }
 }
 
 impl From<String> for Ident {
     fn from(value: String) -> Self {
-        Self(value)
+        Self {
+            width: value.chars().count(),
+            value,
+        }
     }
 }
 
 /// Struct that stores a lexed token
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Token {
+    /// Number of lines the token spans after the line of its location, like
+    /// for a block comment continued with a `\` on the next line.
+    extra_lines: usize,
+    /// Characters of the source the token was lexed from, if they are kept
+    /// (cf. [`Options::with_lexemes`](crate::Options::with_lexemes)).
+    lexeme: Option<String>,
     /// Location of the token
     ///
     /// The location is stored with the token to have it when parsing.
@@ -92,11 +206,47 @@ pub struct Token {
 }
 
 impl Token {
+    /// Extends the location of the [`Token`] until the end of another
+    /// location.
+    ///
+    /// See [`Location::extend_to`].
+    pub(crate) fn extend_location(&mut self, end: &Location) {
+        self.extra_lines = self
+            .extra_lines
+            .max(end.get_line().saturating_sub(self.location.get_line()));
+        self.location.extend_to(end);
+    }
+
     /// Converts a `char` into a token whose value is a [`TokenValue::Char`]
-    pub(crate) fn from_char(ch: char, location: &Location) -> Self {
+    ///
+    /// `size` is the length of the char literal in the source, without the
+    /// prefix and the quotes.
+    pub(crate) fn from_char(
+        ch: char,
+        encoding: Encoding,
+        size: usize,
+        location: &Location,
+    ) -> Self {
         Self {
-            value: TokenValue::Char(ch),
-            location: location.to_owned().into_past_with_length(1),
+            extra_lines: 0,
+            lexeme: None,
+            value: TokenValue::Char(ch, encoding),
+            location: location.to_owned().into_past_with_length(size),
+        }
+    }
+
+    /// Creates a token whose value is a [`TokenValue::Comment`], from the
+    /// opening delimiter of the comment.
+    ///
+    /// `location` is the location of the last character of the delimiter.
+    pub(crate) fn from_comment(opening: &str, location: &Location) -> Self {
+        let mut comment_location = location.to_owned().into_past_with_length(1);
+        comment_location.extend_to(location);
+        Self {
+            extra_lines: 0,
+            lexeme: None,
+            value: TokenValue::Comment(opening.to_owned()),
+            location: comment_location,
         }
     }
 
@@ -110,47 +260,86 @@ impl Token {
     ) -> Self {
         let len = literal.len();
         let value = literal.take_value();
+        let options = *lex_data.options();
         let token_value = match Keyword::from_value_or_res(&value) {
+            TryKeyword::Success(keyword) | TryKeyword::Deprecated(keyword)
+                if keyword.since() > options.standard() =>
+            {
+                lex_data.push_err(location.to_owned().into_past_with_length(len).to_suggestion(
+                    format!(
+                        "'{keyword}' is a keyword since {}, but the standard is {}: it is read as an identifier.",
+                        keyword.since(),
+                        options.standard()
+                    ),
+                ));
+                TokenValue::Ident(value)
+            }
             TryKeyword::Success(keyword) => TokenValue::Keyword(keyword),
+            TryKeyword::Deprecated(keyword) if options.standard() < CStandard::C23 => {
+                TokenValue::Keyword(keyword)
+            }
             TryKeyword::Deprecated(keyword) => {
-                let new_keyword = value
-                    .char_indices()
-                    .filter_map(|(idx, ch)| {
-                        if idx == 0 {
-                            None
-                        } else if idx == 1 {
-                            Some(ch.to_ascii_lowercase())
-                        } else {
-                            Some(ch)
-                        }
-                    })
-                    .collect::<String>();
-                lex_data.push_err(location.to_owned().into_past_with_length(len).to_warning(format!("Underscore operators are deprecated since C23. Consider using the new keyword: {new_keyword}")));
+                let advice = keyword.modern().map_or_else(
+                    // `_Noreturn` is the only one replaced by an attribute
+                    || "Consider using the attribute: [[noreturn]]".to_owned(),
+                    |new_keyword| format!("Consider using the new keyword: {new_keyword}"),
+                );
+                lex_data.push_pedantic(location.to_owned().into_past_with_length(len).to_warning(
+                    format!("Underscore operators are deprecated since C23. {advice}"),
+                ));
                 TokenValue::Keyword(keyword)
             }
             TryKeyword::Failure => TokenValue::Ident(value),
         };
         Self {
+            extra_lines: 0,
+            lexeme: None,
             location: location.to_owned().into_past_with_length(len),
             value: token_value,
         }
     }
 
+    /// Creates a token whose value is a [`TokenValue::Newline`], at the end
+    /// of the current line.
+    pub(crate) fn from_newline(location: &Location) -> Self {
+        Self {
+            extra_lines: 0,
+            lexeme: None,
+            value: TokenValue::Newline,
+            location: location.to_owned(),
+        }
+    }
+
     /// Converts a [`Number`] into a token whose value is a
     /// [`TokenValue::Number`].
-    pub(crate) fn from_number(number: Number, location: &Location) -> Self {
+    ///
+    /// The `size` is the length of the original literal, with the prefix and
+    /// the suffix.
+    pub(crate) fn from_number(number: Number, size: usize, location: &Location) -> Self {
         Self {
+            extra_lines: 0,
+            lexeme: None,
             value: TokenValue::Number(number),
-            location: location.to_owned(),
+            location: location.to_owned().into_past_with_length(size),
         }
     }
 
     /// Converts a string constant into a token whose value is a
     /// [`TokenValue::Str`]
-    pub(crate) fn from_str(str: String, location: &Location) -> Self {
+    ///
+    /// `size` is the length of the string literal in the source, without the
+    /// prefix and the quotes.
+    pub(crate) fn from_str(
+        str: String,
+        encoding: Encoding,
+        size: usize,
+        location: &Location,
+    ) -> Self {
         Self {
-            location: location.to_owned().into_past_with_length(str.len()),
-            value: TokenValue::Str(str),
+            extra_lines: 0,
+            lexeme: None,
+            location: location.to_owned().into_past_with_length(size),
+            value: TokenValue::Str(str, encoding),
         }
     }
 
@@ -158,11 +347,66 @@ impl Token {
     /// [`TokenValue::Symbol`].
     pub(crate) fn from_symbol(symbol: Symbol, size: usize, location: &Location) -> Self {
         Self {
+            extra_lines: 0,
+            lexeme: None,
             value: TokenValue::Symbol(symbol),
             location: location.to_owned().into_past_with_length(size),
         }
     }
 
+    /// Converts a whitespace character into a token whose value is a
+    /// [`TokenValue::Whitespace`].
+    pub(crate) fn from_whitespace(ch: char, location: &Location) -> Self {
+        Self {
+            extra_lines: 0,
+            lexeme: None,
+            value: TokenValue::Whitespace(ch.to_string()),
+            location: location.to_owned(),
+        }
+    }
+
+    /// Returns the number of lines the [`Token`] spans after the line of its
+    /// location.
+    pub(crate) const fn get_extra_lines(&self) -> usize {
+        self.extra_lines
+    }
+
+    /// Returns the characters of the source the [`Token`] was lexed from.
+    ///
+    /// The lexemes are only kept if they were enabled with
+    /// [`Options::with_lexemes`](crate::Options::with_lexemes), and the lexeme
+    /// of a token that spans over several lines only contains its first line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, Options, lex_file_with_options};
+    ///
+    /// let options = Options::default().with_lexemes(true);
+    /// let (tokens, _) =
+    ///     lex_file_with_options("x = 1e3;", &mut Location::from(""), options).into_parts();
+    /// let lexemes = tokens
+    ///     .iter()
+    ///     .map(|token| token.get_lexeme())
+    ///     .collect::<Vec<_>>();
+    /// assert!(lexemes == [Some("x"), Some("="), Some("1e3"), Some(";")]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get_lexeme(&self) -> Option<&str> {
+        self.lexeme.as_deref()
+    }
+
+    /// Returns a reference to the location of the [`Token`]
+    pub(crate) const fn get_location(&self) -> &Location {
+        &self.location
+    }
+
+    /// Returns a mutable reference to the location of the [`Token`]
+    pub(crate) const fn get_location_mut(&mut self) -> &mut Location {
+        &mut self.location
+    }
+
     /// Returns a reference to the value of the [`Token`]
     #[inline]
     #[must_use]
@@ -179,6 +423,33 @@ impl Token {
     pub(crate) fn into_value_location(self) -> (TokenValue, Location) {
         (self.value, self.location)
     }
+
+    /// Checks if the [`Token`] only describes the layout of the source: a
+    /// [`TokenValue::Whitespace`], a [`TokenValue::Newline`] or a
+    /// [`TokenValue::Comment`].
+    pub(crate) const fn is_layout(&self) -> bool {
+        matches!(
+            self.value,
+            TokenValue::Comment(_) | TokenValue::Newline | TokenValue::Whitespace(_)
+        )
+    }
+
+    /// Stores the characters of the source the [`Token`] was lexed from.
+    ///
+    /// `line` is the line of the source where the token starts.
+    ///
+    /// The location of a char or a string literal doesn't contain its prefix
+    /// and its quotes, so they are added back.
+    pub(crate) fn set_lexeme(&mut self, line: &str) {
+        let span = self.location.get_span(line);
+        #[expect(clippy::wildcard_enum_match_arm)]
+        let lexeme = match self.value {
+            TokenValue::Char(_, encoding) => format!("{encoding}'{span}'"),
+            TokenValue::Str(_, encoding) => format!("{encoding}\"{span}\""),
+            _ => span,
+        };
+        self.lexeme = Some(lexeme);
+    }
 }
 
 #[expect(clippy::min_ident_chars)]
@@ -190,7 +461,12 @@ impl fmt::Display for Token {
 }
 
 /// Enum that contains the value of the Token.
-#[derive(PartialEq, Debug)]
+///
+/// New kinds of tokens can be added when new options are introduced, so this
+/// enum is marked as non-exhaustive.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[non_exhaustive]
 pub enum TokenValue {
     /// Chars
     ///
@@ -198,11 +474,25 @@ pub enum TokenValue {
     ///
     /// - Delimited with single quotes `'`
     /// - Contain a single character.
+    /// - Can be prefixed by an [`Encoding`], like `L'o'`.
     ///
     /// # Examples
     ///
-    /// `'o'` and `'\u2205'`
-    Char(char),
+    /// `'o'`, `'\u2205'` and `u8'o'`
+    Char(char, Encoding),
+    /// Comments
+    ///
+    /// Only emitted if enabled with
+    /// [`Options::with_whitespace`](crate::Options::with_whitespace).
+    ///
+    /// # Rules
+    ///
+    /// - Contains the whole comment, with its delimiters.
+    ///
+    /// # Examples
+    ///
+    /// `"/* comment */"` and `"// comment"`
+    Comment(String),
     /// Identifiers
     ///
     /// # Rules
@@ -224,6 +514,11 @@ pub enum TokenValue {
     ///
     /// `const`, `int`, `sizeof`, `thread_local`
     Keyword(Keyword),
+    /// End of a line
+    ///
+    /// Only emitted if enabled with
+    /// [`Options::with_whitespace`](crate::Options::with_whitespace).
+    Newline,
     /// Number constants
     ///
     /// # Rules
@@ -240,11 +535,12 @@ pub enum TokenValue {
     ///
     /// - Delimited by double quotes
     /// - Successive quotes are merged
+    /// - Can be prefixed by an [`Encoding`], like `L"Hello"`.
     ///
     /// # Examples
     ///
-    /// `""`, `"Hello world"` and `"Hello""World"`
-    Str(String),
+    /// `""`, `"Hello world"`, `"Hello""World"` and `u8"Hello"`
+    Str(String, Encoding),
     /// Symbols
     ///
     /// # Rules
@@ -255,19 +551,138 @@ pub enum TokenValue {
     ///
     /// `<<=`, `+`, `[`
     Symbol(Symbol),
+    /// Whitespace
+    ///
+    /// Only emitted if enabled with
+    /// [`Options::with_whitespace`](crate::Options::with_whitespace).
+    ///
+    /// # Rules
+    ///
+    /// - Contains the exact spaces and tabs found between two tokens
+    /// - Successive whitespace is merged
+    ///
+    /// # Examples
+    ///
+    /// `"    "` for an indentation of 4 spaces
+    Whitespace(String),
+}
+
+impl TokenValue {
+    /// Returns the C source of the token.
+    ///
+    /// Unlike the [`Display`](fmt::Display) implementation, which tags the
+    /// tokens for debugging, like `Ident(foo)` or `Keyword(const)`, this only
+    /// writes the characters of the token, like `foo` or `const`. The chars
+    /// and strings are escaped again, and the numbers are written with
+    /// [`Number::to_source`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::*;
+    ///
+    /// let (tokens, _) = lex_file("const x = '\\n';", &mut Location::from("")).into_parts();
+    /// let source = tokens
+    ///     .iter()
+    ///     .map(|token| token.get_value().to_source())
+    ///     .collect::<Vec<_>>();
+    /// assert!(source == ["const", "x", "=", "'\\n'", ";"]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_source(&self) -> String {
+        match self {
+            Self::Char(ch, encoding) => format!("{encoding}'{}'", escape_char(*ch, '\'')),
+            Self::Comment(comment) => comment.to_owned(),
+            Self::Ident(ident) => ident.to_owned(),
+            Self::Keyword(keyword) => keyword.to_string(),
+            Self::Newline => "\n".to_owned(),
+            Self::Number(number) => number.to_source(),
+            Self::Str(string, encoding) => format!(
+                "{encoding}\"{}\"",
+                string
+                    .chars()
+                    .map(|ch| escape_char(ch, '"'))
+                    .collect::<String>()
+            ),
+            Self::Symbol(symbol) => symbol.as_str().to_owned(),
+            Self::Whitespace(whitespace) => whitespace.to_owned(),
+        }
+    }
 }
 
+/// Displays the tokens tagged with their kind, for debugging.
+///
+/// See [`TokenValue::to_source`] to get the source of a token.
 #[expect(clippy::min_ident_chars, clippy::use_debug)]
 impl fmt::Display for TokenValue {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Char(arg0) => write!(f, "'{arg0}'"),
+            Self::Char(arg0, arg1) => write!(f, "{arg1}'{arg0}'"),
+            Self::Comment(arg0) => write!(f, "Comment({arg0:?})"),
             Self::Keyword(arg0) => write!(f, "Keyword({arg0})"),
+            Self::Newline => "Newline".fmt(f),
             Self::Number(arg0) => write!(f, "{arg0}"),
             Self::Symbol(arg0) => write!(f, "{arg0:?}"),
             Self::Ident(arg0) => write!(f, "Ident({arg0})"),
-            Self::Str(arg0) => write!(f, "\"{arg0}\""),
+            Self::Str(arg0, arg1) => write!(f, "{arg1}\"{arg0}\""),
+            Self::Whitespace(arg0) => write!(f, "Whitespace({arg0:?})"),
         }
     }
 }
+
+/// Escapes a character of a char or a string literal.
+///
+/// `quote` is the delimiter of the literal, that needs to be escaped.
+fn escape_char(ch: char, quote: char) -> String {
+    match ch {
+        '\\' => "\\\\".to_owned(),
+        '\n' => "\\n".to_owned(),
+        '\t' => "\\t".to_owned(),
+        '\r' => "\\r".to_owned(),
+        '\0' => "\\0".to_owned(),
+        _ if ch == quote => format!("\\{quote}"),
+        _ => ch.to_string(),
+    }
+}
+
+/// Checks if a character can continue an identifier, after its first
+/// character.
+///
+/// This accepts the characters that can start an identifier (cf.
+/// [`is_ident_start`]) and the digits. The other characters that aren't ASCII
+/// depend on the standard:
+///
+/// - since C23, they must have the `XID_Continue` Unicode property;
+/// - before, they must be in the ranges of Annex D.1 of the C11 standard.
+pub fn is_ident_continue(ch: char, options: &Options) -> bool {
+    if ch.is_ascii() {
+        ch.is_ascii_digit() || is_ident_start(ch, options)
+    } else if options.standard() >= CStandard::C23 {
+        unicode_ident::is_xid_continue(ch)
+    } else {
+        C11_IDENT_RANGES.iter().any(|range| range.contains(&ch))
+    }
+}
+
+/// Checks if a character can start an identifier.
+///
+/// This accepts the ASCII letters and `_`, and `$` if it is allowed in the
+/// [`Options`]. A digit can't start an identifier: a token like `3abc` is a
+/// number constant, with an invalid suffix. The other characters that aren't
+/// ASCII depend on the standard:
+///
+/// - since C23, they must have the `XID_Start` Unicode property;
+/// - before, they must be in the ranges of Annex D.1 of the C11 standard, but
+///   not in those of Annex D.2, like the combining marks.
+pub fn is_ident_start(ch: char, options: &Options) -> bool {
+    if ch.is_ascii() {
+        ch.is_ascii_alphabetic() || ch == '_' || (ch == '$' && options.allows_dollars())
+    } else if options.standard() >= CStandard::C23 {
+        unicode_ident::is_xid_start(ch)
+    } else {
+        is_ident_continue(ch, options)
+            && !C11_NON_START_RANGES.iter().any(|range| range.contains(&ch))
+    }
+}
@@ -53,23 +53,28 @@
 #![feature(
     is_ascii_octdigit,
     f128,
-    concat_idents,
+    try_trait_v2_residual,
     pattern,
-    let_chains,
-    try_trait_v2,
-    const_vec_string_slice
+    try_trait_v2
 )]
 
 mod errors;
 mod lexer;
+mod options;
 mod parser;
 
 #[expect(clippy::useless_attribute, clippy::pub_use)]
-pub use crate::errors::api::{CompileError, Location, Res};
+pub use crate::errors::api::{CompileError, ErrorCode, ErrorLevel, Location, Res};
 #[expect(clippy::useless_attribute, clippy::pub_use)]
-pub use crate::lexer::api::{Number, TokenValue, display_tokens, lex_file};
+pub use crate::lexer::api::{
+    Encoding, Keyword, Lexer, Macro, Number, NumberType, Token, TokenValue, display_tokens, lex_file, lex_file_by_lines, lex_file_with_macros, lex_file_with_options, modernise_keywords, relex_file
+};
 #[expect(clippy::useless_attribute, clippy::pub_use)]
-pub use crate::parser::api::parse_tokens;
+pub use crate::options::api::{CStandard, Options};
+#[expect(clippy::useless_attribute, clippy::pub_use)]
+pub use crate::parser::api::{
+    BinaryOperator, Instruction, Operand, UnaryOperator, parse_header, parse_header_with_options, parse_tokens, parse_tokens_with_options
+};
 
 /// String to represent the empty symbol, displayed for empty nodes.
 const EMPTY: &str = "\u{2205} ";
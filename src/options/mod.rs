@@ -0,0 +1,458 @@
+//! Module to define the options of the compiler.
+//!
+//! The [`Options`] are given to the lexer and the parser to choose how to
+//! handle the features that depend on the C standard or on the strictness
+//! wanted by the user.
+
+pub mod api {
+    //! Api module to choose what functions to export.
+
+    #![allow(clippy::pub_use)]
+
+    pub use super::Options;
+    pub use super::standard::CStandard;
+}
+
+mod standard;
+
+use standard::CStandard;
+
+/// Options to choose how the C source is compiled.
+///
+/// The default options compile C23 code, with no pedantic errors, without
+/// the report of ambiguous escape sequences, with digraphs, without
+/// trigraphs, without `$` in identifiers, without GNU extensions, without K&R
+/// function definitions, without the implicit returns, without the lexemes of
+/// the tokens, without a parsing budget, without the report of undefined
+/// macros, without the report of unused variables and without whitespace
+/// tokens.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::{CStandard, Options};
+///
+/// let options = Options::default()
+///     .with_standard(CStandard::C89)
+///     .with_pedantic(true);
+/// assert!(options.standard() == CStandard::C89);
+/// assert!(options.is_pedantic());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[expect(clippy::struct_excessive_bools)]
+pub struct Options {
+    /// Reports the hexadecimal escape sequences followed by hexadecimal digits.
+    ambiguous_escapes: bool,
+    /// Allows the `$` character in identifiers.
+    dollars: bool,
+    /// Allows the GNU extensions.
+    gnu: bool,
+    /// Models the implicit return of `main`, and reports the other functions
+    /// that can end without a return.
+    implicit_returns: bool,
+    /// Allows the K&R function definitions in C23.
+    kr_definitions: bool,
+    /// Keeps the characters of the source each token was lexed from.
+    lexemes: bool,
+    /// Maximum number of characters on a line, unlimited if [`None`].
+    max_columns: Option<usize>,
+    /// Maximum number of lines in a file, unlimited if [`None`].
+    max_lines: Option<usize>,
+    /// Maximum number of nodes parsed before stopping, unlimited if [`None`].
+    max_nodes: Option<usize>,
+    /// Maximum length of a string literal, unlimited if [`None`].
+    max_string_length: Option<usize>,
+    /// Keeps the digraphs as separate symbols, like `<` and `:` for `<:`.
+    no_digraphs: bool,
+    /// Raises errors instead of warnings for non-standard code.
+    pedantic: bool,
+    /// C standard of the source code.
+    standard: CStandard,
+    /// Translates the trigraphs before lexing.
+    trigraphs: bool,
+    /// Reports the identifiers that aren't macros in the conditions of the
+    /// directives.
+    undefined_macros: bool,
+    /// Reports the local variables that are never used.
+    unused_variables: bool,
+    /// Emits tokens for whitespace, comments and newlines.
+    whitespace: bool,
+}
+
+impl Options {
+    /// Checks if the `$` character is allowed in identifiers.
+    #[inline]
+    #[must_use]
+    pub const fn allows_dollars(&self) -> bool {
+        self.dollars
+    }
+
+    /// Checks if the GNU extensions are allowed.
+    #[inline]
+    #[must_use]
+    pub const fn allows_gnu_extensions(&self) -> bool {
+        self.gnu
+    }
+
+    /// Checks if the K&R function definitions are allowed in C23.
+    ///
+    /// They are always allowed with older standards.
+    #[inline]
+    #[must_use]
+    pub const fn allows_kr_definitions(&self) -> bool {
+        self.kr_definitions
+    }
+
+    /// Checks if the pedantic mode is enabled.
+    #[inline]
+    #[must_use]
+    pub const fn is_pedantic(&self) -> bool {
+        self.pedantic
+    }
+
+    /// Checks if the lexer keeps the lexemes of the tokens.
+    #[inline]
+    #[must_use]
+    pub const fn keeps_lexemes(&self) -> bool {
+        self.lexemes
+    }
+
+    /// Checks if the lexer emits tokens for whitespace, comments and newlines.
+    #[inline]
+    #[must_use]
+    pub const fn keeps_whitespace(&self) -> bool {
+        self.whitespace
+    }
+
+    /// Returns the maximum number of characters on a line.
+    ///
+    /// A longer line raises an error. By default, this is only limited by the
+    /// size of [`usize`].
+    #[inline]
+    #[must_use]
+    pub const fn max_columns(&self) -> usize {
+        match self.max_columns {
+            Some(max_columns) => max_columns,
+            None => usize::MAX,
+        }
+    }
+
+    /// Returns the maximum number of lines in a file.
+    ///
+    /// A longer file raises an error. By default, this is only limited by the
+    /// size of [`usize`].
+    #[inline]
+    #[must_use]
+    pub const fn max_lines(&self) -> usize {
+        match self.max_lines {
+            Some(max_lines) => max_lines,
+            None => usize::MAX,
+        }
+    }
+
+    /// Returns the maximum number of nodes parsed before stopping.
+    ///
+    /// By default, this is only limited by the size of [`usize`].
+    #[inline]
+    #[must_use]
+    pub const fn max_nodes(&self) -> usize {
+        match self.max_nodes {
+            Some(max_nodes) => max_nodes,
+            None => usize::MAX,
+        }
+    }
+
+    /// Returns the maximum length of a string literal, in bytes.
+    ///
+    /// A longer string is truncated with a warning. By default, this is only
+    /// limited by the size of [`usize`].
+    #[inline]
+    #[must_use]
+    pub const fn max_string_length(&self) -> usize {
+        match self.max_string_length {
+            Some(max_string_length) => max_string_length,
+            None => usize::MAX,
+        }
+    }
+
+    /// Checks if the implicit return of `main` is modelled.
+    #[inline]
+    #[must_use]
+    pub const fn models_implicit_returns(&self) -> bool {
+        self.implicit_returns
+    }
+
+    /// Checks if the hexadecimal escape sequences followed by hexadecimal
+    /// digits are reported.
+    #[inline]
+    #[must_use]
+    pub const fn reports_ambiguous_escapes(&self) -> bool {
+        self.ambiguous_escapes
+    }
+
+    /// Checks if the identifiers that aren't macros are reported in the
+    /// conditions of the directives.
+    #[inline]
+    #[must_use]
+    pub const fn reports_undefined_macros(&self) -> bool {
+        self.undefined_macros
+    }
+
+    /// Checks if the local variables that are never used are reported.
+    #[inline]
+    #[must_use]
+    pub const fn reports_unused_variables(&self) -> bool {
+        self.unused_variables
+    }
+
+    /// Returns the C standard of the source code.
+    #[inline]
+    #[must_use]
+    pub const fn standard(&self) -> CStandard {
+        self.standard
+    }
+
+    /// Checks if the digraphs must be translated.
+    ///
+    /// Digraphs are translated by default, as they are defined by every
+    /// standard since C95.
+    #[inline]
+    #[must_use]
+    pub const fn translates_digraphs(&self) -> bool {
+        !self.no_digraphs
+    }
+
+    /// Checks if the trigraphs must be translated.
+    ///
+    /// Trigraphs are only translated if they were enabled with
+    /// [`Options::with_trigraphs`], and if the standard is older than C23, as
+    /// they were removed in C23.
+    #[inline]
+    #[must_use]
+    pub fn translates_trigraphs(&self) -> bool {
+        self.trigraphs && self.standard < CStandard::C23
+    }
+
+    /// Enables or disables the report of the ambiguous escape sequences.
+    ///
+    /// A hexadecimal escape sequence contains at most 2 digits, so `"\xabc"`
+    /// is read as `"\xab" "c"`. In standard C, the sequence takes all the
+    /// hexadecimal digits that follow it. When enabled, a suggestion is raised
+    /// for every hexadecimal digit that directly follows a full hexadecimal
+    /// escape sequence.
+    #[inline]
+    #[must_use]
+    pub const fn with_ambiguous_escapes(self, ambiguous_escapes: bool) -> Self {
+        Self {
+            ambiguous_escapes,
+            ..self
+        }
+    }
+
+    /// Enables or disables the translation of digraphs.
+    ///
+    /// Digraphs are alternative spellings of some symbols: `<:` for `[`, `:>`
+    /// for `]`, `<%` for `{`, `%>` for `}` and `%:` for `#`. They are enabled
+    /// by default. When disabled, `a<:b:>` is lexed as `a < : b : >`.
+    #[inline]
+    #[must_use]
+    pub const fn with_digraphs(self, digraphs: bool) -> Self {
+        Self {
+            no_digraphs: !digraphs,
+            ..self
+        }
+    }
+
+    /// Enables or disables the `$` character in identifiers.
+    ///
+    /// Many compilers accept `$` in identifiers, like `a$b`, but it isn't
+    /// part of the standard. When disabled, `$` is an unsupported character.
+    #[inline]
+    #[must_use]
+    pub const fn with_dollars(self, dollars: bool) -> Self {
+        Self { dollars, ..self }
+    }
+
+    /// Enables or disables the GNU extensions.
+    ///
+    /// The GNU extensions, like statement expressions `({ ... })`, raise
+    /// errors if they aren't enabled, and warnings if they are.
+    #[inline]
+    #[must_use]
+    pub const fn with_gnu_extensions(self, gnu: bool) -> Self {
+        Self { gnu, ..self }
+    }
+
+    /// Enables or disables the implicit returns.
+    ///
+    /// Since C99, reaching the end of `main` returns `0`. When enabled, this
+    /// `return 0;` is added at the end of the body of `main`, and a warning is
+    /// raised for the other functions that return a value, but whose end can
+    /// be reached without a `return`.
+    #[inline]
+    #[must_use]
+    pub const fn with_implicit_returns(self, implicit_returns: bool) -> Self {
+        Self {
+            implicit_returns,
+            ..self
+        }
+    }
+
+    /// Enables or disables the K&R function definitions in C23.
+    ///
+    /// K&R function definitions, like `int f(a) int a; { ... }`, were removed
+    /// in C23. Legacy code that uses them raises errors in C23 if they aren't
+    /// enabled, and warnings if they are. They are always accepted with older
+    /// standards.
+    #[inline]
+    #[must_use]
+    pub const fn with_kr_definitions(self, kr_definitions: bool) -> Self {
+        Self {
+            kr_definitions,
+            ..self
+        }
+    }
+
+    /// Enables or disables the lexemes of the tokens.
+    ///
+    /// When enabled, every token keeps the characters of the source it was
+    /// lexed from, available with
+    /// [`Token::get_lexeme`](crate::Token::get_lexeme). This allows to
+    /// rebuild the source verbatim, as the spelling of the numbers and of
+    /// the escape sequences is lost in the values of the tokens: `0xFF` and
+    /// `255` are both lexed to the same number.
+    #[inline]
+    #[must_use]
+    pub const fn with_lexemes(self, lexemes: bool) -> Self {
+        Self { lexemes, ..self }
+    }
+
+    /// Sets the maximum number of characters on a line.
+    ///
+    /// This protects the lexer against generated files with huge lines: the
+    /// lexing of a line stops with an error when it exceeds the limit.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_columns(self, max_columns: usize) -> Self {
+        Self {
+            max_columns: Some(max_columns),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of lines in a file.
+    ///
+    /// This protects the lexer against generated files with a huge number of
+    /// lines: the lexing of the file stops with an error when it exceeds the
+    /// limit.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_lines(self, max_lines: usize) -> Self {
+        Self {
+            max_lines: Some(max_lines),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of nodes parsed before stopping.
+    ///
+    /// This keeps the parser responsive on huge generated files, for example
+    /// in an IDE. Every token makes at most one node. The budget is checked
+    /// at the end of every statement, even in the body of a function: when it
+    /// is exceeded, the parser stops and returns the statements parsed so far,
+    /// with a suggestion.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_nodes(self, max_nodes: usize) -> Self {
+        Self {
+            max_nodes: Some(max_nodes),
+            ..self
+        }
+    }
+
+    /// Sets the maximum length of a string literal, in bytes.
+    ///
+    /// This protects the lexer against generated files with huge strings made
+    /// of many adjacent literals: the length is checked after the literals
+    /// are concatenated, and the rest of a longer string is dropped with a
+    /// warning.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_string_length(self, max_string_length: usize) -> Self {
+        Self {
+            max_string_length: Some(max_string_length),
+            ..self
+        }
+    }
+
+    /// Enables or disables the pedantic mode.
+    ///
+    /// In pedantic mode, code that doesn't respect the C standard raises
+    /// errors instead of warnings.
+    #[inline]
+    #[must_use]
+    pub const fn with_pedantic(self, pedantic: bool) -> Self {
+        Self { pedantic, ..self }
+    }
+
+    /// Sets the C standard of the source code.
+    #[inline]
+    #[must_use]
+    pub const fn with_standard(self, standard: CStandard) -> Self {
+        Self { standard, ..self }
+    }
+
+    /// Enables or disables the translation of trigraphs.
+    ///
+    /// Trigraphs are sequences of 3 characters starting with `??`, such as
+    /// `??=` for `#`. Like GCC, they are disabled by default, even for the
+    /// standards that define them.
+    #[inline]
+    #[must_use]
+    pub const fn with_trigraphs(self, trigraphs: bool) -> Self {
+        Self { trigraphs, ..self }
+    }
+
+    /// Enables or disables the report of the undefined macros.
+    ///
+    /// In the conditions of `#if` and `#elif`, the identifiers that aren't
+    /// macros evaluate to `0`, like `FOO` in `#if FOO`. When enabled, a
+    /// warning is raised for every such identifier.
+    #[inline]
+    #[must_use]
+    pub const fn with_undefined_macros(self, undefined_macros: bool) -> Self {
+        Self {
+            undefined_macros,
+            ..self
+        }
+    }
+
+    /// Enables or disables the report of the unused local variables.
+    ///
+    /// When enabled, the parser raises a suggestion for every local variable
+    /// that is declared but never used, unless it is marked with
+    /// `[[maybe_unused]]`. The parameters of the functions are not reported.
+    #[inline]
+    #[must_use]
+    pub const fn with_unused_variables(self, unused_variables: bool) -> Self {
+        Self {
+            unused_variables,
+            ..self
+        }
+    }
+
+    /// Enables or disables the whitespace tokens.
+    ///
+    /// By default, the lexer skips the whitespace. When enabled, the lexer
+    /// emits [`TokenValue::Whitespace`](crate::TokenValue::Whitespace) tokens
+    /// for every run of spaces and tabs, a
+    /// [`TokenValue::Comment`](crate::TokenValue::Comment) token for every
+    /// comment, and a [`TokenValue::Newline`](crate::TokenValue::Newline) token
+    /// at the end of every line, so that a formatter can rebuild the layout of
+    /// the source. These tokens are ignored by the parser.
+    #[inline]
+    #[must_use]
+    pub const fn with_whitespace(self, whitespace: bool) -> Self {
+        Self { whitespace, ..self }
+    }
+}
@@ -0,0 +1,46 @@
+//! Module to define the [`CStandard`] type.
+
+use core::fmt;
+
+/// Versions of the C standard.
+///
+/// The standards are ordered chronologically, so they can be compared to check
+/// if a feature is available.
+///
+/// # Examples
+///
+/// ```
+/// use c_parser::CStandard;
+///
+/// assert!(CStandard::C89 < CStandard::C99);
+/// assert!(CStandard::default() == CStandard::C23);
+/// ```
+#[expect(clippy::arbitrary_source_item_ordering)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CStandard {
+    /// ANSI C, also known as C90
+    C89,
+    /// C99
+    C99,
+    /// C11
+    C11,
+    /// C17, also known as C18
+    C17,
+    /// C23
+    #[default]
+    C23,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for CStandard {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::C89 => "C89".fmt(f),
+            Self::C99 => "C99".fmt(f),
+            Self::C11 => "C11".fmt(f),
+            Self::C17 => "C17".fmt(f),
+            Self::C23 => "C23".fmt(f),
+        }
+    }
+}
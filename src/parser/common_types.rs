@@ -0,0 +1,496 @@
+//! Module to compute the common type of the branches of the conditional
+//! operators.
+//!
+//! The value of `a ? b : c` has the common type of `b` and `c`:
+//!
+//! - if both are arithmetic, it is the type given by the usual arithmetic
+//!   conversions, like `double` for `a ? 1 : 2.0`;
+//! - if both are pointers to the same type, it is this pointer type, and if one
+//!   of them is `void *`, it is `void *`;
+//! - if one of them is a null pointer constant, like `0`, `nullptr` or `(void
+//!   *)0`, it is the type of the other one.
+//!
+//! The other combinations, like `a ? 1 : "s"`, are errors. There is no
+//! type-checking yet, so the types of the branches are only known for the
+//! constants, the casts, the arithmetic operations and the variables whose
+//! declaration is visible. The common type isn't computed if the type of a
+//! branch is unknown.
+//!
+//! The declarations with a `typeof` specifier, like `typeof(a) b`, have the
+//! type of the operand of the specifier, if it is known.
+
+use core::mem;
+
+use super::keyword::attributes::{AttributeKeyword, BasicDataType, Modifiers, SpecialAttributes};
+use super::types::binary::{Binary, BinaryOperator};
+use super::types::braced_blocks::BracedBlock;
+use super::types::builtin::{BuiltinArgument, TypeName};
+use super::types::literal::{Attribute, Literal, Variable, VariableName};
+use super::types::ternary::{Ternary, ValueType};
+use super::types::unary::{Unary, UnaryOperator};
+use super::types::{Ast, FunctionCall};
+use super::visit::{VisitMut, walk_mut, walk_variable_mut};
+use crate::errors::api::CompileError;
+use crate::lexer::api::{Number, NumberType};
+
+/// Declaration of a variable, with its type if it is known.
+type Declaration = (String, Option<ValueType>);
+
+/// Stack of the scopes of the declared variables, from the outermost to the
+/// innermost.
+struct Scopes {
+    /// Declarations of the opened scopes, the first one being the file scope.
+    declarations: Vec<Vec<Declaration>>,
+    /// Errors for the incompatible branches.
+    errors: Vec<CompileError>,
+    /// Parameters of the last function declaration, declared in the scope of
+    /// the body that follows it.
+    parameters: Vec<Declaration>,
+}
+
+impl Scopes {
+    /// Returns the type of the result of a binary operator, if it is known.
+    ///
+    /// The arithmetic operands are converted with the usual arithmetic
+    /// conversions, and an integer can be added to a pointer.
+    fn binary_type(&self, op: &BinaryOperator, arg_l: &Ast, arg_r: &Ast) -> Option<ValueType> {
+        match (op, self.type_of(arg_l), self.type_of(arg_r)) {
+            (
+                BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+                | BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::BitwiseAnd
+                | BinaryOperator::BitwiseXor
+                | BinaryOperator::BitwiseOr,
+                Some(ValueType::Arithmetic(left)),
+                Some(ValueType::Arithmetic(right)),
+            ) => Some(ValueType::Arithmetic(left.common_type(&right))),
+            (
+                BinaryOperator::Add | BinaryOperator::Subtract,
+                Some(pointer @ ValueType::Pointer(_)),
+                Some(ValueType::Arithmetic(_)),
+            )
+            | (
+                BinaryOperator::Add,
+                Some(ValueType::Arithmetic(_)),
+                Some(pointer @ ValueType::Pointer(_)),
+            ) => Some(pointer),
+            // `ptrdiff_t`, in the LP64 data model
+            (
+                BinaryOperator::Subtract,
+                Some(ValueType::Pointer(_)),
+                Some(ValueType::Pointer(_)),
+            ) => Some(ValueType::Arithmetic(NumberType::Long)),
+            (
+                BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight,
+                left @ Some(ValueType::Arithmetic(_)),
+                Some(ValueType::Arithmetic(_)),
+            )
+            | (
+                BinaryOperator::Assign
+                | BinaryOperator::AddAssign
+                | BinaryOperator::SubAssign
+                | BinaryOperator::MulAssign
+                | BinaryOperator::DivAssign
+                | BinaryOperator::ModAssign
+                | BinaryOperator::ShiftLeftAssign
+                | BinaryOperator::ShiftRightAssign
+                | BinaryOperator::AndAssign
+                | BinaryOperator::XorAssign
+                | BinaryOperator::OrAssign,
+                left,
+                _,
+            ) => left,
+            (
+                BinaryOperator::Lt
+                | BinaryOperator::Le
+                | BinaryOperator::Gt
+                | BinaryOperator::Ge
+                | BinaryOperator::Equal
+                | BinaryOperator::Different
+                | BinaryOperator::LogicalAnd
+                | BinaryOperator::LogicalOr,
+                _,
+                _,
+            ) => Some(ValueType::Arithmetic(NumberType::Int)),
+            (BinaryOperator::Comma, _, right) => right,
+            (
+                BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+                | BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::BitwiseAnd
+                | BinaryOperator::BitwiseXor
+                | BinaryOperator::BitwiseOr
+                | BinaryOperator::ShiftLeft
+                | BinaryOperator::ShiftRight
+                | BinaryOperator::ArraySubscript
+                | BinaryOperator::StructEnumMemberAccess
+                | BinaryOperator::StructEnumMemberPointerAccess,
+                _,
+                _,
+            ) => None,
+        }
+    }
+
+    /// Computes the common type of the branches of a conditional operator.
+    fn common_type(&self, ternary: &Ternary) -> Result<Option<ValueType>, String> {
+        let Some(failure) = &ternary.failure else {
+            return Ok(None);
+        };
+        let success_null = is_null_pointer_constant(&ternary.success);
+        let failure_null = is_null_pointer_constant(failure);
+        match (self.type_of(&ternary.success), self.type_of(failure)) {
+            (Some(ValueType::Arithmetic(left)), Some(ValueType::Arithmetic(right))) => {
+                Ok(Some(ValueType::Arithmetic(left.common_type(&right))))
+            }
+            (Some(ValueType::Pointer(left)), Some(ValueType::Pointer(right))) => {
+                if failure_null {
+                    Ok(Some(ValueType::Pointer(left)))
+                } else if success_null {
+                    Ok(Some(ValueType::Pointer(right)))
+                } else if unqualified(&left) == unqualified(&right) {
+                    Ok(Some(ValueType::Pointer(if left.len() >= right.len() {
+                        left
+                    } else {
+                        right
+                    })))
+                } else if is_void_pointer(&left) || is_void_pointer(&right) {
+                    Ok(Some(ValueType::Pointer(void_pointer())))
+                } else {
+                    Err(incompatible_error(
+                        &ValueType::Pointer(left),
+                        &ValueType::Pointer(right),
+                    ))
+                }
+            }
+            (Some(pointer @ ValueType::Pointer(_)), _) if failure_null => Ok(Some(pointer)),
+            (_, Some(pointer @ ValueType::Pointer(_))) if success_null => Ok(Some(pointer)),
+            (Some(left), Some(right)) => Err(incompatible_error(&left, &right)),
+            (None, _) | (_, None) => Ok(None),
+        }
+    }
+
+    /// Returns the type of a declaration or of a cast, if it is known.
+    ///
+    /// The `typeof` specifiers are resolved with the types of the visible
+    /// declarations: after `int a;`, `typeof(a) b` declares an `int`.
+    fn declaration_type(&self, attrs: &[Attribute]) -> Option<ValueType> {
+        let Some(specifier) = attrs.iter().find_map(|attr| {
+            if let Attribute::Typeof(specifier) = attr {
+                Some(specifier)
+            } else {
+                None
+            }
+        }) else {
+            return declared_type(attrs);
+        };
+        if attrs.contains(&Attribute::Indirection) {
+            // pointer to the type of the operand, like `typeof(a) *p`
+            return declared_type(attrs);
+        }
+        match &*specifier.operand {
+            BuiltinArgument::Expression(expression) => self.type_of(expression),
+            BuiltinArgument::Type(TypeName::Variable(variable)) => {
+                self.declaration_type(&variable.attrs)
+            }
+            BuiltinArgument::Type(TypeName::Array(..) | TypeName::FunctionPointer(..)) => None,
+        }
+    }
+
+    /// Declares a variable in the innermost scope.
+    fn declare(&mut self, variable: &Variable, value_type: Option<ValueType>) {
+        if let VariableName::UserDefined(name) = &variable.name
+            && let Some(scope) = self.declarations.last_mut()
+        {
+            scope.push((name.to_owned(), value_type));
+        }
+    }
+
+    /// Returns the type of an expression, if it is known.
+    fn type_of(&self, node: &Ast) -> Option<ValueType> {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match node {
+            Ast::Leaf(Literal::Char(_) | Literal::ConstantBool(_)) => {
+                Some(ValueType::Arithmetic(NumberType::Int))
+            }
+            Ast::Leaf(Literal::Number(number)) => Some(ValueType::Arithmetic(number.number_type())),
+            Ast::Leaf(Literal::Str(_)) => Some(ValueType::Pointer(vec![
+                Attribute::Keyword(AttributeKeyword::BasicDataType(BasicDataType::Char)),
+                Attribute::Indirection,
+            ])),
+            Ast::Leaf(Literal::Variable(Variable {
+                attrs,
+                name: VariableName::UserDefined(name),
+                ..
+            })) if attrs.is_empty() => self
+                .declarations
+                .iter()
+                .rev()
+                .find_map(|scope| scope.iter().rev().find(|(declared, _)| declared == name))
+                .and_then(|(_, value_type)| value_type.clone()),
+            Ast::ParensBlock(parens) => self.type_of(parens.get_inner()),
+            Ast::Ternary(ternary) => ternary.common_type.clone(),
+            Ast::Unary(Unary {
+                op: UnaryOperator::Cast(attrs),
+                ..
+            }) => self.declaration_type(attrs),
+            Ast::Binary(Binary { op, arg_l, arg_r }) => self.binary_type(op, arg_l, arg_r),
+            _ => None,
+        }
+    }
+
+    /// Walks a [`Ternary`], and computes its common type.
+    ///
+    /// The branches are walked first, so that the common type of a nested
+    /// conditional operator is already known.
+    fn visit_ternary(&mut self, ternary: &mut Ternary) {
+        self.visit(&mut ternary.condition);
+        self.visit(&mut ternary.success);
+        if let Some(failure) = &mut ternary.failure {
+            self.visit(failure);
+        }
+        match self.common_type(ternary) {
+            Ok(common_type) => ternary.common_type = common_type,
+            Err(msg) => {
+                if let Some(location) = ternary.condition.first_location() {
+                    self.errors.push(location.to_failure(msg));
+                }
+            }
+        }
+    }
+}
+
+impl VisitMut for Scopes {
+    /// Walks an [`Ast`] in the order of the source, to declare the variables
+    /// and compute the common types of the conditional operators.
+    fn visit(&mut self, node: &mut Ast) {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match node {
+            Ast::Binary(Binary {
+                op: BinaryOperator::ArraySubscript,
+                arg_l,
+                arg_r,
+            }) => {
+                // the type of an array isn't supported yet
+                if let Ast::Leaf(Literal::Variable(variable)) = &**arg_l
+                    && !variable.attrs.is_empty()
+                {
+                    self.declare(variable, None);
+                } else {
+                    self.visit(arg_l);
+                }
+                self.visit(arg_r);
+            }
+            Ast::FunctionCall(FunctionCall { args, variable, .. })
+                if !variable.attrs.is_empty() =>
+            {
+                // the type of a function isn't supported yet
+                self.declare(variable, None);
+                self.parameters = args.iter().filter_map(parameter).collect();
+            }
+            Ast::Ternary(ternary) => self.visit_ternary(ternary),
+            _ => walk_mut(self, node),
+        }
+    }
+
+    /// Walks a [`BracedBlock`], in a new scope if the block is closed.
+    ///
+    /// The parameters of a function are declared in the scope of its body.
+    fn visit_block(&mut self, block: &mut BracedBlock) {
+        if block.full {
+            self.declarations.push(mem::take(&mut self.parameters));
+        }
+        for elt in &mut block.elts {
+            if !matches!(elt, Ast::BracedBlock(BracedBlock { full: true, .. })) {
+                self.parameters.clear();
+            }
+            self.visit(elt);
+        }
+        if block.full {
+            self.declarations.pop();
+        }
+    }
+
+    /// Declares a [`Variable`], if it has attributes.
+    fn visit_variable(&mut self, variable: &mut Variable) {
+        walk_variable_mut(self, variable);
+        if !variable.attrs.is_empty() {
+            self.declare(variable, self.declaration_type(&variable.attrs));
+        }
+    }
+}
+
+/// Returns the arithmetic type of a declaration, after the integer
+/// promotions.
+///
+/// Returns [`None`] if the type isn't a basic arithmetic type, like a
+/// `struct` or a type defined with `typedef`.
+fn arithmetic_type(attrs: &[Attribute]) -> Option<NumberType> {
+    let mut floating = None;
+    let mut longs: u32 = 0;
+    let mut small = false;
+    let mut unsigned = false;
+    for attr in attrs {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match attr {
+            Attribute::Keyword(AttributeKeyword::BasicDataType(BasicDataType::Double)) => {
+                floating = Some(NumberType::Double);
+            }
+            Attribute::Keyword(AttributeKeyword::BasicDataType(BasicDataType::Float)) => {
+                floating = Some(NumberType::Float);
+            }
+            Attribute::Keyword(
+                AttributeKeyword::BasicDataType(BasicDataType::Bool | BasicDataType::Char)
+                | AttributeKeyword::Modifiers(Modifiers::Short),
+            ) => small = true,
+            Attribute::Keyword(AttributeKeyword::Modifiers(Modifiers::Long)) => {
+                longs = longs.saturating_add(1);
+            }
+            Attribute::Keyword(AttributeKeyword::Modifiers(Modifiers::Unsigned)) => {
+                unsigned = true;
+            }
+            Attribute::Keyword(
+                AttributeKeyword::BasicDataType(BasicDataType::Int)
+                | AttributeKeyword::Modifiers(Modifiers::Signed)
+                | AttributeKeyword::Qualifiers(_)
+                | AttributeKeyword::Storage(_),
+            )
+            | Attribute::Standard(_) => (),
+            _ => return None,
+        }
+    }
+    Some(match (floating, longs, unsigned) {
+        (Some(NumberType::Double), 1.., _) => NumberType::LongDouble,
+        (Some(number_type), ..) => number_type,
+        // `char`, `short` and `_Bool` are promoted to `int`
+        (None, _, _) if small => NumberType::Int,
+        (None, 0, false) => NumberType::Int,
+        (None, 0, true) => NumberType::UInt,
+        (None, 1, false) => NumberType::Long,
+        (None, 1, true) => NumberType::ULong,
+        (None, _, false) => NumberType::LongLong,
+        (None, _, true) => NumberType::ULongLong,
+    })
+}
+
+/// Computes the common types of the conditional operators, and reports the
+/// incompatible branches.
+///
+/// See the [module documentation](self) for more information.
+pub fn compute_common_types(node: &mut Ast) -> Vec<CompileError> {
+    let mut scopes = Scopes {
+        declarations: vec![vec![]],
+        errors: vec![],
+        parameters: vec![],
+    };
+    scopes.visit(node);
+    scopes.errors
+}
+
+/// Returns the type of a declaration or of a cast, if it is known.
+fn declared_type(attrs: &[Attribute]) -> Option<ValueType> {
+    if attrs
+        .iter()
+        .any(|attr| matches!(attr, Attribute::Function(_)))
+    {
+        None
+    } else if attrs.contains(&Attribute::Indirection) {
+        Some(ValueType::Pointer(
+            attrs
+                .iter()
+                .filter(|attr| {
+                    !matches!(
+                        attr,
+                        Attribute::Keyword(AttributeKeyword::Storage(_)) | Attribute::Standard(_)
+                    )
+                })
+                .cloned()
+                .collect(),
+        ))
+    } else {
+        arithmetic_type(attrs).map(ValueType::Arithmetic)
+    }
+}
+
+/// Returns the error message for branches of incompatible types.
+fn incompatible_error(left: &ValueType, right: &ValueType) -> String {
+    format!(
+        "Found branches of incompatible types '{left}' and '{right}' in a conditional operator, but they must have a common type."
+    )
+}
+
+/// Checks if an expression is a null pointer constant: `0`, `nullptr` or
+/// `(void *)0`.
+fn is_null_pointer_constant(node: &Ast) -> bool {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Leaf(Literal::Nullptr) => true,
+        Ast::Leaf(Literal::Number(number)) => matches!(
+            number,
+            Number::Int(0)
+                | Number::Long(0)
+                | Number::LongLong(0)
+                | Number::UInt(0)
+                | Number::ULong(0)
+                | Number::ULongLong(0)
+        ),
+        Ast::ParensBlock(parens) => is_null_pointer_constant(parens.get_inner()),
+        Ast::Unary(Unary {
+            op: UnaryOperator::Cast(attrs),
+            arg,
+        }) => *attrs == void_pointer() && is_null_pointer_constant(arg),
+        _ => false,
+    }
+}
+
+/// Checks if a pointer type is a pointer to `void`, with any qualifiers.
+fn is_void_pointer(attrs: &[Attribute]) -> bool {
+    unqualified(attrs) == void_pointer()
+}
+
+/// Returns the declaration of a parameter of a function, if it is named.
+fn parameter(node: &Ast) -> Option<Declaration> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Leaf(Literal::Variable(Variable {
+            attrs,
+            name: VariableName::UserDefined(name),
+            ..
+        })) => Some((name.to_owned(), declared_type(attrs))),
+        Ast::Binary(Binary {
+            op: BinaryOperator::ArraySubscript,
+            arg_l,
+            ..
+        }) => parameter(arg_l).map(|(name, _)| (name, None)),
+        _ => None,
+    }
+}
+
+/// Returns the attributes of a pointer type, without the qualifiers.
+fn unqualified(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| {
+            !matches!(
+                attr,
+                Attribute::Keyword(
+                    AttributeKeyword::Qualifiers(_)
+                        | AttributeKeyword::SpecialAttributes(SpecialAttributes::Restrict)
+                )
+            )
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns the attributes of the `void *` type.
+fn void_pointer() -> Vec<Attribute> {
+    vec![
+        Attribute::Keyword(AttributeKeyword::BasicDataType(BasicDataType::Void)),
+        Attribute::Indirection,
+    ]
+}
@@ -0,0 +1,65 @@
+//! Module to find the `while` loops whose body is never executed.
+//!
+//! The condition of a `while` loop is evaluated before its body, so a loop
+//! like `while (0) { x; }` never executes its body, and is most likely a
+//! mistake. On the contrary, the body of a `do`-`while` loop is executed
+//! before its condition: `do { x; } while (0);` executes its body once, and
+//! is a common idiom in macros.
+//!
+//! The `while` of a `do`-`while` loop is followed by a `;` and not by a body,
+//! so only the `while` loops that are followed by a statement are reported.
+
+use super::implicit_returns::is_control_flow;
+use super::keyword::control_flow::keyword::ControlFlowKeyword;
+use super::modifiers::constant::constant_value;
+use super::types::Ast;
+use super::types::braced_blocks::BracedBlock;
+use super::visit::{Visit, walk_block};
+use crate::errors::api::CompileError;
+
+/// `while` loops found while walking the [`Ast`].
+#[derive(Default)]
+struct Loops {
+    /// Suggestions for the loops whose body is never executed.
+    errors: Vec<CompileError>,
+}
+
+impl Visit<'_> for Loops {
+    /// Walks a [`BracedBlock`] to find the `while` loops in its statements.
+    fn visit_block(&mut self, block: &BracedBlock) {
+        for window in block.elts.windows(3) {
+            if let [keyword, Ast::ParensBlock(parens), body] = window
+                && is_control_flow(keyword, Some(&ControlFlowKeyword::While))
+                && constant_value(parens.get_inner(), &|_| None) == Some(0)
+                && !is_empty_statement(body)
+                && let Some(location) = body.first_location()
+            {
+                self.errors.push(location.to_suggestion(
+                    "The condition of this 'while' loop is always false, so its body is never executed. Perhaps this is unintended? To execute a block once, like in a macro, use 'do { ... } while (0);' instead."
+                        .to_owned(),
+                ));
+            }
+        }
+        walk_block(self, block);
+    }
+}
+
+/// Reports the `while` loops with a constant false condition, whose body is
+/// never executed.
+///
+/// See the [module documentation](self) for more information.
+pub fn check_dead_loops(node: &Ast) -> Vec<CompileError> {
+    let mut loops = Loops::default();
+    loops.visit(node);
+    loops.errors
+}
+
+/// Checks if a statement does nothing, like `;` or `{}`.
+fn is_empty_statement(node: &Ast) -> bool {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Empty => true,
+        Ast::BracedBlock(BracedBlock { elts, .. }) => elts.iter().all(|elt| *elt == Ast::Empty),
+        _ => false,
+    }
+}
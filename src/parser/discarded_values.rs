@@ -0,0 +1,104 @@
+//! Module to find the values discarded by the comma operator.
+//!
+//! In `a, b`, the value of `a` is computed and then thrown away. This is only
+//! useful if computing `a` has a side effect, like a call, an assignment or an
+//! increment. Otherwise, the left operand is most likely a mistake.
+
+use super::types::Ast;
+use super::types::binary::{Binary, BinaryOperator};
+use super::types::literal::Literal;
+use super::types::ternary::Ternary;
+use super::types::unary::{Unary, UnaryOperator};
+use super::visit::{Visit, walk};
+use crate::errors::api::CompileError;
+
+/// Comma operators found while walking the [`Ast`].
+#[derive(Default)]
+struct Commas {
+    /// Suggestions for the discarded values.
+    errors: Vec<CompileError>,
+}
+
+impl Visit<'_> for Commas {
+    /// Walks an [`Ast`] to find the comma operators.
+    fn visit(&mut self, node: &Ast) {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match node {
+            Ast::Binary(Binary {
+                op: BinaryOperator::Comma,
+                arg_l,
+                ..
+            }) if is_pure(arg_l) => {
+                if let Some(location) = arg_l.first_location().or_else(|| node.first_location()) {
+                    self.errors.push(location.to_suggestion(
+                        "The left operand of this comma operator has no side effect, so its value is discarded. Perhaps this is unintended?"
+                            .to_owned(),
+                    ));
+                }
+                walk(self, node);
+            }
+            Ast::ParensBlock(parens) => match parens.get_inner() {
+                // header of a `for` loop, like `(i = 0, j = 0; i < j; i++, j--)`
+                Ast::BracedBlock(_) => (),
+                inner => self.visit(inner),
+            },
+            _ => walk(self, node),
+        }
+    }
+}
+
+/// Reports the comma operators whose left operand has no side effect.
+///
+/// The headers of the `for` loops and the arguments of the function calls are
+/// skipped. See the [module documentation](self) for more information.
+pub fn check_discarded_values(node: &Ast) -> Vec<CompileError> {
+    let mut commas = Commas::default();
+    commas.visit(node);
+    commas.errors
+}
+
+/// Checks if an expression is pure, i.e., if evaluating it has no side
+/// effect.
+///
+/// The declarations, like the `int a` of `int a, b`, aren't pure.
+fn is_pure(node: &Ast) -> bool {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Binary(Binary { op, arg_l, arg_r }) => {
+            !matches!(
+                op,
+                BinaryOperator::Assign
+                    | BinaryOperator::AddAssign
+                    | BinaryOperator::SubAssign
+                    | BinaryOperator::MulAssign
+                    | BinaryOperator::DivAssign
+                    | BinaryOperator::ModAssign
+                    | BinaryOperator::ShiftLeftAssign
+                    | BinaryOperator::ShiftRightAssign
+                    | BinaryOperator::AndAssign
+                    | BinaryOperator::XorAssign
+                    | BinaryOperator::OrAssign
+            ) && is_pure(arg_l)
+                && is_pure(arg_r)
+        }
+        Ast::Leaf(Literal::Variable(variable)) => variable.attrs.is_empty(),
+        Ast::Leaf(_) => true,
+        Ast::ParensBlock(parens) => is_pure(parens.get_inner()),
+        Ast::Ternary(Ternary {
+            condition,
+            success,
+            failure,
+            ..
+        }) => is_pure(condition) && is_pure(success) && failure.as_deref().is_none_or(is_pure),
+        Ast::Unary(Unary { arg, op }) => {
+            !matches!(
+                op,
+                UnaryOperator::PostfixDecrement
+                    | UnaryOperator::PostfixIncrement
+                    | UnaryOperator::PrefixDecrement
+                    | UnaryOperator::PrefixIncrement
+            ) && is_pure(arg)
+        }
+        _ => false,
+    }
+}
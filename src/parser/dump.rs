@@ -0,0 +1,373 @@
+//! Module to dump the [`Ast`] in the style of `clang -Xclang -ast-dump`.
+//!
+//! Each node is written on its own line, with the kind of the node, like
+//! `BinaryOperator` or `IntegerLiteral`, followed by its key attributes. The
+//! children are indented below their parent with the tree characters of
+//! clang. The addresses and the source ranges are not written, and the types
+//! are only written when they are known from the source, like for literals
+//! and declarations.
+
+use super::keyword::control_flow::keyword::ControlFlowKeyword;
+use super::keyword::control_flow::node::ControlFlowNode;
+use super::repr_attrs;
+use super::types::binary::{Binary, BinaryOperator};
+use super::types::braced_blocks::BracedBlock;
+use super::types::builtin::{AlignOf, BuiltinArgument};
+use super::types::literal::{Literal, Variable};
+use super::types::unary::UnaryOperator;
+use super::types::{Ast, FunctionCall};
+use crate::lexer::api::Number;
+
+/// Node that can be dumped.
+trait Dump {
+    /// Returns the children of the node, in the order of the source.
+    fn children(&self) -> Vec<&dyn Dump>;
+    /// Returns the line of the node: its kind and its key attributes.
+    fn label(&self) -> String;
+}
+
+impl Dump for Ast {
+    fn children(&self) -> Vec<&dyn Dump> {
+        match self {
+            Self::AlignOf(AlignOf(operand)) => vec![&**operand],
+            Self::ArrayDesignator(designator) => {
+                let mut children: Vec<&dyn Dump> = vec![&*designator.lo];
+                if let Some(hi) = &designator.hi {
+                    children.push(&**hi);
+                }
+                children
+            }
+            Self::CaseRange(range) => vec![&*range.lo, &*range.hi],
+            Self::ArraySize(_)
+            | Self::Empty
+            | Self::Label(_)
+            | Self::Leaf(_)
+            | Self::MemberDesignator(_) => vec![],
+            Self::Binary(Binary { op, arg_l, arg_r }) => match op {
+                BinaryOperator::StructEnumMemberAccess
+                | BinaryOperator::StructEnumMemberPointerAccess => vec![&**arg_l],
+                BinaryOperator::ArraySubscript
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+                | BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::ShiftRight
+                | BinaryOperator::ShiftLeft
+                | BinaryOperator::Lt
+                | BinaryOperator::Le
+                | BinaryOperator::Gt
+                | BinaryOperator::Ge
+                | BinaryOperator::Equal
+                | BinaryOperator::Different
+                | BinaryOperator::BitwiseAnd
+                | BinaryOperator::BitwiseXor
+                | BinaryOperator::BitwiseOr
+                | BinaryOperator::LogicalAnd
+                | BinaryOperator::LogicalOr
+                | BinaryOperator::Comma
+                | BinaryOperator::Assign
+                | BinaryOperator::AddAssign
+                | BinaryOperator::SubAssign
+                | BinaryOperator::MulAssign
+                | BinaryOperator::DivAssign
+                | BinaryOperator::ModAssign
+                | BinaryOperator::ShiftLeftAssign
+                | BinaryOperator::ShiftRightAssign
+                | BinaryOperator::AndAssign
+                | BinaryOperator::XorAssign
+                | BinaryOperator::OrAssign => vec![&**arg_l, &**arg_r],
+            },
+            Self::BracedBlock(block) => block.children(),
+            Self::BuiltinCall(call) => call.args.iter().map(|arg| -> &dyn Dump { arg }).collect(),
+            Self::CompoundLiteral(literal) => elements(&literal.initialiser.elts),
+            Self::ControlFlow(ctrl) => ctrl.children(),
+            Self::ExpressionCall(call) => {
+                let mut children: Vec<&dyn Dump> = vec![&*call.callee];
+                children.extend(elements(&call.args));
+                children
+            }
+            Self::FunctionArgsBuild(elts) => elements(elts),
+            Self::FunctionCall(FunctionCall { variable, args, .. }) => {
+                let mut children: Vec<&dyn Dump> = vec![];
+                if variable.attrs.is_empty() {
+                    children.push(variable);
+                }
+                children.extend(elements(args));
+                children
+            }
+            Self::ListInitialiser(list) => elements(&list.elts),
+            Self::ParensBlock(parens) => vec![parens.get_inner()],
+            Self::StatementExpression(statement) => vec![&statement.0],
+            Self::Ternary(ternary) => {
+                let mut children: Vec<&dyn Dump> = vec![&*ternary.condition, &*ternary.success];
+                if let Some(failure) = &ternary.failure {
+                    children.push(&**failure);
+                }
+                children
+            }
+            Self::Unary(unary) => vec![&*unary.arg],
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::AlignOf(_) => "UnaryExprOrTypeTraitExpr alignof".to_owned(),
+            Self::ArrayDesignator(_) => "ArrayDesignator".to_owned(),
+            Self::CaseRange(_) => "CaseRange".to_owned(),
+            Self::ArraySize(size) => format!("ArraySize '{size}'"),
+            Self::Binary(Binary { op, arg_r, .. }) => match op {
+                BinaryOperator::ArraySubscript => "ArraySubscriptExpr".to_owned(),
+                BinaryOperator::StructEnumMemberAccess
+                | BinaryOperator::StructEnumMemberPointerAccess => {
+                    format!("MemberExpr {op}{arg_r}")
+                }
+                BinaryOperator::AddAssign
+                | BinaryOperator::SubAssign
+                | BinaryOperator::MulAssign
+                | BinaryOperator::DivAssign
+                | BinaryOperator::ModAssign
+                | BinaryOperator::ShiftLeftAssign
+                | BinaryOperator::ShiftRightAssign
+                | BinaryOperator::AndAssign
+                | BinaryOperator::XorAssign
+                | BinaryOperator::OrAssign => format!("CompoundAssignOperator '{op}'"),
+                BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+                | BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::ShiftRight
+                | BinaryOperator::ShiftLeft
+                | BinaryOperator::Lt
+                | BinaryOperator::Le
+                | BinaryOperator::Gt
+                | BinaryOperator::Ge
+                | BinaryOperator::Equal
+                | BinaryOperator::Different
+                | BinaryOperator::BitwiseAnd
+                | BinaryOperator::BitwiseXor
+                | BinaryOperator::BitwiseOr
+                | BinaryOperator::LogicalAnd
+                | BinaryOperator::LogicalOr
+                | BinaryOperator::Comma
+                | BinaryOperator::Assign => format!("BinaryOperator '{op}'"),
+            },
+            Self::BracedBlock(block) => block.label(),
+            Self::BuiltinCall(call) => format!("BuiltinCallExpr '{}'", call.name),
+            Self::CompoundLiteral(literal) => {
+                format!("CompoundLiteralExpr '{}'", literal.type_name)
+            }
+            Self::ControlFlow(ctrl) => ctrl.label(),
+            Self::Empty => "<<<NULL>>>".to_owned(),
+            Self::ExpressionCall(_) => "CallExpr".to_owned(),
+            Self::FunctionArgsBuild(_) => "ParenListExpr".to_owned(),
+            Self::FunctionCall(FunctionCall { variable, .. }) => {
+                if variable.attrs.is_empty() {
+                    "CallExpr".to_owned()
+                } else {
+                    format!(
+                        "FunctionDecl {} '{}'",
+                        variable.name,
+                        repr_attrs(&variable.attrs)
+                    )
+                }
+            }
+            Self::Label(label) => format!("LabelStmt '{label}'"),
+            Self::Leaf(literal) => literal_label(literal),
+            Self::ListInitialiser(_) => "InitListExpr".to_owned(),
+            Self::MemberDesignator(member) => format!("MemberDesignator '.{member}'"),
+            Self::ParensBlock(_) => "ParenExpr".to_owned(),
+            Self::StatementExpression(_) => "StmtExpr".to_owned(),
+            Self::Ternary(ternary) => ternary.common_type.as_ref().map_or_else(
+                || "ConditionalOperator".to_owned(),
+                |common_type| format!("ConditionalOperator '{common_type}'"),
+            ),
+            Self::Unary(unary) => match &unary.op {
+                UnaryOperator::Cast(attrs) => format!("CStyleCastExpr '{}'", repr_attrs(attrs)),
+                UnaryOperator::Sizeof => "UnaryExprOrTypeTraitExpr sizeof".to_owned(),
+                UnaryOperator::PostfixDecrement | UnaryOperator::PostfixIncrement => {
+                    format!("UnaryOperator postfix '{}'", unary.op)
+                }
+                UnaryOperator::AddressOf
+                | UnaryOperator::BitwiseNot
+                | UnaryOperator::Indirection
+                | UnaryOperator::LogicalNot
+                | UnaryOperator::Minus
+                | UnaryOperator::Plus
+                | UnaryOperator::PrefixDecrement
+                | UnaryOperator::PrefixIncrement => {
+                    format!("UnaryOperator prefix '{}'", unary.op)
+                }
+            },
+        }
+    }
+}
+
+impl Dump for BracedBlock {
+    fn children(&self) -> Vec<&dyn Dump> {
+        elements(&self.elts)
+    }
+
+    fn label(&self) -> String {
+        "CompoundStmt".to_owned()
+    }
+}
+
+impl Dump for BuiltinArgument {
+    fn children(&self) -> Vec<&dyn Dump> {
+        match self {
+            Self::Expression(node) => node.children(),
+            Self::Type(_) => vec![],
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::Expression(node) => node.label(),
+            Self::Type(type_name) => format!("TypeName '{type_name}'"),
+        }
+    }
+}
+
+impl Dump for ControlFlowNode {
+    fn children(&self) -> Vec<&dyn Dump> {
+        let mut children: Vec<&dyn Dump> = vec![];
+        match self {
+            Self::Ast(_, node) | Self::ColonAst(_, Some(node)) => children.push(&**node),
+            Self::ControlFlow(_, Some(ctrl)) => children.push(&**ctrl),
+            Self::IdentBlock(_, _, Some(block)) => children.push(block),
+            Self::ParensBlock(_, parens, block) => {
+                if let Some(inner) = parens {
+                    children.push(inner.get_inner());
+                }
+                if let Some(body) = block {
+                    children.push(body);
+                }
+            }
+            Self::ValueColonAst(_, value, node) => {
+                children.push(&**value);
+                if let Some(statement) = node {
+                    children.push(&**statement);
+                }
+            }
+            Self::ColonAst(_, None)
+            | Self::ControlFlow(_, None)
+            | Self::IdentBlock(_, _, None)
+            | Self::SemiColon(_) => (),
+        }
+        children
+    }
+
+    fn label(&self) -> String {
+        let kind = match self.get_keyword() {
+            ControlFlowKeyword::Break => "BreakStmt",
+            ControlFlowKeyword::Case => "CaseStmt",
+            ControlFlowKeyword::Continue => "ContinueStmt",
+            ControlFlowKeyword::Default => "DefaultStmt",
+            ControlFlowKeyword::Do => "DoStmt",
+            ControlFlowKeyword::Else => "ElseStmt",
+            ControlFlowKeyword::Enum => "EnumDecl",
+            ControlFlowKeyword::For => "ForStmt",
+            ControlFlowKeyword::Goto => "GotoStmt",
+            ControlFlowKeyword::If => "IfStmt",
+            ControlFlowKeyword::Return => "ReturnStmt",
+            ControlFlowKeyword::Struct => "RecordDecl struct",
+            ControlFlowKeyword::Switch => "SwitchStmt",
+            ControlFlowKeyword::Typedef => "TypedefDecl",
+            ControlFlowKeyword::Union => "RecordDecl union",
+            ControlFlowKeyword::While => "WhileStmt",
+        };
+        if let Self::IdentBlock(_, Some(name), _) = self {
+            format!("{kind} {name}")
+        } else {
+            kind.to_owned()
+        }
+    }
+}
+
+impl Dump for Variable {
+    fn children(&self) -> Vec<&dyn Dump> {
+        vec![]
+    }
+
+    fn label(&self) -> String {
+        if self.attrs.is_empty() {
+            format!("DeclRefExpr '{}'", self.name)
+        } else {
+            format!("VarDecl {} '{}'", self.name, repr_attrs(&self.attrs))
+        }
+    }
+}
+
+/// Dumps an [`Ast`] in the style of `clang -Xclang -ast-dump`.
+///
+/// See the [module documentation](self) for more information.
+pub fn dump_ast(node: &Ast) -> String {
+    let mut out = String::new();
+    dump_node(node, "", &mut out);
+    out
+}
+
+/// Writes a node and its children, with the indentation of clang.
+///
+/// The line of the node is written without indentation, as it is written by
+/// the parent, with the tree characters. The `indent` is the one of the
+/// children.
+fn dump_node(node: &dyn Dump, indent: &str, out: &mut String) {
+    out.push_str(&node.label());
+    out.push('\n');
+    let children = node.children();
+    let last = children.len().saturating_sub(1);
+    for (idx, child) in children.into_iter().enumerate() {
+        out.push_str(indent);
+        if idx == last {
+            out.push_str("`-");
+            dump_node(child, &format!("{indent}  "), out);
+        } else {
+            out.push_str("|-");
+            dump_node(child, &format!("{indent}| "), out);
+        }
+    }
+}
+
+/// Returns the elements of a list, without the last one if it is empty.
+///
+/// The last element is empty when the list ends with a separator, like the
+/// `;` at the end of a block.
+fn elements(elts: &[Ast]) -> Vec<&dyn Dump> {
+    let kept = match elts.split_last() {
+        Some((Ast::Empty, init)) => init,
+        Some(_) | None => elts,
+    };
+    kept.iter().map(|elt| -> &dyn Dump { elt }).collect()
+}
+
+/// Returns the line of a literal.
+fn literal_label(literal: &Literal) -> String {
+    match literal {
+        Literal::Char(ch) => format!("CharacterLiteral '{ch}'"),
+        Literal::ConstantBool(value) => format!("CXXBoolLiteralExpr 'bool' {value}"),
+        Literal::LabelAddress(address) => format!("AddrLabelExpr 'void *' {}", address.label),
+        Literal::Nullptr => "CXXNullPtrLiteralExpr".to_owned(),
+        Literal::Number(number) => number_label(number),
+        Literal::Str(value) => format!("StringLiteral \"{value}\""),
+        Literal::Variable(variable) => variable.label(),
+    }
+}
+
+/// Returns the line of a number constant, with its type.
+fn number_label(number: &Number) -> String {
+    let (kind, type_name) = match number {
+        Number::Int(_) => ("IntegerLiteral", "int"),
+        Number::Long(_) => ("IntegerLiteral", "long"),
+        Number::LongLong(_) => ("IntegerLiteral", "long long"),
+        Number::UInt(_) => ("IntegerLiteral", "unsigned int"),
+        Number::ULong(_) => ("IntegerLiteral", "unsigned long"),
+        Number::ULongLong(_) => ("IntegerLiteral", "unsigned long long"),
+        Number::Float(_) => ("FloatingLiteral", "float"),
+        Number::Double(_) => ("FloatingLiteral", "double"),
+        Number::LongDouble(_) => ("FloatingLiteral", "long double"),
+    };
+    format!("{kind} '{type_name}' {number}")
+}
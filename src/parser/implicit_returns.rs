@@ -0,0 +1,279 @@
+//! Module to model the implicit return of `main` and to report the functions
+//! that can end without a return.
+//!
+//! Since C99, reaching the end of `main` returns `0`: this `return 0;` is
+//! added at the end of the body. Reaching the end of another function that
+//! returns a value is undefined behaviour if the value is used, so a warning
+//! is raised.
+//!
+//! A body returns if a `return` is found at its outermost level, and isn't the
+//! statement of a control flow, like in `if (x) return 1;`. The `return` in
+//! nested blocks are not followed, so a body that only returns in both
+//! branches of an `if` is reported.
+//!
+//! A `for` loop with an omitted condition, like `for (;;)`, loops forever: its
+//! condition is always true, unlike a `0` condition. The end of the body isn't
+//! reached after such a loop, unless the body of the loop contains a `break`
+//! that leaves it. The `break` of the nested loops and `switch` only leave
+//! them.
+
+use super::keyword::attributes::{AttributeKeyword, BasicDataType};
+use super::keyword::control_flow::keyword::ControlFlowKeyword;
+use super::keyword::control_flow::node::ControlFlowNode;
+use super::types::braced_blocks::BracedBlock;
+use super::types::literal::{Attribute, Literal, Variable, VariableName};
+use super::types::{Ast, FunctionCall, ParensBlock};
+use super::visit::VisitMut;
+use crate::errors::api::CompileError;
+use crate::lexer::api::Number;
+use crate::options::api::{CStandard, Options};
+
+/// Function definitions found while walking the [`Ast`].
+struct Definitions {
+    /// Warnings for the functions that can end without a return.
+    errors: Vec<CompileError>,
+    /// Options of the parser, as `main` only returns `0` since C99.
+    options: Options,
+}
+
+impl VisitMut for Definitions {
+    /// Walks the outer blocks to find the function definitions.
+    ///
+    /// A function definition is a function declaration with a return type,
+    /// followed by its body. The closed blocks are the bodies of the
+    /// functions, and are skipped.
+    fn visit_block(&mut self, block: &mut BracedBlock) {
+        if block.full {
+            return;
+        }
+        let mut iter = block.elts.iter_mut().peekable();
+        while let Some(elt) = iter.next() {
+            if let Ast::FunctionCall(FunctionCall { variable, .. }) = elt
+                && !variable.attrs.is_empty()
+            {
+                if let Some(Ast::BracedBlock(body @ BracedBlock { full: true, .. })) =
+                    iter.peek_mut().map(|next| &mut **next)
+                {
+                    check_definition(variable, body, self.options, &mut self.errors);
+                }
+            } else {
+                self.visit(elt);
+            }
+        }
+    }
+}
+
+/// Checks a function definition, and adds the implicit `return 0;` of `main`.
+fn check_definition(
+    variable: &Variable,
+    body: &mut BracedBlock,
+    options: Options,
+    errors: &mut Vec<CompileError>,
+) {
+    if is_void(&variable.attrs) || returns(&body.elts) {
+        return;
+    }
+    let VariableName::UserDefined(name) = &variable.name else {
+        return;
+    };
+    if name == "main" && options.standard() >= CStandard::C99 {
+        body.elts.extend([
+            Ast::BracedBlock(BracedBlock {
+                elts: vec![Ast::ControlFlow(ControlFlowNode::SemiColon(
+                    ControlFlowKeyword::Return,
+                ))],
+                full: true,
+            }),
+            Ast::Leaf(Literal::Number(Number::Int(0))),
+            Ast::Empty,
+        ]);
+    } else if let Some(location) = &variable.location {
+        errors.push(location.to_warning(format!(
+            "Control reaches the end of the non-void function '{name}' without a 'return'."
+        )));
+    }
+}
+
+/// Checks if the statements of a block contain a `break` that leaves the
+/// block, like in `if (x) break;`.
+///
+/// A `break` only leaves its nearest loop or `switch`, so the bodies of the
+/// nested loops and `switch` are skipped.
+fn contains_break(elts: &[Ast]) -> bool {
+    let mut rest = elts;
+    while !rest.is_empty() {
+        let (statement, next) = rest.split_at(statement_len(rest));
+        if statement_breaks(statement) {
+            return true;
+        }
+        rest = next;
+    }
+    false
+}
+
+/// Returns the keyword of the full block pushed for a control flow keyword,
+/// like `if` for `[(if)]`.
+fn control_flow_keyword(node: &Ast) -> Option<&ControlFlowKeyword> {
+    if let Ast::BracedBlock(BracedBlock { elts, full: true }) = node
+        && let [Ast::ControlFlow(ctrl)] = elts.as_slice()
+    {
+        Some(ctrl.get_keyword())
+    } else {
+        None
+    }
+}
+
+/// Returns the number of elements until the end of the first statement, that
+/// ends with a `;`.
+fn expression_len(elts: &[Ast]) -> usize {
+    elts.iter()
+        .position(|elt| *elt == Ast::Empty)
+        .map_or(elts.len(), |idx| idx.saturating_add(1))
+}
+
+/// Returns the condition of a `for` loop, from the parenthesised block that
+/// follows the keyword: `cond` in `for (init; cond; incr)`.
+///
+/// The condition is [`None`] if it was omitted, like in `for (;;)`, and is
+/// then always true.
+fn for_condition(parens: &ParensBlock) -> Option<&Ast> {
+    if let Ast::BracedBlock(BracedBlock { elts, .. }) = parens.get_inner()
+        && let [_, condition, _] = elts.as_slice()
+        && *condition != Ast::Empty
+    {
+        Some(condition)
+    } else {
+        None
+    }
+}
+
+/// Checks if a node is the full block pushed for a control flow keyword, like
+/// `[(if)]` or `[(return)]`.
+pub(super) fn is_control_flow(node: &Ast, keyword: Option<&ControlFlowKeyword>) -> bool {
+    if let Ast::BracedBlock(BracedBlock { elts, full: true }) = node
+        && let [Ast::ControlFlow(ctrl)] = elts.as_slice()
+    {
+        keyword.is_none_or(|kw| ctrl.get_keyword() == kw)
+    } else {
+        false
+    }
+}
+
+/// Returns the number of elements of the keyword of a control flow and of
+/// its condition, like `[(if)], (x)` in `if (x) break;`.
+fn header_len(elts: &[Ast]) -> usize {
+    if matches!(elts.get(1), Some(Ast::ParensBlock(_))) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Checks if the elements start with a `for` loop that never ends: its
+/// condition is omitted and its body doesn't contain any `break`.
+fn is_infinite_loop(elts: &[Ast]) -> bool {
+    if let [keyword, Ast::ParensBlock(parens), rest @ ..] = elts
+        && is_control_flow(keyword, Some(&ControlFlowKeyword::For))
+        && for_condition(parens).is_none()
+    {
+        let (body, _) = rest.split_at(statement_len(rest));
+        !contains_break(body)
+    } else {
+        false
+    }
+}
+
+/// Checks if the return type of a function is `void`.
+fn is_void(attrs: &[Attribute]) -> bool {
+    attrs.contains(&Attribute::Keyword(AttributeKeyword::BasicDataType(
+        BasicDataType::Void,
+    ))) && !attrs.contains(&Attribute::Indirection)
+}
+
+/// Models the implicit return of `main`, and reports the non-void functions
+/// that can end without a return.
+///
+/// See the [module documentation](self) for more information.
+pub fn model_implicit_returns(node: &mut Ast, options: Options) -> Vec<CompileError> {
+    let mut definitions = Definitions {
+        errors: vec![],
+        options,
+    };
+    definitions.visit(node);
+    definitions.errors
+}
+
+/// Checks if the elements of a body contain a `return` or an infinite loop
+/// that is always reached.
+fn returns(elts: &[Ast]) -> bool {
+    elts.iter().enumerate().any(|(idx, elt)| {
+        (is_control_flow(elt, Some(&ControlFlowKeyword::Return))
+            || elts.get(idx..).is_some_and(is_infinite_loop))
+            && idx
+                .checked_sub(1)
+                .and_then(|prev| elts.get(prev))
+                .is_none_or(|prev| {
+                    !matches!(prev, Ast::ParensBlock(_)) && !is_control_flow(prev, None)
+                })
+    })
+}
+
+/// Checks if a statement contains a `break` that leaves it.
+///
+/// See [`contains_break`] for more information.
+fn statement_breaks(statement: &[Ast]) -> bool {
+    let Some(Ast::BracedBlock(BracedBlock { elts, .. })) = statement.first() else {
+        return false;
+    };
+    match statement.first().and_then(control_flow_keyword) {
+        None => contains_break(elts),
+        Some(ControlFlowKeyword::Break) => true,
+        Some(ControlFlowKeyword::If | ControlFlowKeyword::Else) => {
+            contains_break(statement.get(header_len(statement)..).unwrap_or_default())
+        }
+        Some(_) => false,
+    }
+}
+
+/// Returns the number of elements of the first statement of a block.
+///
+/// The statements of the control flows are flattened in the elements of the
+/// block, like `[(if)], (x), [(break)], ∅` for `if (x) break;`.
+fn statement_len(elts: &[Ast]) -> usize {
+    let tail = |start: usize| elts.get(start..).unwrap_or_default();
+    let Some(first) = elts.first() else {
+        return 0;
+    };
+    #[expect(clippy::wildcard_enum_match_arm)]
+    let len = match control_flow_keyword(first) {
+        // the labels are followed by their statement
+        None => match first {
+            Ast::BracedBlock(_) | Ast::ControlFlow(_) | Ast::Label(_) => 1,
+            _ => expression_len(elts),
+        },
+        Some(ControlFlowKeyword::Do) => {
+            let body = statement_len(tail(1)).saturating_add(1);
+            // the `while (x);` of the loop
+            body.saturating_add(expression_len(tail(body)))
+        }
+        Some(
+            keyword @ (ControlFlowKeyword::Else
+            | ControlFlowKeyword::For
+            | ControlFlowKeyword::If
+            | ControlFlowKeyword::Switch
+            | ControlFlowKeyword::While),
+        ) => {
+            let header = header_len(elts);
+            let len = header.saturating_add(statement_len(tail(header)));
+            if *keyword == ControlFlowKeyword::If
+                && elts.get(len).and_then(control_flow_keyword) == Some(&ControlFlowKeyword::Else)
+            {
+                len.saturating_add(statement_len(tail(len)))
+            } else {
+                len
+            }
+        }
+        _ => expression_len(elts),
+    };
+    len.clamp(1, elts.len())
+}
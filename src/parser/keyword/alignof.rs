@@ -0,0 +1,78 @@
+//! Handler for the `alignof` operator.
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+
+use super::super::parse_content::parse_block;
+use super::super::state::{BlockType, ParsingState};
+use super::super::symbols::builtins::{
+    parse_expression, parse_type_name, split_arguments, starts_type_name
+};
+use super::super::types::Ast;
+use super::super::types::builtin::{AlignOf, BuiltinArgument};
+use crate::errors::api::{CompileError, ErrorLevel, Location, Res};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+
+/// Handler for `alignof` and `_Alignof`
+///
+/// Unlike `sizeof`, the operand must be a type name in parenthesis, like
+/// `alignof(int)`. An expression operand, like `alignof(x)`, is accepted with
+/// a warning as a GNU extension, and is an error in pedantic mode.
+pub fn handle_alignof(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    if tokens
+        .as_slice()
+        .first()
+        .is_none_or(|token| *token.get_value() != TokenValue::Symbol(Symbol::ParenthesisOpen))
+    {
+        return Res::from(location.into_failure(
+            "Found 'alignof' without parenthesis, but its operand must be a type name in parenthesis, like 'alignof(int)'.".to_owned(),
+        ));
+    }
+    let parenthesis_location = tokens.next().map_or_else(
+        || location.clone(),
+        |parenthesis| parenthesis.into_value_location().1,
+    );
+    let mut raw_operands = split_arguments(tokens, parenthesis_location, &BlockType::Parenthesis)?;
+    let Some(raw_operand) = raw_operands.pop().filter(|_| raw_operands.is_empty()) else {
+        return Res::from(location.into_failure(
+            "Expected one type name as operand of 'alignof', like 'alignof(int)'.".to_owned(),
+        ));
+    };
+    let mut errors = vec![];
+    let operand = if starts_type_name(&raw_operand) {
+        BuiltinArgument::Type(parse_type_name(
+            raw_operand,
+            &location,
+            p_state.options(),
+            &mut errors,
+        )?)
+    } else {
+        let warning = location.to_warning(
+            "Found an expression as operand of 'alignof', but standard C only allows type names. This is a GNU extension.".to_owned(),
+        );
+        if p_state.options().is_pedantic() {
+            return Res::from(warning.promote_to(ErrorLevel::Failure));
+        }
+        p_state.push_warning(warning);
+        BuiltinArgument::Expression(parse_expression(
+            raw_operand,
+            p_state.options(),
+            &mut errors,
+        ))
+    };
+    if errors.iter().any(CompileError::is_error) {
+        return Res::from_errors(errors);
+    }
+    current
+        .push_block_as_leaf(Ast::AlignOf(AlignOf(Box::new(operand))))
+        .map_err(|msg| location.into_failure(msg))?;
+    let res = parse_block(tokens, p_state, current);
+    errors
+        .into_iter()
+        .fold(res, |acc, error| acc.add_err(Some(error)))
+}
@@ -7,9 +7,10 @@ use core::fmt;
 use super::super::types::ListInitialiser;
 use super::super::types::binary::Binary;
 use super::super::types::braced_blocks::BracedBlock;
-use super::super::types::literal::{Literal, Variable};
+use super::super::types::literal::{Attribute, Literal, Variable, VariableName};
 use super::super::types::unary::Unary;
 use super::Ast;
+use super::control_flow::node::ControlFlowNode;
 use super::sort::PushInNode;
 use crate::lexer::api::Keyword;
 use crate::parser::types::ternary::Ternary;
@@ -18,7 +19,7 @@ use crate::parser::types::ternary::Ternary;
 macro_rules! define_attribute_keywords {
     ($($name:ident: $($variant:ident)*,)*) => {
 
-        #[derive(Debug, PartialEq, Eq)]
+        #[derive(Debug, PartialEq, Eq, Clone)]
         pub enum AttributeKeyword {
             $($name($name),)*
         }
@@ -36,7 +37,7 @@ macro_rules! define_attribute_keywords {
         }
 
         $(
-            #[derive(Debug, PartialEq, Eq)]
+            #[derive(Debug, PartialEq, Eq, Clone)]
             pub enum $name {
                 $($variant,)*
             }
@@ -69,12 +70,69 @@ impl From<AttributeKeyword> for Ast {
     }
 }
 
-impl PushInNode for AttributeKeyword {
+impl From<Attribute> for Ast {
+    fn from(attr: Attribute) -> Self {
+        Self::Leaf(Literal::Variable(Variable {
+            attrs: vec![attr],
+            location: None,
+            name: VariableName::Empty,
+        }))
+    }
+}
+
+/// Checks that a `restrict` qualifier is applied on a pointer, like in
+/// `int *restrict p`.
+///
+/// `previous` are the attributes before the qualifier. A user-defined type is
+/// accepted, as it can be a pointer defined with `typedef`.
+fn check_restrict(attr: &Attribute, previous: &[Attribute]) -> Result<(), String> {
+    let is_qualifier = |prev: &&Attribute| {
+        matches!(
+            prev,
+            Attribute::Keyword(
+                AttributeKeyword::Qualifiers(Qualifiers::Const | Qualifiers::Volatile)
+                    | AttributeKeyword::SpecialAttributes(
+                        SpecialAttributes::Restrict | SpecialAttributes::UAtomic
+                    )
+            )
+        )
+    };
+    if *attr
+        == Attribute::Keyword(AttributeKeyword::SpecialAttributes(
+            SpecialAttributes::Restrict,
+        ))
+        && !previous
+            .iter()
+            .rev()
+            .find(|prev| !is_qualifier(prev))
+            .is_some_and(|prev| matches!(prev, Attribute::Indirection | Attribute::User(_)))
+    {
+        Err("Found 'restrict' applied to a type that isn't a pointer, but 'restrict' can only qualify pointers, like in 'int *restrict p'.".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+impl PushInNode for Attribute {
     fn push_in_node(self, node: &mut Ast) -> Result<(), String> {
         match node {
-            Ast::Empty => *node = Ast::from(self),
-            Ast::Leaf(Literal::Variable(var)) => var.push_keyword(self),
-            Ast::ParensBlock(_) | Ast::Leaf(_) => {
+            Ast::Empty => {
+                check_restrict(&self, &[])?;
+                *node = Ast::from(self);
+            }
+            Ast::Leaf(Literal::Variable(var)) => {
+                check_restrict(&self, &var.attrs)?;
+                var.push_attr(self);
+            }
+            Ast::ArrayDesignator(_)
+            | Ast::CaseRange(_)
+            | Ast::MemberDesignator(_)
+            | Ast::ArraySize(_)
+            | Ast::ParensBlock(_)
+            | Ast::StatementExpression(_)
+            | Ast::CompoundLiteral(_)
+            | Ast::Label(_)
+            | Ast::Leaf(_) => {
                 return Err(format!(
                     "invalid attribute. Attribute keywords can only be applied to variables, but found {node}"
                 ));
@@ -86,8 +144,15 @@ impl PushInNode for AttributeKeyword {
                     failure: Some(arg), ..
                 }
                 | Ternary { success: arg, .. },
+            )
+            | Ast::ControlFlow(
+                ControlFlowNode::ColonAst(_, Some(arg))
+                | ControlFlowNode::ValueColonAst(_, _, Some(arg)),
             ) => return self.push_in_node(arg),
             Ast::ControlFlow(_)
+            | Ast::AlignOf(_)
+            | Ast::BuiltinCall(_)
+            | Ast::ExpressionCall(_)
             | Ast::FunctionCall(_)
             | Ast::ListInitialiser(ListInitialiser { full: true, .. }) => {
                 return Err(format!(
@@ -96,11 +161,22 @@ impl PushInNode for AttributeKeyword {
             }
             Ast::FunctionArgsBuild(elts)
             | Ast::ListInitialiser(ListInitialiser { elts, .. })
-            | Ast::BracedBlock(BracedBlock { elts, .. }) => match elts.last_mut() {
-                Some(last) => return self.push_in_node(last),
-                None => elts.push(Ast::from(self)),
-            },
+            | Ast::BracedBlock(BracedBlock { elts, .. }) => {
+                if let Some(last) = elts.last_mut()
+                    && !matches!(last, Ast::BracedBlock(BracedBlock { full: true, .. }))
+                {
+                    return self.push_in_node(last);
+                }
+                check_restrict(&self, &[])?;
+                elts.push(Ast::from(self));
+            }
         }
         Ok(())
     }
 }
+
+impl PushInNode for AttributeKeyword {
+    fn push_in_node(self, node: &mut Ast) -> Result<(), String> {
+        Attribute::Keyword(self).push_in_node(node)
+    }
+}
@@ -7,7 +7,7 @@ use super::node::ControlFlowNode;
 
 /// Control flow keywords
 // TODO: struct, enum, can be used as attribute
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ControlFlowKeyword {
     /// Break out of a loop or a case
     Break,
@@ -77,9 +77,8 @@ impl From<ControlFlowKeyword> for ControlFlowNode {
             ControlFlowKeyword::Break
             | ControlFlowKeyword::Return
             | ControlFlowKeyword::Continue => Self::SemiColon(keyword),
-            ControlFlowKeyword::Case | ControlFlowKeyword::Default | ControlFlowKeyword::Goto => {
-                Self::ColonAst(keyword, None)
-            }
+            ControlFlowKeyword::Case => Self::ValueColonAst(keyword, Box::from(Ast::Empty), None),
+            ControlFlowKeyword::Default | ControlFlowKeyword::Goto => Self::ColonAst(keyword, None),
             ControlFlowKeyword::For
             | ControlFlowKeyword::While
             | ControlFlowKeyword::Switch
@@ -12,6 +12,10 @@ use super::sort::PushInNode;
 
 impl PushInNode for ControlFlowKeyword {
     fn push_in_node(self, node: &mut Ast) -> Result<(), String> {
+        if matches!(self, Self::Case | Self::Default) {
+            // the value and the statement of a label are pushed inside it
+            return node.push_block_as_leaf(Ast::ControlFlow(ControlFlowNode::from(self)));
+        }
         let block = Ast::BracedBlock(BracedBlock {
             elts: vec![Ast::ControlFlow(ControlFlowNode::from(self))],
             full: true,
@@ -21,15 +25,79 @@ impl PushInNode for ControlFlowKeyword {
     }
 }
 
+/// Checks if the current [`Ast`] is at the beginning of a statement, where
+/// `default` is a label of a `switch`.
+pub fn is_node_statement_start(node: &Ast) -> bool {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Empty => true,
+        Ast::BracedBlock(BracedBlock { elts, full: false }) => {
+            elts.last().is_none_or(is_node_statement_start)
+        }
+        Ast::ControlFlow(
+            ControlFlowNode::ColonAst(_, Some(statement))
+            | ControlFlowNode::ValueColonAst(_, _, Some(statement)),
+        ) => is_node_statement_start(statement),
+        _ => false,
+    }
+}
+
+/// Checks if the current [`Ast`] ends with a control flow that is waiting for
+/// its parenthesis, like `if` in `if (x) {}`.
+///
+/// The control flows are pushed in closed blocks, so the closed blocks are
+/// also searched.
+pub fn is_node_condition_context(node: &Ast) -> bool {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::BracedBlock(BracedBlock { elts, .. }) | Ast::FunctionArgsBuild(elts) => {
+            elts.last().is_some_and(is_node_condition_context)
+        }
+        Ast::ControlFlow(ctrl) => is_ctrl_condition_context(ctrl),
+        _ => false,
+    }
+}
+
+/// Checks if the [`ControlFlowNode`] is waiting for its parenthesis.
+///
+/// See [`is_node_condition_context`] for more information.
+fn is_ctrl_condition_context(ctrl: &ControlFlowNode) -> bool {
+    match ctrl {
+        ControlFlowNode::ParensBlock(_, None, _) => true,
+        ControlFlowNode::ControlFlow(_, Some(inner)) => is_ctrl_condition_context(inner),
+        ControlFlowNode::Ast(_, statement)
+        | ControlFlowNode::ColonAst(_, Some(statement))
+        | ControlFlowNode::ValueColonAst(_, _, Some(statement)) => {
+            is_node_condition_context(statement)
+        }
+        ControlFlowNode::ColonAst(_, None)
+        | ControlFlowNode::ControlFlow(_, None)
+        | ControlFlowNode::IdentBlock(..)
+        | ControlFlowNode::ParensBlock(_, Some(_), _)
+        | ControlFlowNode::SemiColon(_)
+        | ControlFlowNode::ValueColonAst(_, _, None) => false,
+    }
+}
+
 /// Checks if the current [`Ast`] is writing inside a `case` control flow.
 pub fn is_node_case_context(node: &Ast) -> bool {
     match node {
-        Ast::Empty
+        Ast::ArrayDesignator(_)
+        | Ast::CaseRange(_)
+        | Ast::MemberDesignator(_)
+        | Ast::ArraySize(_)
+        | Ast::Empty
+        | Ast::Label(_)
         | Ast::Leaf(_)
         | Ast::ParensBlock(_)
+        | Ast::StatementExpression(_)
+        | Ast::CompoundLiteral(_)
         | Ast::Unary(_)
         | Ast::Binary(_)
         | Ast::Ternary(_)
+        | Ast::AlignOf(_)
+        | Ast::BuiltinCall(_)
+        | Ast::ExpressionCall(_)
         | Ast::FunctionCall(_)
         | Ast::ListInitialiser(_)
         | Ast::BracedBlock(BracedBlock { full: true, .. }) => false,
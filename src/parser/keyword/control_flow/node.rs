@@ -2,13 +2,14 @@
 
 use core::fmt;
 
+use super::super::super::modifiers::conversions::OperatorConversions;
 use super::super::super::types::braced_blocks::BracedBlock;
 use super::super::super::types::{Ast, ParensBlock};
 use super::keyword::ControlFlowKeyword;
 use crate::parser::repr_option;
 
 /// Node representation of a control flow.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ControlFlowNode {
     /// Keyword expects a node: `return 3+4`
     Ast(ControlFlowKeyword, Box<Ast>),
@@ -23,6 +24,8 @@ pub enum ControlFlowNode {
     ParensBlock(ControlFlowKeyword, Option<ParensBlock>, Option<BracedBlock>),
     /// Keyword expects a semicolon: `break;`
     SemiColon(ControlFlowKeyword),
+    /// Keyword expects a value, a colon and a node: `case 1: x`
+    ValueColonAst(ControlFlowKeyword, Box<Ast>, Option<Box<Ast>>),
 }
 
 impl ControlFlowNode {
@@ -34,7 +37,8 @@ impl ControlFlowNode {
             | Self::ControlFlow(keyword, _)
             | Self::IdentBlock(keyword, _, _)
             | Self::ParensBlock(keyword, _, _)
-            | Self::SemiColon(keyword) => keyword,
+            | Self::SemiColon(keyword)
+            | Self::ValueColonAst(keyword, ..) => keyword,
         }
     }
 
@@ -42,7 +46,9 @@ impl ControlFlowNode {
     pub fn is_full(&self) -> bool {
         match self {
             Self::Ast(_, ast) => **ast != Ast::Empty,
-            Self::ColonAst(_, ast) => ast.as_ref().is_some_and(|node| **node != Ast::Empty),
+            Self::ColonAst(_, ast) | Self::ValueColonAst(_, _, ast) => {
+                ast.as_ref().is_some_and(|node| **node != Ast::Empty)
+            }
             Self::ControlFlow(_, control_flow_node) => control_flow_node
                 .as_ref()
                 .is_some_and(|node| node.is_full()),
@@ -61,6 +67,10 @@ impl ControlFlowNode {
             Self::Ast(_, ast) | Self::ColonAst(_, Some(ast)) if **ast == Ast::Empty => {
                 *ast = Box::new(node);
             }
+            Self::ColonAst(_, Some(ast)) | Self::ValueColonAst(_, _, Some(ast)) => {
+                return ast.push_block_as_leaf(node);
+            }
+            Self::ValueColonAst(_, value, None) => return value.push_block_as_leaf(node),
             Self::ColonAst(_, None) => return Err("Missing colon after keyword.".to_owned()),
             Self::ControlFlow(keyword, old_ctrl @ None) => {
                 if let Ast::ControlFlow(node_ctrl) = node {
@@ -95,11 +105,43 @@ impl ControlFlowNode {
 
     /// Tries to push a colon inside the control flow node.
     pub fn push_colon(&mut self) -> Result<(), String> {
-        if let Self::ColonAst(_, node @ None) = self {
-            *node = Some(Box::from(Ast::Empty));
-            Ok(())
-        } else {
-            Err("Found extra colon: illegal in control flow keyword context.".to_owned())
+        match self {
+            Self::ValueColonAst(keyword, value, _) if **value == Ast::Empty => Err(format!(
+                "Expected a value after '{keyword}', but found ':'."
+            )),
+            Self::ColonAst(_, node @ None) | Self::ValueColonAst(_, _, node @ None) => {
+                *node = Some(Box::from(Ast::Empty));
+                Ok(())
+            }
+            Self::Ast(..)
+            | Self::ColonAst(..)
+            | Self::ControlFlow(..)
+            | Self::IdentBlock(..)
+            | Self::ParensBlock(..)
+            | Self::SemiColon(_)
+            | Self::ValueColonAst(..) => {
+                Err("Found extra colon: illegal in control flow keyword context.".to_owned())
+            }
+        }
+    }
+
+    /// Tries to push an operator inside the control flow node.
+    ///
+    /// The operator is pushed in the value of a `case` before the colon, and
+    /// in the statement after the colon.
+    ///
+    /// See [`Ast::push_op`] for more information.
+    pub fn push_op<T>(&mut self, op: T) -> Result<(), String>
+    where
+        T: OperatorConversions + fmt::Display,
+    {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match self {
+            Self::ValueColonAst(_, value, None) => value.push_op(op),
+            Self::ColonAst(_, Some(ast)) | Self::ValueColonAst(_, _, Some(ast)) => ast.push_op(op),
+            _ => Err(format!(
+                "Illegal operator {op} in this context: unfinished control flow."
+            )),
         }
     }
 }
@@ -130,6 +172,9 @@ impl fmt::Display for ControlFlowNode {
                 )
             }
             Self::SemiColon(keyword) => write!(f, "({keyword})"),
+            Self::ValueColonAst(keyword, value, ast) => {
+                write!(f, "({keyword} {value}: {})", repr_option(ast))
+            }
         }
     }
 }
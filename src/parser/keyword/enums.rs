@@ -0,0 +1,188 @@
+//! Handler for enum declarations with a list of enumerators.
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+
+use super::super::parse_content::parse_block;
+use super::super::state::{BlockType, ParsingState};
+use super::super::symbols::builtins::{parse_type_name, split_arguments};
+use super::super::types::Ast;
+use super::super::types::builtin::TypeName;
+use super::super::types::enumeration::Enum;
+use super::super::types::literal::Attribute;
+use super::sort::PushInNode as _;
+use crate::errors::api::{CompileError, Location, Res};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+use crate::options::api::CStandard;
+
+/// Handler for `enum` followed by a list of enumerators: `enum E { A, B = 5 }`
+///
+/// The enumerators are split on the top-level commas, and the values of the
+/// enumerators are computed when possible. The enum is then pushed as an
+/// attribute of the declared variable, like a type keyword.
+///
+/// Since C23, the underlying type of the enum can be given after a colon:
+/// `enum E : unsigned char { A }`. The enumerators that don't fit in it are
+/// reported.
+pub fn handle_enum(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let mut enum_node = Enum::default();
+    if let Some(TokenValue::Ident(name)) = tokens.as_slice().first().map(Token::get_value) {
+        enum_node.name = Some(name.clone());
+        tokens.next();
+    }
+    let mut errors = vec![];
+    if tokens
+        .as_slice()
+        .first()
+        .is_some_and(|token| *token.get_value() == TokenValue::Symbol(Symbol::Colon))
+    {
+        enum_node.underlying = Some(parse_underlying_type(
+            tokens,
+            p_state,
+            location.clone(),
+            &mut errors,
+        )?);
+    }
+    let range = enum_node.underlying_range();
+    let brace_location = tokens
+        .next()
+        .map_or_else(|| location.clone(), |brace| brace.into_value_location().1);
+    let mut raw_enumerators = split_arguments(tokens, brace_location.clone(), &BlockType::Brace)?;
+    // trailing comma: `enum { A, B, }`
+    if raw_enumerators.last().is_some_and(Vec::is_empty) {
+        raw_enumerators.pop();
+    }
+    if raw_enumerators.is_empty() {
+        return Res::from(location.into_failure(
+            "Found an empty enum, but an enum must contain at least one enumerator.".to_owned(),
+        ));
+    }
+    for raw_enumerator in raw_enumerators {
+        let mut raw_iter = raw_enumerator.into_iter();
+        let Some(first) = raw_iter.next() else {
+            return Res::from(
+                brace_location
+                    .into_failure("Expected an enumerator name, but found nothing.".to_owned()),
+            );
+        };
+        let (value, name_location) = first.into_value_location();
+        let TokenValue::Ident(name) = value else {
+            return Res::from(
+                name_location
+                    .into_failure(format!("Expected an enumerator name, but found {value}.")),
+            );
+        };
+        let explicit = match raw_iter.next() {
+            None => None,
+            Some(token) if *token.get_value() == TokenValue::Symbol(Symbol::Assign) => {
+                if raw_iter.len() == 0 {
+                    return Res::from(token.into_value_location().1.into_failure(format!(
+                        "Expected a value for enumerator '{name}', but found nothing."
+                    )));
+                }
+                let mut value_p_state = ParsingState::from(*p_state.options());
+                let mut value_node = Ast::Empty;
+                let res = parse_block(&mut raw_iter, &mut value_p_state, &mut value_node);
+                errors.extend(res.into_errors());
+                errors.extend(value_p_state.mismatched_error());
+                Some(value_node)
+            }
+            Some(token) => {
+                let (other, other_location) = token.into_value_location();
+                return Res::from(other_location.into_failure(format!(
+                    "Expected '=' or ',' after enumerator '{name}', but found {other}."
+                )));
+            }
+        };
+        if let Err(err) = enum_node.push_enumerator(name, explicit) {
+            return Res::from(name_location.into_failure(err));
+        }
+        if let (Some(enumerator), Some(values)) = (enum_node.enumerators.last(), &range)
+            && let Some(enumerator_value) = enumerator.value
+            && !values.contains(&enumerator_value)
+        {
+            errors.push(name_location.to_warning(format!(
+                "Found enumerator '{}' with value {enumerator_value}, but it doesn't fit in the underlying type of the enum, whose values are between {} and {}.",
+                enumerator.name,
+                values.start(),
+                values.end()
+            )));
+        }
+    }
+    if errors.iter().any(CompileError::is_error) {
+        return Res::from_errors(errors);
+    }
+    Attribute::Enum(enum_node)
+        .push_in_node(current)
+        .map_err(|err| location.into_failure(err))?;
+    for warning in errors {
+        p_state.push_warning(warning);
+    }
+    parse_block(tokens, p_state, current)
+}
+
+/// Checks if the tokens after the `enum` keyword are a list of enumerators:
+/// `enum { ... }` or `enum E { ... }`, with an optional underlying type, like
+/// `enum E : unsigned char { ... }`.
+pub fn is_enum_definition(tokens: &[Token]) -> bool {
+    let is_brace = |token: &Token| *token.get_value() == TokenValue::Symbol(Symbol::BraceOpen);
+    let after_name = match tokens {
+        [first, rest @ ..] if matches!(first.get_value(), TokenValue::Ident(_)) => rest,
+        _ => tokens,
+    };
+    match after_name {
+        [first, type_tokens @ ..] if *first.get_value() == TokenValue::Symbol(Symbol::Colon) => {
+            type_tokens
+                .iter()
+                .find(|token| {
+                    !matches!(
+                        token.get_value(),
+                        TokenValue::Ident(_) | TokenValue::Keyword(_)
+                    )
+                })
+                .is_some_and(is_brace)
+        }
+        [first, ..] => is_brace(first),
+        [] => false,
+    }
+}
+
+/// Parses the underlying type of an enum, after the name: `: unsigned char` in
+/// `enum E : unsigned char { A }`.
+///
+/// The underlying type was introduced in C23.
+fn parse_underlying_type(
+    tokens: &mut IntoIter<Token>,
+    p_state: &ParsingState,
+    location: Location,
+    errors: &mut Vec<CompileError>,
+) -> Result<Vec<Attribute>, CompileError> {
+    let colon_location = tokens
+        .next()
+        .map_or(location, |colon| colon.into_value_location().1);
+    if p_state.options().standard() < CStandard::C23 {
+        return Err(colon_location.into_failure(format!(
+            "Found an underlying type for an enum, but they are only supported since C23, and the current standard is {}.",
+            p_state.options().standard()
+        )));
+    }
+    let mut type_tokens = vec![];
+    while tokens
+        .as_slice()
+        .first()
+        .is_some_and(|token| *token.get_value() != TokenValue::Symbol(Symbol::BraceOpen))
+    {
+        type_tokens.extend(tokens.next());
+    }
+    match parse_type_name(type_tokens, &colon_location, p_state.options(), errors)? {
+        TypeName::Variable(variable) => Ok(variable.attrs),
+        TypeName::Array(..) | TypeName::FunctionPointer(..) => Err(colon_location.into_failure(
+            "Expected an integer type after ':' in enum, but found a derived type.".to_owned(),
+        )),
+    }
+}
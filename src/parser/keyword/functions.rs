@@ -7,17 +7,22 @@ use super::Ast;
 use super::sort::PushInNode;
 
 /// List of existing function keywords
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum FunctionKeyword {
     /// Alignof
     ///
     /// Returns the alignment, in bytes, of the input
+    ///
+    /// This is only used to sort the keywords: `alignof(int)` is an
+    /// [`AlignOf`](super::super::types::builtin::AlignOf) node.
     Alignof,
     /// Sizeof
     ///
     /// Yields the size in bytes of the object representation of the argument
     /// (the argument is of type type).
-    // TODO: works without parens
+    ///
+    /// This is only used with parenthesis: `sizeof x` is a
+    /// [`UnaryOperator::Sizeof`](super::super::types::unary::UnaryOperator::Sizeof).
     Sizeof,
     /// Static assert
     ///
@@ -28,6 +33,9 @@ pub enum FunctionKeyword {
     ///
     /// Returns the type of a variable, *with* the qualifiers
     ///
+    /// This is only used to sort the keywords: `typeof(a) b` is an
+    /// [`Attribute::Typeof`](super::super::types::literal::Attribute::Typeof).
+    ///
     /// # Examples
     ///
     /// `typeof(const int)` is `const int`
@@ -41,6 +49,8 @@ pub enum FunctionKeyword {
     ///
     /// Returns the type of a variable, *without* the qualifiers
     ///
+    /// This is only used to sort the keywords, like [`Self::Typeof`].
+    ///
     /// # Examples
     ///
     /// `typeof(const int)` is `int`
@@ -2,22 +2,30 @@
 //! [`Ast`].
 
 extern crate alloc;
+pub mod alignof;
 pub mod attributes;
 pub mod control_flow;
+pub mod enums;
 pub mod functions;
 pub mod sort;
+pub mod type_of;
 
 use alloc::vec::IntoIter;
 
-use control_flow::is_node_case_context;
+use alignof::handle_alignof;
+use control_flow::{is_node_case_context, is_node_statement_start};
+use enums::{handle_enum, is_enum_definition};
 use sort::{KeywordParsing, PushInNode as _};
+use type_of::handle_typeof;
 
 use super::parse_content::parse_block;
 use super::state::ParsingState;
+use super::symbols::builtins::is_followed_by_initialiser;
 use super::types::Ast;
+use super::types::unary::UnaryOperator;
 use crate::Location;
 use crate::errors::api::Res;
-use crate::lexer::api::{Keyword, Token};
+use crate::lexer::api::{Keyword, Symbol, Token, TokenValue};
 
 /// Main handler to push a keyword into an [`Ast`].
 ///
@@ -29,7 +37,50 @@ pub fn handle_keyword(
     tokens: &mut IntoIter<Token>,
     location: Location,
 ) -> Res<()> {
-    let case_context = is_node_case_context(current);
+    // without parenthesis, `sizeof` is a prefix operator: `sizeof x`, and so it
+    // is before a compound literal: `sizeof (int[]){1, 2}`
+    if keyword == Keyword::Sizeof
+        && tokens.as_slice().split_first().is_none_or(|(first, rest)| {
+            *first.get_value() != TokenValue::Symbol(Symbol::ParenthesisOpen)
+                || is_followed_by_initialiser(rest)
+        })
+    {
+        current
+            .push_op(UnaryOperator::Sizeof)
+            .map_err(|msg| location.into_failure(msg))?;
+        return parse_block(tokens, p_state, current);
+    }
+    if matches!(keyword, Keyword::Alignof | Keyword::UAlignof) {
+        return handle_alignof(current, p_state, tokens, location);
+    }
+    if matches!(keyword, Keyword::Typeof | Keyword::TypeofUnqual) {
+        return handle_typeof(
+            current,
+            p_state,
+            tokens,
+            location,
+            keyword == Keyword::TypeofUnqual,
+        );
+    }
+    if keyword == Keyword::Enum && is_enum_definition(tokens.as_slice()) {
+        return handle_enum(current, p_state, tokens, location);
+    }
+    // GNU computed goto: `goto *ptr;`
+    if keyword == Keyword::Goto
+        && tokens
+            .as_slice()
+            .first()
+            .is_some_and(|token| *token.get_value() == TokenValue::Symbol(Symbol::Star))
+    {
+        let msg = "Found a computed goto 'goto *', but this is a GNU extension.";
+        if !p_state.options().allows_gnu_extensions() {
+            return Res::from(
+                location.into_failure(format!("{msg} Please enable the GNU extensions to use it.")),
+            );
+        }
+        p_state.push_warning(location.to_warning(msg.to_owned()));
+    }
+    let case_context = is_node_case_context(current) || is_node_statement_start(current);
     let parsed_keyword = KeywordParsing::from((keyword, case_context));
     parsed_keyword
         .push_in_node(current)
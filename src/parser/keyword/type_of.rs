@@ -0,0 +1,93 @@
+//! Handler for the `typeof` and `typeof_unqual` specifiers.
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+
+use super::super::parse_content::parse_block;
+use super::super::state::{BlockType, ParsingState};
+use super::super::symbols::builtins::{
+    parse_expression, parse_type_name, split_arguments, starts_type_name
+};
+use super::super::types::Ast;
+use super::super::types::binary::{Binary, BinaryOperator};
+use super::super::types::builtin::{BuiltinArgument, Typeof};
+use super::super::types::literal::Attribute;
+use super::sort::PushInNode as _;
+use crate::errors::api::{CompileError, Location, Res};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+
+/// Handler for `typeof` and `typeof_unqual`
+///
+/// The specifier is pushed as an [`Attribute`] of the declared variable, like
+/// a type keyword: in `typeof(1 + 1) x = 2;`, `x` is declared with the type of
+/// `1 + 1`. The operand, between parenthesis, is a type name or an
+/// expression.
+pub fn handle_typeof(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+    unqualified: bool,
+) -> Res<()> {
+    let keyword = if unqualified {
+        "typeof_unqual"
+    } else {
+        "typeof"
+    };
+    if tokens
+        .as_slice()
+        .first()
+        .is_none_or(|token| *token.get_value() != TokenValue::Symbol(Symbol::ParenthesisOpen))
+    {
+        return Res::from(location.into_failure(format!(
+            "Found '{keyword}' without parenthesis, but its operand must be in parenthesis, like '{keyword}(x)'."
+        )));
+    }
+    let parenthesis_location = tokens.next().map_or_else(
+        || location.clone(),
+        |parenthesis| parenthesis.into_value_location().1,
+    );
+    let mut raw_operands = split_arguments(tokens, parenthesis_location, &BlockType::Parenthesis)?;
+    if raw_operands.is_empty() || raw_operands.iter().any(Vec::is_empty) {
+        return Res::from(location.into_failure(format!(
+            "Expected an expression or a type name as operand of '{keyword}', like '{keyword}(x)'."
+        )));
+    }
+    let mut errors = vec![];
+    let operand = if raw_operands.len() == 1
+        && let Some(raw_operand) = raw_operands.pop_if(|raw_operand| starts_type_name(raw_operand))
+    {
+        BuiltinArgument::Type(parse_type_name(
+            raw_operand,
+            &location,
+            p_state.options(),
+            &mut errors,
+        )?)
+    } else {
+        // the top-level commas are comma operators, like in `typeof(a, b)`
+        let mut expressions = raw_operands
+            .into_iter()
+            .map(|raw_operand| parse_expression(raw_operand, p_state.options(), &mut errors));
+        let first = expressions.next().unwrap_or_default();
+        BuiltinArgument::Expression(expressions.fold(first, |arg_l, arg_r| {
+            Ast::Binary(Binary {
+                op: BinaryOperator::Comma,
+                arg_l: Box::new(arg_l),
+                arg_r: Box::new(arg_r),
+            })
+        }))
+    };
+    if errors.iter().any(CompileError::is_error) {
+        return Res::from_errors(errors);
+    }
+    Attribute::Typeof(Typeof {
+        operand: Box::new(operand),
+        unqualified,
+    })
+    .push_in_node(current)
+    .map_err(|msg| location.into_failure(msg))?;
+    let res = parse_block(tokens, p_state, current);
+    errors
+        .into_iter()
+        .fold(res, |acc, error| acc.add_err(Some(error)))
+}
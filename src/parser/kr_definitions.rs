@@ -0,0 +1,272 @@
+//! Module to rewrite the K&R function definitions into prototypes.
+//!
+//! A K&R definition, like `int f(a, b) int a; char *b; { ... }`, declares the
+//! types of the parameters between the parenthesis and the body. The
+//! declarations are moved inside the parenthesis, so that the definition is
+//! parsed like `int f(int a, char *b) { ... }`.
+
+use core::mem;
+
+use crate::errors::api::{CompileError, Location};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+use crate::options::api::{CStandard, Options};
+
+/// Finds the end of a K&R definition.
+///
+/// `tokens` start at the `(` after the name of the function. If it is a K&R
+/// definition, the indexes of the `)` and of the `{` are returned.
+fn find_kr_definition(tokens: &[Token]) -> Option<(usize, usize)> {
+    if *tokens.first()?.get_value() != TokenValue::Symbol(Symbol::ParenthesisOpen) {
+        return None;
+    }
+    let mut close = 1;
+    loop {
+        if !matches!(tokens.get(close)?.get_value(), TokenValue::Ident(_)) {
+            return None;
+        }
+        close = close.checked_add(1)?;
+        match tokens.get(close)?.get_value() {
+            TokenValue::Symbol(Symbol::Comma) => close = close.checked_add(1)?,
+            TokenValue::Symbol(Symbol::ParenthesisClose) => break,
+            TokenValue::Char(..)
+            | TokenValue::Comment(_)
+            | TokenValue::Ident(_)
+            | TokenValue::Keyword(_)
+            | TokenValue::Newline
+            | TokenValue::Number(_)
+            | TokenValue::Str(..)
+            | TokenValue::Symbol(_)
+            | TokenValue::Whitespace(_) => return None,
+        }
+    }
+    let first_declaration = close.checked_add(1)?;
+    if !matches!(
+        tokens.get(first_declaration)?.get_value(),
+        TokenValue::Ident(_) | TokenValue::Keyword(_)
+    ) {
+        return None;
+    }
+    let mut depth: usize = 0;
+    for (idx, token) in tokens.iter().enumerate().skip(first_declaration) {
+        match token.get_value() {
+            TokenValue::Symbol(Symbol::BraceOpen) if depth == 0 => {
+                let previous = tokens.get(idx.checked_sub(1)?)?;
+                return (*previous.get_value() == TokenValue::Symbol(Symbol::SemiColon))
+                    .then_some((close, idx));
+            }
+            TokenValue::Symbol(Symbol::ParenthesisOpen | Symbol::BracketOpen) => {
+                depth = depth.saturating_add(1);
+            }
+            TokenValue::Symbol(Symbol::ParenthesisClose | Symbol::BracketClose) => {
+                depth = depth.saturating_sub(1);
+            }
+            TokenValue::Symbol(Symbol::BraceClose | Symbol::Assign) => return None,
+            TokenValue::Char(..)
+            | TokenValue::Comment(_)
+            | TokenValue::Ident(_)
+            | TokenValue::Keyword(_)
+            | TokenValue::Newline
+            | TokenValue::Number(_)
+            | TokenValue::Str(..)
+            | TokenValue::Symbol(_)
+            | TokenValue::Whitespace(_) => (),
+        }
+    }
+    None
+}
+
+/// Checks if the token is the name of one of the parameters.
+fn is_parameter(token: &Token, parameters: &[(String, Option<Vec<Token>>)]) -> bool {
+    if let TokenValue::Ident(name) = token.get_value() {
+        parameters.iter().any(|(parameter, _)| parameter == name)
+    } else {
+        false
+    }
+}
+
+/// Moves the declarations of the parameters inside the parenthesis.
+///
+/// `tokens` are the tokens of the K&R definition, from the `(` to the token
+/// before the `{`.
+fn rewrite_definition(
+    tokens: Vec<Token>,
+    close: usize,
+    errors: &mut Vec<CompileError>,
+) -> Vec<Token> {
+    let mut iter = tokens.into_iter();
+    let list = iter
+        .by_ref()
+        .take(close.saturating_add(1))
+        .collect::<Vec<_>>();
+    let mut parameters = list
+        .iter()
+        .filter_map(|token| match token.get_value() {
+            TokenValue::Ident(name) => Some((name.to_owned(), None)),
+            TokenValue::Char(..)
+            | TokenValue::Comment(_)
+            | TokenValue::Keyword(_)
+            | TokenValue::Newline
+            | TokenValue::Number(_)
+            | TokenValue::Str(..)
+            | TokenValue::Symbol(_)
+            | TokenValue::Whitespace(_) => None,
+        })
+        .collect::<Vec<(String, Option<Vec<Token>>)>>();
+    for declaration in split_on(iter.collect(), &Symbol::SemiColon) {
+        let mut specifiers = None;
+        for mut declarator in split_on(declaration, &Symbol::Comma) {
+            let shared = specifiers.get_or_insert_with(|| {
+                let end = declarator
+                    .iter()
+                    .position(|token| {
+                        is_parameter(token, &parameters)
+                            || matches!(
+                                token.get_value(),
+                                TokenValue::Symbol(Symbol::Star | Symbol::ParenthesisOpen)
+                            )
+                    })
+                    .unwrap_or(declarator.len());
+                declarator.drain(..end).collect::<Vec<_>>()
+            });
+            let Some(name_token) = declarator
+                .iter()
+                .find(|token| is_parameter(token, &parameters))
+            else {
+                if let Some(location) = declarator
+                    .first()
+                    .or_else(|| shared.first())
+                    .map(|token| token.get_location().to_owned())
+                {
+                    errors.push(location.into_failure(
+                        "Found a declaration in a K&R function definition that doesn't declare a parameter.".to_owned(),
+                    ));
+                }
+                continue;
+            };
+            let name_location = name_token.get_location().to_owned();
+            let Some((name, old)) = parameters
+                .iter_mut()
+                .find(|(name, _)| *name_token.get_value() == TokenValue::Ident(name.to_owned()))
+            else {
+                continue;
+            };
+            if old.is_some() {
+                errors.push(name_location.into_failure(format!(
+                    "Parameter '{name}' is declared twice in K&R function definition."
+                )));
+            }
+            let mut full = shared.clone();
+            full.extend(declarator);
+            *old = Some(full);
+        }
+    }
+    let mut rewritten = vec![];
+    for token in list {
+        if let TokenValue::Ident(name) = token.get_value()
+            && let Some((_, declaration @ Some(_))) = parameters
+                .iter_mut()
+                .find(|(parameter, _)| parameter == name)
+        {
+            rewritten.extend(declaration.take().unwrap_or_default());
+        } else {
+            rewritten.push(token);
+        }
+    }
+    rewritten
+}
+
+/// Rewrites the K&R function definitions into prototypes.
+///
+/// K&R definitions were removed in C23. They raise an error if the standard
+/// is C23, unless they are enabled with [`Options::with_kr_definitions`], in
+/// which case they raise a warning.
+pub fn rewrite_kr_definitions(
+    tokens: Vec<Token>,
+    options: &Options,
+) -> (Vec<Token>, Vec<CompileError>) {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut errors = vec![];
+    let mut depth: usize = 0;
+    let mut rest = tokens.into_iter();
+    while let Some(token) = rest.next() {
+        match token.get_value() {
+            TokenValue::Symbol(
+                Symbol::BraceOpen | Symbol::ParenthesisOpen | Symbol::BracketOpen,
+            ) => depth = depth.saturating_add(1),
+            TokenValue::Symbol(
+                Symbol::BraceClose | Symbol::ParenthesisClose | Symbol::BracketClose,
+            ) => depth = depth.saturating_sub(1),
+            TokenValue::Char(..)
+            | TokenValue::Comment(_)
+            | TokenValue::Ident(_)
+            | TokenValue::Keyword(_)
+            | TokenValue::Newline
+            | TokenValue::Number(_)
+            | TokenValue::Str(..)
+            | TokenValue::Symbol(_)
+            | TokenValue::Whitespace(_) => (),
+        }
+        let name_location = (depth == 0 && matches!(token.get_value(), TokenValue::Ident(_)))
+            .then(|| token.get_location().to_owned());
+        output.push(token);
+        if let Some(location) = name_location
+            && let Some((close, brace)) = find_kr_definition(rest.as_slice())
+        {
+            errors.extend(standard_error(location, options));
+            let definition = rest.by_ref().take(brace).collect();
+            output.extend(rewrite_definition(definition, close, &mut errors));
+        }
+    }
+    (output, errors)
+}
+
+/// Splits a list of tokens on a symbol, outside of parenthesis and brackets.
+///
+/// Empty parts are dropped.
+fn split_on(tokens: Vec<Token>, separator: &Symbol) -> Vec<Vec<Token>> {
+    let mut parts = vec![];
+    let mut current = vec![];
+    let mut depth: usize = 0;
+    for token in tokens {
+        match token.get_value() {
+            TokenValue::Symbol(symbol) if depth == 0 && symbol == separator => {
+                parts.push(mem::take(&mut current));
+                continue;
+            }
+            TokenValue::Symbol(Symbol::ParenthesisOpen | Symbol::BracketOpen) => {
+                depth = depth.saturating_add(1);
+            }
+            TokenValue::Symbol(Symbol::ParenthesisClose | Symbol::BracketClose) => {
+                depth = depth.saturating_sub(1);
+            }
+            TokenValue::Char(..)
+            | TokenValue::Comment(_)
+            | TokenValue::Ident(_)
+            | TokenValue::Keyword(_)
+            | TokenValue::Newline
+            | TokenValue::Number(_)
+            | TokenValue::Str(..)
+            | TokenValue::Symbol(_)
+            | TokenValue::Whitespace(_) => (),
+        }
+        current.push(token);
+    }
+    parts.push(current);
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Returns the error for a K&R definition, depending on the [`Options`].
+fn standard_error(location: Location, options: &Options) -> Option<CompileError> {
+    if options.standard() < CStandard::C23 {
+        None
+    } else if options.allows_kr_definitions() {
+        Some(location.to_warning(
+            "Found a K&R function definition, but they were removed in C23.".to_owned(),
+        ))
+    } else {
+        Some(location.into_failure(
+            "Found a K&R function definition, but they were removed in C23. Please declare the types of the parameters inside the parenthesis, or enable the K&R definitions.".to_owned(),
+        ))
+    }
+}
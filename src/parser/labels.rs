@@ -0,0 +1,63 @@
+//! Module to resolve the addresses of the labels, like `&&start`.
+//!
+//! The labels have a function scope: the address of a label can be taken
+//! before or after the label, in any block of the function, but the label must
+//! be defined in the same function.
+
+use super::types::Ast;
+use super::types::braced_blocks::BracedBlock;
+use super::types::literal::{LabelAddress, Literal};
+use super::visit::{Visit, walk};
+use crate::errors::api::CompileError;
+
+/// Labels and addresses of labels found in a function
+#[derive(Default)]
+struct Labels<'node> {
+    /// Addresses of labels, like `&&start`
+    addresses: Vec<&'node LabelAddress>,
+    /// Names of the labels defined, like `start` in `start: x = 1;`
+    labels: Vec<&'node str>,
+}
+
+impl<'node> Labels<'node> {
+    /// Reports the addresses of the labels that aren't defined in the node.
+    fn check(mut self, node: &'node Ast, errors: &mut Vec<CompileError>) {
+        self.visit(node);
+        for address in self.addresses {
+            if !self.labels.contains(&address.label.as_str()) {
+                errors.push(address.location.to_failure(format!(
+                    "Found the address of the label '{}', but this label isn't defined in this function.",
+                    address.label
+                )));
+            }
+        }
+    }
+}
+
+impl<'node> Visit<'node> for Labels<'node> {
+    /// Walks an [`Ast`] to find the labels and their addresses.
+    fn visit(&mut self, node: &'node Ast) {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match node {
+            Ast::Label(label) => self.labels.push(label),
+            Ast::Leaf(Literal::LabelAddress(address)) => self.addresses.push(address),
+            _ => walk(self, node),
+        }
+    }
+}
+
+/// Reports the addresses of the labels that aren't defined in their function.
+///
+/// See the [module documentation](self) for more information.
+pub fn check_label_addresses(node: &Ast) -> Vec<CompileError> {
+    let mut errors = vec![];
+    if let Ast::BracedBlock(BracedBlock { elts, .. }) = node {
+        // each element of the file is a declaration or the body of a function
+        for elt in elts {
+            Labels::default().check(elt, &mut errors);
+        }
+    } else {
+        Labels::default().check(node, &mut errors);
+    }
+    errors
+}
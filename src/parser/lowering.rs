@@ -0,0 +1,314 @@
+//! Module to lower the [`Ast`] into a flat list of [`Instruction`]s.
+//!
+//! Each operation of an expression is computed into a new temporary, written
+//! `t0`, `t1`, etc., so that the operands of an instruction are only
+//! constants, variables or temporaries that were already computed, like in a
+//! three-address code: `x = a + b * c` is lowered to `t0 = b * c`,
+//! `t1 = a + t0` and `x = t1`. Only the arithmetic expressions and the
+//! assignments can be lowered for now.
+
+use core::fmt;
+
+use super::types::Ast;
+use super::types::binary::{Binary, BinaryOperator};
+use super::types::braced_blocks::BracedBlock;
+use super::types::literal::{Literal, Variable, VariableName};
+use super::types::unary::{Unary, UnaryOperator};
+use crate::errors::api::{CompileError, CompileRes, Location};
+use crate::lexer::api::Number;
+
+/// Instruction of the lowered [`Ast`]
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum Instruction {
+    /// Copy of a value: `x = t0`
+    Assign {
+        /// Operand that receives the value
+        dest: Operand,
+        /// Value that is copied
+        value: Operand,
+    },
+    /// Binary operation: `t1 = a + t0`
+    Binary {
+        /// Operand that receives the result
+        dest: Operand,
+        /// Left operand
+        left: Operand,
+        /// Operator
+        op: BinaryOperator,
+        /// Right operand
+        right: Operand,
+    },
+    /// Unary operation: `t0 = -a`
+    Unary {
+        /// Operand that receives the result
+        dest: Operand,
+        /// Operand of the operator
+        arg: Operand,
+        /// Operator
+        op: UnaryOperator,
+    },
+}
+
+impl fmt::Display for Instruction {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Assign { dest, value } => write!(f, "{dest} = {value}"),
+            Self::Binary {
+                dest,
+                left,
+                op,
+                right,
+            } => write!(f, "{dest} = {left} {op} {right}"),
+            Self::Unary { dest, arg, op } => write!(f, "{dest} = {op}{arg}"),
+        }
+    }
+}
+
+/// State of the lowering: the instructions emitted so far.
+#[derive(Default)]
+struct Lowering {
+    /// Instructions emitted so far
+    instructions: Vec<Instruction>,
+    /// Location of the nearest statement that has one, for the errors on the
+    /// nodes without locations, like the constants.
+    ///
+    /// It is the location of the statement that is being lowered, or else of
+    /// the last statement that had one, or else of the enclosing block.
+    statement: Option<Location>,
+    /// Number of temporaries created so far
+    temporaries: usize,
+}
+
+impl Lowering {
+    /// Returns the variable assigned by an assignment, like `x` in `x = 1` or
+    /// in `int x = 1`.
+    fn assigned_operand(&self, node: &Ast, assignment: &Ast) -> CompileRes<Operand> {
+        if let Ast::Leaf(Literal::Variable(variable)) = node {
+            self.variable_operand(variable, assignment)
+        } else {
+            Err(self.unsupported(assignment))
+        }
+    }
+
+    /// Lowers an expression, and returns the operand that contains its value.
+    fn lower_expression(&mut self, node: &Ast) -> CompileRes<Operand> {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match node {
+            Ast::Leaf(Literal::Number(number)) => Ok(Operand::Constant(number.to_owned())),
+            Ast::Leaf(Literal::ConstantBool(value)) => {
+                Ok(Operand::Constant(Number::Int((*value).into())))
+            }
+            Ast::Leaf(Literal::Variable(variable)) => self.variable_operand(variable, node),
+            Ast::ParensBlock(parens) => self.lower_expression(parens.get_inner()),
+            Ast::Unary(Unary { op, arg }) => match op {
+                UnaryOperator::BitwiseNot
+                | UnaryOperator::LogicalNot
+                | UnaryOperator::Minus
+                | UnaryOperator::Plus => {
+                    let arg_operand = self.lower_expression(arg)?;
+                    let dest = self.new_temporary();
+                    self.instructions.push(Instruction::Unary {
+                        dest: dest.clone(),
+                        arg: arg_operand,
+                        op: op.to_owned(),
+                    });
+                    Ok(dest)
+                }
+                _ => Err(self.unsupported(node)),
+            },
+            Ast::Binary(Binary { op, arg_l, arg_r }) => match op {
+                BinaryOperator::Comma => {
+                    self.lower_expression(arg_l)?;
+                    self.lower_expression(arg_r)
+                }
+                BinaryOperator::Assign => {
+                    let dest = self.assigned_operand(arg_l, node)?;
+                    let value = self.lower_expression(arg_r)?;
+                    self.instructions.push(Instruction::Assign {
+                        dest: dest.clone(),
+                        value,
+                    });
+                    Ok(dest)
+                }
+                BinaryOperator::AddAssign
+                | BinaryOperator::SubAssign
+                | BinaryOperator::MulAssign
+                | BinaryOperator::DivAssign
+                | BinaryOperator::ModAssign
+                | BinaryOperator::ShiftLeftAssign
+                | BinaryOperator::ShiftRightAssign
+                | BinaryOperator::AndAssign
+                | BinaryOperator::XorAssign
+                | BinaryOperator::OrAssign => {
+                    let dest = self.assigned_operand(arg_l, node)?;
+                    let right = self.lower_expression(arg_r)?;
+                    self.instructions.push(Instruction::Binary {
+                        dest: dest.clone(),
+                        left: dest.clone(),
+                        op: compound_operator(op),
+                        right,
+                    });
+                    Ok(dest)
+                }
+                BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+                | BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::ShiftRight
+                | BinaryOperator::ShiftLeft
+                | BinaryOperator::Lt
+                | BinaryOperator::Le
+                | BinaryOperator::Gt
+                | BinaryOperator::Ge
+                | BinaryOperator::Equal
+                | BinaryOperator::Different
+                | BinaryOperator::BitwiseAnd
+                | BinaryOperator::BitwiseXor
+                | BinaryOperator::BitwiseOr => {
+                    let left = self.lower_expression(arg_l)?;
+                    let right = self.lower_expression(arg_r)?;
+                    let dest = self.new_temporary();
+                    self.instructions.push(Instruction::Binary {
+                        dest: dest.clone(),
+                        left,
+                        op: op.to_owned(),
+                        right,
+                    });
+                    Ok(dest)
+                }
+                // the right operand of `&&` and `||` is only evaluated if needed
+                BinaryOperator::LogicalAnd
+                | BinaryOperator::LogicalOr
+                | BinaryOperator::ArraySubscript
+                | BinaryOperator::StructEnumMemberAccess
+                | BinaryOperator::StructEnumMemberPointerAccess => Err(self.unsupported(node)),
+            },
+            _ => Err(self.unsupported(node)),
+        }
+    }
+
+    /// Lowers a statement, or a block of statements.
+    ///
+    /// The value of an expression statement is dropped, but its operations
+    /// are still lowered.
+    fn lower_statement(&mut self, node: &Ast) -> CompileRes<()> {
+        if let Some(location) = node.first_location() {
+            self.statement = Some(location.to_owned());
+        }
+        match node {
+            // the labels are only used by the `goto`s, that can't be lowered yet
+            Ast::Empty | Ast::Label(_) => Ok(()),
+            Ast::BracedBlock(BracedBlock { elts, .. }) => {
+                elts.iter().try_for_each(|elt| self.lower_statement(elt))
+            }
+            Ast::ArrayDesignator(_)
+            | Ast::CaseRange(_)
+            | Ast::MemberDesignator(_)
+            | Ast::ArraySize(_)
+            | Ast::Binary(_)
+            | Ast::AlignOf(_)
+            | Ast::BuiltinCall(_)
+            | Ast::ControlFlow(_)
+            | Ast::FunctionArgsBuild(_)
+            | Ast::ExpressionCall(_)
+            | Ast::FunctionCall(_)
+            | Ast::Leaf(_)
+            | Ast::ListInitialiser(_)
+            | Ast::ParensBlock(_)
+            | Ast::StatementExpression(_)
+            | Ast::CompoundLiteral(_)
+            | Ast::Ternary(_)
+            | Ast::Unary(_) => self.lower_expression(node).map(|_| ()),
+        }
+    }
+
+    /// Creates a new temporary.
+    const fn new_temporary(&mut self) -> Operand {
+        let temporary = Operand::Temporary(self.temporaries);
+        self.temporaries = self.temporaries.saturating_add(1);
+        temporary
+    }
+
+    /// Returns the error for a node that can't be lowered.
+    ///
+    /// The error is located at the first location of the node, or of the
+    /// nearest statement if the node has none (cf. [`Lowering::statement`]).
+    /// Only the variables have locations, so if the [`Ast`] has none, like
+    /// `return 1;`, the error is located at the beginning of an unnamed file.
+    fn unsupported(&self, node: &Ast) -> CompileError {
+        node.first_location()
+            .or(self.statement.as_ref())
+            .cloned()
+            .unwrap_or_else(|| Location::from(String::new()))
+            .into_failure(format!(
+                "Found {node}, but only the arithmetic expressions and the assignments can be lowered for now."
+            ))
+    }
+
+    /// Returns the operand of a variable, if it has a name.
+    fn variable_operand(&self, variable: &Variable, node: &Ast) -> CompileRes<Operand> {
+        if let VariableName::UserDefined(name) = &variable.name {
+            Ok(Operand::Variable(name.to_owned()))
+        } else {
+            Err(self.unsupported(node))
+        }
+    }
+}
+
+/// Operand of an [`Instruction`]
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum Operand {
+    /// Number constant: `1`
+    Constant(Number),
+    /// Temporary created by the lowering: `t0`
+    Temporary(usize),
+    /// Variable of the source: `x`
+    Variable(String),
+}
+
+impl fmt::Display for Operand {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constant(number) => number.fmt(f),
+            Self::Temporary(idx) => write!(f, "t{idx}"),
+            Self::Variable(name) => name.fmt(f),
+        }
+    }
+}
+
+/// Returns the arithmetic operator of a compound assignment, like `+` for
+/// `+=`.
+///
+/// # Panics
+///
+/// If the operator isn't a compound assignment.
+fn compound_operator(op: &BinaryOperator) -> BinaryOperator {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match op {
+        BinaryOperator::AddAssign => BinaryOperator::Add,
+        BinaryOperator::SubAssign => BinaryOperator::Subtract,
+        BinaryOperator::MulAssign => BinaryOperator::Multiply,
+        BinaryOperator::DivAssign => BinaryOperator::Divide,
+        BinaryOperator::ModAssign => BinaryOperator::Modulo,
+        BinaryOperator::ShiftLeftAssign => BinaryOperator::ShiftLeft,
+        BinaryOperator::ShiftRightAssign => BinaryOperator::ShiftRight,
+        BinaryOperator::AndAssign => BinaryOperator::BitwiseAnd,
+        BinaryOperator::XorAssign => BinaryOperator::BitwiseXor,
+        BinaryOperator::OrAssign => BinaryOperator::BitwiseOr,
+        _ => panic!("Never happens: {op} isn't a compound assignment"),
+    }
+}
+
+/// Lowers an [`Ast`] into a flat list of [`Instruction`]s.
+///
+/// See the [module documentation](self) for more information.
+pub fn lower_ast(node: &Ast) -> CompileRes<Vec<Instruction>> {
+    let mut lowering = Lowering::default();
+    lowering.lower_statement(node)?;
+    Ok(lowering.instructions)
+}
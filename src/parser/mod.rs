@@ -8,20 +8,49 @@ pub mod api {
 
     #![allow(clippy::pub_use)]
 
-    pub use super::parse_content::parse_tokens;
+    pub use super::lowering::{Instruction, Operand};
+    pub use super::modifiers::constant::{ConstantError, try_constant_value};
+    pub use super::parse_content::{
+        parse_header, parse_header_with_options, parse_tokens, parse_tokens_with_options
+    };
+    pub use super::types::binary::BinaryOperator;
+    pub use super::types::unary::UnaryOperator;
 }
 
+mod common_types;
+mod dead_loops;
+mod discarded_values;
+mod dump;
+mod implicit_returns;
 mod keyword;
+mod kr_definitions;
+mod labels;
+mod lowering;
 mod modifiers;
 mod parse_content;
+mod redefinitions;
 mod state;
 mod symbols;
+mod thread_local;
 mod types;
+mod unused_variables;
+mod visit;
 
 use core::fmt;
 
+use types::literal::Attribute;
+
 use crate::EMPTY;
 
+/// Displays the attributes of a type, separated by spaces, like `const int *`.
+fn repr_attrs(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .map(|attr| format!("{attr}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Displays an option with the [`EMPTY`] string.
 #[expect(clippy::ref_option)]
 fn repr_option<T: fmt::Display>(opt: &Option<T>) -> String {
@@ -3,14 +3,18 @@
 use core::cmp::Ordering;
 use core::{fmt, mem};
 
-use super::super::types::ListInitialiser;
-use super::super::types::binary::Binary;
+use super::super::keyword::control_flow::node::ControlFlowNode;
+use super::super::types::binary::{Binary, BinaryOperator};
 use super::super::types::braced_blocks::BracedBlock;
 use super::super::types::literal::{Attribute, Literal, Variable, VariableName};
 use super::super::types::operator::{Associativity, Operator as _};
 use super::super::types::unary::Unary;
+use super::super::types::{ExpressionCall, FunctionCall, ListInitialiser, StatementExpression};
 use super::conversions::OperatorConversions;
 use crate::EMPTY;
+use crate::errors::api::{CompileError, Location};
+use crate::parser::dump::dump_ast;
+use crate::parser::lowering::{Instruction, lower_ast};
 use crate::parser::repr_vec;
 use crate::parser::types::Ast;
 use crate::parser::types::ternary::Ternary;
@@ -34,7 +38,13 @@ impl Ast {
                 Ok(())
             }
             Self::Leaf(_) => make_error("constant"),
-            Self::ParensBlock(_) => make_error("parenthesis"),
+            Self::Label(_) => make_error("Labels"),
+            Self::ArrayDesignator(_) => make_error("array designators"),
+            Self::CaseRange(_) => make_error("case ranges"),
+            Self::MemberDesignator(_) => make_error("member designators"),
+            Self::ArraySize(_) => make_error("array sizes"),
+            Self::ParensBlock(_) | Self::StatementExpression(_) => make_error("parenthesis"),
+            Self::CompoundLiteral(_) => make_error("Compound literals"),
             Self::Unary(Unary { arg, .. }) | Self::Binary(Binary { arg_l: arg, .. }) => {
                 arg.add_attribute_to_left_variable(previous_attrs)
             }
@@ -42,7 +52,10 @@ impl Ast {
                 condition.add_attribute_to_left_variable(previous_attrs)
             }
             Self::FunctionArgsBuild(_) => make_error("Functions arguments"),
-            Self::FunctionCall(_) => make_error("Functions"),
+            Self::AlignOf(_) => make_error("Alignof operators"),
+            Self::ExpressionCall(_) | Self::FunctionCall(_) | Self::BuiltinCall(_) => {
+                make_error("Functions")
+            }
             Self::ListInitialiser(_) => make_error("List initialisers"),
             Self::BracedBlock(_) => make_error("Blocks"),
             Self::ControlFlow(_) => make_error("Control flow keywords"),
@@ -58,12 +71,29 @@ impl Ast {
         match self {
             Self::Empty | Self::Ternary(Ternary { failure: None, .. }) => true,
             Self::Leaf(Literal::Variable(_)) => is_user_variable,
-            Self::Leaf(_) | Self::ParensBlock(_) | Self::FunctionCall(_) => false,
+            Self::ArrayDesignator(_)
+            | Self::CaseRange(_)
+            | Self::MemberDesignator(_)
+            | Self::ArraySize(_)
+            | Self::Label(_)
+            | Self::Leaf(_)
+            | Self::ParensBlock(_)
+            | Self::StatementExpression(_)
+            | Self::CompoundLiteral(_)
+            | Self::ExpressionCall(_)
+            | Self::FunctionCall(_)
+            | Self::AlignOf(_)
+            | Self::BuiltinCall(_) => false,
             Self::Unary(Unary { arg, .. })
             | Self::Binary(Binary { arg_r: arg, .. })
             | Self::Ternary(Ternary {
                 failure: Some(arg), ..
-            }) => arg.can_push_leaf(is_user_variable),
+            })
+            | Self::ControlFlow(
+                ControlFlowNode::ColonAst(_, Some(arg))
+                | ControlFlowNode::ValueColonAst(_, _, Some(arg))
+                | ControlFlowNode::ValueColonAst(_, arg, None),
+            ) => arg.can_push_leaf(is_user_variable),
             Self::FunctionArgsBuild(vec) => vec
                 .last()
                 .is_none_or(|child| child.can_push_leaf(is_user_variable)),
@@ -78,6 +108,121 @@ impl Ast {
         }
     }
 
+    /// Dumps the [`Ast`] in the style of `clang -Xclang -ast-dump`.
+    ///
+    /// Each node is written on its own line, with its kind and its key
+    /// attributes, and the children are indented below their parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, lex_file, parse_tokens};
+    ///
+    /// let tokens = lex_file("x = 1;", &mut Location::from("")).unwrap_or_display(&[], "lexer");
+    /// let node = parse_tokens(tokens).unwrap_or_display(&[], "parser");
+    /// assert!(
+    ///     node.dump()
+    ///         == "CompoundStmt\n`-BinaryOperator '='\n  |-DeclRefExpr 'x'\n  `-IntegerLiteral 'int' 1\n"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn dump(&self) -> String {
+        dump_ast(self)
+    }
+
+    /// Returns the location of the first variable of the [`Ast`], to report a
+    /// diagnostic on it.
+    ///
+    /// The nodes are searched in the order of the source, so the location of
+    /// `x` is returned for `1 + x`.
+    pub(crate) fn first_location(&self) -> Option<&Location> {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match self {
+            Self::Binary(Binary { arg_l, arg_r, .. }) => {
+                arg_l.first_location().or_else(|| arg_r.first_location())
+            }
+            Self::BracedBlock(BracedBlock { elts, .. })
+            | Self::StatementExpression(StatementExpression(BracedBlock { elts, .. })) => {
+                elts.iter().find_map(Self::first_location)
+            }
+            Self::ControlFlow(
+                ControlFlowNode::Ast(_, node)
+                | ControlFlowNode::ColonAst(_, Some(node))
+                | ControlFlowNode::ValueColonAst(_, _, Some(node)),
+            ) => node.first_location(),
+            Self::ExpressionCall(ExpressionCall { args, callee }) => callee
+                .first_location()
+                .or_else(|| args.iter().find_map(Self::first_location)),
+            Self::FunctionCall(FunctionCall { args, variable, .. }) => variable
+                .location
+                .as_ref()
+                .or_else(|| args.iter().find_map(Self::first_location)),
+            Self::Leaf(Literal::Variable(Variable { location, .. })) => location.as_ref(),
+            Self::ParensBlock(parens) => parens.get_inner().first_location(),
+            Self::Ternary(Ternary {
+                condition,
+                failure,
+                success,
+                ..
+            }) => condition
+                .first_location()
+                .or_else(|| success.first_location())
+                .or_else(|| failure.as_deref().and_then(Self::first_location)),
+            Self::Unary(Unary { arg, .. }) => arg.first_location(),
+            _ => None,
+        }
+    }
+
+    /// Checks if the right-most argument of the [`Ast`] is missing.
+    ///
+    /// This is the case when an operator is waiting for its argument, like in
+    /// `a + -`. The subscript of `x[]` isn't considered missing, as it is valid
+    /// in declarations.
+    fn is_missing_argument(&self) -> bool {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match self {
+            Self::Empty => true,
+            Self::Unary(Unary { arg, .. }) => arg.is_missing_argument(),
+            Self::Binary(Binary { op, arg_r, .. }) => {
+                *op != BinaryOperator::ArraySubscript && arg_r.is_missing_argument()
+            }
+            _ => false,
+        }
+    }
+
+    /// Lowers the [`Ast`] into a flat list of instructions.
+    ///
+    /// Each operation is computed into a new temporary, so that the operands
+    /// of an instruction are only constants, variables or temporaries. Only
+    /// the arithmetic expressions and the assignments can be lowered for now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Ast`] contains a node that can't be lowered.
+    /// The error is located on the node, or on its statement if the node has
+    /// no location, like a constant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use c_parser::{Location, lex_file, parse_tokens};
+    ///
+    /// let tokens =
+    ///     lex_file("x = a + b * c;", &mut Location::from("")).unwrap_or_display(&[], "lexer");
+    /// let node = parse_tokens(tokens).unwrap_or_display(&[], "parser");
+    /// let instructions = node.lower().unwrap();
+    /// let displayed = instructions
+    ///     .iter()
+    ///     .map(ToString::to_string)
+    ///     .collect::<Vec<_>>();
+    /// assert!(displayed == ["t0 = b * c", "t1 = a + t0", "x = t1"]);
+    /// ```
+    #[inline]
+    pub fn lower(&self) -> Result<Vec<Instruction>, CompileError> {
+        lower_ast(self)
+    }
+
     /// Pushes a node at the bottom of the [`Ast`].
     ///
     /// This methods considers `node` as a leaf, and pushes it as a leaf into
@@ -107,9 +252,14 @@ impl Ast {
             // previous is incomplete variable: waiting for variable name
             Self::Leaf(Literal::Variable(var)) => {
                 let err = format!("{node}");
-                if let Self::Leaf(Literal::Variable(Variable { attrs, name })) = node
+                if let Self::Leaf(Literal::Variable(Variable {
+                    attrs,
+                    location,
+                    name,
+                })) = node
                     && attrs.is_empty()
                 {
+                    var.location = location;
                     var.push_name(name)
                 } else {
                     Err(format!(
@@ -121,12 +271,30 @@ impl Ast {
             //
             //
             // atomic: failure
+            Self::AlignOf(old) => Err(successive_literal_error("Alignof", old, node)),
+            Self::ArrayDesignator(old) => {
+                Err(successive_literal_error("Array designator", old, node))
+            }
+            Self::ArraySize(old) => Err(successive_literal_error("Array size", old, node)),
+            Self::CaseRange(old) => Err(successive_literal_error("Case range", old, node)),
+            Self::CompoundLiteral(old) => {
+                Err(successive_literal_error("Compound literal", old, node))
+            }
+            Self::MemberDesignator(old) => {
+                Err(successive_literal_error("Member designator", old, node))
+            }
             Self::ParensBlock(old) => Err(successive_literal_error("Parenthesis group", old, node)),
+            Self::StatementExpression(old) => {
+                Err(successive_literal_error("Statement expression", old, node))
+            }
+            Self::Label(old) => Err(successive_literal_error("Label", old, node)),
             Self::Leaf(old) => Err(successive_literal_error("Literal", old, node)),
             //
             //
             // full: failure
-            Self::FunctionCall(_) => Err(successive_literal_error("Function call", self, node)),
+            Self::ExpressionCall(_) | Self::FunctionCall(_) | Self::BuiltinCall(_) => {
+                Err(successive_literal_error("Function call", self, node))
+            }
             Self::ListInitialiser(ListInitialiser { full: true, .. }) => {
                 Err(successive_literal_error("List initialiser", self, node))
             }
@@ -207,6 +375,7 @@ impl Ast {
     where
         T: OperatorConversions + fmt::Display,
     {
+        let missing_argument = self.is_missing_argument();
         match self {
             //
             //
@@ -216,9 +385,19 @@ impl Ast {
             //
             // self is a non-modifiable block: Op -> Self
             Self::ListInitialiser(ListInitialiser { full: true, .. })
+            | Self::ExpressionCall(_)
             | Self::FunctionCall(_)
+            | Self::AlignOf(_)
+            | Self::BuiltinCall(_)
+            | Self::Label(_)
             | Self::Leaf(_)
-            | Self::ParensBlock(_) => op.try_push_op_as_root(self),
+            | Self::ArrayDesignator(_)
+            | Self::CaseRange(_)
+            | Self::MemberDesignator(_)
+            | Self::ArraySize(_)
+            | Self::ParensBlock(_)
+            | Self::CompoundLiteral(_)
+            | Self::StatementExpression(_) => op.try_push_op_as_root(self),
             //
             //
             // full block: make space: Self = [Self, Empty]
@@ -251,6 +430,12 @@ impl Ast {
             //
             //
             // operators
+            // missing argument: Self.arg = Op (e.g. `- -a` or `a + -b`)
+            Self::Unary(Unary { arg, .. }) | Self::Binary(Binary { arg_r: arg, .. })
+                if missing_argument =>
+            {
+                arg.push_op(op)
+            }
             Self::Unary(Unary { op: old_op, arg }) => {
                 match old_op.precedence().cmp(&op.precedence()) {
                     Ordering::Less => op.try_push_op_as_root(self),
@@ -296,9 +481,7 @@ impl Ast {
             //
             //
             // Control flows
-            Self::ControlFlow(_) => Err(format!(
-                "Illegal operator {op} in this context: unfinished control flow."
-            )),
+            Self::ControlFlow(ctrl) => ctrl.push_op(op),
         }
     }
 }
@@ -308,14 +491,24 @@ impl fmt::Display for Ast {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Empty => EMPTY.fmt(f),
+            Self::AlignOf(val) => val.fmt(f),
+            Self::ArrayDesignator(designator) => designator.fmt(f),
+            Self::CaseRange(range) => range.fmt(f),
+            Self::MemberDesignator(member) => write!(f, ".{member}"),
+            Self::ArraySize(size) => size.fmt(f),
             Self::Binary(val) => val.fmt(f),
+            Self::ExpressionCall(val) => val.fmt(f),
             Self::FunctionCall(val) => val.fmt(f),
+            Self::BuiltinCall(val) => val.fmt(f),
+            Self::Label(label) => write!(f, "{label}:"),
             Self::Leaf(val) => val.fmt(f),
             Self::Ternary(val) => val.fmt(f),
             Self::Unary(val) => val.fmt(f),
             Self::BracedBlock(block) => block.fmt(f),
             Self::ListInitialiser(list_initialiser) => list_initialiser.fmt(f),
             Self::ParensBlock(parens) => parens.fmt(f),
+            Self::StatementExpression(val) => val.fmt(f),
+            Self::CompoundLiteral(val) => val.fmt(f),
             Self::ControlFlow(ctrl) => ctrl.fmt(f),
             Self::FunctionArgsBuild(vec) => write!(f, "({})", repr_vec(vec)),
         }
@@ -0,0 +1,337 @@
+//! Module to evaluate the integer constant expressions of an
+//! [`Ast`](super::super::types::Ast).
+
+use super::super::types::Ast;
+use super::super::types::binary::{Binary, BinaryOperator};
+use super::super::types::braced_blocks::BracedBlock;
+use super::super::types::literal::{Literal, Variable, VariableName};
+use super::super::types::ternary::Ternary;
+use super::super::types::unary::{Unary, UnaryOperator};
+use crate::lexer::api::Number;
+
+/// Value of an integer constant expression.
+///
+/// As in the conditions of `#if`, the signed values have the type `intmax_t`
+/// and the unsigned values have the type `uintmax_t`.
+#[derive(Debug, Clone, Copy)]
+enum Constant {
+    /// Value of type `intmax_t`.
+    Signed(i64),
+    /// Value of type `uintmax_t`.
+    Unsigned(u64),
+}
+
+impl Constant {
+    /// Creates a signed constant from a boolean, like the result of a
+    /// comparison.
+    fn from_bool(value: bool) -> Self {
+        Self::Signed(i64::from(value))
+    }
+
+    /// Creates a constant from the value of an identifier.
+    ///
+    /// The value is signed if it fits in `intmax_t`.
+    fn from_i128(value: i128) -> Result<Self, ConstantError> {
+        i64::try_from(value).ok().map(Self::Signed).map_or_else(
+            || {
+                u64::try_from(value)
+                    .ok()
+                    .map(Self::Unsigned)
+                    .ok_or(ConstantError::Overflow)
+            },
+            Ok,
+        )
+    }
+
+    /// Returns the value as an [`i128`], that can hold both types.
+    fn into_i128(self) -> i128 {
+        match self {
+            Self::Signed(value) => i128::from(value),
+            Self::Unsigned(value) => i128::from(value),
+        }
+    }
+
+    /// Converts the value to `uintmax_t`, with the C conversion rules: a
+    /// negative value wraps around.
+    const fn into_unsigned(self) -> u64 {
+        match self {
+            Self::Signed(value) => value.cast_unsigned(),
+            Self::Unsigned(value) => value,
+        }
+    }
+
+    /// Checks if the value is zero.
+    const fn is_zero(self) -> bool {
+        match self {
+            Self::Signed(value) => value == 0,
+            Self::Unsigned(value) => value == 0,
+        }
+    }
+}
+
+/// Error raised when an expression can't be evaluated as an integer constant
+/// expression.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConstantError {
+    /// Division or remainder by zero.
+    DivisionByZero,
+    /// The expression isn't an integer constant expression.
+    NotConstant,
+    /// The result of a signed operation doesn't fit in `intmax_t`, or a shift
+    /// count is negative or too big.
+    Overflow,
+}
+
+/// Computes the value of a binary operator applied on two constants.
+///
+/// If one of the operands is unsigned, both are converted to `uintmax_t`, and
+/// the unsigned operations wrap around.
+fn binary_value(
+    op: &BinaryOperator,
+    left: Constant,
+    right: Constant,
+) -> Result<Constant, ConstantError> {
+    if matches!(op, BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight) {
+        return shift_value(op, left, right);
+    }
+    if matches!(op, BinaryOperator::Divide | BinaryOperator::Modulo) && right.is_zero() {
+        return Err(ConstantError::DivisionByZero);
+    }
+    match (left, right) {
+        (Constant::Signed(lhs), Constant::Signed(rhs)) => signed_value(op, lhs, rhs),
+        (Constant::Signed(_) | Constant::Unsigned(_), _) => {
+            unsigned_value(op, left.into_unsigned(), right.into_unsigned())
+        }
+    }
+}
+
+/// Evaluates an integer constant expression, made of integer constants,
+/// operators and parenthesis.
+///
+/// `lookup` returns the values of the identifiers that are constants, like
+/// the enumerators that were already declared.
+///
+/// Returns [`None`] if the expression isn't an integer constant expression,
+/// or if it overflows or divides by zero.
+pub fn constant_value(node: &Ast, lookup: &dyn Fn(&str) -> Option<i128>) -> Option<i128> {
+    try_constant_value(node, lookup).ok()
+}
+
+/// Evaluates an integer constant expression, with the signedness of its
+/// operands.
+fn evaluate(node: &Ast, lookup: &dyn Fn(&str) -> Option<i128>) -> Result<Constant, ConstantError> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Leaf(Literal::Number(number)) => number_value(number),
+        Ast::Leaf(Literal::Char(ch)) => Ok(Constant::Signed(i64::from(u32::from(*ch)))),
+        Ast::Leaf(Literal::ConstantBool(value)) => Ok(Constant::from_bool(*value)),
+        Ast::Leaf(Literal::Variable(Variable {
+            attrs,
+            name: VariableName::UserDefined(name),
+            ..
+        })) if attrs.is_empty() => {
+            Constant::from_i128(lookup(name).ok_or(ConstantError::NotConstant)?)
+        }
+        // expression parsed on its own, without `;`
+        Ast::BracedBlock(BracedBlock { elts, full: false }) if elts.len() == 1 => {
+            evaluate(elts.first().ok_or(ConstantError::NotConstant)?, lookup)
+        }
+        Ast::ParensBlock(parens) => evaluate(parens.get_inner(), lookup),
+        Ast::Unary(Unary { op, arg }) => unary_value(op, evaluate(arg, lookup)?),
+        Ast::Binary(Binary { op, arg_l, arg_r }) => {
+            let left = evaluate(arg_l, lookup)?;
+            // the right operand isn't evaluated when the result is known
+            if *op == BinaryOperator::LogicalAnd && left.is_zero() {
+                Ok(Constant::from_bool(false))
+            } else if *op == BinaryOperator::LogicalOr && !left.is_zero() {
+                Ok(Constant::from_bool(true))
+            } else {
+                binary_value(op, left, evaluate(arg_r, lookup)?)
+            }
+        }
+        Ast::Ternary(Ternary {
+            condition,
+            success,
+            failure: Some(failure),
+            ..
+        }) => {
+            let (taken, other) = if evaluate(condition, lookup)?.is_zero() {
+                (failure, success)
+            } else {
+                (success, failure)
+            };
+            let value = evaluate(taken, lookup)?;
+            // the result is unsigned if one of the branches is unsigned
+            if matches!(evaluate(other, lookup), Ok(Constant::Unsigned(_))) {
+                Ok(Constant::Unsigned(value.into_unsigned()))
+            } else {
+                Ok(value)
+            }
+        }
+        _ => Err(ConstantError::NotConstant),
+    }
+}
+
+/// Returns the value of an integer constant.
+///
+/// The signed constants are converted to `intmax_t`, and the unsigned ones to
+/// `uintmax_t`.
+#[expect(
+    clippy::useless_conversion,
+    reason = "types depend on the architecture"
+)]
+fn number_value(number: &Number) -> Result<Constant, ConstantError> {
+    match number {
+        Number::Int(val) => Ok(Constant::Signed(i64::from(*val))),
+        Number::Long(val) => Ok(Constant::Signed(i64::from(*val))),
+        Number::LongLong(val) => Ok(Constant::Signed(*val)),
+        Number::UInt(val) => Ok(Constant::Unsigned(u64::from(*val))),
+        Number::ULong(val) => Ok(Constant::Unsigned(u64::from(*val))),
+        Number::ULongLong(val) => Ok(Constant::Unsigned(*val)),
+        Number::Float(_) | Number::Double(_) | Number::LongDouble(_) => {
+            Err(ConstantError::NotConstant)
+        }
+    }
+}
+
+/// Computes the value of a shift.
+///
+/// The type of the result is the type of the left operand. A negative shift
+/// count, or a shift count that is at least the width of the type, is an
+/// overflow.
+fn shift_value(
+    op: &BinaryOperator,
+    left: Constant,
+    right: Constant,
+) -> Result<Constant, ConstantError> {
+    let count = u32::try_from(right.into_i128())
+        .ok()
+        .filter(|count| *count < u64::BITS)
+        .ok_or(ConstantError::Overflow)?;
+    let left_shift = *op == BinaryOperator::ShiftLeft;
+    match left {
+        Constant::Signed(value) if left_shift => {
+            // shifting a negative value, or a bit into the sign, is undefined
+            if value < 0 || value.leading_zeros() <= count {
+                Err(ConstantError::Overflow)
+            } else {
+                Ok(Constant::Signed(value << count))
+            }
+        }
+        Constant::Signed(value) => Ok(Constant::Signed(value >> count)),
+        Constant::Unsigned(value) if left_shift => Ok(Constant::Unsigned(value << count)),
+        Constant::Unsigned(value) => Ok(Constant::Unsigned(value >> count)),
+    }
+}
+
+/// Computes the value of a binary operator applied on two signed constants.
+///
+/// An operation whose result doesn't fit in `intmax_t` is an overflow.
+fn signed_value(op: &BinaryOperator, left: i64, right: i64) -> Result<Constant, ConstantError> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    let value = match op {
+        BinaryOperator::Multiply => left.checked_mul(right),
+        BinaryOperator::Divide => left.checked_div(right),
+        BinaryOperator::Modulo => left.checked_rem(right),
+        BinaryOperator::Add => left.checked_add(right),
+        BinaryOperator::Subtract => left.checked_sub(right),
+        BinaryOperator::Lt => Some(i64::from(left < right)),
+        BinaryOperator::Le => Some(i64::from(left <= right)),
+        BinaryOperator::Gt => Some(i64::from(left > right)),
+        BinaryOperator::Ge => Some(i64::from(left >= right)),
+        BinaryOperator::Equal => Some(i64::from(left == right)),
+        BinaryOperator::Different => Some(i64::from(left != right)),
+        BinaryOperator::BitwiseAnd => Some(left & right),
+        BinaryOperator::BitwiseXor => Some(left ^ right),
+        BinaryOperator::BitwiseOr => Some(left | right),
+        BinaryOperator::LogicalAnd => Some(i64::from(left != 0 && right != 0)),
+        BinaryOperator::LogicalOr => Some(i64::from(left != 0 || right != 0)),
+        _ => return Err(ConstantError::NotConstant),
+    };
+    value.map(Constant::Signed).ok_or(ConstantError::Overflow)
+}
+
+/// Evaluates an integer constant expression, and returns why it can't be
+/// evaluated otherwise.
+///
+/// The operands are evaluated as in the conditions of `#if`: the signed
+/// values have the type `intmax_t`, and the unsigned ones `uintmax_t`. If an
+/// operand is unsigned, the other one is converted to `uintmax_t`, so
+/// `0u - 1` is the biggest `uintmax_t`.
+///
+/// `lookup` returns the values of the identifiers that are constants, like
+/// the enumerators that were already declared.
+///
+/// # Errors
+///
+/// Returns an error if the expression isn't an integer constant expression,
+/// if it divides by zero, or if a signed operation overflows.
+pub fn try_constant_value(
+    node: &Ast,
+    lookup: &dyn Fn(&str) -> Option<i128>,
+) -> Result<i128, ConstantError> {
+    evaluate(node, lookup).map(Constant::into_i128)
+}
+
+/// Computes the value of a unary operator applied on a constant.
+fn unary_value(op: &UnaryOperator, value: Constant) -> Result<Constant, ConstantError> {
+    match (op, value) {
+        (UnaryOperator::Minus, Constant::Signed(signed)) => signed
+            .checked_neg()
+            .map(Constant::Signed)
+            .ok_or(ConstantError::Overflow),
+        (UnaryOperator::Minus, Constant::Unsigned(unsigned)) => {
+            Ok(Constant::Unsigned(unsigned.wrapping_neg()))
+        }
+        (UnaryOperator::Plus, _) => Ok(value),
+        (UnaryOperator::LogicalNot, _) => Ok(Constant::from_bool(value.is_zero())),
+        (UnaryOperator::BitwiseNot, Constant::Signed(signed)) => Ok(Constant::Signed(!signed)),
+        (UnaryOperator::BitwiseNot, Constant::Unsigned(unsigned)) => {
+            Ok(Constant::Unsigned(!unsigned))
+        }
+        // the value of a cast depends on the size of the type
+        (
+            UnaryOperator::AddressOf
+            | UnaryOperator::Cast(_)
+            | UnaryOperator::Indirection
+            | UnaryOperator::PostfixDecrement
+            | UnaryOperator::PostfixIncrement
+            | UnaryOperator::PrefixDecrement
+            | UnaryOperator::PrefixIncrement
+            | UnaryOperator::Sizeof,
+            _,
+        ) => Err(ConstantError::NotConstant),
+    }
+}
+
+/// Computes the value of a binary operator applied on two unsigned constants.
+///
+/// The arithmetic operations wrap around.
+fn unsigned_value(op: &BinaryOperator, left: u64, right: u64) -> Result<Constant, ConstantError> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    let value = match op {
+        BinaryOperator::Multiply => left.wrapping_mul(right),
+        BinaryOperator::Divide => left
+            .checked_div(right)
+            .ok_or(ConstantError::DivisionByZero)?,
+        BinaryOperator::Modulo => left
+            .checked_rem(right)
+            .ok_or(ConstantError::DivisionByZero)?,
+        BinaryOperator::Add => left.wrapping_add(right),
+        BinaryOperator::Subtract => left.wrapping_sub(right),
+        BinaryOperator::BitwiseAnd => left & right,
+        BinaryOperator::BitwiseXor => left ^ right,
+        BinaryOperator::BitwiseOr => left | right,
+        // the comparisons have the type `int`
+        BinaryOperator::Lt => return Ok(Constant::from_bool(left < right)),
+        BinaryOperator::Le => return Ok(Constant::from_bool(left <= right)),
+        BinaryOperator::Gt => return Ok(Constant::from_bool(left > right)),
+        BinaryOperator::Ge => return Ok(Constant::from_bool(left >= right)),
+        BinaryOperator::Equal => return Ok(Constant::from_bool(left == right)),
+        BinaryOperator::Different => return Ok(Constant::from_bool(left != right)),
+        BinaryOperator::LogicalAnd => return Ok(Constant::from_bool(left != 0 && right != 0)),
+        BinaryOperator::LogicalOr => return Ok(Constant::from_bool(left != 0 || right != 0)),
+        _ => return Err(ConstantError::NotConstant),
+    };
+    Ok(Constant::Unsigned(value))
+}
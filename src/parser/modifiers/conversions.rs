@@ -5,7 +5,7 @@ use core::{marker, mem};
 
 use super::super::types::Ast;
 use super::super::types::binary::{Binary, BinaryOperator};
-use super::super::types::operator::Operator;
+use super::super::types::operator::{Associativity, Operator};
 use super::super::types::unary::{Unary, UnaryOperator};
 use super::make_lhs::make_lhs;
 use crate::parser::types::ternary::{Ternary, TernaryOperator};
@@ -70,6 +70,7 @@ impl OperatorConversions for TernaryOperator {
 
     fn try_to_node_with_arg(self, arg: Ast) -> Result<Ast, String> {
         Ok(Ast::Ternary(Ternary {
+            common_type: None,
             op: Self,
             condition: Box::new(arg),
             success: Box::new(Ast::Empty),
@@ -81,6 +82,11 @@ impl OperatorConversions for TernaryOperator {
 #[expect(clippy::missing_trait_methods)]
 impl OperatorConversions for UnaryOperator {
     fn try_to_node(self) -> Result<Ast, String> {
+        if self.associativity() == Associativity::LeftToRight {
+            return Err(format!(
+                "Tried to call postfix operator {self} without an argument."
+            ));
+        }
         Ok(Ast::Unary(Unary {
             op: self,
             arg: Box::new(Ast::Empty),
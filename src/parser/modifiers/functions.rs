@@ -2,21 +2,115 @@
 
 use core::mem;
 
-use super::super::types::binary::Binary;
+use super::super::keyword::control_flow::node::ControlFlowNode;
+use super::super::types::binary::{Binary, BinaryOperator};
 use super::super::types::braced_blocks::BracedBlock;
-use super::super::types::literal::Literal;
+use super::super::types::builtin::{Builtin, BuiltinArgument, BuiltinCall};
+use super::super::types::literal::{Literal, Variable, VariableName};
 use super::super::types::unary::Unary;
-use super::super::types::{Ast, FunctionCall, FunctionOperator, ListInitialiser};
+use super::super::types::{Ast, ExpressionCall, FunctionCall, FunctionOperator, ListInitialiser};
 use crate::parser::types::ternary::Ternary;
 
-/// Checks if it is possible to create a function from the last
-/// [`Literal::Variable`].
+/// Checks if it is possible to create a function call from the last postfix
+/// expression.
+///
+/// See [`get_last_callee`] for the expressions that can be called.
 pub fn can_make_function(current: &mut Ast) -> bool {
-    get_last_variable(current).is_some()
+    get_last_callee(current).is_some()
+}
+
+/// Returns the builtin that corresponds to the last [`Literal::Variable`], if
+/// it exists.
+///
+/// The variable must only be a name, without attributes, like `offsetof`.
+pub fn get_last_builtin(current: &mut Ast) -> Option<Builtin> {
+    if let Some(Ast::Leaf(Literal::Variable(Variable {
+        attrs,
+        name: VariableName::UserDefined(name),
+        ..
+    }))) = get_last_variable(current)
+        && attrs.is_empty()
+    {
+        Builtin::from_name(name)
+    } else {
+        None
+    }
+}
+
+/// Returns the last postfix expression of the [`Ast`], that can be called.
+///
+/// This is either a [`Literal::Variable`], like `f`, or an expression that
+/// designates a function, like `s.f`, `p->f`, `a[0]`, `f(x)` or `(*fp)`. As
+/// the postfix operators are left-associative, the callee is the whole chain
+/// of postfix operators: in `a.b[c](d)`, the callee is `a.b[c]`.
+fn get_last_callee(current: &mut Ast) -> Option<&mut Ast> {
+    match current {
+        //
+        //
+        // success
+        Ast::Leaf(Literal::Variable(_))
+        | Ast::Binary(Binary {
+            op:
+                BinaryOperator::ArraySubscript
+                | BinaryOperator::StructEnumMemberAccess
+                | BinaryOperator::StructEnumMemberPointerAccess,
+            ..
+        })
+        | Ast::ExpressionCall(_)
+        | Ast::ParensBlock(_) => Some(current),
+        // a function declaration can't be called
+        Ast::FunctionCall(FunctionCall { variable, .. }) => {
+            variable.attrs.is_empty().then_some(current)
+        }
+        //
+        //
+        // recurse
+        // operators
+        Ast::Unary(Unary { arg: child, .. })
+        | Ast::Binary(Binary { arg_r: child, .. })
+        | Ast::Ternary(
+            Ternary {
+                failure: Some(child),
+                ..
+            }
+            | Ternary { success: child, .. },
+        )
+        | Ast::ControlFlow(
+            ControlFlowNode::ColonAst(_, Some(child))
+            | ControlFlowNode::ValueColonAst(_, _, Some(child))
+            | ControlFlowNode::ValueColonAst(_, child, None),
+        ) => get_last_callee(child),
+        //
+        //
+        // failure
+        Ast::ArrayDesignator(_)
+        | Ast::CaseRange(_)
+        | Ast::MemberDesignator(_)
+        | Ast::ArraySize(_)
+        | Ast::Empty
+        | Ast::Label(_)
+        | Ast::Leaf(_)
+        | Ast::StatementExpression(_)
+        | Ast::CompoundLiteral(_)
+        | Ast::BracedBlock(BracedBlock { full: true, .. })
+        | Ast::AlignOf(_)
+        | Ast::BuiltinCall(_)
+        | Ast::ListInitialiser(ListInitialiser { full: true, .. })
+        | Ast::ControlFlow(_) => None,
+        //
+        //
+        // recurse
+        // lists
+        Ast::FunctionArgsBuild(vec)
+        | Ast::ListInitialiser(ListInitialiser { elts: vec, .. })
+        | Ast::BracedBlock(BracedBlock { elts: vec, .. }) => {
+            vec.last_mut().and_then(get_last_callee)
+        }
+    }
 }
 
 /// Returns the last variable of the [`Ast`].
-fn get_last_variable(current: &mut Ast) -> Option<&mut Ast> {
+pub fn get_last_variable(current: &mut Ast) -> Option<&mut Ast> {
     match current {
         //
         //
@@ -24,25 +118,45 @@ fn get_last_variable(current: &mut Ast) -> Option<&mut Ast> {
         Ast::Leaf(Literal::Variable(_)) => Some(current),
         //
         //
+        // recurse
+        // operators
+        Ast::Unary(Unary { arg: child, .. })
+        | Ast::Binary(Binary { arg_r: child, .. })
+        | Ast::Ternary(
+            Ternary {
+                failure: Some(child),
+                ..
+            }
+            | Ternary { success: child, .. },
+        )
+        | Ast::ControlFlow(
+            ControlFlowNode::ColonAst(_, Some(child))
+            | ControlFlowNode::ValueColonAst(_, _, Some(child))
+            | ControlFlowNode::ValueColonAst(_, child, None),
+        ) => get_last_variable(child),
+        //
+        //
         // failure
-        Ast::Empty
+        Ast::ArrayDesignator(_)
+        | Ast::CaseRange(_)
+        | Ast::MemberDesignator(_)
+        | Ast::ArraySize(_)
+        | Ast::Empty
+        | Ast::Label(_)
         | Ast::Leaf(_)
-        | Ast::ControlFlow(_)
         | Ast::ParensBlock(_)
+        | Ast::StatementExpression(_)
+        | Ast::CompoundLiteral(_)
         | Ast::BracedBlock(BracedBlock { full: true, .. })
-        | Ast::Ternary(Ternary { failure: None, .. })
+        | Ast::ExpressionCall(_)
         | Ast::FunctionCall(_)
-        | Ast::ListInitialiser(ListInitialiser { full: true, .. }) => None,
+        | Ast::AlignOf(_)
+        | Ast::BuiltinCall(_)
+        | Ast::ListInitialiser(ListInitialiser { full: true, .. })
+        | Ast::ControlFlow(_) => None,
         //
         //
         // recurse
-        // operators
-        Ast::Unary(Unary { arg: child, .. })
-        | Ast::Binary(Binary { arg_r: child, .. })
-        | Ast::Ternary(Ternary {
-            failure: Some(child),
-            ..
-        }) => get_last_variable(child),
         // lists
         Ast::FunctionArgsBuild(vec)
         | Ast::ListInitialiser(ListInitialiser { elts: vec, .. })
@@ -52,19 +166,62 @@ fn get_last_variable(current: &mut Ast) -> Option<&mut Ast> {
     }
 }
 
-/// Tries to create a function from the last [`Literal::Variable`].
+/// Checks if the last node of the [`Ast`] is a function declaration, like
+/// `int f(int x)`.
+///
+/// A function call, like `f(x)`, has no attributes.
+pub fn is_function_declaration(current: &Ast) -> bool {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match current {
+        Ast::FunctionCall(FunctionCall { variable, .. }) => !variable.attrs.is_empty(),
+        Ast::BracedBlock(BracedBlock { elts, full: false }) => {
+            elts.last().is_some_and(is_function_declaration)
+        }
+        _ => false,
+    }
+}
+
+/// Tries to create a function call from the last postfix expression.
+///
+/// A [`Literal::Variable`] becomes a [`FunctionCall`], and any other callee
+/// becomes an [`ExpressionCall`].
 pub fn make_function(current: &mut Ast, arguments: Vec<Ast>) {
-    if let Some(ast) = get_last_variable(current) {
-        if let Ast::Leaf(Literal::Variable(variable)) = mem::take(ast) {
-            *ast = Ast::FunctionCall(FunctionCall {
+    if let Some(ast) = get_last_callee(current) {
+        let callee = mem::take(ast);
+        *ast = if let Ast::Leaf(Literal::Variable(variable)) = callee {
+            Ast::FunctionCall(FunctionCall {
                 variable,
                 op: FunctionOperator,
                 args: arguments,
+            })
+        } else {
+            Ast::ExpressionCall(ExpressionCall {
+                args: arguments,
+                callee: Box::new(callee),
+            })
+        };
+    } else {
+        panic!("never happens: can_make_function checked")
+    }
+}
+
+/// Replaces the last [`Literal::Variable`] by a call to a builtin.
+pub fn make_builtin_call(current: &mut Ast, builtin: Builtin, args: Vec<BuiltinArgument>) {
+    if let Some(ast) = get_last_variable(current) {
+        if let Ast::Leaf(Literal::Variable(Variable {
+            name: VariableName::UserDefined(name),
+            ..
+        })) = mem::take(ast)
+        {
+            *ast = Ast::BuiltinCall(BuiltinCall {
+                args,
+                builtin,
+                name,
             });
         } else {
-            panic!("never happens: apply_last_variable only returns var")
+            panic!("never happens: get_last_builtin checked")
         }
     } else {
-        panic!("never happens: can_make_function checked")
+        panic!("never happens: get_last_builtin checked")
     }
 }
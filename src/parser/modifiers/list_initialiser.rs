@@ -1,9 +1,17 @@
 //! Module that modifies [`ListInitialiser`] within an existing node.
 
+extern crate alloc;
+use alloc::vec::IntoIter;
+use core::iter::Peekable;
+use core::mem;
+
+use super::super::types::array::{ArrayLength, ArraySize};
 use super::super::types::binary::{Binary, BinaryOperator};
 use super::super::types::braced_blocks::BracedBlock;
+use super::super::types::literal::{Attribute, Literal, Variable};
 use super::super::types::unary::Unary;
 use super::super::types::{Ast, ListInitialiser};
+use super::constant::constant_value;
 use crate::parser::types::ternary::Ternary;
 
 /// Applies a closure to the current [`ListInitialiser`].
@@ -38,12 +46,22 @@ pub fn apply_to_last_list_initialiser<T, F: Fn(&mut Vec<Ast>, &mut bool) -> T>(
         //
         // failure
         // atomic
-        Ast::Empty
+        Ast::ArrayDesignator(_)
+        | Ast::CaseRange(_)
+        | Ast::MemberDesignator(_)
+        | Ast::ArraySize(_)
+        | Ast::Empty
+        | Ast::Label(_)
         | Ast::Leaf(_)
         | Ast::ControlFlow(_)
         | Ast::ParensBlock(_)
+        | Ast::StatementExpression(_)
+        | Ast::CompoundLiteral(_)
         // full lists
+        | Ast::ExpressionCall(_)
         | Ast::FunctionCall(_)
+        | Ast::AlignOf(_)
+        | Ast::BuiltinCall(_)
         | Ast::BracedBlock(BracedBlock{full: true, ..})
         | Ast::ListInitialiser(ListInitialiser{full: true, ..}) => Err(()),
         //
@@ -64,6 +82,24 @@ pub fn apply_to_last_list_initialiser<T, F: Fn(&mut Vec<Ast>, &mut bool) -> T>(
     }
 }
 
+/// Returns the lengths of the dimensions of an array declarator, from the
+/// outermost to the innermost, like `[2, 3]` for `int a[2][3]`.
+fn array_lengths(mut declarator: &Ast) -> Vec<&ArrayLength> {
+    let mut lengths = vec![];
+    while let Ast::Binary(Binary {
+        op: BinaryOperator::ArraySubscript,
+        arg_l,
+        arg_r,
+    }) = declarator
+        && let Ast::ArraySize(ArraySize { length, .. }) = &**arg_r
+    {
+        lengths.push(length);
+        declarator = arg_l;
+    }
+    lengths.reverse();
+    lengths
+}
+
 /// Checks if a `{` is meant as a [`ListInitialiser`] or as a [`BracedBlock`].
 ///
 /// # Returns
@@ -96,12 +132,22 @@ pub fn can_push_list_initialiser(ast: &mut Ast) -> Result<bool, String> {
         //
         Ast::Empty
         // full: can't push
+        | Ast::ArrayDesignator(_)
+        | Ast::CaseRange(_)
+        | Ast::MemberDesignator(_)
+        | Ast::ArraySize(_)
+        | Ast::Label(_)
         | Ast::Leaf(_)
         | Ast::ControlFlow(_)
         | Ast::ParensBlock(_)
+        | Ast::StatementExpression(_)
+        | Ast::CompoundLiteral(_)
         | Ast::BracedBlock(BracedBlock { full: true, .. })
         | Ast::ListInitialiser(ListInitialiser { full: true, .. })
-        | Ast::FunctionCall(_) => Ok(false),
+        | Ast::ExpressionCall(_)
+        | Ast::FunctionCall(_)
+        | Ast::AlignOf(_)
+        | Ast::BuiltinCall(_) => Ok(false),
         //
         //
         // illegal leaf: can't push
@@ -125,3 +171,168 @@ pub fn can_push_list_initialiser(ast: &mut Ast) -> Result<bool, String> {
         }
     }
 }
+
+/// Distributes the elements of a list initialiser over the elements of an
+/// array, whose sub-arrays have the lengths `sub_lengths`.
+///
+/// The array has at most `length` elements, or any number of elements if the
+/// length is unknown, like in `int a[][2]`. The lists with designators, like
+/// `{[1] = 2}`, are left unchanged.
+fn distribute(
+    elts: Vec<Ast>,
+    length: Option<usize>,
+    sub_lengths: &[usize],
+) -> Result<Vec<Ast>, String> {
+    if elts.iter().any(|elt| {
+        matches!(
+            elt,
+            Ast::Binary(Binary {
+                op: BinaryOperator::Assign,
+                ..
+            })
+        )
+    }) {
+        return Ok(elts);
+    }
+    let mut iter = elts
+        .into_iter()
+        .filter(|elt| *elt != Ast::Empty)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .peekable();
+    let mut distributed = vec![];
+    while iter.peek().is_some() {
+        if let Some(len) = length
+            && distributed.len() >= len
+        {
+            return Err(format!(
+                "Found too many elements in the initialiser of the array: expected at most {len}."
+            ));
+        }
+        distributed.push(next_element(&mut iter, sub_lengths)?);
+    }
+    Ok(distributed)
+}
+
+/// Distributes the elements of the list initialiser of a multidimensional
+/// array, when the braces of the sub-arrays were elided.
+///
+/// This is applied on the last statement of the [`Ast`], after its `}` was
+/// found. For example, `int a[2][2] = {1, 2, 3, 4}` is rewritten as
+/// `int a[2][2] = {{1, 2}, {3, 4}}`. The arrays of structs, unions and
+/// user-defined types are left unchanged, as their number of members is
+/// unknown.
+///
+/// # Errors
+///
+/// Returns an error if the list contains more elements than the array.
+pub fn distribute_initialiser(current: &mut Ast) -> Result<(), String> {
+    if let Ast::BracedBlock(BracedBlock { elts, full: false }) = current
+        && let Some(last) = elts.last_mut()
+    {
+        return distribute_initialiser(last);
+    }
+    if get_first_variable(current).is_none_or(|var| {
+        var.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Tag(..) | Attribute::User(_)))
+    }) {
+        return Ok(());
+    }
+    let Some(Binary { arg_l, arg_r, .. }) = get_last_assignment(current) else {
+        return Ok(());
+    };
+    let Ast::ListInitialiser(ListInitialiser { elts, full: true }) = &mut **arg_r else {
+        return Ok(());
+    };
+    let lengths = array_lengths(arg_l);
+    let Some((first, others)) = lengths.split_first() else {
+        return Ok(());
+    };
+    let length = match first {
+        ArrayLength::Constant(len) => match length_value(len) {
+            Some(value) => Some(value),
+            None => return Ok(()),
+        },
+        ArrayLength::Empty => None,
+        ArrayLength::Star | ArrayLength::Variable(_) => return Ok(()),
+    };
+    let Some(sub_lengths) = others
+        .iter()
+        .map(|len| match len {
+            ArrayLength::Constant(sub_len) => length_value(sub_len),
+            ArrayLength::Empty | ArrayLength::Star | ArrayLength::Variable(_) => None,
+        })
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(());
+    };
+    if sub_lengths.is_empty() {
+        return Ok(());
+    }
+    *elts = distribute(mem::take(elts), length, &sub_lengths)?;
+    Ok(())
+}
+
+/// Returns the variable declared first in a declaration, which holds the type
+/// of all the declarators, like `a` in `int a, b[2][2]`.
+fn get_first_variable(node: &Ast) -> Option<&Variable> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Leaf(Literal::Variable(var)) => Some(var),
+        Ast::Binary(Binary { arg_l: arg, .. }) | Ast::Unary(Unary { arg, .. }) => {
+            get_first_variable(arg)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the last assignment of a declaration, like `b = {1, 2}` in
+/// `int a, b = {1, 2}`.
+fn get_last_assignment(node: &mut Ast) -> Option<&mut Binary> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Binary(
+            binary @ Binary {
+                op: BinaryOperator::Assign,
+                ..
+            },
+        ) => Some(binary),
+        Ast::Binary(Binary {
+            op: BinaryOperator::Comma,
+            arg_r,
+            ..
+        }) => get_last_assignment(arg_r),
+        _ => None,
+    }
+}
+
+/// Computes the length of an array from its constant expression.
+fn length_value(length: &Ast) -> Option<usize> {
+    usize::try_from(constant_value(length, &|_| None)?).ok()
+}
+
+/// Builds the next element of an array, whose sub-arrays have the lengths
+/// `sub_lengths`.
+///
+/// A braced list initialises the whole element. Otherwise, the braces of the
+/// element were elided, and it is initialised with as many elements as
+/// needed.
+fn next_element(iter: &mut Peekable<IntoIter<Ast>>, sub_lengths: &[usize]) -> Result<Ast, String> {
+    let Some((length, others)) = sub_lengths.split_first() else {
+        return Ok(iter.next().unwrap_or_default());
+    };
+    if let Some(Ast::ListInitialiser(_)) = iter.peek()
+        && let Some(Ast::ListInitialiser(ListInitialiser { elts, .. })) = iter.next()
+    {
+        return Ok(Ast::ListInitialiser(ListInitialiser {
+            elts: distribute(elts, Some(*length), others)?,
+            full: true,
+        }));
+    }
+    let mut elts = vec![];
+    while elts.len() < *length && iter.peek().is_some() {
+        elts.push(next_element(iter, others)?);
+    }
+    Ok(Ast::ListInitialiser(ListInitialiser { elts, full: true }))
+}
@@ -15,6 +15,66 @@ use super::super::types::unary::{Unary, UnaryOperator};
 use super::super::types::{Ast, ListInitialiser};
 use crate::parser::types::ternary::Ternary;
 
+/// Checks that an expression on the left-hand-side of an assignment is an
+/// lvalue, like `a`, `a[i]`, `s.x`, `p->x` or `*p`.
+///
+/// The expressions that are not yet complete are accepted, as the error is
+/// raised elsewhere.
+fn check_lvalue(current: &Ast) -> Result<(), String> {
+    let make_error = |val: &str| {
+        Err(format!(
+            "LHS: expected a modifiable lvalue, found {val}. Only variables, array subscripts, member accesses and dereferences can be assigned."
+        ))
+    };
+    match current {
+        // success
+        Ast::Leaf(Literal::Variable(_))
+        | Ast::Binary(Binary {
+            op:
+                BinaryOperator::ArraySubscript
+                | BinaryOperator::StructEnumMemberAccess
+                | BinaryOperator::StructEnumMemberPointerAccess,
+            ..
+        })
+        | Ast::Unary(Unary {
+            op: UnaryOperator::Indirection,
+            ..
+        })
+        // designated initialiser, like `[6] = 7`
+        | Ast::ArrayDesignator(_)
+        | Ast::CaseRange(_)
+        | Ast::MemberDesignator(_)
+        | Ast::ArraySize(_)
+        | Ast::Empty
+        | Ast::BracedBlock(_)
+        | Ast::ControlFlow(_)
+        | Ast::FunctionArgsBuild(_)
+        | Ast::ListInitialiser(_)
+        | Ast::StatementExpression(_)
+        | Ast::CompoundLiteral(_)
+        // `a ? b : c = d` is an assignment to the result of the ternary, that
+        // isn't an lvalue, but the parser keeps it for the precedence
+        | Ast::Ternary(_) => Ok(()),
+        // recurse
+        Ast::ParensBlock(parens) => check_lvalue(parens.get_inner()),
+        // declaration with a user-defined type, like `T *p = a`
+        Ast::Binary(Binary {
+            op: BinaryOperator::Multiply,
+            arg_l,
+            arg_r,
+        }) if matches!(**arg_l, Ast::Leaf(Literal::Variable(_))) => check_lvalue(arg_r),
+        // failure
+        Ast::Label(label) => make_error(&format!("label {label}")),
+        Ast::Leaf(lit) => make_error(&format!("constant literal {lit}")),
+        Ast::Unary(Unary { op, .. }) => make_error(&format!("unary operator {op}")),
+        Ast::Binary(Binary { op, .. }) => make_error(&format!("binary operator '{op}'")),
+        Ast::AlignOf(_) => make_error("alignof operator"),
+        Ast::ExpressionCall(_) | Ast::FunctionCall(_) | Ast::BuiltinCall(_) => {
+            make_error("function call")
+        }
+    }
+}
+
 /// Checks if the current [`Ast`] has a variable with attributes.
 ///
 /// If it is the case, an expression is not allowed in the LHS because it is a
@@ -24,12 +84,22 @@ fn has_attributes(current: &Ast) -> bool {
         // success
         Ast::Leaf(Literal::Variable(Variable { attrs, .. })) => !attrs.is_empty(),
         // failure
-        Ast::Empty
+        Ast::ArrayDesignator(_)
+        | Ast::CaseRange(_)
+        | Ast::MemberDesignator(_)
+        | Ast::ArraySize(_)
+        | Ast::Empty
+        | Ast::Label(_)
         | Ast::Leaf(_)
         | Ast::BracedBlock(_)
         | Ast::ParensBlock(_)
+        | Ast::StatementExpression(_)
+        | Ast::CompoundLiteral(_)
         | Ast::ControlFlow(_)
+        | Ast::ExpressionCall(_)
         | Ast::FunctionCall(_)
+        | Ast::AlignOf(_)
+        | Ast::BuiltinCall(_)
         | Ast::ListInitialiser(_)
         | Ast::FunctionArgsBuild(_) => false,
         // recurse
@@ -53,14 +123,14 @@ fn has_attributes(current: &Ast) -> bool {
 /// This is called when an assign
 /// [`Operator`](super::super::types::operator::Operator) is created or a
 /// function is created, to convert `*` to a type attribute. It also check that
-/// the [`Ast`] is a valid LHS.
+/// the [`Ast`] is a valid LHS: a declaration, or an lvalue expression.
 pub fn make_lhs(current: &mut Ast) -> Result<(), String> {
     if has_attributes(current) {
         /* LHS is a declaration */
         make_lhs_aux(current, false)
     } else {
         /* LHS is an expression */
-        Ok(())
+        check_lvalue(current)
     }
 }
 
@@ -117,12 +187,22 @@ fn make_lhs_aux(current: &mut Ast, push_indirection: bool) -> Result<(), String>
         // failure
         Ast::Empty => make_error("nothing"),
         Ast::FunctionArgsBuild(_) => make_error("function argument"),
+        Ast::ArrayDesignator(_) => make_error("array designator"),
+        Ast::CaseRange(_) => make_error("case range"),
+        Ast::MemberDesignator(_) => make_error("member designator"),
+        Ast::ArraySize(_) => make_error("array size"),
         Ast::ParensBlock(_) => make_error("parenthesis"),
+        Ast::StatementExpression(_) => make_error("statement expression"),
+        Ast::CompoundLiteral(_) => make_error("compound literal"),
+        Ast::Label(label) => make_error(&format!("label {label}.")),
         Ast::Leaf(lit) => make_error(&format!("constant literal {lit}.")),
         Ast::Unary(Unary { op, .. }) => make_error(&format!("unary operator {op}")),
         Ast::Binary(Binary { op, .. }) => make_error(&format!("binary operator '{op}'")),
         Ast::Ternary(_) => make_error("ternary operator"),
-        Ast::FunctionCall(_) => make_error("function"),
+        Ast::AlignOf(_) => make_error("alignof operator"),
+        Ast::ExpressionCall(_) | Ast::FunctionCall(_) | Ast::BuiltinCall(_) => {
+            make_error("function")
+        }
         Ast::ListInitialiser(ListInitialiser { full: true, .. }) => make_error("list initialiser"),
         Ast::BracedBlock(BracedBlock { full: true, .. }) => make_error("block"),
         Ast::ControlFlow(_) => make_error("control flow"),
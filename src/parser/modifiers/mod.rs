@@ -2,6 +2,7 @@
 //! [`Ast`](super::types::Ast).
 
 pub mod ast;
+pub mod constant;
 pub mod conversions;
 pub mod functions;
 pub mod list_initialiser;
@@ -3,14 +3,25 @@
 extern crate alloc;
 use alloc::vec::IntoIter;
 
+use super::common_types::compute_common_types;
+use super::dead_loops::check_dead_loops;
+use super::discarded_values::check_discarded_values;
+use super::implicit_returns::model_implicit_returns;
 use super::keyword::handle_keyword;
+use super::kr_definitions::rewrite_kr_definitions;
+use super::labels::check_label_addresses;
+use super::redefinitions::check_redefinitions;
 use super::state::ParsingState;
+use super::symbols::attribute_specifiers::handle_gnu_attribute;
 use super::symbols::handle_symbol;
+use super::thread_local::check_thread_local;
 use super::types::Ast;
 use super::types::braced_blocks::BracedBlock;
 use super::types::literal::{Literal, Variable};
+use super::unused_variables::check_unused_variables;
 use crate::errors::api::{Location, Res};
 use crate::lexer::api::{Token, TokenValue};
+use crate::options::api::Options;
 
 /// Deletes unnecessary outer block if necessary
 fn clean_nodes(nodes: Vec<Ast>) -> Ast {
@@ -49,27 +60,38 @@ pub fn parse_block(
     p_state: &mut ParsingState,
     current: &mut Ast,
 ) -> Res<()> {
+    if p_state.is_stopped() {
+        return Res::from(());
+    }
     tokens.next().map_or_else(
         || Res::from(()),
         |token| {
+            p_state.count_node();
             #[cfg(feature = "debug")]
             println!("Token = {token}\t & Current = {current}\n\t & State = {p_state:?}");
             let (value, location) = token.into_value_location();
             match value {
-                TokenValue::Char(ch) => {
+                TokenValue::Char(ch, _) => {
                     handle_literal(current, Literal::Char(ch), location, p_state, tokens)
                 }
-                TokenValue::Ident(val) => handle_literal(
-                    current,
-                    Literal::Variable(Variable::from(val)),
-                    location,
-                    p_state,
-                    tokens,
-                ),
+                TokenValue::Ident(val) if val == "__attribute__" || val == "__attribute" => {
+                    handle_gnu_attribute(current, p_state, tokens, location)
+                }
+                TokenValue::Ident(val) => {
+                    let mut variable = Variable::from(val);
+                    variable.location = Some(location.clone());
+                    handle_literal(
+                        current,
+                        Literal::Variable(variable),
+                        location,
+                        p_state,
+                        tokens,
+                    )
+                }
                 TokenValue::Number(nb) => {
                     handle_literal(current, Literal::Number(nb), location, p_state, tokens)
                 }
-                TokenValue::Str(val) => {
+                TokenValue::Str(val, _) => {
                     handle_literal(current, Literal::Str(val), location, p_state, tokens)
                 }
                 TokenValue::Symbol(symbol) => {
@@ -78,24 +100,47 @@ pub fn parse_block(
                 TokenValue::Keyword(keyword) => {
                     handle_keyword(keyword, current, p_state, tokens, location)
                 }
+                TokenValue::Comment(_) | TokenValue::Newline | TokenValue::Whitespace(_) => {
+                    panic!("Never happens: layout tokens are removed before parsing")
+                }
             }
         },
     )
 }
 
-/// Parses a list of tokens into an AST.
+/// Parses the tokens of a source or header file into an AST.
 ///
-/// This function manages the blocks with successive calls and checks.
-#[must_use]
-#[inline]
-pub fn parse_tokens(tokens: Vec<Token>) -> Res<Ast> {
+/// The errors that point at a token are given the index of that token in
+/// `tokens`.
+fn parse_file(tokens: Vec<Token>, options: Options, header: bool) -> Res<Ast> {
+    let locations: Vec<Location> = tokens
+        .iter()
+        .map(|token| token.get_location().to_owned())
+        .collect();
+    let (node, mut errors) = parse_file_nodes(tokens, options, header).into_parts();
+    for error in &mut errors {
+        error.set_token_index(&locations);
+    }
+    Res::from((node, errors))
+}
+
+/// Parses the tokens of a source or header file into an AST, without linking
+/// the errors to their tokens.
+fn parse_file_nodes(mut tokens: Vec<Token>, options: Options, header: bool) -> Res<Ast> {
+    tokens.retain(|token| !token.is_layout());
+    let (prototypes, mut errors) = rewrite_kr_definitions(tokens, &options);
     let mut nodes = vec![];
-    let mut errors = vec![];
-    let mut tokens_iter = tokens.into_iter();
-    while tokens_iter.len() != 0 {
+    let mut tokens_iter = prototypes.into_iter();
+    // the state is kept between the blocks to count the nodes of the file
+    let mut p_state = if header {
+        ParsingState::new_header(options)
+    } else {
+        ParsingState::from(options)
+    };
+    while tokens_iter.len() != 0 && !p_state.is_stopped() {
         let mut outer_node_block = Ast::BracedBlock(BracedBlock::default());
-        let mut p_state = ParsingState::default();
         let res = parse_block(&mut tokens_iter, &mut p_state, &mut outer_node_block);
+        errors.extend(p_state.take_suggestions());
         if res.has_failures() {
             errors.extend(res.into_errors());
             return Res::from((clean_nodes(nodes), errors));
@@ -107,5 +152,57 @@ pub fn parse_tokens(tokens: Vec<Token>) -> Res<Ast> {
         }
         nodes.push(outer_node_block);
     }
-    Res::from((clean_nodes(nodes), errors))
+    let mut node = clean_nodes(nodes);
+    errors.extend(check_thread_local(&node));
+    errors.extend(check_label_addresses(&node));
+    errors.extend(check_redefinitions(&node, options.standard()));
+    errors.extend(check_discarded_values(&node));
+    errors.extend(check_dead_loops(&node));
+    errors.extend(compute_common_types(&mut node));
+    if options.models_implicit_returns() {
+        errors.extend(model_implicit_returns(&mut node, options));
+    }
+    if options.reports_unused_variables() {
+        errors.extend(check_unused_variables(&node));
+    }
+    Res::from((node, errors))
+}
+
+/// Parses a list of tokens into an AST.
+///
+/// This function manages the blocks with successive calls and checks.
+#[must_use]
+#[inline]
+pub fn parse_tokens(tokens: Vec<Token>) -> Res<Ast> {
+    parse_tokens_with_options(tokens, Options::default())
+}
+
+/// Parses a list of tokens into an AST, with the given [`Options`].
+///
+/// See [`parse_tokens`] for more information.
+#[must_use]
+#[inline]
+pub fn parse_tokens_with_options(tokens: Vec<Token>, options: Options) -> Res<Ast> {
+    parse_file(tokens, options, false)
+}
+
+/// Parses the list of tokens of a header file into an AST.
+///
+/// A header is meant to declare, not to define: the tokens are parsed as with
+/// [`parse_tokens`], but a warning is raised for every function definition
+/// with a body.
+#[must_use]
+#[inline]
+pub fn parse_header(tokens: Vec<Token>) -> Res<Ast> {
+    parse_header_with_options(tokens, Options::default())
+}
+
+/// Parses the list of tokens of a header file into an AST, with the given
+/// [`Options`].
+///
+/// See [`parse_header`] for more information.
+#[must_use]
+#[inline]
+pub fn parse_header_with_options(tokens: Vec<Token>, options: Options) -> Res<Ast> {
+    parse_file(tokens, options, true)
 }
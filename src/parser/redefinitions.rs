@@ -0,0 +1,367 @@
+//! Module to report the redefinitions of the typedefs, the structs, the
+//! unions and the functions.
+//!
+//! A name can be declared many times, but can only be defined once in a
+//! scope:
+//!
+//! - since C11, a typedef can be redefined with the same type, like in `typedef
+//!   int T; typedef int T;`, but not with another type;
+//! - since C23, a struct or a union can be redefined with the same members, but
+//!   not with other members;
+//! - a function can only have one body.
+//!
+//! The control flow keywords, like `typedef` and `struct`, are pushed in
+//! closed blocks, and the declaration that follows them is in the next
+//! elements of the block, until the end of the statement. The types and the
+//! members are compared as they are written, so `const int` and `int const`
+//! are considered different.
+
+use core::{mem, slice};
+
+use super::keyword::control_flow::keyword::ControlFlowKeyword;
+use super::keyword::control_flow::node::ControlFlowNode;
+use super::types::binary::{Binary, BinaryOperator};
+use super::types::braced_blocks::BracedBlock;
+use super::types::literal::{Attribute, Literal, Variable, VariableName};
+use super::types::{Ast, FunctionCall};
+use super::visit::Visit;
+use super::{repr_attrs, repr_vec};
+use crate::errors::api::{CompileError, Location};
+use crate::options::api::CStandard;
+
+/// Definition of a typedef, a tag or a function.
+struct Definition {
+    /// Location of the name, to point at the first definition.
+    location: Option<Location>,
+    /// Name of the definition, with the keyword for the tags, like `struct S`.
+    name: String,
+    /// Type of a typedef, or members of a tag, as written, if they are known.
+    value: Option<String>,
+}
+
+/// Stack of the scopes of the definitions, from the outermost to the
+/// innermost.
+struct Definitions {
+    /// Errors for the redefinitions.
+    errors: Vec<CompileError>,
+    /// Functions defined with a body.
+    functions: Vec<Definition>,
+    /// Typedefs and tags of the opened scopes, the first one being the file
+    /// scope.
+    scopes: Vec<Vec<Definition>>,
+    /// Standard of the source, as the allowed redefinitions depend on it.
+    standard: CStandard,
+    /// State of the statement that is being walked.
+    statement: Statement,
+}
+
+impl Definitions {
+    /// Defines a function, and reports it if it already has a body.
+    fn define_function(&mut self, variable: &Variable) {
+        let VariableName::UserDefined(name) = &variable.name else {
+            return;
+        };
+        if let Some(previous) = self
+            .functions
+            .iter()
+            .find(|function| function.name == *name)
+        {
+            if let Some(location) = &variable.location {
+                self.errors.push(with_previous(
+                    location.to_failure(format!(
+                        "Found a redefinition of the function '{name}', but a function can only be defined once."
+                    )),
+                    previous,
+                ));
+            }
+        } else {
+            self.functions.push(Definition {
+                location: variable.location.clone(),
+                name: name.to_owned(),
+                value: None,
+            });
+        }
+    }
+
+    /// Defines a struct or a union with its members, and reports it if it is
+    /// already defined in the current scope.
+    fn define_tag(&mut self, (keyword, tag): &(ControlFlowKeyword, Tag), members: &[Ast]) {
+        let Some((name, location)) = &tag.name else {
+            return;
+        };
+        let definition = Definition {
+            location: location.clone(),
+            name: format!("{keyword} {name}"),
+            value: Some(repr_vec(members)),
+        };
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        let Some(previous) = scope.iter().find(|old| old.name == definition.name) else {
+            scope.push(definition);
+            return;
+        };
+        let msg = if previous.value == definition.value {
+            if self.standard >= CStandard::C23 {
+                return;
+            }
+            format!(
+                "Found a redefinition of '{}', but tags can only be redefined with the same members since C23.",
+                definition.name
+            )
+        } else {
+            format!(
+                "Found a redefinition of '{}' with different members, but it was already defined.",
+                definition.name
+            )
+        };
+        if let Some(new_location) = location {
+            self.errors
+                .push(with_previous(new_location.to_failure(msg), previous));
+        }
+    }
+
+    /// Defines a typedef, and reports it if it is already defined in the
+    /// current scope with another type.
+    fn define_typedef(&mut self, definition: Definition) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        let Some(previous) = scope.iter().find(|old| old.name == definition.name) else {
+            scope.push(definition);
+            return;
+        };
+        let msg = match (&previous.value, &definition.value) {
+            (Some(old), Some(new)) if old != new => format!(
+                "Found a redefinition of the typedef '{}' as '{new}', but it was already defined as '{old}'.",
+                definition.name
+            ),
+            _ if self.standard < CStandard::C11 => format!(
+                "Found a redefinition of the typedef '{}', but typedefs can only be redefined since C11.",
+                definition.name
+            ),
+            _ => return,
+        };
+        if let Some(location) = &definition.location {
+            self.errors
+                .push(with_previous(location.to_failure(msg), previous));
+        }
+    }
+
+    /// Defines the typedefs declared by a declaration, like `A` and `B` in
+    /// `typedef int A, *B;`.
+    fn define_typedefs(&mut self, node: &Ast, tag: Option<&(ControlFlowKeyword, Tag)>) {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match node {
+            Ast::Binary(Binary {
+                op: BinaryOperator::Comma,
+                arg_l,
+                arg_r,
+            }) => {
+                self.define_typedefs(arg_l, tag);
+                self.define_typedefs(arg_r, tag);
+            }
+            Ast::Binary(Binary {
+                op: BinaryOperator::ArraySubscript,
+                arg_l,
+                arg_r,
+            }) => {
+                if let Ast::Leaf(Literal::Variable(variable)) = &**arg_l {
+                    self.define_typedef(typedef_definition(variable, tag, Some(&**arg_r)));
+                }
+            }
+            Ast::FunctionCall(FunctionCall { variable, .. }) => {
+                let mut definition = typedef_definition(variable, tag, None);
+                definition.value = None;
+                self.define_typedef(definition);
+            }
+            Ast::Leaf(Literal::Variable(variable)) => {
+                self.define_typedef(typedef_definition(variable, tag, None));
+            }
+            _ => (),
+        }
+    }
+
+    /// Walks the elements of a block, in the current scope.
+    #[expect(clippy::wildcard_enum_match_arm)]
+    fn visit_elts<'node>(&mut self, elts: &'node [Ast]) {
+        let mut previous: Option<&'node Ast> = None;
+        for elt in elts {
+            match elt {
+                Ast::Empty => self.statement = Statement::default(),
+                Ast::BracedBlock(
+                    block @ BracedBlock {
+                        elts: inner,
+                        full: true,
+                    },
+                ) => {
+                    if let [Ast::ControlFlow(ctrl)] = inner.as_slice()
+                        && is_keyword_marker(ctrl)
+                    {
+                        match ctrl.get_keyword() {
+                            ControlFlowKeyword::Typedef => self.statement.typedef = true,
+                            keyword @ (ControlFlowKeyword::Struct | ControlFlowKeyword::Union) => {
+                                self.statement.tag = Some((keyword.clone(), Tag::default()));
+                            }
+                            ControlFlowKeyword::Break
+                            | ControlFlowKeyword::Case
+                            | ControlFlowKeyword::Continue
+                            | ControlFlowKeyword::Default
+                            | ControlFlowKeyword::Do
+                            | ControlFlowKeyword::Else
+                            | ControlFlowKeyword::Enum
+                            | ControlFlowKeyword::For
+                            | ControlFlowKeyword::Goto
+                            | ControlFlowKeyword::If
+                            | ControlFlowKeyword::Return
+                            | ControlFlowKeyword::Switch
+                            | ControlFlowKeyword::While => (),
+                        }
+                    } else if let Some((keyword, tag)) = &mut self.statement.tag
+                        && !tag.defined
+                    {
+                        tag.defined = true;
+                        let declared = (keyword.clone(), tag.clone());
+                        self.define_tag(&declared, inner);
+                    } else {
+                        if let Some(Ast::FunctionCall(FunctionCall { variable, .. })) = previous
+                            && !variable.attrs.is_empty()
+                        {
+                            self.define_function(variable);
+                        }
+                        self.visit_block(block);
+                    }
+                }
+                Ast::Leaf(Literal::Variable(Variable {
+                    attrs,
+                    location,
+                    name: VariableName::UserDefined(name),
+                })) if attrs.is_empty()
+                    && self
+                        .statement
+                        .tag
+                        .as_ref()
+                        .is_some_and(|(_, tag)| tag.name.is_none() && !tag.defined) =>
+                {
+                    if let Some((_, tag)) = &mut self.statement.tag {
+                        tag.name = Some((name.to_owned(), location.clone()));
+                    }
+                }
+                _ if self.statement.typedef => {
+                    let tag = self.statement.tag.clone();
+                    self.define_typedefs(elt, tag.as_ref());
+                }
+                _ => self.visit(elt),
+            }
+            previous = Some(elt);
+        }
+    }
+}
+
+impl Visit<'_> for Definitions {
+    /// Walks the elements of a block, in a new scope if the block is closed.
+    ///
+    /// The statement is kept between the open blocks, as they belong to the
+    /// same scope.
+    fn visit_block(&mut self, block: &BracedBlock) {
+        if block.full {
+            self.scopes.push(vec![]);
+            let statement = mem::take(&mut self.statement);
+            self.visit_elts(&block.elts);
+            self.statement = statement;
+            self.scopes.pop();
+        } else {
+            self.visit_elts(&block.elts);
+        }
+    }
+}
+
+/// State of the statement that is being walked.
+#[derive(Default)]
+struct Statement {
+    /// Struct or union declared by the statement, with its keyword.
+    tag: Option<(ControlFlowKeyword, Tag)>,
+    /// The statement is a `typedef`.
+    typedef: bool,
+}
+
+/// Struct or union declared by a statement.
+#[derive(Default, Clone)]
+struct Tag {
+    /// The members of the tag were found.
+    defined: bool,
+    /// Name of the tag and its location, if the tag isn't anonymous.
+    name: Option<(String, Option<Location>)>,
+}
+
+/// Reports the redefinitions of the typedefs, the structs, the unions and the
+/// functions.
+///
+/// See the [module documentation](self) for more information.
+pub fn check_redefinitions(node: &Ast, standard: CStandard) -> Vec<CompileError> {
+    let mut definitions = Definitions {
+        errors: vec![],
+        functions: vec![],
+        scopes: vec![vec![]],
+        standard,
+        statement: Statement::default(),
+    };
+    if let Ast::BracedBlock(BracedBlock { elts, .. }) = node {
+        definitions.visit_elts(elts);
+    } else {
+        definitions.visit_elts(slice::from_ref(node));
+    }
+    definitions.errors
+}
+
+/// Checks if a control flow is only a keyword, without its content, like the
+/// `typedef` or the `struct` that are pushed in a closed block.
+const fn is_keyword_marker(ctrl: &ControlFlowNode) -> bool {
+    matches!(
+        ctrl,
+        ControlFlowNode::ControlFlow(_, None) | ControlFlowNode::IdentBlock(_, None, None)
+    )
+}
+
+/// Makes the definition of a typedef from its declarator.
+///
+/// The tag of `struct S` is written `'S'` by the parser when the members
+/// aren't given, so it is written `struct S` in all the cases. The type of an
+/// anonymous struct isn't known.
+fn typedef_definition(
+    variable: &Variable,
+    tag: Option<&(ControlFlowKeyword, Tag)>,
+    size: Option<&Ast>,
+) -> Definition {
+    let mut attrs = variable.attrs.clone();
+    if let Some((keyword, Tag { name, .. })) = tag {
+        if let Some((tag_name, _)) = name {
+            attrs.insert(0, Attribute::Tag(keyword.to_owned(), tag_name.to_owned()));
+        } else if let Some(first) = attrs.first_mut()
+            && let Attribute::User(tag_name) = first
+        {
+            *first = Attribute::Tag(keyword.to_owned(), mem::take(tag_name));
+        }
+    }
+    let value = (!attrs.is_empty()).then(|| {
+        let value = repr_attrs(&attrs);
+        size.map_or_else(|| value.clone(), |length| format!("{value} [{length}]"))
+    });
+    Definition {
+        location: variable.location.clone(),
+        name: variable.name.to_string(),
+        value,
+    }
+}
+
+/// Attaches a note to an error, to point at the previous definition.
+fn with_previous(error: CompileError, previous: &Definition) -> CompileError {
+    if let Some(location) = &previous.location {
+        error.with_note(
+            location.to_owned(),
+            format!("'{}' was first defined here.", previous.name),
+        )
+    } else {
+        error
+    }
+}
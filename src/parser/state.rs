@@ -1,7 +1,11 @@
 //! Module to follow the opening and closing blocks status.
 
+use core::mem;
+
 use crate::Location;
 use crate::errors::api::CompileError;
+use crate::lexer::api::{Symbol, Token};
+use crate::options::api::Options;
 
 /// Type to save the closed blocks.
 #[derive(Debug)]
@@ -37,8 +41,17 @@ pub enum BlockType {
 }
 
 impl BlockType {
+    /// Returns the symbol that closes the block.
+    pub const fn get_closing_symbol(&self) -> Symbol {
+        match self {
+            Self::Brace => Symbol::BraceClose,
+            Self::Bracket => Symbol::BracketClose,
+            Self::Parenthesis => Symbol::ParenthesisClose,
+        }
+    }
+
     /// Returns the characters that correspond.
-    const fn get_delimiters(&self) -> (char, char) {
+    pub const fn get_delimiters(&self) -> (char, char) {
         match self {
             Self::Brace => ('{', '}'),
             Self::Bracket => ('[', ']'),
@@ -66,14 +79,77 @@ pub struct ParsingState {
     /// This is pushed and popped on recursion calls to check that the block
     /// ended with the right character.
     closed_blocks: Vec<BlockState>,
+    /// Raises a warning for every function definition, as headers should only
+    /// contain declarations.
+    header: bool,
+    /// Number of tokens parsed so far, as every token makes at most one node.
+    ///
+    /// This is compared to [`Options::max_nodes`] at the end of every
+    /// statement.
+    nodes: usize,
+    /// Options chosen by the user to parse the file.
+    options: Options,
+    /// Number of parenthesis opened around the node that is being parsed.
+    ///
+    /// The `;` in parenthesis, like in `for (;;)` or in `({ x; })`, don't end
+    /// a statement, so the parsing can't stop there.
+    parenthesis: usize,
+    /// The budget of nodes was exceeded, so the remaining tokens are not
+    /// parsed.
+    stopped: bool,
+    /// Suggestions and warnings found while building the
+    /// [`Ast`](super::types::Ast).
+    ///
+    /// They are stored here and not returned in a [`Res`](crate::Res), as an
+    /// error in a [`Res`](crate::Res) stops the parsing of the enclosing
+    /// blocks.
+    suggestions: Vec<CompileError>,
 }
 
 impl ParsingState {
+    /// Stops the parsing if the budget of nodes was exceeded.
+    ///
+    /// This is called at the end of every statement, so that the partial
+    /// [`Ast`](super::types::Ast) is only made of complete statements. `next`
+    /// is the first token that won't be parsed.
+    pub fn check_node_budget(&mut self, next: Option<&Token>) {
+        if self.stopped || self.parenthesis != 0 || self.nodes <= self.options.max_nodes() {
+            return;
+        }
+        if let Some(token) = next {
+            self.stopped = true;
+            self.suggestions.push(token.get_location().to_suggestion(format!(
+                "Parsing stopped: the maximum number of nodes ({}) was exceeded, so the rest of the file was not parsed.",
+                self.options.max_nodes()
+            )));
+        }
+    }
+
+    /// Closes a parenthesis opened with [`Self::open_parenthesis`].
+    pub const fn close_parenthesis(&mut self) {
+        self.parenthesis = self.parenthesis.saturating_sub(1);
+    }
+
+    /// Counts a token that was parsed.
+    pub const fn count_node(&mut self) {
+        self.nodes = self.nodes.saturating_add(1);
+    }
+
     /// Contains opening blocks that weren't closed
     pub const fn has_opening_blocks(&self) -> bool {
         !self.closed_blocks.is_empty()
     }
 
+    /// Checks if the file being parsed is a header.
+    pub const fn is_header(&self) -> bool {
+        self.header
+    }
+
+    /// Checks if the parsing was stopped, as the budget of nodes was exceeded.
+    pub const fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
     /// Returns errors for the unopened blocks (cf. [`BlockState`]).
     pub fn mismatched_error(&mut self) -> Vec<CompileError> {
         let mut errors = vec![];
@@ -83,15 +159,57 @@ impl ParsingState {
         errors
     }
 
-    /// Pops the last opened block and compares it to a block type.
-    pub fn pop_and_compare_block(&mut self, block_type: &BlockType) -> bool {
-        let res = self
-            .closed_blocks
-            .pop()
-            .map(|block| block.block_type)
-            .as_ref()
-            == Some(block_type);
-        res
+    /// Creates a [`ParsingState`] to parse a header file.
+    pub fn new_header(options: Options) -> Self {
+        Self {
+            header: true,
+            ..Self::from(options)
+        }
+    }
+
+    /// Opens a parenthesis, in which the `;` don't end a statement.
+    pub const fn open_parenthesis(&mut self) {
+        self.parenthesis = self.parenthesis.saturating_add(1);
+    }
+
+    /// Returns the options chosen by the user to parse the file.
+    pub const fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Pops the last closing block and compares it to the type of the block
+    /// opened at `location`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the block was closed by another delimiter, or if the
+    /// end of the block was reached without closing delimiter.
+    ///
+    /// When the parsing was stopped, the blocks that are still open are closed
+    /// without error.
+    pub fn pop_and_compare_block(
+        &mut self,
+        block_type: &BlockType,
+        location: &Location,
+    ) -> Result<(), CompileError> {
+        if self.stopped {
+            return Ok(());
+        }
+        match self.closed_blocks.pop() {
+            Some(block) if block.block_type == *block_type => Ok(()),
+            Some(block) => {
+                let (open, close) = block_type.get_delimiters();
+                let found = block.block_type.get_delimiters().1;
+                Err(block
+                    .location
+                    .into_failure(format!(
+                        "Mismatched bracket: expected '{close}' to match '{open}' at {}, found '{found}'.",
+                        location.to_line_col()
+                    ))
+                    .with_note(location.to_owned(), format!("'{open}' opened here.")))
+            }
+            None => Err(block_type.mismatched_err_end(location.to_owned())),
+        }
     }
 
     /// Pushes a block.
@@ -101,4 +219,28 @@ impl ParsingState {
             location,
         });
     }
+
+    /// Stores a suggestion, to be returned after the parsing.
+    pub fn push_suggestion(&mut self, suggestion: CompileError) {
+        self.suggestions.push(suggestion);
+    }
+
+    /// Stores a warning, to be returned after the parsing.
+    pub fn push_warning(&mut self, warning: CompileError) {
+        self.suggestions.push(warning);
+    }
+
+    /// Returns the stored suggestions and warnings.
+    pub fn take_suggestions(&mut self) -> Vec<CompileError> {
+        mem::take(&mut self.suggestions)
+    }
+}
+
+impl From<Options> for ParsingState {
+    fn from(options: Options) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
 }
@@ -0,0 +1,254 @@
+//! Handler for the array subscripts, the array declarators and the array
+//! designators, like `a[1]`, `int a[10]`, `void f(int a[static 1])` or
+//! `{[0 ... 3] = 1}`.
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+
+use super::super::keyword::attributes::AttributeKeyword;
+use super::super::keyword::sort::KeywordParsing;
+use super::super::modifiers::constant::constant_value;
+use super::super::modifiers::list_initialiser::apply_to_last_list_initialiser;
+use super::super::parse_content::parse_block;
+use super::super::state::{BlockType, ParsingState};
+use super::super::types::Ast;
+use super::super::types::array::{ArrayDesignator, ArrayLength, ArraySize};
+use super::super::types::binary::{Binary, BinaryOperator};
+use super::super::types::braced_blocks::BracedBlock;
+use super::super::types::literal::{Literal, Variable};
+use super::super::types::unary::Unary;
+use super::builtins::split_arguments;
+use crate::errors::api::{CompileError, Location, Res};
+use crate::lexer::api::{Keyword, Symbol, Token, TokenValue};
+
+/// Handler for `[`
+///
+/// Pushes an array subscript, like in `a[1]`, the size of an array
+/// declarator, like in `int a[10]`, or an array designator, like in
+/// `{[6] = 7}`.
+pub fn handle_bracket_open(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    if is_designator(current) {
+        return handle_array_designator(current, p_state, tokens, location);
+    }
+    let declarator = if is_array_declarator(current) {
+        Some(parse_declarator_prefix(
+            tokens,
+            matches!(current, Ast::FunctionArgsBuild(_)),
+            &location,
+        )?)
+    } else {
+        None
+    };
+    let mut bracket_node = Ast::Empty;
+    parse_block(tokens, p_state, &mut bracket_node)?;
+    p_state.pop_and_compare_block(&BlockType::Bracket, &location)?;
+    if let Some((is_static, qualifiers, is_star)) = declarator {
+        let length = if is_star {
+            ArrayLength::Star
+        } else if bracket_node == Ast::Empty {
+            ArrayLength::Empty
+        } else if constant_value(&bracket_node, &|_| None).is_some() {
+            ArrayLength::Constant(Box::new(bracket_node))
+        } else {
+            ArrayLength::Variable(Box::new(bracket_node))
+        };
+        if is_static && matches!(length, ArrayLength::Empty | ArrayLength::Star) {
+            return Res::from(location.into_failure(
+                "Expected the length of the array after 'static' in array declarator.".to_owned(),
+            ));
+        }
+        bracket_node = Ast::ArraySize(ArraySize {
+            is_static,
+            length,
+            qualifiers,
+        });
+    }
+    if let Err(err) = current.push_op(BinaryOperator::ArraySubscript) {
+        return Res::from(location.into_failure(err));
+    }
+    current
+        .push_block_as_leaf(bracket_node)
+        .map_err(|err| location.into_failure(err))?;
+    parse_block(tokens, p_state, current)
+}
+
+/// Handler for the `[` of an array designator, like `[6]` in `{1, [6] = 7}`
+///
+/// With the GNU extensions, the designator can be a range, like `[0 ... 3]`.
+/// The bounds of the range are folded when they are constants, and the first
+/// index can't be greater than the last one.
+fn handle_array_designator(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let mut content = split_arguments(tokens, location.clone(), &BlockType::Bracket)?;
+    if content.len() != 1 {
+        return Res::from(location.into_failure(
+            "Expected one index between the brackets of the array designator.".to_owned(),
+        ));
+    }
+    let mut index = content.pop().unwrap_or_default();
+    let mut errors = vec![];
+    let designator = if let Some(position) = index
+        .iter()
+        .position(|token| *token.get_value() == TokenValue::Symbol(Symbol::Ellipsis))
+    {
+        let msg = "Found a range designator '[first ... last]', but this is a GNU extension.";
+        if !p_state.options().allows_gnu_extensions() {
+            return Res::from(
+                location.into_failure(format!("{msg} Please enable the GNU extensions to use it.")),
+            );
+        }
+        errors.push(location.to_warning(msg.to_owned()));
+        let hi_tokens = index.split_off(position).into_iter().skip(1).collect();
+        let lo = parse_designator_index(index, p_state, &location, &mut errors)?;
+        let hi = parse_designator_index(hi_tokens, p_state, &location, &mut errors)?;
+        if let (Some(lo_value), Some(hi_value)) = (
+            constant_value(&lo, &|_| None),
+            constant_value(&hi, &|_| None),
+        ) && lo_value > hi_value
+        {
+            return Res::from(location.into_failure(format!(
+                "Found an empty range designator '[{lo} ... {hi}]': the first index {lo_value} is greater than the last index {hi_value}."
+            )));
+        }
+        ArrayDesignator {
+            hi: Some(Box::new(hi)),
+            lo: Box::new(lo),
+        }
+    } else {
+        ArrayDesignator {
+            hi: None,
+            lo: Box::new(parse_designator_index(
+                index,
+                p_state,
+                &location,
+                &mut errors,
+            )?),
+        }
+    };
+    if errors.iter().any(CompileError::is_error) {
+        return Res::from_errors(errors);
+    }
+    current
+        .push_block_as_leaf(Ast::ArrayDesignator(designator))
+        .map_err(|err| location.into_failure(err))?;
+    for warning in errors {
+        p_state.push_warning(warning);
+    }
+    parse_block(tokens, p_state, current)
+}
+
+/// Checks if a `[` or a `.` after the [`Ast`] opens a designator, like in
+/// `{1, [6] = 7}` or in `{.x = 1}`.
+///
+/// This is the case if the symbol starts a new element of a list initialiser.
+pub fn is_designator(current: &mut Ast) -> bool {
+    apply_to_last_list_initialiser(current, &|elts, _| {
+        elts.last().is_none_or(|elt| *elt == Ast::Empty)
+    }) == Ok(true)
+}
+
+/// Checks if a `[` after the [`Ast`] opens an array declarator, like in
+/// `int a[10]`, and not an array subscript, like in `a[10]`.
+fn is_array_declarator(current: &Ast) -> bool {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match current {
+        Ast::Leaf(Literal::Variable(Variable { attrs, .. })) => !attrs.is_empty(),
+        // multidimensional arrays: `int a[2][3]`
+        Ast::Binary(
+            Binary {
+                op: BinaryOperator::ArraySubscript,
+                arg_l: child,
+                ..
+            }
+            | Binary { arg_r: child, .. },
+        )
+        | Ast::Unary(Unary { arg: child, .. }) => is_array_declarator(child),
+        Ast::FunctionArgsBuild(elts) | Ast::BracedBlock(BracedBlock { elts, full: false }) => {
+            elts.last().is_some_and(is_array_declarator)
+        }
+        _ => false,
+    }
+}
+
+/// Parses an index of an array designator, like `6` in `[6]`.
+fn parse_designator_index(
+    index: Vec<Token>,
+    p_state: &ParsingState,
+    location: &Location,
+    errors: &mut Vec<CompileError>,
+) -> Result<Ast, CompileError> {
+    if index.is_empty() {
+        return Err(location.to_owned().into_failure(
+            "Expected an index in the array designator, but found nothing.".to_owned(),
+        ));
+    }
+    let mut index_p_state = ParsingState::from(*p_state.options());
+    let mut index_node = Ast::Empty;
+    let res = parse_block(&mut index.into_iter(), &mut index_p_state, &mut index_node);
+    errors.extend(res.into_errors());
+    errors.extend(index_p_state.mismatched_error());
+    Ok(index_node)
+}
+
+/// Parses the beginning of an array declarator: `static`, the qualifiers,
+/// and the `*` of `[*]`.
+///
+/// They are only allowed in function parameters.
+fn parse_declarator_prefix(
+    tokens: &mut IntoIter<Token>,
+    is_parameter: bool,
+    location: &Location,
+) -> Result<(bool, Vec<AttributeKeyword>, bool), CompileError> {
+    let mut is_static = false;
+    let mut qualifiers = vec![];
+    while let Some(token) = tokens.as_slice().first() {
+        let TokenValue::Keyword(
+            keyword @ (Keyword::Static
+            | Keyword::Const
+            | Keyword::Volatile
+            | Keyword::Restrict
+            | Keyword::UAtomic),
+        ) = token.get_value()
+        else {
+            break;
+        };
+        if !is_parameter {
+            return Err(token.get_location().to_owned().into_failure(format!(
+                "Found '{keyword}' in array declarator, but it is only allowed in function parameters."
+            )));
+        }
+        if *keyword == Keyword::Static {
+            is_static = true;
+        } else if let KeywordParsing::Attr(qualifier) =
+            KeywordParsing::from((keyword.clone(), false))
+        {
+            qualifiers.push(qualifier);
+        } else {
+            panic!("Never happens: qualifiers are attribute keywords")
+        }
+        tokens.next();
+    }
+    let mut is_star = false;
+    if let [star, close, ..] = tokens.as_slice()
+        && *star.get_value() == TokenValue::Symbol(Symbol::Star)
+        && *close.get_value() == TokenValue::Symbol(Symbol::BracketClose)
+    {
+        if !is_parameter {
+            return Err(location.to_owned().into_failure(
+                "Found '[*]' in array declarator, but variable length arrays of unspecified size are only allowed in function prototypes.".to_owned(),
+            ));
+        }
+        is_star = true;
+        tokens.next();
+    }
+    Ok((is_static, qualifiers, is_star))
+}
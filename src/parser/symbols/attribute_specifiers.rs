@@ -0,0 +1,230 @@
+//! Handler for the attribute specifiers, like `[[nodiscard]]` or
+//! `[[gnu::packed]]`, and for the GNU attributes, like
+//! `__attribute__((packed))`.
+//!
+//! The arguments of the attributes are kept as tokens, as they don't need to
+//! be valid expressions, like in `__attribute__((format(printf, 1, 2)))`.
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+
+use super::super::keyword::sort::PushInNode as _;
+use super::super::parse_content::parse_block;
+use super::super::state::{BlockType, ParsingState};
+use super::super::types::Ast;
+use super::super::types::literal::Attribute;
+use super::super::types::standard_attribute::StandardAttribute;
+use super::builtins::split_arguments;
+use crate::errors::api::{CompileError, Location, Res};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+
+/// Returns the name or the namespace of an attribute.
+///
+/// Keywords are accepted, as in `[[gnu::const]]`.
+fn attribute_token(
+    token: Option<Token>,
+    location: &Location,
+) -> Result<(String, Location), CompileError> {
+    match token.map(Token::into_value_location) {
+        Some((TokenValue::Ident(name), name_location)) => Ok((name, name_location)),
+        Some((TokenValue::Keyword(keyword), name_location)) => {
+            Ok((keyword.to_string(), name_location))
+        }
+        Some((other, other_location)) => {
+            Err(other_location
+                .into_failure(format!("Expected an attribute name, but found {other}.")))
+        }
+        None => Err(location
+            .to_owned()
+            .into_failure("Expected an attribute name, but found nothing.".to_owned())),
+    }
+}
+
+/// Handler for `[[`
+///
+/// Parses the attributes until the `]]`, and pushes them into the current
+/// node.
+pub fn handle_attribute_specifier(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    tokens.next();
+    let raw_attributes = split_arguments(tokens, location.clone(), &BlockType::Bracket)?;
+    if !tokens
+        .next()
+        .is_some_and(|token| *token.get_value() == TokenValue::Symbol(Symbol::BracketClose))
+    {
+        return Res::from(
+            location.into_failure("Expected ']]' at the end of the attributes.".to_owned()),
+        );
+    }
+    push_attributes(current, raw_attributes, None, &location)?;
+    parse_block(tokens, p_state, current)
+}
+
+/// Handler for `__attribute__`
+///
+/// Parses the GNU attributes between the double parenthesis, like in
+/// `__attribute__((packed, aligned(16)))`, if the GNU extensions are enabled.
+/// They are stored in the `gnu` namespace, as they would be written
+/// `[[gnu::packed, gnu::aligned(16)]]`.
+pub fn handle_gnu_attribute(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let msg = "Found a GNU attribute '__attribute__((...))', but this is a GNU extension.";
+    if !p_state.options().allows_gnu_extensions() {
+        return Res::from(
+            location.into_failure(format!("{msg} Please enable the GNU extensions to use it.")),
+        );
+    }
+    let is_open = |token: Option<&Token>| {
+        token.is_some_and(|open| *open.get_value() == TokenValue::Symbol(Symbol::ParenthesisOpen))
+    };
+    if !is_open(tokens.as_slice().first()) || !is_open(tokens.as_slice().get(1)) {
+        return Res::from(location.into_failure(
+            "Expected '((' after '__attribute__', like in '__attribute__((packed))'.".to_owned(),
+        ));
+    }
+    tokens.next();
+    let mut content = capture_balanced(tokens, location.clone())?.into_iter();
+    // inner `(`, checked above
+    content.next();
+    let raw_attributes = split_arguments(&mut content, location.clone(), &BlockType::Parenthesis)?;
+    if let Some(extra) = content.next() {
+        return Res::from(extra.get_location().to_owned().into_failure(format!(
+            "Expected ')' after the GNU attributes, but found {}.",
+            extra.get_value()
+        )));
+    }
+    push_attributes(current, raw_attributes, Some("gnu"), &location)?;
+    p_state.push_warning(location.to_warning(msg.to_owned()));
+    parse_block(tokens, p_state, current)
+}
+
+/// Reads the tokens until the `)` that matches an already read `(`, and
+/// returns the tokens in between.
+///
+/// The tokens are not parsed, but the delimiters must be balanced: `(`, `[`
+/// and `{` must be closed in the right order.
+fn capture_balanced(
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Result<Vec<Token>, CompileError> {
+    let mut blocks = vec![(BlockType::Parenthesis, location)];
+    let mut captured = vec![];
+    for token in tokens.by_ref() {
+        if let TokenValue::Symbol(symbol) = token.get_value() {
+            #[expect(clippy::wildcard_enum_match_arm)]
+            match symbol {
+                Symbol::BraceOpen => {
+                    blocks.push((BlockType::Brace, token.get_location().to_owned()));
+                }
+                Symbol::BracketOpen => {
+                    blocks.push((BlockType::Bracket, token.get_location().to_owned()));
+                }
+                Symbol::ParenthesisOpen => {
+                    blocks.push((BlockType::Parenthesis, token.get_location().to_owned()));
+                }
+                Symbol::BraceClose | Symbol::BracketClose | Symbol::ParenthesisClose => {
+                    let (block, _) = blocks.pop().expect("not empty: returned when empty");
+                    if *symbol != block.get_closing_symbol() {
+                        let (open, close) = block.get_delimiters();
+                        return Err(token.get_location().to_owned().into_failure(format!(
+                            "Mismatched '{open}' in the arguments of an attribute: expected '{close}', but found {symbol:?}."
+                        )));
+                    }
+                    if blocks.is_empty() {
+                        return Ok(captured);
+                    }
+                }
+                _ => (),
+            }
+        }
+        captured.push(token);
+    }
+    let (block, open_location) = blocks.pop().expect("not empty: returned when empty");
+    Err(block.mismatched_err_end(open_location))
+}
+
+/// Parses one attribute, like `nodiscard`, `gnu::packed` or
+/// `deprecated("reason")`.
+///
+/// The arguments are kept as tokens, without being parsed.
+fn parse_attribute(
+    tokens: Vec<Token>,
+    location: &Location,
+) -> Result<StandardAttribute, CompileError> {
+    let mut iter = tokens.into_iter();
+    let (mut name, name_location) = attribute_token(iter.next(), location)?;
+    let mut namespace = None;
+    if iter
+        .as_slice()
+        .first()
+        .is_some_and(|token| *token.get_value() == TokenValue::Symbol(Symbol::DoubleColon))
+    {
+        iter.next();
+        namespace = Some(name);
+        name = attribute_token(iter.next(), &name_location)?.0;
+    }
+    let Some(open) = iter.next() else {
+        return Ok(StandardAttribute {
+            arguments: None,
+            name,
+            namespace,
+        });
+    };
+    let (open_value, open_location) = open.into_value_location();
+    if open_value != TokenValue::Symbol(Symbol::ParenthesisOpen) {
+        return Err(open_location.into_failure(format!(
+            "Expected '(', ',' or ']]' after attribute '{name}', but found {open_value}."
+        )));
+    }
+    let arguments = capture_balanced(&mut iter, open_location)?;
+    if let Some(extra) = iter.next() {
+        return Err(extra.get_location().to_owned().into_failure(format!(
+            "Expected ',' or ']]' after the arguments of attribute '{name}', but found {}.",
+            extra.get_value()
+        )));
+    }
+    Ok(StandardAttribute {
+        arguments: Some(
+            arguments
+                .into_iter()
+                .map(|token| token.into_value_location().0)
+                .collect(),
+        ),
+        name,
+        namespace,
+    })
+}
+
+/// Parses the attributes of a list, and pushes them into the current node.
+///
+/// The `namespace` is given to the attributes that don't have one, like `gnu`
+/// for the GNU attributes.
+fn push_attributes(
+    current: &mut Ast,
+    raw_attributes: Vec<Vec<Token>>,
+    namespace: Option<&str>,
+    location: &Location,
+) -> Result<(), CompileError> {
+    for raw_attribute in raw_attributes {
+        // empty attributes are allowed, as in `[[]]` or `[[a,,b]]`
+        if raw_attribute.is_empty() {
+            continue;
+        }
+        let mut attribute = parse_attribute(raw_attribute, location)?;
+        if attribute.namespace.is_none() {
+            attribute.namespace = namespace.map(str::to_owned);
+        }
+        Attribute::Standard(attribute)
+            .push_in_node(current)
+            .map_err(|err| location.to_failure(err))?;
+    }
+    Ok(())
+}
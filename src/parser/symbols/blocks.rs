@@ -4,17 +4,29 @@ extern crate alloc;
 use alloc::vec::IntoIter;
 use core::mem;
 
+use super::super::keyword::control_flow::is_node_condition_context;
 use super::super::modifiers::list_initialiser::{
-    apply_to_last_list_initialiser, can_push_list_initialiser
+    apply_to_last_list_initialiser, can_push_list_initialiser, distribute_initialiser
 };
 use super::super::parse_content::parse_block;
 use super::super::state::ParsingState;
-use super::super::types::binary::BinaryOperator;
 use super::super::types::braced_blocks::BracedBlock;
-use super::super::types::{Ast, ListInitialiser, ParensBlock};
-use crate::errors::api::{Location, Res};
-use crate::lexer::api::Token;
-use crate::parser::modifiers::functions::{can_make_function, make_function};
+use super::super::types::literal::{Attribute, Literal, Variable, VariableName};
+use super::super::types::unary::UnaryOperator;
+use super::super::types::{
+    Ast, CompoundLiteral, ListInitialiser, ParensBlock, StatementExpression
+};
+use super::arrays::handle_bracket_open;
+use super::attribute_specifiers::handle_attribute_specifier;
+use super::builtins::{
+    block_length, handle_builtin_call, is_followed_by_initialiser, parse_type_name, split_arguments
+};
+use super::function_pointers::{handle_function_pointer, is_function_pointer};
+use crate::errors::api::{CompileError, Location, Res};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+use crate::parser::modifiers::functions::{
+    can_make_function, get_last_builtin, is_function_declaration, make_function
+};
 use crate::parser::state::BlockType;
 
 /// State to indicate what needs to be done
@@ -47,6 +59,7 @@ pub fn blocks_handler(
         // semi-colon
         TodoBlock::SemiColon => {
             handle_semicolon(current);
+            p_state.check_node_budget(tokens.as_slice().first());
             parse_block(tokens, p_state, current)
         }
         // parenthesis
@@ -60,22 +73,14 @@ pub fn blocks_handler(
             p_state.push_closing_block(BlockType::Bracket, location);
             Res::from(())
         }
-        TodoBlock::OpenBracket => {
-            let mut bracket_node = Ast::Empty;
-            parse_block(tokens, p_state, &mut bracket_node)?;
-            if p_state.pop_and_compare_block(&BlockType::Bracket) {
-                if let Err(err) = current.push_op(BinaryOperator::ArraySubscript) {
-                    Res::from(location.into_failure(err))
-                } else {
-                    current
-                        .push_block_as_leaf(bracket_node)
-                        .map_err(|err| location.into_failure(err))?;
-                    parse_block(tokens, p_state, current)
-                }
-            } else {
-                Res::from(BlockType::Bracket.mismatched_err_end(location))
-            }
+        TodoBlock::OpenBracket
+            if tokens.as_slice().first().is_some_and(|token| {
+                *token.get_value() == TokenValue::Symbol(Symbol::BracketOpen)
+            }) =>
+        {
+            handle_attribute_specifier(current, p_state, tokens, location)
         }
+        TodoBlock::OpenBracket => handle_bracket_open(current, p_state, tokens, location),
         // brace
         TodoBlock::CloseBraceBlock
             if apply_to_last_list_initialiser(current, &|_, full| *full = true).is_err() =>
@@ -93,10 +98,13 @@ pub fn blocks_handler(
                     .map_err(|err| location.into_failure(err))?;
                 parse_block(tokens, p_state, current)
             }
-            Ok(false) => handle_brace_block_open(current, tokens, p_state, location),
+            Ok(false) => handle_brace_block_open(current, tokens, p_state, &location),
         },
         // others
-        TodoBlock::CloseBraceBlock => parse_block(tokens, p_state, current),
+        TodoBlock::CloseBraceBlock => {
+            distribute_initialiser(current).map_err(|err| location.into_failure(err))?;
+            parse_block(tokens, p_state, current)
+        }
     }
 }
 
@@ -107,15 +115,19 @@ fn handle_brace_block_open(
     current: &mut Ast,
     tokens: &mut IntoIter<Token>,
     p_state: &mut ParsingState,
-    location: Location,
+    location: &Location,
 ) -> Res<()> {
+    let warning = (p_state.is_header() && is_function_declaration(current)).then(|| {
+        location.to_warning(
+            "Found function definition in header: headers should only contain declarations. Consider moving the body of the function to a source file.".to_owned(),
+        )
+    });
     let mut brace_block = Ast::BracedBlock(BracedBlock::default());
     parse_block(tokens, p_state, &mut brace_block)?;
-    if !p_state.pop_and_compare_block(&BlockType::Brace) {
-        return Res::from(BlockType::Brace.mismatched_err_end(location));
-    }
+    p_state.pop_and_compare_block(&BlockType::Brace, location)?;
     current.push_braced_block(brace_block);
-    parse_block(tokens, p_state, current)
+    p_state.check_node_budget(tokens.as_slice().first());
+    parse_block(tokens, p_state, current).add_err(warning)
 }
 
 /// Handler for `(`
@@ -127,40 +139,211 @@ fn handle_parenthesis_open(
     tokens: &mut IntoIter<Token>,
     location: Location,
 ) -> Res<()> {
-    if can_make_function(current) {
-        let mut arguments_node = Ast::FunctionArgsBuild(vec![Ast::Empty]);
-        parse_block(tokens, p_state, &mut arguments_node)?;
-        if p_state.pop_and_compare_block(&BlockType::Parenthesis) {
-            if let Ast::FunctionArgsBuild(vec) = &mut arguments_node {
-                let mut error = None;
-                if vec.last().is_some_and(|last| *last == Ast::Empty) {
-                    vec.pop();
-                    if !vec.is_empty() {
-                        error = Some(location.to_suggestion(
-                            "Found extra comma in function argument list. Please remove the comma.".to_owned(),
-                        ));
-                    }
-                }
-                make_function(current, mem::take(vec));
-                parse_block(tokens, p_state, current).add_err(error)
-            } else {
-                panic!("a function args build cannot be dismissed as root");
-            }
-        } else {
-            Res::from(BlockType::Parenthesis.mismatched_err_end(location))
-        }
+    if let Some(builtin) = get_last_builtin(current) {
+        handle_builtin_call(current, builtin, p_state, tokens, location)
+    } else if tokens
+        .as_slice()
+        .first()
+        .is_some_and(|token| *token.get_value() == TokenValue::Symbol(Symbol::BraceOpen))
+    {
+        handle_statement_expression(current, p_state, tokens, location)
+    } else if is_function_pointer(current, tokens) {
+        handle_function_pointer(current, p_state, tokens, location)
+    } else if can_make_function(current) {
+        let (arguments, error) = parse_function_arguments(p_state, tokens, &location)?;
+        make_function(current, arguments);
+        parse_block(tokens, p_state, current).add_err(error)
+    } else if !is_node_condition_context(current) && is_followed_by_initialiser(tokens.as_slice()) {
+        handle_compound_literal(current, p_state, tokens, location)
     } else {
         let mut parenthesized_block = Ast::Empty;
-        parse_block(tokens, p_state, &mut parenthesized_block)?;
-        if p_state.pop_and_compare_block(&BlockType::Parenthesis) {
+        p_state.open_parenthesis();
+        let res = parse_block(tokens, p_state, &mut parenthesized_block);
+        p_state.close_parenthesis();
+        res?;
+        p_state.pop_and_compare_block(&BlockType::Parenthesis, &location)?;
+        if let Some(attrs) = get_cast_type(&mut parenthesized_block, tokens) {
             current
-                .push_block_as_leaf(ParensBlock::make_parens_ast(parenthesized_block))
+                .push_op(UnaryOperator::Cast(attrs))
                 .map_err(|err| location.into_failure(err))?;
-            parse_block(tokens, p_state, current)
         } else {
-            Res::from(BlockType::Parenthesis.mismatched_err_end(location))
+            current
+                .push_block_as_leaf(ParensBlock::make_parens_ast(parenthesized_block))
+                .map_err(|err| location.into_failure(err))?;
+        }
+        parse_block(tokens, p_state, current)
+    }
+}
+
+/// Returns the attributes of the type name between the parenthesis, if they
+/// are a cast, like `(int *)` in `(int *)p`.
+///
+/// The parenthesis are a cast if they only contain a type name, without a
+/// variable name, and if they are followed by the beginning of an
+/// expression.
+fn get_cast_type(
+    parenthesized_block: &mut Ast,
+    tokens: &IntoIter<Token>,
+) -> Option<Vec<Attribute>> {
+    let Ast::Leaf(Literal::Variable(Variable {
+        attrs,
+        name: VariableName::Empty,
+        ..
+    })) = parenthesized_block
+    else {
+        return None;
+    };
+    let is_operand = tokens.as_slice().first().is_some_and(|token| {
+        matches!(
+            token.get_value(),
+            TokenValue::Char(..)
+                | TokenValue::Ident(_)
+                | TokenValue::Keyword(_)
+                | TokenValue::Number(_)
+                | TokenValue::Str(..)
+                | TokenValue::Symbol(
+                    Symbol::Ampersand
+                        | Symbol::BitwiseNot
+                        | Symbol::Decrement
+                        | Symbol::Increment
+                        | Symbol::LogicalNot
+                        | Symbol::Minus
+                        | Symbol::ParenthesisOpen
+                        | Symbol::Plus
+                        | Symbol::Star
+                )
+        )
+    });
+    (is_operand && !attrs.is_empty()).then(|| mem::take(attrs))
+}
+
+/// Handler for the `(` of a compound literal, like `(int[]){1, 2, 3}`
+///
+/// The parenthesis must contain a type name, as an expression in parenthesis
+/// can't be followed by a list initialiser. The list initialiser is parsed on
+/// its own, as it belongs to the compound literal.
+fn handle_compound_literal(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let mut content = split_arguments(tokens, location.clone(), &BlockType::Parenthesis)?;
+    let mut errors = vec![];
+    let parsed_type = match content.pop() {
+        Some(raw_type) if content.is_empty() => {
+            parse_type_name(raw_type, &location, p_state.options(), &mut errors).ok()
+        }
+        Some(_) | None => None,
+    };
+    let Some(type_name) = parsed_type else {
+        return Res::from(location.into_failure(
+            "Found a list initialiser after an expression in parenthesis, but only a type name can be followed by a list initialiser, like in '(int[]){1, 2, 3}'.".to_owned(),
+        ));
+    };
+    let brace_location = tokens
+        .next()
+        .map_or(location, |brace| brace.get_location().to_owned());
+    let Some(length) = block_length(tokens.as_slice(), &BlockType::Brace) else {
+        return Res::from(BlockType::Brace.mismatched_err_end(brace_location));
+    };
+    let raw_initialiser: Vec<Token> = tokens.by_ref().take(length).collect();
+    tokens.next();
+    let mut initialiser = Ast::ListInitialiser(ListInitialiser::default());
+    let mut initialiser_p_state = ParsingState::from(*p_state.options());
+    let initialiser_res = parse_block(
+        &mut raw_initialiser.into_iter(),
+        &mut initialiser_p_state,
+        &mut initialiser,
+    );
+    errors.extend(initialiser_res.into_errors());
+    errors.extend(initialiser_p_state.mismatched_error());
+    if errors.iter().any(CompileError::is_error) {
+        return Res::from_errors(errors);
+    }
+    let Ast::ListInitialiser(mut list) = initialiser else {
+        panic!("a list initialiser cannot be dismissed as root");
+    };
+    list.full = true;
+    current
+        .push_block_as_leaf(Ast::CompoundLiteral(CompoundLiteral {
+            initialiser: list,
+            type_name: Box::new(type_name),
+        }))
+        .map_err(|err| brace_location.into_failure(err))?;
+    let res = parse_block(tokens, p_state, current);
+    errors
+        .into_iter()
+        .fold(res, |acc, error| acc.add_err(Some(error)))
+}
+
+/// Parses the arguments of a function, after the `(`.
+///
+/// A suggestion is returned if the arguments end with an extra comma.
+pub fn parse_function_arguments(
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: &Location,
+) -> Res<(Vec<Ast>, Option<CompileError>)> {
+    let mut arguments_node = Ast::FunctionArgsBuild(vec![Ast::Empty]);
+    parse_block(tokens, p_state, &mut arguments_node)?;
+    p_state.pop_and_compare_block(&BlockType::Parenthesis, location)?;
+    let Ast::FunctionArgsBuild(mut arguments) = arguments_node else {
+        panic!("a function args build cannot be dismissed as root");
+    };
+    let mut error = None;
+    if arguments.last().is_some_and(|last| *last == Ast::Empty) {
+        arguments.pop();
+        if !arguments.is_empty() {
+            error = Some(location.to_suggestion(
+                "Found extra comma in function argument list. Please remove the comma.".to_owned(),
+            ));
         }
     }
+    Res::from((arguments, error))
+}
+
+/// Handler for `({`
+///
+/// Parses a GNU statement expression, if the GNU extensions are enabled.
+fn handle_statement_expression(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let msg = "Found a statement expression '({ ... })', but this is a GNU extension.";
+    if !p_state.options().allows_gnu_extensions() {
+        return Res::from(
+            location.into_failure(format!("{msg} Please enable the GNU extensions to use it.")),
+        );
+    }
+    p_state.push_warning(location.to_warning(msg.to_owned()));
+    let brace_location = tokens
+        .next()
+        .expect("checked by caller")
+        .into_value_location()
+        .1;
+    let mut brace_block = Ast::BracedBlock(BracedBlock::default());
+    p_state.open_parenthesis();
+    let res = parse_block(tokens, p_state, &mut brace_block);
+    p_state.close_parenthesis();
+    res?;
+    p_state.pop_and_compare_block(&BlockType::Brace, &brace_location)?;
+    if !tokens
+        .next()
+        .is_some_and(|token| *token.get_value() == TokenValue::Symbol(Symbol::ParenthesisClose))
+    {
+        return Res::from(BlockType::Parenthesis.mismatched_err_end(location));
+    }
+    let Ast::BracedBlock(mut block) = brace_block else {
+        panic!("never happens: a braced block cannot be dismissed as root")
+    };
+    block.full = true;
+    current
+        .push_block_as_leaf(Ast::StatementExpression(StatementExpression(block)))
+        .map_err(|err| location.into_failure(err))?;
+    parse_block(tokens, p_state, current)
 }
 
 /// Handler for `;`
@@ -171,12 +354,11 @@ fn handle_semicolon(current: &mut Ast) {
         && !*full
     {
         elts.push(Ast::Empty);
-    } else if *current != Ast::Empty {
+    } else {
+        // an empty statement is kept, like the empty clauses of `for (;;)`
         *current = Ast::BracedBlock(BracedBlock {
             elts: vec![mem::take(current), Ast::Empty],
             full: false,
         });
-    } else {
-        /* last is empty: nothing to be done */
     }
 }
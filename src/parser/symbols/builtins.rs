@@ -0,0 +1,360 @@
+//! Handler for calls to builtin functions that take types as arguments.
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+use core::mem;
+
+use super::super::keyword::sort::KeywordParsing;
+use super::super::modifiers::functions::make_builtin_call;
+use super::super::parse_content::parse_block;
+use super::super::state::{BlockType, ParsingState};
+use super::super::types::Ast;
+use super::super::types::builtin::{ArgumentKind, Builtin, BuiltinArgument, TypeName};
+use super::super::types::literal::{Attribute, Variable};
+use crate::errors::api::{CompileError, Location, Res};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+use crate::options::api::Options;
+use crate::parser::keyword::control_flow::keyword::ControlFlowKeyword;
+
+/// Returns the number of tokens before the symbol that closes the block that
+/// was just opened, like the `)` of a `(`.
+pub fn block_length(tokens: &[Token], block_type: &BlockType) -> Option<usize> {
+    let closing = TokenValue::Symbol(block_type.get_closing_symbol());
+    let mut depth: usize = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match token.get_value() {
+            value if depth == 0 && *value == closing => return Some(idx),
+            TokenValue::Symbol(
+                Symbol::BraceOpen | Symbol::ParenthesisOpen | Symbol::BracketOpen,
+            ) => depth = depth.saturating_add(1),
+            TokenValue::Symbol(
+                Symbol::BraceClose | Symbol::ParenthesisClose | Symbol::BracketClose,
+            ) => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Handler for `(` after the name of a builtin
+///
+/// Splits the arguments on the top-level commas, and parses each one either as
+/// an expression or as a type name, depending on the builtin.
+pub fn handle_builtin_call(
+    current: &mut Ast,
+    builtin: Builtin,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let raw_args = split_arguments(tokens, location.clone(), &BlockType::Parenthesis)?;
+    let kinds = builtin.arguments();
+    if raw_args.len() != kinds.len() {
+        return Res::from(location.into_failure(format!(
+            "Wrong number of arguments for builtin: expected {}, but found {}.",
+            kinds.len(),
+            raw_args.len()
+        )));
+    }
+    let mut args = Vec::with_capacity(kinds.len());
+    let mut errors = vec![];
+    for (raw_arg, kind) in raw_args.into_iter().zip(kinds) {
+        match kind {
+            ArgumentKind::Expression => {
+                if raw_arg.is_empty() {
+                    return Res::from(
+                        location
+                            .into_failure("Expected an expression, but found nothing.".to_owned()),
+                    );
+                }
+                args.push(BuiltinArgument::Expression(parse_expression(
+                    raw_arg,
+                    p_state.options(),
+                    &mut errors,
+                )));
+            }
+            ArgumentKind::Type => {
+                args.push(BuiltinArgument::Type(parse_type_name(
+                    raw_arg,
+                    &location,
+                    p_state.options(),
+                    &mut errors,
+                )?));
+            }
+        }
+    }
+    if errors.iter().any(CompileError::is_error) {
+        return Res::from_errors(errors);
+    }
+    make_builtin_call(current, builtin, args);
+    let res = parse_block(tokens, p_state, current);
+    errors
+        .into_iter()
+        .fold(res, |acc, error| acc.add_err(Some(error)))
+}
+
+/// Checks if the parenthesis that were just opened are followed by a list
+/// initialiser, like in `(int[]){1, 2, 3}`.
+pub fn is_followed_by_initialiser(tokens: &[Token]) -> bool {
+    block_length(tokens, &BlockType::Parenthesis)
+        .and_then(|length| tokens.get(length.checked_add(1)?))
+        .is_some_and(|token| *token.get_value() == TokenValue::Symbol(Symbol::BraceOpen))
+}
+
+/// Parses the declarator of a type name, after the specifiers: the array
+/// sizes, like `[3]`, or the parameters of a function pointer, like
+/// `(*)(void)`.
+fn parse_abstract_declarator(
+    base: TypeName,
+    tokens: &mut IntoIter<Token>,
+    options: &Options,
+    errors: &mut Vec<CompileError>,
+) -> Result<TypeName, CompileError> {
+    let Some(token) = tokens.next() else {
+        return Ok(base);
+    };
+    let (value, location) = token.into_value_location();
+    match value {
+        TokenValue::Symbol(Symbol::BracketOpen) => {
+            let mut raw_sizes = vec![];
+            let mut next_location = location;
+            loop {
+                let mut raw_size =
+                    split_arguments(tokens, next_location.clone(), &BlockType::Bracket)?;
+                if raw_size.len() > 1 {
+                    return Err(next_location
+                        .into_failure("Expected an array size, but found a comma.".to_owned()));
+                }
+                raw_sizes.push(raw_size.pop().unwrap_or_default());
+                let Some(next) = tokens.next() else { break };
+                let (next_value, location_bis) = next.into_value_location();
+                if next_value != TokenValue::Symbol(Symbol::BracketOpen) {
+                    return Err(location_bis.into_failure(format!(
+                        "Expected end of type name, but found {next_value}."
+                    )));
+                }
+                next_location = location_bis;
+            }
+            Ok(raw_sizes
+                .into_iter()
+                .rev()
+                .fold(base, |elements, raw_size| {
+                    let size = (!raw_size.is_empty()).then(|| {
+                        let mut size_p_state = ParsingState::from(*options);
+                        let mut size = Ast::Empty;
+                        let res =
+                            parse_block(&mut raw_size.into_iter(), &mut size_p_state, &mut size);
+                        errors.extend(res.into_errors());
+                        errors.extend(size_p_state.mismatched_error());
+                        size
+                    });
+                    TypeName::Array(Box::new(elements), size)
+                }))
+        }
+        TokenValue::Symbol(Symbol::ParenthesisOpen) => {
+            let pointer = split_arguments(tokens, location.clone(), &BlockType::Parenthesis)?;
+            let is_pointer = pointer.len() == 1
+                && pointer.first().is_some_and(|inner| {
+                    inner.len() == 1
+                        && inner.first().is_some_and(|star| {
+                            *star.get_value() == TokenValue::Symbol(Symbol::Star)
+                        })
+                });
+            if !is_pointer {
+                return Err(location.into_failure(
+                    "Expected '(*)' for a function pointer in type name.".to_owned(),
+                ));
+            }
+            let Some(open) = tokens.next() else {
+                return Err(location.into_failure(
+                    "Expected the parameters of the function pointer in type name.".to_owned(),
+                ));
+            };
+            let (open_value, open_location) = open.into_value_location();
+            if open_value != TokenValue::Symbol(Symbol::ParenthesisOpen) {
+                return Err(open_location.into_failure(format!(
+                    "Expected the parameters of the function pointer in type name, but found {open_value}."
+                )));
+            }
+            let params = split_arguments(tokens, open_location.clone(), &BlockType::Parenthesis)?
+                .into_iter()
+                .map(|param| parse_type_name(param, &open_location, options, errors))
+                .collect::<Result<_, _>>()?;
+            if let Some(extra) = tokens.next() {
+                return Err(extra.get_location().to_owned().into_failure(format!(
+                    "Expected end of type name, but found {}.",
+                    extra.get_value()
+                )));
+            }
+            Ok(TypeName::FunctionPointer(Box::new(base), params))
+        }
+        TokenValue::Char(..)
+        | TokenValue::Comment(_)
+        | TokenValue::Ident(_)
+        | TokenValue::Keyword(_)
+        | TokenValue::Newline
+        | TokenValue::Number(_)
+        | TokenValue::Str(..)
+        | TokenValue::Symbol(_)
+        | TokenValue::Whitespace(_) => {
+            Err(location.into_failure("Expected a type name, but found an expression.".to_owned()))
+        }
+    }
+}
+
+/// Parses the tokens of an expression, like an argument of a builtin, into an
+/// [`Ast`].
+pub fn parse_expression(
+    tokens: Vec<Token>,
+    options: &Options,
+    errors: &mut Vec<CompileError>,
+) -> Ast {
+    let mut p_state = ParsingState::from(*options);
+    let mut node = Ast::Empty;
+    let res = parse_block(&mut tokens.into_iter(), &mut p_state, &mut node);
+    errors.extend(res.into_errors());
+    errors.extend(p_state.mismatched_error());
+    node
+}
+
+/// Parses a type name, like `const struct S *` or `int (*)(void)`.
+///
+/// The specifiers and qualifiers are stored in a [`Variable`] without name,
+/// as they would be for a declaration.
+pub fn parse_type_name(
+    tokens: Vec<Token>,
+    location: &Location,
+    options: &Options,
+    errors: &mut Vec<CompileError>,
+) -> Result<TypeName, CompileError> {
+    let mut variable = Variable::default();
+    let mut iter = tokens.into_iter();
+    while let Some(token) = iter.as_slice().first() {
+        match token.get_value() {
+            TokenValue::Symbol(Symbol::Star) => variable.push_attr(Attribute::Indirection),
+            TokenValue::Ident(name) => variable.push_attr(Attribute::User(name.to_owned())),
+            TokenValue::Keyword(keyword) => match KeywordParsing::from((keyword.clone(), false)) {
+                KeywordParsing::Attr(attr) => variable.push_keyword(attr),
+                KeywordParsing::CtrlFlow(
+                    ctrl @ (ControlFlowKeyword::Struct
+                    | ControlFlowKeyword::Union
+                    | ControlFlowKeyword::Enum),
+                ) => {
+                    let keyword_location = token.get_location().to_owned();
+                    iter.next();
+                    let Some(TokenValue::Ident(name)) =
+                        iter.as_slice().first().map(Token::get_value)
+                    else {
+                        return Err(keyword_location.into_failure(format!(
+                            "Expected a name after '{ctrl}' in type name."
+                        )));
+                    };
+                    variable.push_attr(Attribute::Tag(ctrl, name.to_owned()));
+                }
+                KeywordParsing::CtrlFlow(_)
+                | KeywordParsing::False
+                | KeywordParsing::Func(_)
+                | KeywordParsing::Nullptr
+                | KeywordParsing::True => {
+                    return Err(token
+                        .get_location()
+                        .to_owned()
+                        .into_failure(format!("Expected a type name, but found '{keyword}'.")));
+                }
+            },
+            TokenValue::Symbol(Symbol::BracketOpen | Symbol::ParenthesisOpen)
+                if !variable.attrs.is_empty() =>
+            {
+                break;
+            }
+            TokenValue::Char(..)
+            | TokenValue::Comment(_)
+            | TokenValue::Newline
+            | TokenValue::Number(_)
+            | TokenValue::Str(..)
+            | TokenValue::Symbol(_)
+            | TokenValue::Whitespace(_) => {
+                return Err(token
+                    .get_location()
+                    .to_owned()
+                    .into_failure("Expected a type name, but found an expression.".to_owned()));
+            }
+        }
+        iter.next();
+    }
+    if variable.attrs.is_empty() {
+        Err(location
+            .to_owned()
+            .into_failure("Expected a type name, but found nothing.".to_owned()))
+    } else {
+        parse_abstract_declarator(TypeName::Variable(variable), &mut iter, options, errors)
+    }
+}
+
+/// Checks if the tokens start like a type name, with a type keyword, like
+/// `const` or `int`, or with `struct`, `union` or `enum`.
+///
+/// The names defined with `typedef` aren't known by the parser, so an
+/// identifier is read as an expression.
+pub fn starts_type_name(tokens: &[Token]) -> bool {
+    tokens.first().is_some_and(|token| {
+        if let TokenValue::Keyword(keyword) = token.get_value() {
+            matches!(
+                KeywordParsing::from((keyword.clone(), false)),
+                KeywordParsing::Attr(_)
+                    | KeywordParsing::CtrlFlow(
+                        ControlFlowKeyword::Struct
+                            | ControlFlowKeyword::Union
+                            | ControlFlowKeyword::Enum
+                    )
+            )
+        } else {
+            false
+        }
+    })
+}
+
+/// Reads the tokens until the closing symbol of the block, and splits them on
+/// the top-level commas.
+pub fn split_arguments(
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+    block_type: &BlockType,
+) -> Result<Vec<Vec<Token>>, CompileError> {
+    let closing = TokenValue::Symbol(block_type.get_closing_symbol());
+    let mut args = vec![];
+    let mut current_arg = vec![];
+    let mut depth: usize = 0;
+    for token in tokens.by_ref() {
+        match token.get_value() {
+            value if depth == 0 && *value == closing => {
+                if !current_arg.is_empty() || !args.is_empty() {
+                    args.push(current_arg);
+                }
+                return Ok(args);
+            }
+            TokenValue::Symbol(Symbol::Comma) if depth == 0 => {
+                args.push(mem::take(&mut current_arg));
+                continue;
+            }
+            TokenValue::Symbol(
+                Symbol::BraceOpen | Symbol::ParenthesisOpen | Symbol::BracketOpen,
+            ) => depth = depth.saturating_add(1),
+            TokenValue::Symbol(
+                Symbol::BraceClose | Symbol::ParenthesisClose | Symbol::BracketClose,
+            ) => depth = depth.saturating_sub(1),
+            TokenValue::Char(..)
+            | TokenValue::Comment(_)
+            | TokenValue::Ident(_)
+            | TokenValue::Keyword(_)
+            | TokenValue::Newline
+            | TokenValue::Number(_)
+            | TokenValue::Str(..)
+            | TokenValue::Symbol(_)
+            | TokenValue::Whitespace(_) => (),
+        }
+        current_arg.push(token);
+    }
+    Err(block_type.mismatched_err_end(location))
+}
@@ -0,0 +1,247 @@
+//! Handler for the GNU case ranges, like `case 1 ... 3:`.
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+use core::mem;
+
+use super::super::keyword::control_flow::keyword::ControlFlowKeyword;
+use super::super::keyword::control_flow::node::ControlFlowNode;
+use super::super::modifiers::constant::constant_value;
+use super::super::parse_content::parse_block;
+use super::super::state::ParsingState;
+use super::super::types::braced_blocks::BracedBlock;
+use super::super::types::{Ast, CaseRange};
+use super::handlers::has_open_ternary;
+use crate::errors::api::{CompileError, ErrorLevel, Location, Res};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+
+/// Previous `case` of a `switch`, with the values it matches.
+struct PreviousCase {
+    /// Last value matched by the `case`
+    hi: i128,
+    /// First value matched by the `case`
+    lo: i128,
+    /// Whether the `case` is a case range.
+    range: bool,
+    /// Value of the `case`, as written
+    value: String,
+}
+
+/// Returns the first and the last values matched by a `case`, if they are
+/// constants.
+fn case_bounds(value: &Ast) -> Option<(i128, i128)> {
+    if let Ast::CaseRange(CaseRange { hi, lo }) = value {
+        Some((
+            constant_value(lo, &|_| None)?,
+            constant_value(hi, &|_| None)?,
+        ))
+    } else {
+        let constant = constant_value(value, &|_| None)?;
+        Some((constant, constant))
+    }
+}
+
+/// Reports a `case` whose value is matched by a previous case range, like `2`
+/// in `case 1 ... 3: break; case 2:`.
+///
+/// This is called on the `:` of a `case`, before it is pushed.
+pub fn check_case_value(current: &mut Ast, p_state: &mut ParsingState, location: &Location) {
+    let Some(open) = open_case_value(current) else {
+        return;
+    };
+    if matches!(open, Ast::CaseRange(_)) || has_open_ternary(open) {
+        return;
+    }
+    let Some(constant) = constant_value(open, &|_| None) else {
+        return;
+    };
+    // the value is taken to not be collected as a previous case
+    let value = mem::take(open);
+    let mut cases = vec![];
+    collect_cases(current, &mut cases);
+    for previous in cases {
+        if previous.range && previous.lo <= constant && constant <= previous.hi {
+            p_state.push_warning(location.to_warning(format!(
+                "Found a duplicate case '{value}': its value is already matched by the previous case range '{}'.",
+                previous.value
+            )));
+        }
+    }
+    if let Some(restored) = open_case_value(current) {
+        *restored = value;
+    }
+}
+
+/// Collects the `case` labels of a `switch` whose values are constants.
+///
+/// The labels are pushed in the statement of the previous label, like in
+/// `case 1: case 2: x;`, so the statements are searched. The closed blocks
+/// are not, as the `case` labels of a nested `switch` are in its block.
+fn collect_cases(node: &Ast, cases: &mut Vec<PreviousCase>) {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::BracedBlock(BracedBlock { elts, full: false }) => {
+            for elt in elts {
+                collect_cases(elt, cases);
+            }
+        }
+        Ast::ControlFlow(ControlFlowNode::ValueColonAst(
+            ControlFlowKeyword::Case,
+            value,
+            statement,
+        )) => {
+            if let Some((lo, hi)) = case_bounds(value) {
+                cases.push(PreviousCase {
+                    hi,
+                    lo,
+                    range: matches!(**value, Ast::CaseRange(_)),
+                    value: value.to_string(),
+                });
+            }
+            if let Some(child) = statement {
+                collect_cases(child, cases);
+            }
+        }
+        Ast::ControlFlow(ControlFlowNode::ColonAst(_, Some(child))) => collect_cases(child, cases),
+        _ => (),
+    }
+}
+
+/// Handler for the `...` of a case range, like in `case 1 ... 3:`
+///
+/// The first value of the range is the value of the `case` that is being
+/// parsed, and the last value is read until the `:` of the `case`. When the
+/// bounds are constants, the first value can't be greater than the last one,
+/// and a range that overlaps a previous `case` of the `switch` is reported.
+pub fn handle_case_range(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let msg = "Found a case range 'case first ... last:', but this is a GNU extension.";
+    if !p_state.options().allows_gnu_extensions() {
+        return Res::from(
+            location.into_failure(format!("{msg} Please enable the GNU extensions to use it.")),
+        );
+    }
+    let warning = location.to_warning(msg.to_owned());
+    if p_state.options().is_pedantic() {
+        return Res::from(warning.promote_to(ErrorLevel::Failure));
+    }
+    let lo = open_case_value(current).map(mem::take).unwrap_or_default();
+    if lo == Ast::Empty {
+        return Res::from(
+            location.into_failure(
+                "Expected the first value of the case range before '...'.".to_owned(),
+            ),
+        );
+    }
+    let Some(hi_tokens) = take_last_value(tokens) else {
+        return Res::from(
+            location
+                .into_failure("Expected ':' after the last value of the case range.".to_owned()),
+        );
+    };
+    if hi_tokens.is_empty() {
+        return Res::from(
+            location
+                .into_failure("Expected the last value of the case range after '...'.".to_owned()),
+        );
+    }
+    let mut errors = vec![warning];
+    let mut hi_p_state = ParsingState::from(*p_state.options());
+    let mut hi = Ast::Empty;
+    let res = parse_block(&mut hi_tokens.into_iter(), &mut hi_p_state, &mut hi);
+    errors.extend(res.into_errors());
+    errors.extend(hi_p_state.mismatched_error());
+    if errors.iter().any(CompileError::is_error) {
+        return Res::from_errors(errors);
+    }
+    let range = CaseRange {
+        hi: Box::new(hi),
+        lo: Box::new(lo),
+    };
+    if let (Some(lo_value), Some(hi_value)) = (
+        constant_value(&range.lo, &|_| None),
+        constant_value(&range.hi, &|_| None),
+    ) {
+        if lo_value > hi_value {
+            return Res::from(location.into_failure(format!(
+                "Found an empty case range '{range}': the first value {lo_value} is greater than the last value {hi_value}."
+            )));
+        }
+        let mut cases = vec![];
+        collect_cases(current, &mut cases);
+        for previous in cases {
+            if previous.lo <= hi_value && lo_value <= previous.hi {
+                errors.push(location.to_warning(format!(
+                    "Found a case range '{range}' that overlaps the previous case '{}': the values of both cases are matched by the first one.",
+                    previous.value
+                )));
+            }
+        }
+    }
+    if let Some(value) = open_case_value(current) {
+        *value = Ast::CaseRange(range);
+    }
+    for error in errors {
+        p_state.push_warning(error);
+    }
+    parse_block(tokens, p_state, current)
+}
+
+/// Returns the value of the `case` that is being parsed, before its `:`.
+pub fn open_case_value(node: &mut Ast) -> Option<&mut Ast> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::ControlFlow(ControlFlowNode::ValueColonAst(ControlFlowKeyword::Case, value, None)) => {
+            Some(value)
+        }
+        Ast::ControlFlow(
+            ControlFlowNode::ColonAst(_, Some(statement))
+            | ControlFlowNode::ValueColonAst(_, _, Some(statement)),
+        ) => open_case_value(statement),
+        Ast::BracedBlock(BracedBlock { elts, full: false }) => {
+            elts.last_mut().and_then(open_case_value)
+        }
+        _ => None,
+    }
+}
+
+/// Takes the tokens of the last value of a case range, until the `:` of the
+/// `case`.
+///
+/// The `:` of a conditional operator, like in `case 1 ... x ? 2 : 3:`, doesn't
+/// end the value. Returns [`None`] if the `:` of the `case` wasn't found.
+fn take_last_value(tokens: &mut IntoIter<Token>) -> Option<Vec<Token>> {
+    let mut depth: usize = 0;
+    let mut ternaries: usize = 0;
+    let end = tokens.as_slice().iter().position(|token| {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match token.get_value() {
+            TokenValue::Symbol(Symbol::ParenthesisOpen | Symbol::BracketOpen) => {
+                depth = depth.saturating_add(1);
+            }
+            TokenValue::Symbol(Symbol::ParenthesisClose | Symbol::BracketClose) => {
+                depth = depth.saturating_sub(1);
+            }
+            TokenValue::Symbol(Symbol::Interrogation) => ternaries = ternaries.saturating_add(1),
+            TokenValue::Symbol(Symbol::Colon) if depth == 0 => {
+                if ternaries == 0 {
+                    return true;
+                }
+                ternaries = ternaries.saturating_sub(1);
+            }
+            TokenValue::Symbol(Symbol::SemiColon | Symbol::BraceOpen | Symbol::BraceClose) => {
+                return true;
+            }
+            _ => (),
+        }
+        false
+    })?;
+    if *tokens.as_slice().get(end)?.get_value() != TokenValue::Symbol(Symbol::Colon) {
+        return None;
+    }
+    Some(tokens.by_ref().take(end).collect())
+}
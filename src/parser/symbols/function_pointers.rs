@@ -0,0 +1,198 @@
+//! Handler for the parenthesized declarators of function pointers, like
+//! `void (*fp)(void)` or `int (*const fps[3])(int)`, and of pointers to
+//! arrays, like `int (*a)[10]`.
+
+extern crate alloc;
+use alloc::vec::IntoIter;
+use core::mem;
+
+use super::super::keyword::attributes::{AttributeKeyword, Qualifiers, SpecialAttributes};
+use super::super::keyword::sort::{KeywordParsing, PushInNode as _};
+use super::super::modifiers::functions::get_last_variable;
+use super::super::parse_content::parse_block;
+use super::super::state::{BlockType, ParsingState};
+use super::super::types::Ast;
+use super::super::types::literal::{Attribute, Literal, Variable, VariableName};
+use super::blocks::parse_function_arguments;
+use super::builtins::split_arguments;
+use crate::errors::api::{CompileError, Location, Res};
+use crate::lexer::api::{Symbol, Token, TokenValue};
+
+/// Handler for the `(` that opens the declarator of a function pointer or of
+/// a pointer to an array
+///
+/// The declarator, between the parenthesis, contains the pointers with their
+/// qualifiers, and the name of the variable. The qualifiers are attached to
+/// the pointer, and not to the function: in `void (*const fp)(void)`, `fp`
+/// is a constant pointer to a function.
+///
+/// The attributes of the variable are stored in the reading order: the
+/// return type, the function type with its parameters, then the pointers.
+/// In the same way, `int (*a)[10]`, a pointer to an array of 10 integers, is
+/// stored as `int [10] *`, whereas `int *a[10]`, an array of 10 pointers, is
+/// the array subscript of `int *`.
+pub fn handle_function_pointer(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let mut declarator = split_arguments(tokens, location.clone(), &BlockType::Parenthesis)?;
+    if declarator.len() != 1 {
+        return Res::from(location.into_failure(
+            "Expected one declarator between the parenthesis of the function pointer, but found a comma.".to_owned(),
+        ));
+    }
+    let (declarator_attrs, errors) = match tokens.next().map(Token::into_value_location) {
+        Some((TokenValue::Symbol(Symbol::ParenthesisOpen), open_location)) => {
+            let (params, error) = parse_function_arguments(p_state, tokens, &open_location)?;
+            (
+                vec![Attribute::Function(params)],
+                error.into_iter().collect(),
+            )
+        }
+        Some((TokenValue::Symbol(Symbol::BracketOpen), open_location)) => {
+            parse_array_sizes(p_state, tokens, open_location)?
+        }
+        _ => {
+            return Res::from(location.into_failure(
+                "Expected the parameters of a function or the size of an array after the parenthesized declarator.".to_owned(),
+            ));
+        }
+    };
+    let Some(variable) = get_last_variable(current) else {
+        panic!("Never happens: checked by is_function_pointer")
+    };
+    if let Ast::Leaf(Literal::Variable(Variable { attrs, .. })) = variable
+        && matches!(declarator_attrs.first(), Some(Attribute::Function(_)))
+        && attrs.contains(&Attribute::Keyword(AttributeKeyword::SpecialAttributes(
+            SpecialAttributes::UNoreturn,
+        )))
+    {
+        return Res::from(location.into_failure(
+            "Found '_Noreturn' applied to a function pointer, but it can only be applied to function declarations.".to_owned(),
+        ));
+    }
+    for attr in declarator_attrs {
+        attr.push_in_node(variable)
+            .map_err(|err| location.to_failure(err))?;
+    }
+    let mut inner = declarator.pop().unwrap_or_default().into_iter();
+    while let Some(token) = inner.as_slice().first() {
+        let token_location = token.get_location().to_owned();
+        match token.get_value() {
+            TokenValue::Symbol(Symbol::Star) => Attribute::Indirection.push_in_node(variable),
+            TokenValue::Keyword(keyword) => match KeywordParsing::from((keyword.clone(), false)) {
+                KeywordParsing::Attr(
+                    qualifier @ (AttributeKeyword::Qualifiers(
+                        Qualifiers::Const | Qualifiers::Volatile,
+                    )
+                    | AttributeKeyword::SpecialAttributes(
+                        SpecialAttributes::Restrict | SpecialAttributes::UAtomic,
+                    )),
+                ) => qualifier.push_in_node(variable),
+                KeywordParsing::Attr(_)
+                | KeywordParsing::CtrlFlow(_)
+                | KeywordParsing::False
+                | KeywordParsing::Func(_)
+                | KeywordParsing::Nullptr
+                | KeywordParsing::True => Err(format!(
+                    "Expected a pointer, a qualifier or a name in the declarator of the function pointer, but found '{keyword}'."
+                )),
+            },
+            TokenValue::Ident(name) => {
+                if let Ast::Leaf(Literal::Variable(var)) = variable {
+                    var.push_name(VariableName::UserDefined(name.to_owned()))
+                        .map_err(|err| token_location.to_failure(err))?;
+                    var.location = Some(token_location);
+                }
+                inner.next();
+                break;
+            }
+            TokenValue::Char(..)
+            | TokenValue::Comment(_)
+            | TokenValue::Newline
+            | TokenValue::Number(_)
+            | TokenValue::Str(..)
+            | TokenValue::Symbol(_)
+            | TokenValue::Whitespace(_) => break,
+        }
+        .map_err(|err| token_location.into_failure(err))?;
+        inner.next();
+    }
+    // the rest of the declarator, like the size of an array of function
+    // pointers, is parsed as if it were written after the name
+    *tokens = inner
+        .chain(mem::take(tokens))
+        .collect::<Vec<_>>()
+        .into_iter();
+    errors
+        .into_iter()
+        .fold(parse_block(tokens, p_state, current), |acc, error| {
+            acc.add_err(Some(error))
+        })
+}
+
+/// Checks if a `(` opens the declarator of a function pointer, like in
+/// `void (*fp)(void)`, or of a pointer to an array, like in `int (*a)[10]`.
+///
+/// This is the case if the last variable of the [`Ast`] only has type
+/// specifiers, and if the `(` is followed by a `*`.
+pub fn is_function_pointer(current: &mut Ast, tokens: &IntoIter<Token>) -> bool {
+    tokens
+        .as_slice()
+        .first()
+        .is_some_and(|token| *token.get_value() == TokenValue::Symbol(Symbol::Star))
+        && matches!(
+            get_last_variable(current),
+            Some(Ast::Leaf(Literal::Variable(Variable {
+                attrs,
+                name: VariableName::Empty,
+                ..
+            }))) if !attrs.is_empty()
+        )
+}
+
+/// Parses the sizes of the arrays after a parenthesized declarator, like
+/// `[2][3]` in `int (*a)[2][3]`.
+///
+/// The sizes are returned in the reading order of the attributes, from the
+/// innermost array to the outermost: `[3]` then `[2]`.
+fn parse_array_sizes(
+    p_state: &ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Result<(Vec<Attribute>, Vec<CompileError>), CompileError> {
+    let mut sizes = vec![];
+    let mut errors = vec![];
+    let mut next_location = location;
+    loop {
+        let mut raw_size = split_arguments(tokens, next_location.clone(), &BlockType::Bracket)?;
+        if raw_size.len() > 1 {
+            return Err(
+                next_location.into_failure("Expected an array size, but found a comma.".to_owned())
+            );
+        }
+        let raw = raw_size.pop().unwrap_or_default();
+        let size = if raw.is_empty() {
+            None
+        } else {
+            let mut size_p_state = ParsingState::from(*p_state.options());
+            let mut size = Ast::Empty;
+            let res = parse_block(&mut raw.into_iter(), &mut size_p_state, &mut size);
+            errors.extend(res.into_errors());
+            errors.extend(size_p_state.mismatched_error());
+            Some(Box::new(size))
+        };
+        sizes.push(Attribute::Array(size));
+        match tokens.as_slice().first() {
+            Some(token) if *token.get_value() == TokenValue::Symbol(Symbol::BracketOpen) => {
+                token.get_location().clone_into(&mut next_location);
+                tokens.next();
+            }
+            _ => break,
+        }
+    }
+    sizes.reverse();
+    Ok((sizes, errors))
+}
@@ -1,10 +1,15 @@
 //! Handlers to be called when a symbol can represent by multiple operator.
 
+use core::mem;
+
+use super::super::keyword::control_flow::node::ControlFlowNode;
+use super::super::modifiers::functions::get_last_variable;
 use super::super::modifiers::list_initialiser::apply_to_last_list_initialiser;
 use super::super::types::binary::{Binary, BinaryOperator};
 use super::super::types::braced_blocks::BracedBlock;
+use super::super::types::literal::{Attribute, Literal, Variable, VariableName};
 use super::super::types::unary::{Unary, UnaryOperator};
-use super::super::types::{Ast, ListInitialiser};
+use super::super::types::{Ast, FunctionCall, ListInitialiser};
 use crate::parser::types::ternary::Ternary;
 
 /// Handler to push a symbol that can be represented by a binary and a unary
@@ -26,6 +31,14 @@ pub fn handle_binary_unary(
 /// failure.
 pub fn handle_colon(current: &mut Ast) -> Result<(), String> {
     match current {
+        //
+        //
+        // nested ternary in the success branch: `a ? b ? c : d : e`
+        Ast::Ternary(Ternary {
+            success,
+            failure: None,
+            ..
+        }) if has_open_ternary(success) => handle_colon(success),
         //
         //
         // success
@@ -38,10 +51,37 @@ pub fn handle_colon(current: &mut Ast) -> Result<(), String> {
         }
         //
         //
+        // label of a statement: `start: x = 1;`
+        Ast::BracedBlock(BracedBlock { elts, full: false })
+            if elts.last().is_some_and(is_label_name) =>
+        {
+            if let Some(last) = elts.last_mut()
+                && let Ast::Leaf(Literal::Variable(Variable {
+                    name: VariableName::UserDefined(label),
+                    ..
+                })) = last
+            {
+                *last = Ast::Label(mem::take(label));
+            }
+            elts.push(Ast::Empty);
+            Ok(())
+        }
+        //
+        //
         // failure
-        Ast::Empty
+        Ast::ArrayDesignator(_)
+        | Ast::CaseRange(_)
+        | Ast::MemberDesignator(_)
+        | Ast::ArraySize(_)
+        | Ast::Empty
+        | Ast::Label(_)
         | Ast::Leaf(_)
         | Ast::ParensBlock(_)
+        | Ast::StatementExpression(_)
+        | Ast::CompoundLiteral(_)
+        | Ast::AlignOf(_)
+        | Ast::BuiltinCall(_)
+        | Ast::ExpressionCall(_)
         | Ast::FunctionCall(_)
         | Ast::ListInitialiser(ListInitialiser { full: true, .. })
         | Ast::BracedBlock(BracedBlock { full: true, .. }) => {
@@ -55,7 +95,11 @@ pub fn handle_colon(current: &mut Ast) -> Result<(), String> {
         | Ast::Binary(Binary { arg_r: arg, .. })
         | Ast::Ternary(Ternary {
             failure: Some(arg), ..
-        }) => handle_colon(arg),
+        })
+        | Ast::ControlFlow(
+            ControlFlowNode::ColonAst(_, Some(arg))
+            | ControlFlowNode::ValueColonAst(_, _, Some(arg)),
+        ) => handle_colon(arg),
         // lists
         Ast::ListInitialiser(ListInitialiser {
             full: false,
@@ -68,6 +112,12 @@ pub fn handle_colon(current: &mut Ast) -> Result<(), String> {
         | Ast::FunctionArgsBuild(vec) => {
             handle_colon(vec.last_mut().expect("Created with one elt"))
         }
+        // control flows
+        Ast::ControlFlow(ControlFlowNode::ValueColonAst(_, value, None))
+            if has_open_ternary(value) =>
+        {
+            handle_colon(value)
+        }
         Ast::ControlFlow(ctrl) => ctrl.push_colon(),
     }
 }
@@ -78,10 +128,73 @@ pub fn handle_comma(current: &mut Ast) -> Result<(), String> {
         vec.push(Ast::Empty);
     } else if apply_to_last_list_initialiser(current, &|vec, _| vec.push(Ast::Empty)).is_err() {
         current.push_op(BinaryOperator::Comma)?;
+        // the declarators of a declaration share its type: `int a, *b, c[3]`
+        if let Some(Binary { arg_l, arg_r, .. }) = get_last_comma(current)
+            && let Some(attrs) = get_declaration_type(arg_l)
+        {
+            **arg_r = Ast::Leaf(Literal::Variable(Variable {
+                attrs,
+                ..Default::default()
+            }));
+        }
     }
     Ok(())
 }
 
+/// Returns the type shared by the declarators of a declaration, like `static
+/// int` in `static int *a = 0`.
+///
+/// The type contains the attributes of the first declarator before its first
+/// `*`: the pointers, arrays and functions are specific to each declarator.
+fn get_declaration_type(node: &Ast) -> Option<Vec<Attribute>> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match node {
+        Ast::Binary(Binary {
+            op: BinaryOperator::Comma | BinaryOperator::Assign | BinaryOperator::ArraySubscript,
+            arg_l,
+            ..
+        }) => get_declaration_type(arg_l),
+        Ast::Leaf(Literal::Variable(Variable { attrs, .. }))
+        | Ast::FunctionCall(FunctionCall {
+            variable: Variable { attrs, .. },
+            ..
+        }) if !attrs.is_empty() => Some(
+            attrs
+                .iter()
+                .take_while(|attr| **attr != Attribute::Indirection)
+                .cloned()
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Returns the comma operator that was just pushed, and is still waiting for
+/// its right argument.
+fn get_last_comma(current: &mut Ast) -> Option<&mut Binary> {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match current {
+        Ast::Binary(binary) => {
+            if binary.op == BinaryOperator::Comma && *binary.arg_r == Ast::Empty {
+                Some(binary)
+            } else {
+                get_last_comma(&mut binary.arg_r)
+            }
+        }
+        Ast::BracedBlock(BracedBlock { elts, full: false }) => {
+            elts.last_mut().and_then(get_last_comma)
+        }
+        Ast::Unary(Unary { arg, .. })
+        | Ast::Ternary(
+            Ternary {
+                failure: Some(arg), ..
+            }
+            | Ternary { success: arg, .. },
+        ) => get_last_comma(arg),
+        _ => None,
+    }
+}
+
 /// Handler to push a symbol that can be represented by 2 different unary
 /// operators.
 pub fn handle_double_unary(
@@ -93,3 +206,107 @@ pub fn handle_double_unary(
         .push_op(first)
         .map_or_else(|_| current.push_op(second), |()| Ok(()))
 }
+
+/// Returns the operators of a comparison chain, like `a < b < c`, if the
+/// comparison operator that was just pushed has a comparison as left
+/// argument.
+///
+/// The operators are returned in the reading order. An explicit chain, like
+/// `(a < b) < c`, has parenthesis as left argument, and is not returned.
+pub fn get_comparison_chain(current: &Ast) -> Option<(&BinaryOperator, &BinaryOperator)> {
+    /// Checks if an operator is a relational or equality operator.
+    const fn is_comparison(op: &BinaryOperator) -> bool {
+        matches!(
+            op,
+            BinaryOperator::Lt
+                | BinaryOperator::Le
+                | BinaryOperator::Gt
+                | BinaryOperator::Ge
+                | BinaryOperator::Equal
+                | BinaryOperator::Different
+        )
+    }
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match current {
+        Ast::Binary(Binary { op, arg_l, arg_r }) if **arg_r == Ast::Empty => {
+            if let Ast::Binary(Binary { op: first, .. }) = &**arg_l
+                && is_comparison(first)
+                && is_comparison(op)
+            {
+                Some((first, op))
+            } else {
+                None
+            }
+        }
+        Ast::Ternary(
+            Ternary {
+                failure: Some(arg), ..
+            }
+            | Ternary { success: arg, .. },
+        )
+        | Ast::Unary(Unary { arg, .. })
+        | Ast::Binary(Binary { arg_r: arg, .. })
+        | Ast::ControlFlow(
+            ControlFlowNode::Ast(_, arg)
+            | ControlFlowNode::ColonAst(_, Some(arg))
+            | ControlFlowNode::ValueColonAst(_, _, Some(arg))
+            | ControlFlowNode::ValueColonAst(_, arg, None),
+        ) => get_comparison_chain(arg),
+        Ast::FunctionArgsBuild(elts)
+        | Ast::BracedBlock(BracedBlock { elts, full: false })
+        | Ast::ListInitialiser(ListInitialiser { elts, full: false }) => {
+            elts.last().and_then(get_comparison_chain)
+        }
+        _ => None,
+    }
+}
+
+/// Checks if the right-most branch of the [`Ast`] contains a ternary operator
+/// that is still waiting for its `:`.
+pub fn has_open_ternary(current: &Ast) -> bool {
+    #[expect(clippy::wildcard_enum_match_arm)]
+    match current {
+        Ast::Ternary(Ternary { failure: None, .. }) => true,
+        Ast::Ternary(Ternary {
+            failure: Some(arg), ..
+        })
+        | Ast::Unary(Unary { arg, .. })
+        | Ast::Binary(Binary { arg_r: arg, .. }) => has_open_ternary(arg),
+        _ => false,
+    }
+}
+
+/// Checks if a statement is only an identifier, that is the name of a label
+/// when it is followed by a `:`.
+const fn is_label_name(node: &Ast) -> bool {
+    matches!(node, Ast::Leaf(Literal::Variable(Variable {
+        attrs,
+        name: VariableName::UserDefined(_),
+        ..
+    })) if attrs.is_empty())
+}
+
+/// Handler for `*`
+///
+/// After type specifiers, like in `int *p`, the `*` is a pointer declarator
+/// and is pushed as an attribute of the variable. Otherwise, it is a
+/// multiplication or an indirection.
+pub fn handle_star(current: &mut Ast) -> Result<(), String> {
+    if let Some(Ast::Leaf(Literal::Variable(
+        var @ Variable {
+            name: VariableName::Empty,
+            ..
+        },
+    ))) = get_last_variable(current)
+        && !var.attrs.is_empty()
+    {
+        var.push_attr(Attribute::Indirection);
+        Ok(())
+    } else {
+        handle_binary_unary(
+            current,
+            BinaryOperator::Multiply,
+            UnaryOperator::Indirection,
+        )
+    }
+}
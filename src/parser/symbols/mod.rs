@@ -2,20 +2,30 @@
 //! [`Ast`].
 
 extern crate alloc;
+mod arrays;
+pub mod attribute_specifiers;
 mod blocks;
+pub mod builtins;
+mod case_ranges;
+mod function_pointers;
 mod handlers;
 mod sort_symbols;
 
 use alloc::vec::IntoIter;
 
+use arrays::is_designator;
 use blocks::blocks_handler;
+use case_ranges::{check_case_value, handle_case_range, open_case_value};
+use handlers::get_comparison_chain;
 use sort_symbols::handle_one_symbol;
 
 use super::parse_content::parse_block;
 use super::state::ParsingState;
 use super::types::Ast;
+use super::types::binary::BinaryOperator;
+use super::types::literal::{LabelAddress, Literal};
 use crate::errors::api::{Location, Res};
-use crate::lexer::api::{Symbol, Token};
+use crate::lexer::api::{Symbol, Token, TokenValue};
 
 /// Main handler to push a symbol into an [`Ast`].
 ///
@@ -27,9 +37,85 @@ pub fn handle_symbol(
     tokens: &mut IntoIter<Token>,
     location: Location,
 ) -> Res<()> {
-    match handle_one_symbol(symbol, current) {
+    let pushed = if symbol == Symbol::LogicalAnd {
+        // `&&` without left operand is the address of a label: `&&label`
+        if current.push_op(BinaryOperator::LogicalAnd).is_err() {
+            return handle_label_address(current, p_state, tokens, location);
+        }
+        Ok(None)
+    } else if symbol == Symbol::Dot && is_designator(current) {
+        return handle_member_designator(current, p_state, tokens, location);
+    } else if symbol == Symbol::Ellipsis && open_case_value(current).is_some() {
+        return handle_case_range(current, p_state, tokens, location);
+    } else {
+        if symbol == Symbol::Colon {
+            check_case_value(current, p_state, &location);
+        }
+        handle_one_symbol(symbol, current)
+    };
+    match pushed {
         Err(err) => Res::from(location.into_failure(err)),
         Ok(Some(block_state)) => blocks_handler(current, tokens, p_state, location, &block_state),
-        Ok(None) => parse_block(tokens, p_state, current),
+        Ok(None) => {
+            if let Some((first, second)) = get_comparison_chain(current) {
+                p_state.push_suggestion(location.to_suggestion(format!(
+                    "Found chained comparisons: 'a {first} b {second} c' means '(a {first} b) {second} c', that compares the result of 'a {first} b', 0 or 1, with 'c'. Did you mean 'a {first} b && b {second} c'? Otherwise, add parenthesis to make it explicit."
+                )));
+            }
+            parse_block(tokens, p_state, current)
+        }
     }
 }
+
+/// Parses a member designator of a list initialiser, like `.x` in `{.x = 1}`.
+fn handle_member_designator(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let Some(TokenValue::Ident(member)) = tokens.as_slice().first().map(Token::get_value) else {
+        return Res::from(location.into_failure(
+            "Expected the name of a member after the '.' of a member designator.".to_owned(),
+        ));
+    };
+    let designator = Ast::MemberDesignator(member.to_owned());
+    tokens.next();
+    current
+        .push_block_as_leaf(designator)
+        .map_err(|err| location.into_failure(err))?;
+    parse_block(tokens, p_state, current)
+}
+
+/// Parses the address of a label `&&label`, if the GNU extensions are enabled.
+fn handle_label_address(
+    current: &mut Ast,
+    p_state: &mut ParsingState,
+    tokens: &mut IntoIter<Token>,
+    location: Location,
+) -> Res<()> {
+    let msg = "Found the address of a label '&&label', but this is a GNU extension.";
+    if !p_state.options().allows_gnu_extensions() {
+        return Res::from(
+            location.into_failure(format!("{msg} Please enable the GNU extensions to use it.")),
+        );
+    }
+    let Some(TokenValue::Ident(label)) = tokens.as_slice().first().map(Token::get_value) else {
+        return Res::from(
+            location.into_failure(
+                "Found '&&' without left operand, but it isn't followed by the name of a label."
+                    .to_owned(),
+            ),
+        );
+    };
+    let address = LabelAddress {
+        label: label.to_owned(),
+        location: location.clone(),
+    };
+    tokens.next();
+    p_state.push_warning(location.to_warning(msg.to_owned()));
+    current
+        .push_block_as_leaf(Ast::Leaf(Literal::LabelAddress(address)))
+        .map_err(|err| location.into_failure(err))?;
+    parse_block(tokens, p_state, current)
+}
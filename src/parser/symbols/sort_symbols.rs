@@ -7,7 +7,9 @@ use super::super::types::Ast;
 use super::super::types::binary::BinaryOperator;
 use super::super::types::unary::UnaryOperator;
 use super::blocks::TodoBlock;
-use super::handlers::{handle_binary_unary, handle_colon, handle_comma, handle_double_unary};
+use super::handlers::{
+    handle_binary_unary, handle_colon, handle_comma, handle_double_unary, handle_star
+};
 use crate::lexer::api::Symbol;
 use crate::parser::types::ternary::TernaryOperator;
 
@@ -36,6 +38,8 @@ enum SymbolParsing {
     Colon,
     /// Comma symbol
     Comma,
+    /// `::` symbol, only valid inside attributes
+    DoubleColon,
     /// There are 2 [`UnaryOperator`] that exist with that symbol.
     ///
     /// Try the first one, and if it is not allowed, try the second.
@@ -45,8 +49,17 @@ enum SymbolParsing {
     /// `++` can be a [`UnaryOperator::PrefixIncrement`] or a
     /// [`UnaryOperator::PostfixIncrement`].
     DoubleUnary(UnaryOperator, UnaryOperator),
+    /// `...` symbol, only valid in macro definitions, range designators and
+    /// case ranges
+    Ellipsis,
+    /// `#` symbol, only valid inside macro definitions
+    Hash,
+    /// `##` symbol, only valid inside macro definitions
+    HashHash,
     /// Interrogation mark
     Interrogation,
+    /// Star symbol: pointer declarator, multiplication or indirection
+    Star,
     /// The symbol exists only for one operator, a [`BinaryOperator`].
     UniqueBinary(BinaryOperator),
     /// The symbol exists only for one operator, a [`UnaryOperator`].
@@ -96,7 +109,6 @@ impl From<Symbol> for SymbolParsing {
             Sy::Ampersand => Self::BinaryUnary(BOp::BitwiseAnd, UOp::AddressOf),
             Sy::Minus => Self::BinaryUnary(BOp::Subtract, UOp::Minus),
             Sy::Plus => Self::BinaryUnary(BOp::Add, UOp::Plus),
-            Sy::Star => Self::BinaryUnary(BOp::Multiply, UOp::Indirection),
             // braces & blocks
             Sy::SemiColon => Self::BracedBlock(TodoBlock::SemiColon),
             Sy::BraceOpen => Self::BracedBlock(TodoBlock::OpenBraceBlock),
@@ -108,7 +120,12 @@ impl From<Symbol> for SymbolParsing {
             // special
             Sy::Colon => Self::Colon,
             Sy::Comma => Self::Comma,
+            Sy::DoubleColon => Self::DoubleColon,
+            Sy::Ellipsis => Self::Ellipsis,
+            Sy::Hash => Self::Hash,
+            Sy::HashHash => Self::HashHash,
             Sy::Interrogation => Self::Interrogation,
+            Sy::Star => Self::Star,
         }
     }
 }
@@ -137,6 +154,28 @@ pub fn handle_one_symbol(symbol: Symbol, current: &mut Ast) -> Result<Option<Tod
         // mod.rs)
         SymbolParsing::Colon => handle_colon(current)?,
         SymbolParsing::Comma => handle_comma(current)?,
+        SymbolParsing::Star => handle_star(current)?,
+        SymbolParsing::DoubleColon => {
+            return Err(
+                "Found '::' outside of an attribute, but C doesn't have namespaces: '::' can only be used in attributes, like in '[[gnu::packed]]'.".to_owned(),
+            );
+        }
+        SymbolParsing::Ellipsis => {
+            return Err(
+                "Found '...' in an expression, but variadic functions are not supported yet."
+                    .to_owned(),
+            );
+        }
+        SymbolParsing::Hash => {
+            return Err(
+                "Found '#' outside of a macro definition: the stringizing operator can only be used in the value of a '#define'.".to_owned(),
+            );
+        }
+        SymbolParsing::HashHash => {
+            return Err(
+                "Found '##' outside of a macro definition: the token pasting operator can only be used in the value of a '#define'.".to_owned(),
+            );
+        }
     }
     Ok(None)
 }
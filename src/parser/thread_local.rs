@@ -0,0 +1,96 @@
+//! Module to check the placement of the `thread_local` storage class.
+//!
+//! A `thread_local` object must have a static storage duration, so
+//! `thread_local` (or its C11 spelling `_Thread_local`) can't be applied to a
+//! function, nor to a variable of a block scope that isn't `static` or
+//! `extern`.
+
+use super::keyword::attributes::{AttributeKeyword, Storage};
+use super::keyword::control_flow::node::ControlFlowNode;
+use super::types::braced_blocks::BracedBlock;
+use super::types::literal::{Attribute, Literal, Variable};
+use super::types::{Ast, FunctionCall};
+use super::visit::{Visit, walk, walk_block, walk_control_flow};
+use crate::errors::api::CompileError;
+
+/// Declarations found while walking the [`Ast`].
+#[derive(Default)]
+struct Declarations {
+    /// Errors for the misplaced `thread_local`.
+    errors: Vec<CompileError>,
+    /// The declarations are inside a braced block, where the variables have
+    /// an automatic storage duration by default.
+    local: bool,
+}
+
+impl Visit<'_> for Declarations {
+    /// Walks an [`Ast`] to find the declarations.
+    fn visit(&mut self, node: &Ast) {
+        #[expect(clippy::wildcard_enum_match_arm)]
+        match node {
+            Ast::FunctionCall(FunctionCall { variable, .. }) if !variable.attrs.is_empty() => {
+                if has_storage(variable, &Storage::ThreadLocal)
+                    && let Some(location) = &variable.location
+                {
+                    self.errors.push(location.to_failure(format!(
+                        "Found 'thread_local' on the function '{}', but only variables can be thread-local.",
+                        variable.name
+                    )));
+                }
+            }
+            Ast::Leaf(Literal::Variable(variable)) => {
+                if self.local
+                    && has_storage(variable, &Storage::ThreadLocal)
+                    && !has_storage(variable, &Storage::Static)
+                    && !has_storage(variable, &Storage::Extern)
+                    && let Some(location) = &variable.location
+                {
+                    self.errors.push(location.to_failure(format!(
+                        "Found 'thread_local' on the local variable '{}', but it must also be 'static' or 'extern'.",
+                        variable.name
+                    )));
+                }
+            }
+            _ => walk(self, node),
+        }
+    }
+
+    /// Walks a [`BracedBlock`], that opens a block scope if it is closed.
+    fn visit_block(&mut self, block: &BracedBlock) {
+        let local = self.local;
+        self.local |= block.full;
+        walk_block(self, block);
+        self.local = local;
+    }
+
+    /// Walks the statements of a [`ControlFlowNode`].
+    ///
+    /// Control flows only appear in block scopes, except the structs, unions
+    /// and enums, whose bodies are skipped as they declare members and not
+    /// variables.
+    fn visit_control_flow(&mut self, ctrl: &ControlFlowNode) {
+        let local = self.local;
+        self.local = true;
+        walk_control_flow(self, ctrl);
+        self.local = local;
+    }
+}
+
+/// Reports the misplaced `thread_local` storage classes.
+///
+/// See the [module documentation](self) for more information.
+pub fn check_thread_local(node: &Ast) -> Vec<CompileError> {
+    let mut declarations = Declarations::default();
+    declarations.visit(node);
+    declarations.errors
+}
+
+/// Checks if a variable has the given storage class.
+fn has_storage(variable: &Variable, storage: &Storage) -> bool {
+    variable.attrs.iter().any(|attr| {
+        matches!(
+            attr,
+            Attribute::Keyword(AttributeKeyword::Storage(class)) if class == storage
+        )
+    })
+}
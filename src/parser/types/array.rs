@@ -0,0 +1,85 @@
+//! Defines the array declarator nodes.
+
+use core::fmt;
+
+use super::Ast;
+use crate::EMPTY;
+use crate::parser::keyword::attributes::AttributeKeyword;
+
+/// Array designator of a list initialiser: `[6]` in `{1, [6] = 7}`
+///
+/// With the GNU extensions, the designator can be a range of indices, like
+/// `[0 ... 3]` in `{[0 ... 3] = 1}`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrayDesignator {
+    /// last index of the range, if the designator is a range
+    pub hi: Option<Box<Ast>>,
+    /// index, or first index of the range
+    pub lo: Box<Ast>,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for ArrayDesignator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(hi) = &self.hi {
+            write!(f, "[{} ... {hi}]", self.lo)
+        } else {
+            write!(f, "[{}]", self.lo)
+        }
+    }
+}
+
+/// Length of an array declarator
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArrayLength {
+    /// Integer constant expression: `int a[10]`
+    Constant(Box<Ast>),
+    /// No length given: `int a[]`
+    Empty,
+    /// Variable length array of unspecified size, only in prototypes:
+    /// `void f(int a[*])`
+    Star,
+    /// Variable length array, whose length isn't an integer constant
+    /// expression: `int a[n]`
+    Variable(Box<Ast>),
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for ArrayLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constant(length) => length.fmt(f),
+            Self::Empty => EMPTY.fmt(f),
+            Self::Star => '*'.fmt(f),
+            Self::Variable(length) => write!(f, "VLA {length}"),
+        }
+    }
+}
+
+/// Size of an array declarator, between the brackets of `int a[10]` or
+/// `void f(int a[static const 1])`
+///
+/// `static` and the qualifiers are only allowed in function parameters.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArraySize {
+    /// `static` was found: the argument has at least `length` elements
+    pub is_static: bool,
+    /// length of the array
+    pub length: ArrayLength,
+    /// qualifiers of the pointer the parameter is converted to, like `const`
+    /// in `int a[const]`
+    pub qualifiers: Vec<AttributeKeyword>,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for ArraySize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_static {
+            "static ".fmt(f)?;
+        }
+        for qualifier in &self.qualifiers {
+            write!(f, "{qualifier} ")?;
+        }
+        self.length.fmt(f)
+    }
+}
@@ -10,10 +10,12 @@ use super::{Associativity, Ast, Operator};
 /// Defines and implements the [`BinaryOperator`] type.
 macro_rules! define_binary_operator {
     ($($name_left:ident $precedence_left:expr, $repr_left:expr)*; $($name_right:ident $precedence_right:expr, $repr_right:expr)*) => {
-       #[derive(Debug, PartialEq, Eq)]
+       /// Binary operator, like `+` in `a + b`.
+       #[derive(Debug, PartialEq, Eq, Clone)]
+       #[non_exhaustive]
        pub enum BinaryOperator {
-         $($name_left,)*
-         $($name_right,)*
+         $(#[doc = concat!("`", $repr_left, "`")] $name_left,)*
+         $(#[doc = concat!("`", $repr_right, "`")] $name_right,)*
        }
 
        impl Operator for BinaryOperator {
@@ -34,6 +36,7 @@ macro_rules! define_binary_operator {
 
         #[expect(clippy::min_ident_chars)]
         impl fmt::Display for BinaryOperator {
+            #[inline]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "{}", match self {
                     $(Self::$name_left => $repr_left,)*
@@ -46,7 +49,7 @@ macro_rules! define_binary_operator {
 }
 
 /// Binary node of the [`Ast`]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Binary {
     /// Operator
     pub op: BinaryOperator,
@@ -106,6 +109,7 @@ define_binary_operator!(
 );
 
 impl PartialEq<UnaryOperator> for BinaryOperator {
+    #[inline]
     fn eq(&self, _: &UnaryOperator) -> bool {
         false
     }
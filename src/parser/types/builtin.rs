@@ -0,0 +1,163 @@
+//! Defines the builtin function call nodes.
+
+use core::fmt;
+
+use super::Ast;
+use super::literal::Variable;
+use crate::parser::{repr_option, repr_vec};
+
+/// `alignof` operator
+///
+/// Unlike `sizeof`, the operand must be a type name in parenthesis, like
+/// `alignof(int)`. An expression operand, like `alignof(x)`, is a GNU
+/// extension.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AlignOf(pub Box<BuiltinArgument>);
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for AlignOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(alignof\u{b0}({}))", self.0)
+    }
+}
+
+/// Kind of an argument of a builtin function.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ArgumentKind {
+    /// The argument is an expression: `x + 1`
+    Expression,
+    /// The argument is a type name: `struct S`
+    Type,
+}
+
+/// Builtin functions whose arguments can't all be parsed as expressions.
+///
+/// Unknown builtins (e.g. `__builtin_*`) are parsed as ordinary function
+/// calls.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Builtin {
+    /// `__builtin_expect(expr, value)`
+    Expect,
+    /// `offsetof(type, member)`
+    Offsetof,
+    /// `__builtin_types_compatible_p(type, type)`
+    TypesCompatible,
+    /// `va_arg(ap, type)`
+    VaArg,
+}
+
+impl Builtin {
+    /// Returns the kinds of the arguments expected by the builtin.
+    pub const fn arguments(&self) -> &'static [ArgumentKind] {
+        match self {
+            Self::Expect => &[ArgumentKind::Expression, ArgumentKind::Expression],
+            Self::Offsetof => &[ArgumentKind::Type, ArgumentKind::Expression],
+            Self::TypesCompatible => &[ArgumentKind::Type, ArgumentKind::Type],
+            Self::VaArg => &[ArgumentKind::Expression, ArgumentKind::Type],
+        }
+    }
+
+    /// Finds the builtin that corresponds to a function name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "__builtin_expect" => Some(Self::Expect),
+            "offsetof" | "__builtin_offsetof" => Some(Self::Offsetof),
+            "__builtin_types_compatible_p" => Some(Self::TypesCompatible),
+            "va_arg" | "__builtin_va_arg" => Some(Self::VaArg),
+            _ => None,
+        }
+    }
+}
+
+/// Argument of a builtin function call
+#[derive(Debug, PartialEq, Clone)]
+pub enum BuiltinArgument {
+    /// Expression argument
+    Expression(Ast),
+    /// Type argument
+    Type(TypeName),
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for BuiltinArgument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expression(ast) => ast.fmt(f),
+            Self::Type(type_name) => type_name.fmt(f),
+        }
+    }
+}
+
+/// Builtin function call
+///
+/// This node is used instead of [`FunctionCall`](super::FunctionCall) for
+/// builtins that take types as arguments, like `offsetof(struct S, x)`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BuiltinCall {
+    /// arguments of the builtin
+    pub args: Vec<BuiltinArgument>,
+    /// builtin that is called
+    pub builtin: Builtin,
+    /// name of the builtin, as written in the source
+    pub name: String,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for BuiltinCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}\u{b0}({}))", self.name, repr_vec(&self.args))
+    }
+}
+
+/// `typeof` specifier, with its operand: `typeof(x)` or
+/// `typeof_unqual(const int)`
+///
+/// The specifier is an [`Attribute`](super::literal::Attribute) of the
+/// declared variable, like a type keyword: `typeof(x) y` declares `y` with the
+/// type of `x`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Typeof {
+    /// operand of the specifier, an expression or a type name
+    pub operand: Box<BuiltinArgument>,
+    /// the qualifiers of the operand are removed, with `typeof_unqual`
+    pub unqualified: bool,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for Typeof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keyword = if self.unqualified {
+            "typeof_unqual"
+        } else {
+            "typeof"
+        };
+        write!(f, "{keyword}({})", self.operand)
+    }
+}
+
+/// Type name, used as an argument of a builtin: `const struct S *`, `int[3]`
+/// or `int (*)(void)`
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeName {
+    /// Array of elements of a type, with its size if given: `int[3]`
+    Array(Box<Self>, Option<Ast>),
+    /// Pointer to a function, with the types of its parameters:
+    /// `int (*)(void)`
+    FunctionPointer(Box<Self>, Vec<Self>),
+    /// Specifiers, qualifiers and pointers, stored as a variable without name:
+    /// `const char *`
+    Variable(Variable),
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for TypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Array(elements, size) => write!(f, "({elements}[{}])", repr_option(size)),
+            Self::FunctionPointer(return_type, params) => {
+                write!(f, "({return_type} (*)({}))", repr_vec(params))
+            }
+            Self::Variable(var) => var.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Defines the enum declaration nodes.
+
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use super::Ast;
+use super::literal::Attribute;
+use crate::parser::keyword::attributes::{AttributeKeyword, BasicDataType, Modifiers};
+use crate::parser::modifiers::constant::constant_value;
+
+/// Enum declaration, with its list of enumerators: `enum E { A, B = 5, C }`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Enum {
+    /// enumerators of the enum, in the declaration order
+    pub enumerators: Vec<Enumerator>,
+    /// name of the enum, if it isn't anonymous
+    pub name: Option<String>,
+    /// underlying type of the enum, if it was given (since C23): `unsigned
+    /// char` in `enum E : unsigned char { A }`
+    pub underlying: Option<Vec<Attribute>>,
+}
+
+impl Enum {
+    /// Pushes a new enumerator in the enum, and computes its value.
+    ///
+    /// If no value was given, the value of the enumerator is one more than the
+    /// value of the previous enumerator, or 0 for the first one. An explicit
+    /// value can refer to the enumerators declared before it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an enumerator with the same name already exists.
+    pub fn push_enumerator(&mut self, name: String, explicit: Option<Ast>) -> Result<(), String> {
+        if self
+            .enumerators
+            .iter()
+            .any(|enumerator| enumerator.name == name)
+        {
+            return Err(format!("Found duplicate enumerator '{name}' in enum."));
+        }
+        let value = match &explicit {
+            Some(node) => constant_value(node, &|identifier| {
+                self.enumerators
+                    .iter()
+                    .find(|enumerator| enumerator.name == identifier)
+                    .and_then(|enumerator| enumerator.value)
+            }),
+            None => self
+                .enumerators
+                .last()
+                .map_or(Some(0), |previous| previous.value?.checked_add(1)),
+        };
+        self.enumerators.push(Enumerator {
+            explicit,
+            name,
+            value,
+        });
+        Ok(())
+    }
+
+    /// Returns the range of the values of the underlying type of the enum.
+    ///
+    /// This is [`None`] if no underlying type was given, or if its range isn't
+    /// known, like for a `typedef`. The sizes are the ones of the LP64 data
+    /// model, and `char` is signed.
+    pub fn underlying_range(&self) -> Option<RangeInclusive<i128>> {
+        let mut signed = i128::from(i32::MIN)..=i128::from(i32::MAX);
+        let mut unsigned = i128::from(u32::MIN)..=i128::from(u32::MAX);
+        let mut is_unsigned = false;
+        for attr in self.underlying.as_ref()? {
+            match attr {
+                Attribute::Keyword(AttributeKeyword::BasicDataType(BasicDataType::Bool)) => {
+                    return Some(0..=1);
+                }
+                Attribute::Keyword(AttributeKeyword::BasicDataType(BasicDataType::Char)) => {
+                    signed = i128::from(i8::MIN)..=i128::from(i8::MAX);
+                    unsigned = i128::from(u8::MIN)..=i128::from(u8::MAX);
+                }
+                Attribute::Keyword(AttributeKeyword::Modifiers(Modifiers::Short)) => {
+                    signed = i128::from(i16::MIN)..=i128::from(i16::MAX);
+                    unsigned = i128::from(u16::MIN)..=i128::from(u16::MAX);
+                }
+                Attribute::Keyword(AttributeKeyword::Modifiers(Modifiers::Long)) => {
+                    signed = i128::from(i64::MIN)..=i128::from(i64::MAX);
+                    unsigned = i128::from(u64::MIN)..=i128::from(u64::MAX);
+                }
+                Attribute::Keyword(AttributeKeyword::Modifiers(Modifiers::Unsigned)) => {
+                    is_unsigned = true;
+                }
+                Attribute::Keyword(
+                    AttributeKeyword::BasicDataType(BasicDataType::Int)
+                    | AttributeKeyword::Modifiers(Modifiers::Signed)
+                    | AttributeKeyword::Qualifiers(_),
+                ) => (),
+                Attribute::Array(_)
+                | Attribute::Enum(_)
+                | Attribute::Function(_)
+                | Attribute::Indirection
+                | Attribute::Keyword(_)
+                | Attribute::Standard(_)
+                | Attribute::Tag(..)
+                | Attribute::Typeof(_)
+                | Attribute::User(_) => return None,
+            }
+        }
+        Some(if is_unsigned { unsigned } else { signed })
+    }
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for Enum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let enumerators = self
+            .enumerators
+            .iter()
+            .map(|enumerator| format!("{enumerator}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        "(enum ".fmt(f)?;
+        if let Some(name) = &self.name {
+            write!(f, "{name} ")?;
+        }
+        if let Some(attrs) = &self.underlying {
+            let underlying = attrs
+                .iter()
+                .map(|attr| format!("{attr}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            write!(f, ": {underlying} ")?;
+        }
+        write!(f, "{{{enumerators}}})")
+    }
+}
+
+/// Enumerator of an enum: `A` or `B = 5`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Enumerator {
+    /// value given in the source, like the `5` of `B = 5`
+    pub explicit: Option<Ast>,
+    /// name of the enumerator
+    pub name: String,
+    /// value of the enumerator, explicit or implicit
+    ///
+    /// This is [`None`] if the value depends on an expression that can't be
+    /// evaluated by the parser, like `A = sizeof(int)`.
+    pub value: Option<i128>,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for Enumerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.value, &self.explicit) {
+            (Some(value), _) => write!(f, "{} = {value}", self.name),
+            (None, Some(explicit)) => write!(f, "{} = {explicit}", self.name),
+            (None, None) => self.name.fmt(f),
+        }
+    }
+}
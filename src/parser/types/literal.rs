@@ -2,17 +2,38 @@
 
 use core::{fmt, mem};
 
+use super::Ast;
+use super::builtin::Typeof;
+use super::enumeration::Enum;
+use super::standard_attribute::StandardAttribute;
+use crate::errors::api::Location;
 use crate::parser::keyword::attributes::AttributeKeyword;
+use crate::parser::keyword::control_flow::keyword::ControlFlowKeyword;
 use crate::parser::keyword::functions::FunctionKeyword;
+use crate::parser::{repr_option, repr_vec};
 use crate::{EMPTY, Number};
 
 /// Attribute of a variable
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Attribute {
+    /// Array type with its size, in the declarator of a pointer to an array,
+    /// like `int (*a)[10]`
+    Array(Option<Box<Ast>>),
+    /// Enum type, declared with its enumerators, like `enum E { A, B }`
+    Enum(Enum),
+    /// Function type with its parameters, in the declarator of a function
+    /// pointer, like `void (*fp)(int)`
+    Function(Vec<Ast>),
     /// Represents the `*` attribute
     Indirection,
     /// Keyword attribute, like `const` or `int`
     Keyword(AttributeKeyword),
+    /// Standard attribute, like `[[gnu::packed]]`
+    Standard(StandardAttribute),
+    /// Struct, union or enum type, referred to by its tag, like `struct S`
+    Tag(ControlFlowKeyword, String),
+    /// Type of an expression or of a type name, like `typeof(x)`
+    Typeof(Typeof),
     /// User-defined attribute, like a user defined type
     User(String),
 }
@@ -21,20 +42,55 @@ pub enum Attribute {
 impl fmt::Display for Attribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::Array(size) => write!(f, "[{}]", repr_option(size)),
+            Self::Enum(enum_node) => enum_node.fmt(f),
+            Self::Function(params) => write!(f, "\u{b0}({})", repr_vec(params)),
             Self::Indirection => '*'.fmt(f),
             Self::Keyword(keyword) => keyword.fmt(f),
+            Self::Standard(attribute) => attribute.fmt(f),
+            Self::Tag(keyword, name) => write!(f, "{keyword} {name}"),
+            Self::Typeof(specifier) => specifier.fmt(f),
             Self::User(val) => write!(f, "'{val}'"),
         }
     }
 }
 
+/// Address of a label, a GNU extension: `&&label`
+///
+/// The label must be defined in the same function, so it is resolved after the
+/// whole function is parsed.
+#[derive(Debug, Clone)]
+pub struct LabelAddress {
+    /// name of the label
+    pub label: String,
+    /// location of the `&&`, to report the undefined labels
+    ///
+    /// The location isn't compared by [`PartialEq`].
+    pub location: Location,
+}
+
+impl PartialEq for LabelAddress {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+    }
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for LabelAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "&&{}", self.label)
+    }
+}
+
 /// Literal
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Literal {
     /// Char
     Char(char),
     /// Boolean constant: `true` or `false`
     ConstantBool(bool),
+    /// Address of a label, a GNU extension: `&&label`
+    LabelAddress(LabelAddress),
     /// `NULL` constant
     Nullptr,
     /// Number constant
@@ -54,16 +110,21 @@ impl fmt::Display for Literal {
             Self::Str(val) => write!(f, "\"{val}\""),
             Self::Number(val) => val.fmt(f),
             Self::ConstantBool(val) => val.fmt(f),
+            Self::LabelAddress(val) => val.fmt(f),
             Self::Variable(val) => val.fmt(f),
         }
     }
 }
 
 /// Variable
-#[derive(Debug, PartialEq, Default, Eq)]
+#[derive(Debug, Default, Clone)]
 pub struct Variable {
     /// attributes of the variable
     pub attrs: Vec<Attribute>,
+    /// location of the name of the variable, if it was read from a token
+    ///
+    /// The location isn't compared by [`PartialEq`].
+    pub location: Option<Location>,
     /// name of the variable
     pub name: VariableName,
 }
@@ -119,6 +180,7 @@ impl From<FunctionKeyword> for Variable {
         Self {
             name: VariableName::Keyword(value),
             attrs: vec![],
+            location: None,
         }
     }
 }
@@ -128,6 +190,7 @@ impl From<String> for Variable {
         Self {
             name: VariableName::UserDefined(name),
             attrs: vec![],
+            location: None,
         }
     }
 }
@@ -137,10 +200,17 @@ impl From<AttributeKeyword> for Variable {
         Self {
             name: VariableName::Empty,
             attrs: vec![Attribute::Keyword(attr)],
+            location: None,
         }
     }
 }
 
+impl PartialEq for Variable {
+    fn eq(&self, other: &Self) -> bool {
+        self.attrs == other.attrs && self.name == other.name
+    }
+}
+
 #[expect(clippy::min_ident_chars)]
 impl fmt::Display for Variable {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -162,7 +232,7 @@ impl fmt::Display for Variable {
 }
 
 /// Variable name
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
 pub enum VariableName {
     /// No variable name yet
     #[default]
@@ -1,16 +1,22 @@
 //! Module that defines the main node types of the [`Ast`]
 
+pub mod array;
 pub mod binary;
 pub mod braced_blocks;
+pub mod builtin;
+pub mod enumeration;
 pub mod literal;
 pub mod operator;
+pub mod standard_attribute;
 pub mod ternary;
 pub mod unary;
 
 use core::fmt;
 
+use array::{ArrayDesignator, ArraySize};
 use binary::Binary;
 use braced_blocks::BracedBlock;
+use builtin::{AlignOf, BuiltinCall, TypeName};
 use literal::{Literal, Variable};
 use operator::{Associativity, Operator};
 use ternary::Ternary;
@@ -20,40 +26,118 @@ use super::keyword::control_flow::node::ControlFlowNode;
 use crate::parser::repr_vec;
 
 /// Struct to represent the Abstract Syntax Tree of the whole C source file.
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, PartialEq, Clone)]
 pub enum Ast {
+    /// `alignof` operator, with its operand: `alignof(int)`
+    AlignOf(AlignOf),
+    /// Array designator of a list initialiser: `[6]` in `{1, [6] = 7}`
+    ArrayDesignator(ArrayDesignator),
+    /// Size of an array declarator: `[static 10]` in `void f(int a[static 10])`
+    ArraySize(ArraySize),
     /// Binary operator
     Binary(Binary),
     /// Braced-block, in `{...}`.
     ///
     /// A whole file is considered to be a block.
     BracedBlock(BracedBlock),
+    /// Builtin function call, with type arguments: `offsetof(struct S, x)`
+    BuiltinCall(BuiltinCall),
+    /// GNU range of values of a `case` label: `1 ... 3` in `case 1 ... 3:`
+    CaseRange(CaseRange),
+    /// Compound literal: `(int[]){1, 2, 3}`
+    CompoundLiteral(CompoundLiteral),
     /// Control Flow blocks
     ControlFlow(ControlFlowNode),
     /// Empty AST
     #[default]
     Empty,
+    /// Call of a function designated by an expression: `s.f(x)` or `f(x)(y)`
+    ExpressionCall(ExpressionCall),
     /// Function arguments: `(x+y, !g(z), (a, !b)++, )`
     FunctionArgsBuild(Vec<Ast>),
     /// Function call
     FunctionCall(FunctionCall),
+    /// Label of a statement, the target of a `goto`: `start` in `start: x = 1;`
+    Label(String),
     /// Literal (constants, variables, etc.)
     Leaf(Literal),
     /// List initialiser: `{1, 2, 3, [6]=7}`
     ListInitialiser(ListInitialiser),
+    /// Member designator of a list initialiser: `.x` in `{.x = 1}`
+    MemberDesignator(String),
     /// Ast surrounded by parenthesis: `(x=2)`
     ParensBlock(ParensBlock),
+    /// GNU statement expression: `({ int a = 1; a + 1; })`
+    StatementExpression(StatementExpression),
     /// Ternary operator
     Ternary(Ternary),
     /// Unary operator
     Unary(Unary),
-    // TODO: CompoundLiteral(CompoundLiteral), Cast, SpecialUnary(SpecialUnary),
+    // TODO: SpecialUnary(SpecialUnary),
+}
+
+/// Range of values of a `case` label
+///
+/// With the GNU extensions, a `case` can match a range of values, like
+/// `case 1 ... 3:` that matches 1, 2 and 3.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CaseRange {
+    /// last value of the range
+    pub hi: Box<Ast>,
+    /// first value of the range
+    pub lo: Box<Ast>,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for CaseRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ... {}", self.lo, self.hi)
+    }
+}
+
+/// Compound literal
+///
+/// Node to represent an unnamed object, initialised with a list initialiser,
+/// such as `(int[]){1, 2, 3}` or `(struct P){.x = 1}`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CompoundLiteral {
+    /// list initialiser of the object
+    pub initialiser: ListInitialiser,
+    /// type of the object, between the parenthesis
+    pub type_name: Box<TypeName>,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for CompoundLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(({}){})", self.type_name, self.initialiser)
+    }
+}
+
+/// Call of a function that is designated by an expression
+///
+/// Node to represent the calls whose callee isn't a name, such as `s.f(x)`,
+/// `a[0](x)`, `(*fp)(x)` or `f(x)(y)`. The calls of a name, like `f(x)`, are
+/// [`FunctionCall`]s.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExpressionCall {
+    /// arguments of the function
+    pub args: Vec<Ast>,
+    /// expression that evaluates to the called function
+    pub callee: Box<Ast>,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for ExpressionCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}\u{b0}({}))", self.callee, repr_vec(&self.args))
+    }
 }
 
 /// Function call
 ///
 /// This node represents functions declaration, functions
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct FunctionCall {
     /// arguments of the function
     pub args: Vec<Ast>,
@@ -77,7 +161,7 @@ impl fmt::Display for FunctionCall {
 ///
 /// This is a constant type, but is used to access the methods of the
 /// [`Operator`] trait.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct FunctionOperator;
 
 impl Operator for FunctionOperator {
@@ -93,7 +177,7 @@ impl Operator for FunctionOperator {
 /// List initialiser
 ///
 /// Node to represent list initialisers, such as `{1, 2, 3, [6]=12}`.
-#[derive(Debug, PartialEq, Default)]
+#[derive(Debug, PartialEq, Default, Clone)]
 pub struct ListInitialiser {
     /// elements of the list
     pub elts: Vec<Ast>,
@@ -126,10 +210,20 @@ impl fmt::Display for ListInitialiser {
 ///
 /// If the C source is `(x = 2)`, the node is a [`ParensBlock`] with value the
 /// [`Ast`] of `x=2`.
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, PartialEq, Clone)]
 pub struct ParensBlock(Box<Ast>);
 
 impl ParensBlock {
+    /// Returns the [`Ast`] inside the parenthesis.
+    pub fn get_inner(&self) -> &Ast {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the [`Ast`] inside the parenthesis.
+    pub const fn get_inner_mut(&mut self) -> &mut Ast {
+        &mut self.0
+    }
+
     /// Adds parenthesis around an [`Ast`].
     ///
     /// # Examples
@@ -148,3 +242,23 @@ impl fmt::Display for ParensBlock {
         write!(f, "({})", self.0)
     }
 }
+
+/// GNU statement expression
+///
+/// The [`BracedBlock`] is the block inside the parenthesis. The value of the
+/// expression is the value of the last statement of the block.
+///
+/// # Examples
+///
+/// If the C source is `({ int a = 1; a + 1; })`, the node is a
+/// [`StatementExpression`] with value the block `{ int a = 1; a + 1; }`, and
+/// the expression is evaluated to `a + 1`.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct StatementExpression(pub BracedBlock);
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for StatementExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({})", self.0)
+    }
+}
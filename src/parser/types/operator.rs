@@ -21,6 +21,7 @@ pub enum Associativity {
     RightToLeft,
 }
 
+/// Operator, with its associativity and precedence.
 #[cfg_attr(doc, doc = include_str!("../../../docs/operators.md"))]
 pub trait Operator: fmt::Debug {
     /// Get associativity of an operator.
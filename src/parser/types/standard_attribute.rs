@@ -0,0 +1,36 @@
+//! Defines the standard attribute nodes, written between `[[` and `]]`.
+
+use core::fmt;
+
+use crate::lexer::api::TokenValue;
+use crate::parser::repr_vec;
+
+/// Standard attribute, like `[[nodiscard]]`, `[[deprecated("reason")]]` or
+/// `[[gnu::packed]]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct StandardAttribute {
+    /// tokens of the arguments between parenthesis, if any
+    ///
+    /// The arguments are not parsed, as they don't need to be valid
+    /// expressions, like `printf` in `[[gnu::format(printf, 1, 2)]]`.
+    pub arguments: Option<Vec<TokenValue>>,
+    /// name of the attribute
+    pub name: String,
+    /// namespace of the attribute, like `gnu` in `gnu::packed`
+    pub namespace: Option<String>,
+}
+
+#[expect(clippy::min_ident_chars)]
+impl fmt::Display for StandardAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "[[".fmt(f)?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, "{namespace}::")?;
+        }
+        self.name.fmt(f)?;
+        if let Some(arguments) = &self.arguments {
+            write!(f, "({})", repr_vec(arguments))?;
+        }
+        "]]".fmt(f)
+    }
+}
@@ -4,15 +4,22 @@ use core::fmt;
 
 use super::Ast;
 use super::binary::BinaryOperator;
+use super::literal::Attribute;
 use super::operator::{Associativity, Operator};
 use super::unary::UnaryOperator;
+use crate::lexer::api::NumberType;
 use crate::parser::repr_option;
 
 /// Ternary node of an [`Ast`]
 ///
 /// The structure is `<condition> ? <success> : <failure>.`
-#[derive(Debug, PartialEq, Default)]
+#[derive(Debug, PartialEq, Default, Clone)]
 pub struct Ternary {
+    /// Common type of the branches, if it is known
+    ///
+    /// This is computed once the whole file is parsed, as it needs the types
+    /// of the declared variables.
+    pub common_type: Option<ValueType>,
     /// Condition [`Ast`] (before `?`)
     pub condition: Box<Ast>,
     /// Failure [`Ast`] (after `:`)
@@ -43,7 +50,7 @@ impl fmt::Display for Ternary {
 ///
 /// This is a constant type, but is used to access the methods of the
 /// [`Operator`] trait.
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
 pub struct TernaryOperator;
 
 impl Operator for TernaryOperator {
@@ -74,3 +81,27 @@ impl fmt::Display for TernaryOperator {
         "?:".fmt(f)
     }
 }
+
+/// Type of the value of an expression, if it can be used in arithmetic
+/// operations or as a pointer
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValueType {
+    /// Arithmetic type, after the integer promotions
+    Arithmetic(NumberType),
+    /// Pointer type, with all its attributes: `const char *`
+    Pointer(Vec<Attribute>),
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Arithmetic(number_type) => number_type.fmt(f),
+            Self::Pointer(attrs) => attrs
+                .iter()
+                .map(|attr| format!("{attr}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+                .fmt(f),
+        }
+    }
+}
@@ -3,10 +3,11 @@
 use core::fmt;
 
 use super::binary::BinaryOperator;
+use super::literal::Attribute;
 use super::{Associativity, Ast, Operator};
 
 /// Unary operator node
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Unary {
     /// Argument
     pub arg: Box<Ast>,
@@ -26,12 +27,15 @@ impl fmt::Display for Unary {
 }
 
 /// Unary operator
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub enum UnaryOperator {
     /// Address-of (`&`)
     AddressOf,
     /// `~`
     BitwiseNot,
+    /// Cast, with the attributes of the type name (`(int *)` in `(int *)p`)
+    Cast(Vec<Attribute>),
     /// Dereference (`*`)
     Indirection,
     /// `!`
@@ -48,6 +52,8 @@ pub enum UnaryOperator {
     PrefixDecrement,
     /// `++` (in `++x`)
     PrefixIncrement,
+    /// `sizeof` without parenthesis (in `sizeof x`)
+    Sizeof,
 }
 
 impl Operator for UnaryOperator {
@@ -61,7 +67,9 @@ impl Operator for UnaryOperator {
             | Self::BitwiseNot
             | Self::LogicalNot
             | Self::Indirection
-            | Self::AddressOf => Associativity::RightToLeft,
+            | Self::AddressOf
+            | Self::Cast(_)
+            | Self::Sizeof => Associativity::RightToLeft,
         }
     }
 
@@ -75,12 +83,15 @@ impl Operator for UnaryOperator {
             | Self::BitwiseNot
             | Self::LogicalNot
             | Self::Indirection
-            | Self::AddressOf => 2,
+            | Self::AddressOf
+            | Self::Cast(_)
+            | Self::Sizeof => 2,
         }
     }
 }
 
 impl PartialEq<BinaryOperator> for UnaryOperator {
+    #[inline]
     fn eq(&self, _: &BinaryOperator) -> bool {
         false
     }
@@ -88,7 +99,19 @@ impl PartialEq<BinaryOperator> for UnaryOperator {
 
 #[expect(clippy::min_ident_chars)]
 impl fmt::Display for UnaryOperator {
+    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Self::Cast(attrs) = self {
+            return write!(
+                f,
+                "({})",
+                attrs
+                    .iter()
+                    .map(|attr| format!("{attr}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        }
         write!(f, "{}", match self {
             Self::PostfixIncrement | Self::PrefixIncrement => "++",
             Self::PostfixDecrement | Self::PrefixDecrement => "--",
@@ -98,6 +121,8 @@ impl fmt::Display for UnaryOperator {
             Self::LogicalNot => "!",
             Self::Indirection => "*",
             Self::AddressOf => "&",
+            Self::Sizeof => "sizeof ",
+            Self::Cast(_) => panic!("Never happens: casts are displayed above"),
         })
     }
 }
@@ -0,0 +1,160 @@
+//! Module to report the local variables that are declared but never used.
+//!
+//! The [`Ast`] is walked in the order of the source, with a stack of scopes:
+//! every braced block opens a scope, where the variables declared in the
+//! block are stored. A variable is used if its name is found in an
+//! expression, in its scope or in a nested one. When a scope is closed, its
+//! unused variables are reported.
+//!
+//! The global variables and the parameters of the functions are not
+//! reported.
+
+use super::keyword::attributes::{AttributeKeyword, Storage};
+use super::types::braced_blocks::BracedBlock;
+use super::types::literal::{Attribute, Variable, VariableName};
+use super::types::standard_attribute::StandardAttribute;
+use super::types::{Ast, FunctionCall};
+use super::visit::{Visit, walk, walk_block, walk_variable};
+use crate::errors::api::{CompileError, Location};
+
+/// Declaration of a local variable.
+struct Declaration {
+    /// Location of the name of the variable.
+    location: Option<Location>,
+    /// Name of the variable.
+    name: String,
+    /// The variable was used in an expression.
+    used: bool,
+}
+
+/// Stack of the scopes of the local variables, from the outermost to the
+/// innermost.
+#[derive(Default)]
+struct Scopes {
+    /// Suggestions for the unused variables of the closed scopes.
+    errors: Vec<CompileError>,
+    /// Declarations of the opened scopes.
+    scopes: Vec<Vec<Declaration>>,
+}
+
+impl Scopes {
+    /// Closes the innermost scope, and reports its unused variables.
+    fn close(&mut self) {
+        for declaration in self.scopes.pop().unwrap_or_default() {
+            if !declaration.used
+                && let Some(location) = declaration.location
+            {
+                self.errors.push(location.to_suggestion(format!(
+                    "Variable '{}' is declared but never used. Consider removing it, or marking it with '[[maybe_unused]]'.",
+                    declaration.name
+                )));
+            }
+        }
+    }
+
+    /// Declares a variable in the innermost scope.
+    ///
+    /// Global variables, `extern` declarations and variables marked with
+    /// `[[maybe_unused]]` are not declared, so they are never reported.
+    fn declare(&mut self, name: &str, variable: &Variable) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        if variable.attrs.iter().any(|attr| {
+            matches!(
+                attr,
+                Attribute::Keyword(AttributeKeyword::Storage(Storage::Extern))
+            ) || is_maybe_unused(attr)
+        }) {
+            return;
+        }
+        scope.push(Declaration {
+            location: variable.location.clone(),
+            name: name.to_owned(),
+            used: false,
+        });
+    }
+
+    /// Opens a new scope.
+    fn open(&mut self) {
+        self.scopes.push(vec![]);
+    }
+
+    /// Marks the innermost visible variable with this name as used.
+    fn use_name(&mut self, name: &str) {
+        if let Some(declaration) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.iter_mut().rev().find(|decl| decl.name == name))
+        {
+            declaration.used = true;
+        }
+    }
+}
+
+impl Visit<'_> for Scopes {
+    /// Walks an [`Ast`] in the order of the source.
+    fn visit(&mut self, node: &Ast) {
+        // with a return type, this is a function declaration, whose arguments
+        // are the parameters
+        if let Ast::FunctionCall(FunctionCall { variable, .. }) = node
+            && !variable.attrs.is_empty()
+        {
+            return;
+        }
+        walk(self, node);
+    }
+
+    /// Walks a [`BracedBlock`], in a new scope if the block is closed.
+    fn visit_block(&mut self, block: &BracedBlock) {
+        if block.full {
+            self.open();
+        }
+        walk_block(self, block);
+        if block.full {
+            self.close();
+        }
+    }
+
+    /// Declares or uses a [`Variable`].
+    ///
+    /// A variable with attributes, like `int x`, is a declaration, and a
+    /// variable without attributes, like `x`, is a use.
+    fn visit_variable(&mut self, variable: &Variable) {
+        // the operand of `typeof` uses its variables, like `a` in `typeof(a) b`
+        walk_variable(self, variable);
+        if let VariableName::UserDefined(name) = &variable.name {
+            if variable.attrs.is_empty() {
+                self.use_name(name);
+            } else {
+                self.declare(name, variable);
+            }
+        }
+    }
+}
+
+/// Reports the local variables that are declared but never used.
+///
+/// The reports are suggestions on the names of the variables. See the
+/// [module documentation](self) for more information.
+pub fn check_unused_variables(node: &Ast) -> Vec<CompileError> {
+    let mut scopes = Scopes::default();
+    scopes.visit(node);
+    while !scopes.scopes.is_empty() {
+        scopes.close();
+    }
+    scopes.errors
+}
+
+/// Checks if an attribute is `[[maybe_unused]]`.
+fn is_maybe_unused(attr: &Attribute) -> bool {
+    matches!(
+        attr,
+        Attribute::Standard(StandardAttribute {
+            name,
+            namespace: None,
+            ..
+        }) if name == "maybe_unused" || name == "__maybe_unused__"
+    )
+}
@@ -0,0 +1,260 @@
+//! Module to walk the [`Ast`] for the passes that run after the parsing.
+//!
+//! A pass implements [`Visit`], or [`VisitMut`] if it modifies the tree, and
+//! only overrides the methods of the nodes it is interested in. The default
+//! methods call the `walk` functions, that visit all the children of the node
+//! in the order of the source. An overridden method can call the `walk`
+//! function itself, to go on with the children of the node.
+//!
+//! Some nodes aren't expressions, and their children are skipped:
+//!
+//! - the bodies of the structs, unions and enums, that declare members and
+//!   constants;
+//! - the member of a member access, like `x` in `s.x`;
+//! - the type names, like the operands of `sizeof(int)`.
+
+#![expect(
+    clippy::arbitrary_source_item_ordering,
+    reason = "the two visitors are generated one after the other"
+)]
+
+use super::keyword::control_flow::node::ControlFlowNode;
+use super::types::array::{ArrayDesignator, ArrayLength, ArraySize};
+use super::types::binary::{Binary, BinaryOperator};
+use super::types::braced_blocks::BracedBlock;
+use super::types::builtin::{AlignOf, BuiltinArgument, BuiltinCall, Typeof};
+use super::types::literal::{Attribute, Literal, Variable};
+use super::types::ternary::Ternary;
+use super::types::unary::Unary;
+use super::types::{
+    Ast, CaseRange, CompoundLiteral, ExpressionCall, FunctionCall, ListInitialiser, StatementExpression
+};
+
+/// Defines a visitor trait and its `walk` functions.
+///
+/// The same walk is generated for the shared and the mutable references: the
+/// shared references carry the lifetime of the tree, so that a pass can keep
+/// references to the nodes.
+macro_rules! define_visitor {
+    (
+        $(#[$doc:meta])*
+        $visit:ident$(<$lifetime:lifetime>)?,
+        $walk:ident,
+        $walk_block:ident,
+        $walk_control_flow:ident,
+        $walk_variable:ident,
+        $get_inner:ident
+        $(, $mutability:tt)?
+    ) => {
+        $(#[$doc])*
+        pub(super) trait $visit$(<$lifetime>)? {
+            /// Visits an [`Ast`].
+            fn visit(&mut self, node: &$($lifetime)? $($mutability)? Ast) {
+                $walk(self, node);
+            }
+
+            /// Visits a [`BracedBlock`], from a block or a statement
+            /// expression.
+            fn visit_block(&mut self, block: &$($lifetime)? $($mutability)? BracedBlock) {
+                $walk_block(self, block);
+            }
+
+            /// Visits a [`ControlFlowNode`].
+            fn visit_control_flow(&mut self, ctrl: &$($lifetime)? $($mutability)? ControlFlowNode) {
+                $walk_control_flow(self, ctrl);
+            }
+
+            /// Visits a [`Variable`], from a leaf or a function call.
+            fn visit_variable(&mut self, variable: &$($lifetime)? $($mutability)? Variable) {
+                $walk_variable(self, variable);
+            }
+        }
+
+        /// Visits the children of an [`Ast`].
+        pub(super) fn $walk<$($lifetime,)? V>(
+            visitor: &mut V,
+            node: &$($lifetime)? $($mutability)? Ast,
+        )
+        where
+            V: $visit$(<$lifetime>)? + ?Sized,
+        {
+            match node {
+                Ast::AlignOf(AlignOf(operand)) => {
+                    if let BuiltinArgument::Expression(expression) = &$($mutability)? **operand {
+                        visitor.visit(expression);
+                    }
+                }
+                Ast::ArrayDesignator(ArrayDesignator { hi, lo }) => {
+                    visitor.visit(lo);
+                    if let Some(hi_node) = hi {
+                        visitor.visit(hi_node);
+                    }
+                }
+                Ast::ArraySize(ArraySize {
+                    length: ArrayLength::Constant(length) | ArrayLength::Variable(length),
+                    ..
+                }) => visitor.visit(length),
+                Ast::Binary(Binary {
+                    op:
+                        BinaryOperator::StructEnumMemberAccess
+                        | BinaryOperator::StructEnumMemberPointerAccess,
+                    arg_l,
+                    ..
+                }) => visitor.visit(arg_l),
+                Ast::Binary(Binary { arg_l, arg_r, .. }) => {
+                    visitor.visit(arg_l);
+                    visitor.visit(arg_r);
+                }
+                Ast::BracedBlock(block) | Ast::StatementExpression(StatementExpression(block)) => {
+                    visitor.visit_block(block);
+                }
+                Ast::BuiltinCall(BuiltinCall { args, .. }) => {
+                    for arg in args {
+                        if let BuiltinArgument::Expression(expression) = arg {
+                            visitor.visit(expression);
+                        }
+                    }
+                }
+                Ast::CaseRange(CaseRange { hi, lo }) => {
+                    visitor.visit(lo);
+                    visitor.visit(hi);
+                }
+                Ast::CompoundLiteral(CompoundLiteral {
+                    initialiser: ListInitialiser { elts, .. },
+                    ..
+                })
+                | Ast::FunctionArgsBuild(elts)
+                | Ast::ListInitialiser(ListInitialiser { elts, .. }) => {
+                    for elt in elts {
+                        visitor.visit(elt);
+                    }
+                }
+                Ast::ControlFlow(ctrl) => visitor.visit_control_flow(ctrl),
+                Ast::ExpressionCall(ExpressionCall { args, callee }) => {
+                    visitor.visit(callee);
+                    for arg in args {
+                        visitor.visit(arg);
+                    }
+                }
+                Ast::FunctionCall(FunctionCall { args, variable, .. }) => {
+                    visitor.visit_variable(variable);
+                    for arg in args {
+                        visitor.visit(arg);
+                    }
+                }
+                Ast::Leaf(Literal::Variable(variable)) => visitor.visit_variable(variable),
+                Ast::ArraySize(_)
+                | Ast::Empty
+                | Ast::Label(_)
+                | Ast::Leaf(_)
+                | Ast::MemberDesignator(_) => (),
+                Ast::ParensBlock(parens) => visitor.visit(parens.$get_inner()),
+                Ast::Ternary(Ternary {
+                    condition,
+                    failure,
+                    success,
+                    ..
+                }) => {
+                    visitor.visit(condition);
+                    visitor.visit(success);
+                    if let Some(failure_node) = failure {
+                        visitor.visit(failure_node);
+                    }
+                }
+                Ast::Unary(Unary { arg, .. }) => visitor.visit(arg),
+            }
+        }
+
+        /// Visits the elements of a [`BracedBlock`].
+        pub(super) fn $walk_block<$($lifetime,)? V>(
+            visitor: &mut V,
+            block: &$($lifetime)? $($mutability)? BracedBlock,
+        )
+        where
+            V: $visit$(<$lifetime>)? + ?Sized,
+        {
+            for elt in &$($mutability)? block.elts {
+                visitor.visit(elt);
+            }
+        }
+
+        /// Visits the children of a [`ControlFlowNode`].
+        ///
+        /// The bodies of the structs, unions and enums are skipped.
+        pub(super) fn $walk_control_flow<$($lifetime,)? V>(
+            visitor: &mut V,
+            ctrl: &$($lifetime)? $($mutability)? ControlFlowNode,
+        )
+        where
+            V: $visit$(<$lifetime>)? + ?Sized,
+        {
+            match ctrl {
+                ControlFlowNode::Ast(_, node) | ControlFlowNode::ColonAst(_, Some(node)) => {
+                    visitor.visit(node);
+                }
+                ControlFlowNode::ControlFlow(_, Some(inner)) => visitor.visit_control_flow(inner),
+                ControlFlowNode::ParensBlock(_, parens, block) => {
+                    if let Some(condition) = parens {
+                        visitor.visit(condition.$get_inner());
+                    }
+                    if let Some(body) = block {
+                        visitor.visit_block(body);
+                    }
+                }
+                ControlFlowNode::ValueColonAst(_, value, statement) => {
+                    visitor.visit(value);
+                    if let Some(node) = statement {
+                        visitor.visit(node);
+                    }
+                }
+                ControlFlowNode::ColonAst(_, None)
+                | ControlFlowNode::ControlFlow(_, None)
+                | ControlFlowNode::IdentBlock(..)
+                | ControlFlowNode::SemiColon(_) => (),
+            }
+        }
+
+        /// Visits the operands of the `typeof` specifiers of a [`Variable`],
+        /// like `a` in `typeof(a) b`.
+        pub(super) fn $walk_variable<$($lifetime,)? V>(
+            visitor: &mut V,
+            variable: &$($lifetime)? $($mutability)? Variable,
+        )
+        where
+            V: $visit$(<$lifetime>)? + ?Sized,
+        {
+            for attr in &$($mutability)? variable.attrs {
+                if let Attribute::Typeof(Typeof { operand, .. }) = attr
+                    && let BuiltinArgument::Expression(expression) = &$($mutability)? **operand
+                {
+                    visitor.visit(expression);
+                }
+            }
+        }
+    };
+}
+
+define_visitor!(
+    /// Visitor of an [`Ast`], by shared references.
+    ///
+    /// See the [module documentation](self) for more information.
+    Visit<'node>,
+    walk,
+    walk_block,
+    walk_control_flow,
+    walk_variable,
+    get_inner
+);
+
+define_visitor!(
+    /// Visitor of an [`Ast`], by mutable references.
+    ///
+    /// See the [module documentation](self) for more information.
+    VisitMut,
+    walk_mut,
+    walk_block_mut,
+    walk_control_flow_mut,
+    walk_variable_mut,
+    get_inner_mut,
+    mut
+);
@@ -0,0 +1,8 @@
+int int_max = 0b01111111111111111111111111111111;
+unsigned int int_max_plus_one = 0b10000000000000000000000000000000;
+unsigned int uint_max = 0B11111111111111111111111111111111;
+long uint_max_plus_one = 0b100000000000000000000000000000000;
+int int_min = -0b10000000000000000000000000000000;
+unsigned int unsigned_suffix = 0b1010u;
+unsigned long long ullong_max =
+    0b1111111111111111111111111111111111111111111111111111111111111111ULL;
@@ -5,17 +5,10 @@ int main()
     // An unusual ternary inside a printf
     printf("Result: %d\n", (a > b) ? (a * b) : (a + b));
 
-    // Using macros with inline assembly
+    // Using macros
     c = A(b, c);
     printf("Macro A result: %d\n", c);
 
-    __asm__(
-        "movl %0, %%eax"
-        "addl $10, %%eax"
-        : "=a"(c)
-        : "r"(c));
-    printf("Inline ASM result: %d\n", c);
-
     // Nested preprocessor conditionals
 
     // Testing a for loop with odd increment/decrement
@@ -0,0 +1,111 @@
+use c_parser::*;
+
+#[test]
+fn compile_error_display() {
+    let content = "int m@in() { }";
+    let (_, errors) = lex_file(content, &mut Location::from("filename.c")).into_parts();
+    assert!(errors.len() == 1);
+    let displayed = errors[0].to_string();
+    assert!(
+        displayed == "filename.c:1:6: error[E0015]: Character '@' not supported.",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn borrow_errors() {
+    let res = lex_file("_Bool b;", &mut Location::from("filename.c"));
+    let errors = res.errors();
+    assert!(errors.len() == 1);
+    assert!(!errors[0].is_error());
+    assert!(res.result().len() == 3);
+    let (tokens, _) = res.into_parts();
+    let (node, errors) = parse_tokens(tokens).into_parts();
+    assert!(errors.is_empty(), "{errors:?}");
+    assert!(node.to_string() == "[(bool b), \u{2205} ..]", "{node}");
+}
+
+#[test]
+fn error_codes() {
+    let content = "s = \"\\q\";\ns = \"\\u12\";\nc = 'ab';\n_Bool b;";
+    let (_, errors) = lex_file(content, &mut Location::from("")).into_parts();
+    let codes = errors
+        .iter()
+        .map(CompileError::get_code)
+        .collect::<Vec<_>>();
+    assert!(
+        codes
+            == [
+                Some(ErrorCode::InvalidEscape),
+                Some(ErrorCode::EscapeTooShort),
+                Some(ErrorCode::CharTooLong),
+                None
+            ],
+        "{codes:?}"
+    );
+    let displayed = errors[0].to_string();
+    assert!(
+        displayed
+            == ":1:7: error[E0007]: Character 'q' can not be escaped, even inside a string or a char.",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn json_errors() {
+    let content = "_Bool b;\nint m@in() { }";
+    let res = lex_file(content, &mut Location::from("dir\\\"file\".c"));
+    let json = res.get_json_errors();
+    let diagnostics = json
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert!(diagnostics.len() == 2, "{json}");
+    let error = &diagnostics[1];
+    assert!(error["file"] == "dir\\\"file\".c", "{error}");
+    assert!(error["line"] == 2, "{error}");
+    assert!(error["col"] == 6, "{error}");
+    assert!(error["length"] == 1, "{error}");
+    assert!(error["level"] == "error", "{error}");
+    assert!(error["code"] == "E0015", "{error}");
+    assert!(
+        error["message"] == "Character '@' not supported.",
+        "{error}"
+    );
+    assert!(diagnostics[0]["level"] == "warning", "{json}");
+    assert!(diagnostics[0]["code"].is_null(), "{json}");
+    let (tokens, _) = lex_file("a, b;", &mut Location::from("")).into_parts();
+    let json = parse_tokens(tokens).get_json_errors();
+    let suggestion = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+    assert!(suggestion["level"] == "suggestion", "{suggestion}");
+}
+
+#[test]
+fn promote_warning() {
+    let (_, errors) = lex_file("_Bool b;", &mut Location::from("filename.c")).into_parts();
+    assert!(errors.len() == 1);
+    let warning = errors.into_iter().next().unwrap();
+    assert!(!warning.is_error());
+    let error = warning.promote_to(ErrorLevel::Failure);
+    assert!(error.is_error());
+    let displayed = error.to_string();
+    assert!(
+        displayed
+            == "filename.c:1:1: error: Underscore operators are deprecated since C23. Consider using the new keyword: bool",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn resolve_error_token() {
+    let content = "int x = 1;\nint y = x 3;";
+    let (tokens, errors) = lex_file(content, &mut Location::from("filename.c")).into_parts();
+    assert!(errors.is_empty());
+    let (_, errors) = parse_tokens(tokens.clone()).into_parts();
+    assert!(errors.len() == 1);
+    assert!(errors[0].get_token_index() == Some(9));
+    let token = errors[0].resolve_token(&tokens).unwrap();
+    assert!(token.get_value() == &TokenValue::Number(Number::Int(3)));
+    let (_, lex_errors) = lex_file("int m@in() { }", &mut Location::from("")).into_parts();
+    assert!(lex_errors[0].get_token_index().is_none());
+}
@@ -5,35 +5,44 @@ mod files {
 
     const PREFIX: &str = "./tests/data/";
 
+    /// Lexes and parses a data file, and checks that neither the lexer nor
+    /// the parser raise any error, warning or suggestion.
     #[expect(clippy::unwrap_used)]
-    fn test_file(file: &str, parser_works: bool) {
+    fn test_file(file: &str) {
         let path = format!("{PREFIX}{file}.c");
         let content = fs::read_to_string(&path).unwrap();
         let mut location = Location::from(path.clone());
         let files: &[(String, &str)] = &[(path, &content)];
-        let tokens = lex_file(&content, &mut location).unwrap_or_display(files, "lexer");
-        if parser_works {
-            let _tree = parse_tokens(tokens).unwrap_or_display(files, "parser");
-        }
+        let res = lex_file(&content, &mut location);
+        let lexer_errors = res.get_displayed_errors(files, "lexer");
+        assert!(lexer_errors.is_empty(), "Lexer errors:\n{lexer_errors}");
+        let tokens = res.unwrap_or_display(files, "lexer");
+        let parser_errors = parse_tokens(tokens).get_displayed_errors(files, "parser");
+        assert!(parser_errors.is_empty(), "Parser errors:\n{parser_errors}");
+    }
+
+    #[test]
+    fn binary() {
+        test_file("binary");
     }
 
     #[test]
     fn escape() {
-        test_file("escape", true);
+        test_file("escape");
     }
 
     #[test]
     fn general() {
-        test_file("general", false);
+        test_file("general");
     }
 
     #[test]
     fn operators() {
-        test_file("operators", true);
+        test_file("operators");
     }
 
     #[test]
     fn no_control_flow() {
-        test_file("no-control-flow", true);
+        test_file("no-control-flow");
     }
 }
@@ -0,0 +1,86 @@
+use c_parser::*;
+
+const SEP: &str = "\n--------------------\n";
+
+fn test_header(content: &str, output: &str, displayed_errors: &str) {
+    let files = &[(String::new(), content)];
+    let mut location = Location::from(String::new());
+    let tokens = lex_file(content, &mut location).unwrap_or_display(files, "lexer");
+    let res = parse_header(tokens);
+    let displayed = res.get_displayed_errors(files, "parser");
+    assert!(
+        displayed_errors == displayed,
+        "{SEP}Mismatch! Expected:\n!{displayed_errors}!\n!= Computed\n!{displayed}!{SEP}"
+    );
+    let node = res.unwrap_or_display(files, "parser");
+    assert!(
+        output == format!("{node}"),
+        "{SEP}Mismatch! Expected:\n{output}\n!= Computed\n{node}{SEP}"
+    );
+}
+
+#[test]
+fn declarations() {
+    test_header(
+        "
+int f(int x);
+struct S { int a; };
+enum E { A, B };
+typedef struct S T;
+",
+        "[((int f)\u{b0}((int x))), \u{2205} , [(struct \u{2205}  \u{2205} )], S, [(int a), \u{2205} ], ((enum E {A = 0, B = 1}) \u{2205} ), \u{2205} , [(typedef \u{2205} )], [(struct \u{2205}  \u{2205} )], ('S' T), \u{2205} ..]",
+        "",
+    );
+}
+
+#[test]
+fn function_body() {
+    test_header(
+        "int g(void);\nint f(int x) { return x; }",
+        "[((int g)\u{b0}((void \u{2205} ))), ((int f)\u{b0}((int x))), [[(return)], x, \u{2205} ]..]",
+        ":2:14: parser warning: Found function definition in header: headers should only contain declarations. Consider moving the body of the function to a source file.
+    2 | int f(int x) { return x; }
+                     ^
+",
+    );
+}
+
+#[test]
+fn kr_function_body() {
+    let content = "int f(a) int a; { return a; }";
+    let files = &[(String::new(), content)];
+    let mut location = Location::from(String::new());
+    let tokens = lex_file(content, &mut location).unwrap_or_display(files, "lexer");
+    let displayed = parse_header(tokens).get_displayed_errors(files, "parser");
+    let expected = ":1:5: parser error: Found a K&R function definition, but they were removed in C23. Please declare the types of the parameters inside the parenthesis, or enable the K&R definitions.
+    1 | int f(a) int a; { return a; }
+            ^
+:1:17: parser warning: Found function definition in header: headers should only contain declarations. Consider moving the body of the function to a source file.
+    1 | int f(a) int a; { return a; }
+                        ^
+";
+    assert!(
+        expected == displayed,
+        "{SEP}Mismatch! Expected:\n!{expected}!\n!= Computed\n!{displayed}!{SEP}"
+    );
+}
+
+#[test]
+fn kr_function_body_c89() {
+    let content = "int f(a) int a; { return a; }";
+    let files = &[(String::new(), content)];
+    let options = Options::default().with_standard(CStandard::C89);
+    let mut location = Location::from(String::new());
+    let tokens =
+        lex_file_with_options(content, &mut location, options).unwrap_or_display(files, "lexer");
+    let displayed =
+        parse_header_with_options(tokens, options).get_displayed_errors(files, "parser");
+    let expected = ":1:17: parser warning: Found function definition in header: headers should only contain declarations. Consider moving the body of the function to a source file.
+    1 | int f(a) int a; { return a; }
+                        ^
+";
+    assert!(
+        expected == displayed,
+        "{SEP}Mismatch! Expected:\n!{expected}!\n!= Computed\n!{displayed}!{SEP}"
+    );
+}
@@ -0,0 +1,150 @@
+use c_parser::*;
+
+fn test_macros(content: &str, expected: &[(&str, Option<&[&str]>, &str)]) {
+    let files = &[(String::new(), content)];
+    let (res, macros) = lex_file_with_macros(
+        content,
+        &mut Location::from(String::new()),
+        Options::default(),
+    );
+    res.unwrap_or_display(files, "lexer");
+    let displayed = macros
+        .iter()
+        .filter(|found| !found.is_predefined())
+        .map(|found| {
+            (
+                found.get_name().to_owned(),
+                found
+                    .get_params()
+                    .map(|params| params.iter().map(String::as_str).collect::<Vec<_>>()),
+                display_tokens(found.get_value()),
+            )
+        })
+        .collect::<Vec<_>>();
+    let expected = expected
+        .iter()
+        .map(|(name, params, value)| {
+            (
+                (*name).to_owned(),
+                params.map(<[&str]>::to_vec),
+                (*value).to_owned(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        expected == displayed,
+        "Mismatch! Expected:\n!{expected:?}!\n!= Computed\n!{displayed:?}!"
+    );
+}
+
+#[test]
+fn macros_defined() {
+    test_macros(
+        "#define N 10\n#define MAX(a, b) a > b ? a : b\nint x;\n",
+        &[
+            ("N", None, "[10]"),
+            (
+                "MAX",
+                Some(&["a", "b"]),
+                "[Ident(a), Gt, Ident(b), Interrogation, Ident(a), Colon, Ident(b)]",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn macros_digraph() {
+    test_macros(
+        "%:define ARR(n) int arr<:n:> = <%0%>\n",
+        &[(
+            "ARR",
+            Some(&["n"]),
+            "[Keyword(int), Ident(arr), BracketOpen, Ident(n), BracketClose, Assign, BraceOpen, 0, BraceClose]",
+        )],
+    );
+}
+
+#[test]
+fn macros_variadic() {
+    test_macros(
+        "#define LOG(fmt, ...) printf(fmt, ## __VA_ARGS__)\n#define ALL(...) __VA_ARGS__\n",
+        &[
+            (
+                "LOG",
+                Some(&["fmt", "..."]),
+                "[Ident(printf), ParenthesisOpen, Ident(fmt), Comma, HashHash, Ident(__VA_ARGS__), ParenthesisClose]",
+            ),
+            ("ALL", Some(&["..."]), "[Ident(__VA_ARGS__)]"),
+        ],
+    );
+}
+
+#[test]
+fn macros_undefined() {
+    test_macros(
+        "#define N 10\n#define M (1)\n#undef N\n#define N 2\n",
+        &[
+            ("M", None, "[ParenthesisOpen, 1, ParenthesisClose]"),
+            ("N", None, "[2]"),
+        ],
+    );
+}
+
+#[test]
+fn macros_predefined() {
+    let content = "int a;\n#if __LINE__ == 2 && defined(__FILE__)\nint b;\n#endif\n";
+    let files = &[(String::new(), content)];
+    let (res, macros) = lex_file_with_macros(
+        content,
+        &mut Location::from(String::new()),
+        Options::default(),
+    );
+    let tokens = res.unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    let expected = "[Keyword(int), Ident(a), SemiColon, Keyword(int), Ident(b), SemiColon]";
+    assert!(
+        expected == displayed,
+        "Mismatch! Expected:\n!{expected}!\n!= Computed\n!{displayed}!"
+    );
+    assert!(
+        macros
+            .iter()
+            .filter(|found| found.is_predefined())
+            .map(Macro::get_name)
+            .eq(["__DATE__", "__FILE__", "__LINE__", "__TIME__"])
+    );
+}
+
+fn test_predefined(content: &str, expected: &str) {
+    let files = &[(String::from("main.c"), content)];
+    let tokens = lex_file(content, &mut Location::from("main.c")).unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(
+        expected == displayed,
+        "Mismatch! Expected:\n!{expected}!\n!= Computed\n!{displayed}!"
+    );
+}
+
+#[test]
+fn predefined_line_file() {
+    test_predefined(
+        "int a = __LINE__;\n\nint b = __LINE__; char *f = __FILE__ \":\";",
+        "[Keyword(int), Ident(a), Assign, 1, SemiColon, Keyword(int), Ident(b), Assign, 3, SemiColon, Keyword(char), Star, Ident(f), Assign, \"main.c:\", SemiColon]",
+    );
+}
+
+#[test]
+fn predefined_date_time() {
+    test_predefined(
+        "__DATE__, __TIME__",
+        "[\"??? ?? ????\", Comma, \"??:??:??\"]",
+    );
+}
+
+#[test]
+fn predefined_line_in_macro() {
+    test_predefined(
+        "#define L __LINE__\n\n#if L == 3\nint x;\n#endif",
+        "[Keyword(int), Ident(x), SemiColon]",
+    );
+}
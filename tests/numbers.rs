@@ -58,7 +58,6 @@ gen_number_test!(
     numbers_24: "7.89E-2" => Number::Double(7.89e-2);
     numbers_25: "1.23E+100" => Number::Double(1.23e100);
     numbers_26: "1.23F" => Number::Float(1.23);
-    // numbers_27: "4.56L" => Number::LongDouble(4.56); // long double not supported
     numbers_28: ".5" => Number::Double(0.5);
     numbers_29: "5." => Number::Double(5.);
     numbers_30: "1e10" => Number::Double(1e10);
@@ -70,4 +69,207 @@ gen_number_test!(
     numbers_36: "123.456f" => Number::Float(123.456);
     numbers_37: "789.0123" => Number::Double(789.0123);
     numbers_38: "0.0001e5f" => Number::Float(10.);
+    numbers_39: "123ull" => Number::ULongLong(123);
+    numbers_40: "1'000" => Number::Int(1000);
+    numbers_41: "0xFF'FF" => Number::Int(65535);
+    numbers_42: "0B101" => Number::Int(5);
+    numbers_43: "0b01111111111111111111111111111111" => Number::Int(2147483647);
+    numbers_44: "0b10000000000000000000000000000000" => Number::UInt(2147483648);
+    numbers_45: "0b11111111111111111111111111111111" => Number::UInt(4294967295);
+    numbers_46: "0b100000000000000000000000000000000" => Number::ULong(4294967296);
+    numbers_47: "0.0" => Number::Double(0.);
+    numbers_48: "0x1.8p3" => Number::Double(12.);
+    numbers_49: "0x.8P1" => Number::Double(1.);
+    numbers_50: "0x1p-1022" => Number::Double(f64::MIN_POSITIVE);
+    numbers_51: "0x0.fffffffffffffp-1022" => Number::Double(f64::from_bits(0x000f_ffff_ffff_ffff));
+    numbers_52: "0x1p-1074" => Number::Double(f64::from_bits(1));
+    numbers_53: "0x1.8p-1075" => Number::Double(f64::from_bits(1));
+    numbers_54: "0x1p-149f" => Number::Float(f32::from_bits(1));
+    numbers_55: "0x1.fffffffffffffp1023" => Number::Double(f64::MAX);
+    numbers_56: "0x1.00000000000008p0" => Number::Double(1.);
+    numbers_57: ".5e3" => Number::Double(500.);
+    numbers_58: ".5f" => Number::Float(0.5);
+    numbers_59: ".25E-1F" => Number::Float(0.025);
+    numbers_60: "0X1.8P3" => Number::Double(12.);
+    numbers_61: "0XFF" => Number::Int(255);
 );
+
+#[test]
+fn digit_separators_chars() {
+    let content = "x = 1'000; c = 'a'; y = x'5';";
+    let path = String::new();
+    let mut location = Location::from(path.as_str());
+    let tokens = lex_file(content, &mut location).unwrap_or_display(&[(path, content)], "lexer");
+    let displayed = display_tokens(&tokens);
+    let expected = "[Ident(x), Assign, 1000, SemiColon, Ident(c), Assign, 'a', SemiColon, Ident(y), Assign, Ident(x), '5', SemiColon]";
+    assert!(
+        displayed == expected,
+        "Mismatch! Expected: {expected}\n != Computed: {displayed}"
+    );
+}
+
+#[test]
+fn leading_dot_floats() {
+    let content = "x = a.b + .5e3 + s..5;";
+    let path = String::new();
+    let mut location = Location::from(path.as_str());
+    let options = Options::default().with_lexemes(true);
+    let tokens = lex_file_with_options(content, &mut location, options)
+        .unwrap_or_display(&[(path, content)], "lexer");
+    let displayed = display_tokens(&tokens);
+    let expected = "[Ident(x), Assign, Ident(a), Dot, Ident(b), Plus, 500, Plus, Ident(s), Dot, 0.5, SemiColon]";
+    assert!(
+        displayed == expected,
+        "Mismatch! Expected: {expected}\n != Computed: {displayed}"
+    );
+    // the full stop is the first character of the constant
+    let lexemes = tokens
+        .iter()
+        .filter_map(|token| token.get_lexeme())
+        .collect::<Vec<_>>();
+    assert!(
+        lexemes
+            == [
+                "x", "=", "a", ".", "b", "+", ".5e3", "+", "s", ".", ".5", ";"
+            ],
+        "Mismatch! Computed: {lexemes:?}"
+    );
+}
+
+#[test]
+fn float_spelled_identifiers() {
+    let content = "x = inf + nan + infinity;";
+    let path = String::new();
+    let mut location = Location::from(path.as_str());
+    let tokens = lex_file(content, &mut location).unwrap_or_display(&[(path, content)], "lexer");
+    let displayed = display_tokens(&tokens);
+    let expected =
+        "[Ident(x), Assign, Ident(inf), Plus, Ident(nan), Plus, Ident(infinity), SemiColon]";
+    assert!(
+        displayed == expected,
+        "Mismatch! Expected: {expected}\n != Computed: {displayed}"
+    );
+}
+
+#[test]
+fn negative_zero() {
+    // the minus is an operator, so the constant itself is a positive zero
+    let content = "-0.0";
+    let path = String::new();
+    let mut location = Location::from(path.as_str());
+    let tokens = lex_file(content, &mut location).unwrap_or_display(&[(path, content)], "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(displayed == "[Minus, 0]", "Mismatch! Computed: {displayed}");
+    let Some(TokenValue::Number(Number::Double(zero))) =
+        tokens.last().map(|token| token.get_value())
+    else {
+        panic!("Lexer error: waiting for Double, but lexer returned {displayed}")
+    };
+    assert!(zero.is_sign_positive(), "Lexer error: 0.0 was read as -0.0");
+    let source = Number::Double(-*zero).to_source();
+    assert!(
+        source == "-0.0",
+        "Mismatch! Expected: -0.0\n != Computed: {source}"
+    );
+}
+
+fn test_number_source(content: &str, expected: &str) {
+    let path = String::new();
+    let mut location = Location::from(path.as_str());
+    let tokens = lex_file(content, &mut location).unwrap_or_display(&[(path, content)], "lexer");
+    let Some(TokenValue::Number(nb)) = tokens.first().map(|token| token.get_value()) else {
+        panic!(
+            "Lexer error: waiting for Number, but lexer returned {}",
+            display_tokens(&tokens)
+        )
+    };
+    let source = nb.to_source();
+    assert!(
+        source == expected,
+        "Mismatch! Expected: {expected}\n != Computed: {source}"
+    );
+    test_number(&source, nb.clone());
+}
+
+#[test]
+fn number_source_double() {
+    test_number_source("1.0", "1.0");
+    test_number_source("1e10", "10000000000.0");
+    test_number_source("1e+1000", "1e999");
+}
+
+#[test]
+fn number_source_long_double() {
+    test_number_source("4.56L", "4.56L");
+    test_number_source("4.56l", "4.56L");
+    test_number_source("0.1L", "0.1L");
+    // doesn't fit in a double, but fits in a long double
+    test_number_source("1e400L", "1e400L");
+    test_number_source(
+        "1.18973149535723176508575932662800702e4932L",
+        "1.189731495357231765085759326628007e4932L",
+    );
+    test_number_source("1e5000L", "1e99999L");
+}
+
+#[test]
+fn long_double_hexadecimal() {
+    let content = "1.5L 0x1.8p0L 1e-4950L 0x57c9647e1a018p-16494L";
+    let path = String::new();
+    let mut location = Location::from(path.as_str());
+    let tokens = lex_file(content, &mut location).unwrap_or_display(&[(path, content)], "lexer");
+    let values: Vec<_> = tokens.iter().map(|token| token.get_value()).collect();
+    let [
+        decimal,
+        hexadecimal,
+        decimal_subnormal,
+        hexadecimal_subnormal,
+    ] = values.as_slice()
+    else {
+        panic!(
+            "Lexer error: expected 4 numbers, but lexer returned {}",
+            display_tokens(&tokens)
+        )
+    };
+    assert!(
+        decimal == hexadecimal,
+        "Mismatch! {decimal:?} != {hexadecimal:?}"
+    );
+    assert!(
+        decimal_subnormal == hexadecimal_subnormal,
+        "Mismatch! {decimal_subnormal:?} != {hexadecimal_subnormal:?}"
+    );
+}
+
+#[test]
+fn number_source_float() {
+    test_number_source("1.5f", "1.5f");
+    test_number_source("1.0f", "1.0f");
+}
+
+#[test]
+fn number_source_int() {
+    test_number_source("42", "42");
+    test_number_source("0xAu", "10U");
+    test_number_source("7ull", "7ULL");
+}
+
+#[test]
+fn number_fits_in() {
+    assert!(Number::Int(300).fits_in(&NumberType::Int));
+    assert!(Number::Int(300).fits_in(&NumberType::UInt));
+    assert!(Number::Int(300).fits_in(&NumberType::Float));
+    assert!(!Number::Int(-300).fits_in(&NumberType::ULongLong));
+    assert!(!Number::ULongLong(1 << 32).fits_in(&NumberType::UInt));
+    assert!(Number::ULongLong(u64::MAX).fits_in(&NumberType::ULongLong));
+    assert!(!Number::ULongLong(u64::MAX).fits_in(&NumberType::LongLong));
+    assert!(!Number::ULongLong(u64::MAX).fits_in(&NumberType::Double));
+    assert!(Number::Double(-2.0).fits_in(&NumberType::Int));
+    assert!(!Number::Double(-2.5).fits_in(&NumberType::Int));
+    assert!(!Number::Double(-2.0).fits_in(&NumberType::UInt));
+    assert!(!Number::Double(f64::INFINITY).fits_in(&NumberType::LongLong));
+    assert!(Number::Double(f64::INFINITY).fits_in(&NumberType::Float));
+    assert!(Number::Double(0.5).fits_in(&NumberType::Float));
+    assert!(!Number::Double(0.1).fits_in(&NumberType::Float));
+    assert!(Number::Float(0.1).fits_in(&NumberType::Double));
+}
@@ -0,0 +1,1130 @@
+use c_parser::*;
+
+fn test_options_errors(content: &str, options: Options, expected: &str) {
+    let files = &[(String::new(), content)];
+    let mut location = Location::from(String::new());
+    let displayed =
+        lex_file_with_options(content, &mut location, options).get_displayed_errors(files, "lexer");
+    assert!(
+        expected == displayed,
+        "Mismatch! Expected:\n!{expected}!\n!= Computed\n!{displayed}!"
+    );
+}
+
+fn test_options_tokens(content: &str, options: Options, expected: &str) {
+    let files = &[(String::new(), content)];
+    let mut location = Location::from(String::new());
+    let tokens =
+        lex_file_with_options(content, &mut location, options).unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(
+        expected == displayed,
+        "Mismatch! Expected:\n!{expected}!\n!= Computed\n!{displayed}!"
+    );
+}
+
+fn test_options_parser(content: &str, options: Options, expected: &str, expected_errors: &str) {
+    let files = &[(String::new(), content)];
+    let mut location = Location::from(String::new());
+    let tokens =
+        lex_file_with_options(content, &mut location, options).unwrap_or_display(files, "lexer");
+    let res = parse_tokens_with_options(tokens, options);
+    let displayed_errors = res.get_displayed_errors(files, "parser");
+    assert!(
+        expected_errors == displayed_errors,
+        "Mismatch! Expected:\n!{expected_errors}!\n!= Computed\n!{displayed_errors}!"
+    );
+    let (node, _) = res.into_parts();
+    let displayed = node.to_string();
+    assert!(
+        expected == displayed,
+        "Mismatch! Expected:\n!{expected}!\n!= Computed\n!{displayed}!"
+    );
+}
+
+#[test]
+fn ambiguous_escapes() {
+    let content = "x = \"\\xabc\";";
+    let options = Options::default().with_ambiguous_escapes(true);
+    test_options_errors(
+        content,
+        options,
+        ":1:10: lexer suggestion[E0001]: Found hexadecimal digit 'c' after the escape sequence '\\xab': it is read as a character, as the escape sequence contains at most 2 digits, but in standard C it would be part of the escape sequence. Consider splitting the string, like \"\\xab\" \"c\", or using '\\u00ab'.
+    1 | x = \"\\xabc\";
+                 ^
+",
+    );
+    let (tokens, _) = lex_file_with_options(content, &mut Location::from(""), options).into_parts();
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed == "[Ident(x), Assign, \"\u{ab}c\", SemiColon]",
+        "{displayed}"
+    );
+}
+
+#[test]
+fn ambiguous_escapes_disabled() {
+    test_options_tokens(
+        "x = \"\\xabc\";",
+        Options::default(),
+        "[Ident(x), Assign, \"\u{ab}c\", SemiColon]",
+    );
+}
+
+#[test]
+fn continuation_whitespace() {
+    test_options_errors(
+        "int x = 1 + \\ \n2;",
+        Options::default(),
+        ":1:14: lexer suggestion[E0013]: Found white space after '\\' at end of line: it was treated as a line continuation, but in standard C the '\\' must be directly followed by the newline. Please remove the space.
+    1 | int x = 1 + \\ 
+                     ^
+",
+    );
+}
+
+#[test]
+fn continuation_whitespace_pedantic() {
+    test_options_errors(
+        "int x = 1 + \\ \n2;",
+        Options::default().with_pedantic(true),
+        ":1:14: lexer error[E0013]: Found white space after '\\' at end of line: this is not a line continuation, as the '\\' must be directly followed by the newline. Please remove the space.
+    1 | int x = 1 + \\ 
+                     ^
+",
+    );
+}
+
+#[test]
+fn alignof_expression() {
+    test_options_parser(
+        "x = alignof(y);",
+        Options::default(),
+        "[(x = (alignof\u{b0}(y))), \u{2205} ..]",
+        ":1:5: parser warning: Found an expression as operand of 'alignof', but standard C only allows type names. This is a GNU extension.
+    1 | x = alignof(y);
+            ^~~~~~~
+",
+    );
+    test_options_parser(
+        "x = alignof(y);",
+        Options::default().with_pedantic(true),
+        "[..]",
+        ":1:5: parser error: Found an expression as operand of 'alignof', but standard C only allows type names. This is a GNU extension.
+    1 | x = alignof(y);
+            ^~~~~~~
+",
+    );
+}
+
+#[test]
+fn line_comment_c89() {
+    test_options_errors(
+        "int x; // comment",
+        Options::default().with_standard(CStandard::C89),
+        ":1:8: lexer warning[E0010]: Line comments were introduced in C99, but the standard is C89. Consider using '/* */' instead.
+    1 | int x; // comment
+               ^~
+",
+    );
+}
+
+#[test]
+fn line_comment_c89_pedantic() {
+    test_options_errors(
+        "int x; // comment",
+        Options::default()
+            .with_standard(CStandard::C89)
+            .with_pedantic(true),
+        ":1:8: lexer error[E0010]: Line comments were introduced in C99, but the standard is C89. Consider using '/* */' instead.
+    1 | int x; // comment
+               ^~
+",
+    );
+}
+
+#[test]
+fn pedantic_promotions() {
+    test_options_errors(
+        "char c = '\\e';",
+        Options::default(),
+        ":1:12: lexer warning[E0006]: Found '\\e', but this escape sequence is a GNU extension. Consider using '\\x1B' instead.
+    1 | char c = '\\e';
+                   ^
+",
+    );
+    test_options_errors(
+        "char c = '\\e';",
+        Options::default().with_pedantic(true),
+        ":1:12: lexer error[E0006]: Found '\\e', but this escape sequence is a GNU extension. Consider using '\\x1B' instead.
+    1 | char c = '\\e';
+                   ^
+",
+    );
+    test_options_errors(
+        "_Bool b;",
+        Options::default().with_pedantic(true),
+        ":1:1: lexer error: Underscore operators are deprecated since C23. Consider using the new keyword: bool
+    1 | _Bool b;
+        ^~~~~
+",
+    );
+}
+
+#[test]
+fn line_comment_c99() {
+    test_options_errors(
+        "int x; // comment",
+        Options::default().with_standard(CStandard::C99),
+        "",
+    );
+}
+
+#[test]
+fn trigraphs_c17() {
+    test_options_errors(
+        "
+char b??(5??) = ??< 'b', 'l', 'o',??/
+                    'b', '\0' ??>;
+int x = 1 ??' ??- 2 ??! 3;
+",
+        Options::default()
+            .with_standard(CStandard::C17)
+            .with_trigraphs(true),
+        ":2:7: lexer warning[E0014]: Trigraph '??(' was translated to '[', but trigraphs were removed in C23. Consider writing '[' instead.
+    2 | char b??(5??) = ??< 'b', 'l', 'o',??/
+              ^~~
+:2:11: lexer warning[E0014]: Trigraph '??)' was translated to ']', but trigraphs were removed in C23. Consider writing ']' instead.
+    2 | char b??(5??) = ??< 'b', 'l', 'o',??/
+                  ^~~
+:2:17: lexer warning[E0014]: Trigraph '??<' was translated to '{', but trigraphs were removed in C23. Consider writing '{' instead.
+    2 | char b??(5??) = ??< 'b', 'l', 'o',??/
+                        ^~~
+:2:35: lexer warning[E0014]: Trigraph '??/' was translated to '\\', but trigraphs were removed in C23. Consider writing '\\' instead.
+    2 | char b??(5??) = ??< 'b', 'l', 'o',??/
+                                          ^~~
+:3:30: lexer warning[E0014]: Trigraph '??>' was translated to '}', but trigraphs were removed in C23. Consider writing '}' instead.
+    3 |                     'b', '\0' ??>;
+                                     ^~~
+:4:11: lexer warning[E0014]: Trigraph '??'' was translated to '^', but trigraphs were removed in C23. Consider writing '^' instead.
+    4 | int x = 1 ??' ??- 2 ??! 3;
+                  ^~~
+:4:15: lexer warning[E0014]: Trigraph '??-' was translated to '~', but trigraphs were removed in C23. Consider writing '~' instead.
+    4 | int x = 1 ??' ??- 2 ??! 3;
+                      ^~~
+:4:21: lexer warning[E0014]: Trigraph '??!' was translated to '|', but trigraphs were removed in C23. Consider writing '|' instead.
+    4 | int x = 1 ??' ??- 2 ??! 3;
+                            ^~~
+",
+    );
+}
+
+#[test]
+fn trigraph_hash() {
+    test_options_errors(
+        "??=define X 1",
+        Options::default()
+            .with_standard(CStandard::C17)
+            .with_trigraphs(true),
+        ":1:1: lexer warning[E0014]: Trigraph '??=' was translated to '#', but trigraphs were removed in C23. Consider writing '#' instead.
+    1 | ??=define X 1
+        ^~~
+",
+    );
+}
+
+#[test]
+fn trigraphs_in_literals() {
+    // unlike the escapes, the trigraphs are also translated in the literals
+    test_options_tokens(
+        "s = \"??=??(??)??/\"??<???=??x\"; c = '??/''; x = a ??! b;",
+        Options::default()
+            .with_standard(CStandard::C17)
+            .with_trigraphs(true),
+        "[Ident(s), Assign, \"#[]\"{?#??x\", SemiColon, Ident(c), Assign, ''', SemiColon, Ident(x), Assign, Ident(a), BitwiseOr, Ident(b), SemiColon]",
+    );
+    // `??/` at the end of a line is a line continuation, even in a string
+    test_options_tokens(
+        "s = \"ab??/\ncd\";",
+        Options::default()
+            .with_standard(CStandard::C17)
+            .with_trigraphs(true),
+        "[Ident(s), Assign, \"abcd\", SemiColon]",
+    );
+}
+
+#[test]
+fn trigraphs_c23() {
+    test_options_tokens(
+        "x = a ??= b;",
+        Options::default().with_trigraphs(true),
+        "[Ident(x), Assign, Ident(a), Interrogation, Interrogation, Assign, Ident(b), SemiColon]",
+    );
+}
+
+#[test]
+fn digraphs() {
+    test_options_tokens(
+        "a<:b:> a<::b",
+        Options::default(),
+        "[Ident(a), BracketOpen, Ident(b), BracketClose, Ident(a), BracketOpen, Colon, Ident(b)]",
+    );
+}
+
+#[test]
+fn digraphs_disabled() {
+    test_options_tokens(
+        "a < : b a<:b:>",
+        Options::default().with_digraphs(false),
+        "[Ident(a), Lt, Colon, Ident(b), Ident(a), Lt, Colon, Ident(b), Colon, Gt]",
+    );
+    test_options_tokens(
+        "%:define X",
+        Options::default().with_digraphs(false),
+        "[Modulo, Colon, Ident(define), Ident(X)]",
+    );
+}
+
+#[test]
+fn dollars() {
+    test_options_tokens(
+        "a$b = $c;",
+        Options::default().with_dollars(true),
+        "[Ident(a$b), Assign, Ident($c), SemiColon]",
+    );
+    test_options_errors(
+        "a$b;",
+        Options::default(),
+        ":1:2: lexer error[E0015]: Character '$' not supported.
+    1 | a$b;
+         ^
+",
+    );
+}
+
+#[test]
+fn encoding_prefix_c99() {
+    let content = "x = L\"a\" u\"b\";";
+    let options = Options::default().with_standard(CStandard::C99);
+    test_options_errors(
+        content,
+        options,
+        ":1:10: lexer suggestion[E0012]: 'u' is an encoding prefix since C11, but the standard is C99: it is read as an identifier.
+    1 | x = L\"a\" u\"b\";
+                 ^
+",
+    );
+    let (tokens, _) = lex_file_with_options(content, &mut Location::from(""), options).into_parts();
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed == "[Ident(x), Assign, L\"a\", Ident(u), \"b\", SemiColon]",
+        "{displayed}"
+    );
+}
+
+#[test]
+fn universal_char_c17() {
+    test_options_tokens(
+        "int \\U0001F600 = a\\u0300;",
+        Options::default().with_standard(CStandard::C17),
+        "[Keyword(int), Ident(\u{1f600}), Assign, Ident(a\u{300}), SemiColon]",
+    );
+    test_options_errors(
+        "int \\u0300a;",
+        Options::default().with_standard(CStandard::C17),
+        ":1:5: lexer error[E0017]: Found a universal character name for U+0300, but this character can't start an identifier.
+    1 | int \\u0300a;
+            ^~~~~~
+",
+    );
+}
+
+#[test]
+fn universal_char_c23() {
+    test_options_errors(
+        "int \\U0001F600;",
+        Options::default().with_standard(CStandard::C23),
+        ":1:5: lexer error[E0017]: Found a universal character name for U+1F600, but this character isn't allowed in an identifier.
+    1 | int \\U0001F600;
+            ^~~~~~~~~~
+",
+    );
+    test_options_tokens(
+        "int a\\u0300;",
+        Options::default().with_standard(CStandard::C23),
+        "[Keyword(int), Ident(a\u{300}), SemiColon]",
+    );
+}
+
+#[test]
+fn utf8_char_c17() {
+    test_options_errors(
+        "x = u8'a';",
+        Options::default().with_standard(CStandard::C17),
+        ":1:5: lexer error[E0016]: Found a char with the prefix 'u8', but this prefix is only allowed for chars since C23, and the standard is C17. Consider removing the prefix.
+    1 | x = u8'a';
+            ^~
+",
+    );
+    test_options_tokens(
+        "x = u8\"a\";",
+        Options::default().with_standard(CStandard::C17),
+        "[Ident(x), Assign, u8\"a\", SemiColon]",
+    );
+}
+
+#[test]
+fn hash_symbols() {
+    test_options_tokens(
+        "a # b ## c ### d",
+        Options::default(),
+        "[Ident(a), Hash, Ident(b), HashHash, Ident(c), HashHash, Hash, Ident(d)]",
+    );
+}
+
+#[test]
+fn implicit_returns_c89() {
+    test_options_parser(
+        "int main() {}",
+        Options::default()
+            .with_implicit_returns(true)
+            .with_standard(CStandard::C89),
+        "[((int main)\u{b0}()), []..]",
+        ":1:5: parser warning: Control reaches the end of the non-void function 'main' without a 'return'.
+    1 | int main() {}
+            ^~~~
+",
+    );
+}
+
+#[test]
+fn implicit_returns_conditional() {
+    test_options_parser(
+        "int g(int a) { if (a) return 1; }",
+        Options::default().with_implicit_returns(true),
+        "[((int g)\u{b0}((int a))), [[(if \u{2205}  \u{2205} )], (a), [(return)], 1, \u{2205} ]..]",
+        ":1:5: parser warning: Control reaches the end of the non-void function 'g' without a 'return'.
+    1 | int g(int a) { if (a) return 1; }
+            ^
+",
+    );
+}
+
+#[test]
+fn implicit_returns_infinite_loop() {
+    test_options_parser(
+        "int f() { for (;;) {} } int g() { for (;0;) {} } int h() { for (;;) if (x) break; } int i() { for (;;) { switch (x) { case 1: break; } } } int j() { for (;;) { while (x) break; } }",
+        Options::default().with_implicit_returns(true),
+        "[((int f)\u{b0}()), [[(for \u{2205}  \u{2205} )], ([\u{2205} , \u{2205} , \u{2205} ..]), []], ((int g)\u{b0}()), [[(for \u{2205}  \u{2205} )], ([\u{2205} , 0, \u{2205} ..]), []], ((int h)\u{b0}()), [[(for \u{2205}  \u{2205} )], ([\u{2205} , \u{2205} , \u{2205} ..]), [(if \u{2205}  \u{2205} )], (x), [(break)], \u{2205} ], ((int i)\u{b0}()), [[(for \u{2205}  \u{2205} )], ([\u{2205} , \u{2205} , \u{2205} ..]), [[(switch \u{2205}  \u{2205} )], (x), [(case 1: \u{2205} ), [(break)], \u{2205} ]]], ((int j)\u{b0}()), [[(for \u{2205}  \u{2205} )], ([\u{2205} , \u{2205} , \u{2205} ..]), [[(while \u{2205}  \u{2205} )], (x), [(break)], \u{2205} ]]..]",
+        ":1:29: parser warning: Control reaches the end of the non-void function 'g' without a 'return'.
+    1 | int f() { for (;;) {} } int g() { for (;0;) {} } int h() { for (;;) if (x) break; } int i() { for (;;) { switch (x) { case 1: break; } } } int j() { for (;;) { while (x) break; } }
+                                    ^
+:1:54: parser warning: Control reaches the end of the non-void function 'h' without a 'return'.
+    1 | int f() { for (;;) {} } int g() { for (;0;) {} } int h() { for (;;) if (x) break; } int i() { for (;;) { switch (x) { case 1: break; } } } int j() { for (;;) { while (x) break; } }
+                                                             ^
+",
+    );
+}
+
+#[test]
+fn implicit_returns_main() {
+    test_options_parser(
+        "int main() {} void f() {} int g() { return 1; }",
+        Options::default().with_implicit_returns(true),
+        "[((int main)\u{b0}()), [[(return)], 0, \u{2205} ], ((void f)\u{b0}()), [], ((int g)\u{b0}()), [[(return)], 1, \u{2205} ]..]",
+        "",
+    );
+}
+
+#[test]
+fn implicit_returns_non_void() {
+    test_options_parser(
+        "int g() {}",
+        Options::default().with_implicit_returns(true),
+        "[((int g)\u{b0}()), []..]",
+        ":1:5: parser warning: Control reaches the end of the non-void function 'g' without a 'return'.
+    1 | int g() {}
+            ^
+",
+    );
+}
+
+#[test]
+fn lexemes() {
+    let content = "x = 0xFF + '\\x41';\ns = \"a\\n\";";
+    let options = Options::default().with_lexemes(true);
+    let (tokens, _) = lex_file_with_options(content, &mut Location::from(""), options).into_parts();
+    let lexemes = tokens
+        .iter()
+        .map(|token| token.get_lexeme().unwrap())
+        .collect::<Vec<_>>();
+    assert!(
+        lexemes
+            == [
+                "x", "=", "0xFF", "+", "'\\x41'", ";", "s", "=", "\"a\\n\"", ";"
+            ],
+        "{lexemes:?}"
+    );
+}
+
+#[test]
+fn lexemes_disabled() {
+    let (tokens, _) = lex_file("x = 0xFF;", &mut Location::from("")).into_parts();
+    assert!(tokens.iter().all(|token| token.get_lexeme().is_none()));
+}
+
+#[test]
+fn keyword_since() {
+    assert!(Keyword::Typeof.since() == CStandard::C23);
+    assert!(Keyword::Int.since() == CStandard::C89);
+}
+
+#[test]
+fn keyword_c99() {
+    test_options_errors(
+        "typeof(x) y; _Bool b;",
+        Options::default().with_standard(CStandard::C99),
+        ":1:1: lexer suggestion: 'typeof' is a keyword since C23, but the standard is C99: it is read as an identifier.
+    1 | typeof(x) y; _Bool b;
+        ^~~~~~
+",
+    );
+}
+
+#[test]
+fn keyword_as_identifier_c89() {
+    test_options_tokens(
+        "int restrict; int inline;",
+        Options::default()
+            .with_standard(CStandard::C89)
+            .with_pedantic(true),
+        "[Keyword(int), Ident(restrict), SemiColon, Keyword(int), Ident(inline), SemiColon]",
+    );
+}
+
+#[test]
+fn restrict_identifier_c89() {
+    test_options_errors(
+        "int restrict;",
+        Options::default().with_standard(CStandard::C89),
+        ":1:5: lexer suggestion: 'restrict' is a keyword since C99, but the standard is C89: it is read as an identifier.
+    1 | int restrict;
+            ^~~~~~~~
+",
+    );
+    test_options_tokens(
+        "int restrict;",
+        Options::default().with_standard(CStandard::C89),
+        "[Keyword(int), Ident(restrict), SemiColon]",
+    );
+}
+
+#[test]
+fn restrict_keyword_c99() {
+    test_options_tokens(
+        "int *restrict p;",
+        Options::default().with_standard(CStandard::C99),
+        "[Keyword(int), Star, Keyword(restrict), Ident(p), SemiColon]",
+    );
+}
+
+#[test]
+fn keyword_as_identifier_c17() {
+    test_options_tokens(
+        "int bool = true;",
+        Options::default().with_standard(CStandard::C17),
+        "[Keyword(int), Ident(bool), Assign, Ident(true), SemiColon]",
+    );
+}
+
+#[test]
+fn statement_expression_gnu() {
+    test_options_parser(
+        "x = ({ int a = 1; a + 1; });",
+        Options::default().with_gnu_extensions(true),
+        "[(x = ([((int a) = 1), (a + 1), \u{2205} ])), \u{2205} ..]",
+        ":1:5: parser warning: Found a statement expression '({ ... })', but this is a GNU extension.
+    1 | x = ({ int a = 1; a + 1; });
+            ^
+",
+    );
+}
+
+#[test]
+fn statement_expression_in_function() {
+    test_options_parser(
+        "int f() { int x = ({ 1; }); return x; }",
+        Options::default().with_gnu_extensions(true),
+        "[((int f)\u{b0}()), [((int x) = ([1, \u{2205} ])), \u{2205} , [(return)], x, \u{2205} ]..]",
+        ":1:19: parser warning: Found a statement expression '({ ... })', but this is a GNU extension.
+    1 | int f() { int x = ({ 1; }); return x; }
+                          ^
+",
+    );
+}
+
+#[test]
+fn statement_expression_dead_loop() {
+    test_options_parser(
+        "void f(){ int y = ({ while(0){x;} 1; }); }",
+        Options::default().with_gnu_extensions(true),
+        "[((void f)\u{b0}()), [((int y) = ([[(while \u{2205}  \u{2205} )], (0), [x, \u{2205} ], 1, \u{2205} ])), \u{2205} ]..]",
+        ":1:19: parser warning: Found a statement expression '({ ... })', but this is a GNU extension.
+    1 | void f(){ int y = ({ while(0){x;} 1; }); }
+                          ^
+:1:31: parser suggestion: The condition of this 'while' loop is always false, so its body is never executed. Perhaps this is unintended? To execute a block once, like in a macro, use 'do { ... } while (0);' instead.
+    1 | void f(){ int y = ({ while(0){x;} 1; }); }
+                                      ^
+",
+    );
+}
+
+#[test]
+fn statement_expression_no_gnu() {
+    test_options_parser(
+        "x = ({ int a = 1; a + 1; });",
+        Options::default(),
+        "[..]",
+        ":1:5: parser error: Found a statement expression '({ ... })', but this is a GNU extension. Please enable the GNU extensions to use it.
+    1 | x = ({ int a = 1; a + 1; });
+            ^
+",
+    );
+}
+
+#[test]
+fn enum_underlying_type() {
+    test_options_parser(
+        "enum E : unsigned char { A = 255 };",
+        Options::default(),
+        "[((enum E : unsigned char {A = 255}) \u{2205} ), \u{2205} ..]",
+        "",
+    );
+}
+
+#[test]
+fn enum_underlying_type_overflow() {
+    test_options_parser(
+        "enum E : unsigned char { A = 256 };",
+        Options::default(),
+        "[((enum E : unsigned char {A = 256}) \u{2205} ), \u{2205} ..]",
+        ":1:26: parser warning: Found enumerator 'A' with value 256, but it doesn't fit in the underlying type of the enum, whose values are between 0 and 255.
+    1 | enum E : unsigned char { A = 256 };
+                                 ^
+",
+    );
+    test_options_parser(
+        "void f() { enum E : unsigned char { A = 256 }; }",
+        Options::default(),
+        "[((void f)\u{b0}()), [((enum E : unsigned char {A = 256}) \u{2205} ), \u{2205} ]..]",
+        ":1:37: parser warning: Found enumerator 'A' with value 256, but it doesn't fit in the underlying type of the enum, whose values are between 0 and 255.
+    1 | void f() { enum E : unsigned char { A = 256 }; }
+                                            ^
+",
+    );
+}
+
+#[test]
+fn enum_underlying_type_c11() {
+    test_options_parser(
+        "enum E : unsigned char { A = 255 };",
+        Options::default().with_standard(CStandard::C11),
+        "[..]",
+        ":1:8: parser error: Found an underlying type for an enum, but they are only supported since C23, and the current standard is C11.
+    1 | enum E : unsigned char { A = 255 };
+               ^
+",
+    );
+}
+
+#[test]
+fn gnu_attribute() {
+    test_options_parser(
+        "int x __attribute__((aligned(16), packed));",
+        Options::default().with_gnu_extensions(true),
+        "[(int [[gnu::aligned(16)]] [[gnu::packed]] x), \u{2205} ..]",
+        ":1:7: parser warning: Found a GNU attribute '__attribute__((...))', but this is a GNU extension.
+    1 | int x __attribute__((aligned(16), packed));
+              ^~~~~~~~~~~~~
+",
+    );
+    test_options_parser(
+        "void f() { int x __attribute__((unused)); }",
+        Options::default().with_gnu_extensions(true),
+        "[((void f)\u{b0}()), [(int [[gnu::unused]] x), \u{2205} ]..]",
+        ":1:18: parser warning: Found a GNU attribute '__attribute__((...))', but this is a GNU extension.
+    1 | void f() { int x __attribute__((unused)); }
+                         ^~~~~~~~~~~~~
+",
+    );
+}
+
+#[test]
+fn gnu_attribute_no_gnu() {
+    test_options_parser(
+        "int x __attribute__((aligned(16)));",
+        Options::default(),
+        "[..]",
+        ":1:7: parser error: Found a GNU attribute '__attribute__((...))', but this is a GNU extension. Please enable the GNU extensions to use it.
+    1 | int x __attribute__((aligned(16)));
+              ^~~~~~~~~~~~~
+",
+    );
+}
+
+#[test]
+fn gnu_attribute_unbalanced() {
+    test_options_parser(
+        "int x __attribute__((aligned(16));",
+        Options::default().with_gnu_extensions(true),
+        "[..]",
+        ":1:7: parser error: Mismatched '(': reached end of block. Perhaps you forgot a closing ')'?
+    1 | int x __attribute__((aligned(16));
+              ^~~~~~~~~~~~~
+",
+    );
+}
+
+#[test]
+fn computed_goto() {
+    test_options_parser(
+        "void f() { void *p = &&L; goto *p; L: ; }",
+        Options::default().with_gnu_extensions(true),
+        "[((void f)\u{b0}()), [((void * p) = &&L), \u{2205} , [[(goto: \u{2205} )], (*p)..], L:, \u{2205} , \u{2205} ]..]",
+        ":1:22: parser warning: Found the address of a label '&&label', but this is a GNU extension.
+    1 | void f() { void *p = &&L; goto *p; L: ; }
+                             ^~
+:1:27: parser warning: Found a computed goto 'goto *', but this is a GNU extension.
+    1 | void f() { void *p = &&L; goto *p; L: ; }
+                                  ^~~~
+",
+    );
+}
+
+#[test]
+fn computed_goto_no_gnu() {
+    test_options_parser(
+        "void f() { goto *p; }",
+        Options::default(),
+        "[..]",
+        ":1:12: parser error: Found a computed goto 'goto *', but this is a GNU extension. Please enable the GNU extensions to use it.
+    1 | void f() { goto *p; }
+                   ^~~~
+",
+    );
+}
+
+#[test]
+fn case_range() {
+    test_options_parser(
+        "int f(int x) { switch (x) { case 1 ... 3: x = 0; } }",
+        Options::default().with_gnu_extensions(true),
+        "[((int f)\u{b0}((int x))), [[(switch \u{2205}  \u{2205} )], (x), [(case 1 ... 3: (x = 0)), \u{2205} ]]..]",
+        ":1:36: parser warning: Found a case range 'case first ... last:', but this is a GNU extension.
+    1 | int f(int x) { switch (x) { case 1 ... 3: x = 0; } }
+                                           ^~~
+",
+    );
+}
+
+#[test]
+fn case_range_duplicate_case() {
+    test_options_parser(
+        "int f(int x) { switch (x) { case 1 ... 3: break; case 2: break; } }",
+        Options::default().with_gnu_extensions(true),
+        "[((int f)\u{b0}((int x))), [[(switch \u{2205}  \u{2205} )], (x), [(case 1 ... 3: \u{2205} ), [(break)], (case 2: \u{2205} ), [(break)], \u{2205} ]]..]",
+        ":1:36: parser warning: Found a case range 'case first ... last:', but this is a GNU extension.
+    1 | int f(int x) { switch (x) { case 1 ... 3: break; case 2: break; } }
+                                           ^~~
+:1:56: parser warning: Found a duplicate case '2': its value is already matched by the previous case range '1 ... 3'.
+    1 | int f(int x) { switch (x) { case 1 ... 3: break; case 2: break; } }
+                                                               ^
+",
+    );
+}
+
+#[test]
+fn case_range_overlap() {
+    test_options_parser(
+        "int f(int x) { switch (x) { case 2: case 1 ... 3: x = 0; } }",
+        Options::default().with_gnu_extensions(true),
+        "[((int f)\u{b0}((int x))), [[(switch \u{2205}  \u{2205} )], (x), [(case 2: (case 1 ... 3: (x = 0))), \u{2205} ]]..]",
+        ":1:44: parser warning: Found a case range 'case first ... last:', but this is a GNU extension.
+    1 | int f(int x) { switch (x) { case 2: case 1 ... 3: x = 0; } }
+                                                   ^~~
+:1:44: parser warning: Found a case range '1 ... 3' that overlaps the previous case '2': the values of both cases are matched by the first one.
+    1 | int f(int x) { switch (x) { case 2: case 1 ... 3: x = 0; } }
+                                                   ^~~
+",
+    );
+}
+
+#[test]
+fn case_range_pedantic() {
+    test_options_parser(
+        "int f(int x) { switch (x) { case 1 ... 3: x = 0; } }",
+        Options::default()
+            .with_gnu_extensions(true)
+            .with_pedantic(true),
+        "[..]",
+        ":1:36: parser error: Found a case range 'case first ... last:', but this is a GNU extension.
+    1 | int f(int x) { switch (x) { case 1 ... 3: x = 0; } }
+                                           ^~~
+",
+    );
+}
+
+#[test]
+fn case_range_reversed() {
+    test_options_parser(
+        "int f(int x) { switch (x) { case 3 ... 1: x = 0; } }",
+        Options::default().with_gnu_extensions(true),
+        "[..]",
+        ":1:36: parser error: Found an empty case range '3 ... 1': the first value 3 is greater than the last value 1.
+    1 | int f(int x) { switch (x) { case 3 ... 1: x = 0; } }
+                                           ^~~
+",
+    );
+}
+
+#[test]
+fn label_address_logical_and() {
+    test_options_parser(
+        "void f() { L: x = a && b; y = a && &&L; }",
+        Options::default().with_gnu_extensions(true),
+        "[((void f)\u{b0}()), [L:, (x = (a && b)), (y = (a && &&L)), \u{2205} ]..]",
+        ":1:36: parser warning: Found the address of a label '&&label', but this is a GNU extension.
+    1 | void f() { L: x = a && b; y = a && &&L; }
+                                           ^~
+",
+    );
+    test_options_parser(
+        "x = &&1;",
+        Options::default().with_gnu_extensions(true),
+        "[..]",
+        ":1:5: parser error: Found '&&' without left operand, but it isn't followed by the name of a label.
+    1 | x = &&1;
+            ^~
+",
+    );
+}
+
+#[test]
+fn label_address_defined() {
+    test_options_parser(
+        "void f() { void *p = &&start; start: x = 1; }",
+        Options::default().with_gnu_extensions(true),
+        "[((void f)\u{b0}()), [((void * p) = &&start), start:, (x = 1), \u{2205} ]..]",
+        ":1:22: parser warning: Found the address of a label '&&label', but this is a GNU extension.
+    1 | void f() { void *p = &&start; start: x = 1; }
+                             ^~
+",
+    );
+}
+
+#[test]
+fn label_address_undefined() {
+    test_options_parser(
+        "void f() { void *p = &&start; } void g() { start: ; }",
+        Options::default().with_gnu_extensions(true),
+        "[((void f)\u{b0}()), [((void * p) = &&start), \u{2205} ], ((void g)\u{b0}()), [start:, \u{2205} , \u{2205} ]..]",
+        ":1:22: parser warning: Found the address of a label '&&label', but this is a GNU extension.
+    1 | void f() { void *p = &&start; } void g() { start: ; }
+                             ^~
+:1:22: parser error: Found the address of the label 'start', but this label isn't defined in this function.
+    1 | void f() { void *p = &&start; } void g() { start: ; }
+                             ^~
+",
+    );
+}
+
+#[test]
+fn range_designator_gnu() {
+    test_options_parser(
+        "int a[4] = {[0 ... 3] = 1};",
+        Options::default().with_gnu_extensions(true),
+        "[(((int a)[4]) = {([0 ... 3] = 1)}), \u{2205} ..]",
+        ":1:13: parser warning: Found a range designator '[first ... last]', but this is a GNU extension.
+    1 | int a[4] = {[0 ... 3] = 1};
+                    ^
+",
+    );
+    test_options_parser(
+        "void f() { int a[4] = {[0 ... 3] = 1}; }",
+        Options::default().with_gnu_extensions(true),
+        "[((void f)\u{b0}()), [(((int a)[4]) = {([0 ... 3] = 1)}), \u{2205} ]..]",
+        ":1:24: parser warning: Found a range designator '[first ... last]', but this is a GNU extension.
+    1 | void f() { int a[4] = {[0 ... 3] = 1}; }
+                               ^
+",
+    );
+}
+
+#[test]
+fn range_designator_no_gnu() {
+    test_options_parser(
+        "int a[4] = {[0 ... 3] = 1};",
+        Options::default(),
+        "[..]",
+        ":1:13: parser error: Found a range designator '[first ... last]', but this is a GNU extension. Please enable the GNU extensions to use it.
+    1 | int a[4] = {[0 ... 3] = 1};
+                    ^
+",
+    );
+}
+
+#[test]
+fn range_designator_reversed() {
+    test_options_parser(
+        "int a[4] = {[3 ... 0] = 1};",
+        Options::default().with_gnu_extensions(true),
+        "[..]",
+        ":1:13: parser error: Found an empty range designator '[3 ... 0]': the first index 3 is greater than the last index 0.
+    1 | int a[4] = {[3 ... 0] = 1};
+                    ^
+",
+    );
+}
+
+#[test]
+fn undefined_macros() {
+    test_options_errors("#if FOO\nint x;\n#endif", Options::default(), "");
+    test_options_errors(
+        "#if FOO\nint x;\n#endif",
+        Options::default().with_undefined_macros(true),
+        ":1:5: lexer warning: Identifier 'FOO' is not defined, and evaluates to 0.
+    1 | #if FOO
+            ^~~
+",
+    );
+}
+
+#[test]
+fn unused_variables() {
+    test_options_parser(
+        "void f(int a) { int x = 1; { int y = 2; } }",
+        Options::default().with_unused_variables(true),
+        "[((void f)\u{b0}((int a))), [((int x) = 1), \u{2205} , [((int y) = 2), \u{2205} ]]..]",
+        ":1:34: parser suggestion: Variable 'y' is declared but never used. Consider removing it, or marking it with '[[maybe_unused]]'.
+    1 | void f(int a) { int x = 1; { int y = 2; } }
+                                         ^
+:1:21: parser suggestion: Variable 'x' is declared but never used. Consider removing it, or marking it with '[[maybe_unused]]'.
+    1 | void f(int a) { int x = 1; { int y = 2; } }
+                            ^
+",
+    );
+}
+
+#[test]
+fn unused_variables_maybe_unused() {
+    test_options_parser(
+        "void f(void) { [[maybe_unused]] int x = 1; }",
+        Options::default().with_unused_variables(true),
+        "[((void f)\u{b0}((void \u{2205} ))), [(([[maybe_unused]] int x) = 1), \u{2205} ]..]",
+        "",
+    );
+}
+
+#[test]
+fn unused_variables_used() {
+    test_options_parser(
+        "void f(int a) { int x = 1; { a = x; } }",
+        Options::default().with_unused_variables(true),
+        "[((void f)\u{b0}((int a))), [((int x) = 1), \u{2205} , [(a = x), \u{2205} ]]..]",
+        "",
+    );
+}
+
+#[test]
+fn whitespace_tokens() {
+    test_options_tokens(
+        "int main() {\n    int  x;\n\n\treturn x; \n}",
+        Options::default().with_whitespace(true),
+        "[Keyword(int), Whitespace(\" \"), Ident(main), ParenthesisOpen, ParenthesisClose, Whitespace(\" \"), BraceOpen, Newline, Whitespace(\"    \"), Keyword(int), Whitespace(\"  \"), Ident(x), SemiColon, Newline, Newline, Whitespace(\"\\t\"), Keyword(return), Whitespace(\" \"), Ident(x), SemiColon, Whitespace(\" \"), Newline, BraceClose]",
+    );
+}
+
+#[test]
+fn whitespace_tokens_comments() {
+    test_options_tokens(
+        "int  /* c */  x; // end \nint y;",
+        Options::default().with_whitespace(true),
+        "[Keyword(int), Whitespace(\"  \"), Comment(\"/* c */\"), Whitespace(\"  \"), Ident(x), SemiColon, Whitespace(\" \"), Comment(\"// end\"), Whitespace(\" \"), Newline, Keyword(int), Whitespace(\" \"), Ident(y), SemiColon]",
+    );
+}
+
+#[test]
+fn whitespace_tokens_strings() {
+    test_options_tokens(
+        "x = \"a\" \n  \"b\" /* c */ \"c\";",
+        Options::default().with_whitespace(true),
+        "[Ident(x), Whitespace(\" \"), Assign, Whitespace(\" \"), \"abc\", SemiColon]",
+    );
+}
+
+#[test]
+fn whitespace_tokens_parser() {
+    let content = "int x;\n\n  x = 1;\n";
+    let files = &[(String::new(), content)];
+    let mut location = Location::from(String::new());
+    let options = Options::default().with_whitespace(true);
+    let tokens =
+        lex_file_with_options(content, &mut location, options).unwrap_or_display(files, "lexer");
+    let node = parse_tokens(tokens).unwrap_or_display(files, "parser");
+    assert!(node.to_string() == "[(int x), (x = 1), \u{2205} ..]");
+}
+
+#[test]
+fn max_columns() {
+    let options = Options::default().with_max_columns(10);
+    test_options_errors("int x = 1;", options, "");
+    test_options_errors(
+        "int xy = 1;",
+        options,
+        ":1:11: lexer error: This line of code exceeds the maximum number of columns (10). Consider refactoring your code.
+    1 | int xy = 1;
+                  ^
+",
+    );
+}
+
+#[test]
+fn max_lines() {
+    let options = Options::default().with_max_lines(3);
+    test_options_errors("a;\nb;\nc;\n", options, "");
+    test_options_errors(
+        "a;\nb;\nc;\nd;",
+        options,
+        ":3:3: lexer error: The file exceeds the maximum number of lines (3). Consider refactoring your code.
+    3 | c;
+          ^
+",
+    );
+}
+
+#[test]
+fn max_nodes() {
+    let content = (0..200)
+        .map(|i| format!("int x{i} = {i};\n"))
+        .collect::<String>();
+    test_options_parser(
+        &content,
+        Options::default().with_max_nodes(8),
+        "[((int x0) = 0), ((int x1) = 1), \u{2205} ..]",
+        ":3:1: parser suggestion: Parsing stopped: the maximum number of nodes (8) was exceeded, so the rest of the file was not parsed.
+    3 | int x2 = 2;
+        ^~~
+",
+    );
+    test_options_parser(
+        "int f() { int x[2] = {1, 2}; }\nint g() { }",
+        Options::default().with_max_nodes(1),
+        "[((int f)\u{b0}()), [(((int x)[2]) = {1, 2}), \u{2205} ]..]",
+        ":1:30: parser suggestion: Parsing stopped: the maximum number of nodes (1) was exceeded, so the rest of the file was not parsed.
+    1 | int f() { int x[2] = {1, 2}; }
+                                     ^
+",
+    );
+}
+
+#[test]
+fn max_nodes_function_body() {
+    let statements = (0..200)
+        .map(|i| format!("    x = {i};\n"))
+        .collect::<String>();
+    let content = format!("void f(int x) {{\n    for (x = 0; x < 2; x++) {{ }}\n{statements}}}\n");
+    test_options_parser(
+        &content,
+        Options::default().with_max_nodes(5),
+        "[((void f)\u{b0}((int x))), [[(for \u{2205}  \u{2205} )], ([(x = 0), (x < 2), (x++)..]), []]..]",
+        ":3:5: parser suggestion: Parsing stopped: the maximum number of nodes (5) was exceeded, so the rest of the file was not parsed.
+    3 |     x = 0;
+            ^
+",
+    );
+}
+
+#[test]
+fn max_string_length() {
+    let options = Options::default().with_max_string_length(4);
+    test_options_errors("x = \"ab\" \"cd\";", options, "");
+    test_options_errors(
+        "x = \"ab\" \"cd\" \"ef\" \"gh\";",
+        options,
+        ":1:16: lexer warning: String literal is longer than the maximum length of 4 bytes, so the rest of the string was dropped.
+    1 | x = \"ab\" \"cd\" \"ef\" \"gh\";
+                       ^~
+",
+    );
+    let content = "x = \"abc\" \"def\";";
+    let mut location = Location::from(String::new());
+    let (tokens, _) = lex_file_with_options(content, &mut location, options).into_parts();
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed == "[Ident(x), Assign, \"abcd\", SemiColon]",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn kr_definition_c89() {
+    test_options_parser(
+        "int f(a, b, c) char *a; int b[], c; { }",
+        Options::default().with_standard(CStandard::C89),
+        "[((int f)°((char * a), ((int b)[\u{2205} ]), (int c))), []..]",
+        "",
+    );
+}
+
+#[test]
+fn kr_definition_c23() {
+    test_options_parser(
+        "int f(a) int a; { }",
+        Options::default(),
+        "[((int f)°((int a))), []..]",
+        ":1:5: parser error: Found a K&R function definition, but they were removed in C23. Please declare the types of the parameters inside the parenthesis, or enable the K&R definitions.
+    1 | int f(a) int a; { }
+            ^
+",
+    );
+    test_options_parser(
+        "int f(a) int a; { }",
+        Options::default().with_kr_definitions(true),
+        "[((int f)°((int a))), []..]",
+        ":1:5: parser warning: Found a K&R function definition, but they were removed in C23.
+    1 | int f(a) int a; { }
+            ^
+",
+    );
+}
+
+#[test]
+fn kr_definition_invalid_declarations() {
+    test_options_parser(
+        "int f(a) int a, a; long c; { }",
+        Options::default().with_standard(CStandard::C89),
+        "[((int f)°((int a))), []..]",
+        ":1:17: parser error: Parameter 'a' is declared twice in K&R function definition.
+    1 | int f(a) int a, a; long c; { }
+                        ^
+:1:20: parser error: Found a declaration in a K&R function definition that doesn't declare a parameter.
+    1 | int f(a) int a, a; long c; { }
+                           ^~~~
+",
+    );
+}
+
+#[test]
+fn deprecated_keywords() {
+    test_options_errors(
+        "_Bool b; _Noreturn void f();",
+        Options::default(),
+        ":1:1: lexer warning: Underscore operators are deprecated since C23. Consider using the new keyword: bool
+    1 | _Bool b; _Noreturn void f();
+        ^~~~~
+:1:10: lexer warning: Underscore operators are deprecated since C23. Consider using the attribute: [[noreturn]]
+    1 | _Bool b; _Noreturn void f();
+                 ^~~~~~~~~
+",
+    );
+}
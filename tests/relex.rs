@@ -0,0 +1,220 @@
+use core::ops::Range;
+
+use c_parser::*;
+
+fn test_relex(old: &str, range: Range<usize>, replacement: &str, options: Options) {
+    let new = format!("{}{replacement}{}", &old[..range.start], &old[range.end..]);
+    let files = &[(String::new(), new.as_str())];
+    let previous = lex_file_with_options(old, &mut Location::from(String::new()), options);
+    let relexed = relex_file(
+        previous,
+        old,
+        range,
+        replacement,
+        &Location::from(String::new()),
+        options,
+    );
+    let full = lex_file_with_options(&new, &mut Location::from(String::new()), options);
+    let relexed_errors = relexed.get_displayed_errors(files, "lexer");
+    let full_errors = full.get_displayed_errors(files, "lexer");
+    assert!(
+        relexed_errors == full_errors,
+        "Mismatch! Expected:\n!{full_errors}!\n!= Computed\n!{relexed_errors}!"
+    );
+    let relexed_tokens = format!("{:?}", relexed.into_parts().0);
+    let full_tokens = format!("{:?}", full.into_parts().0);
+    assert!(
+        relexed_tokens == full_tokens,
+        "Mismatch! Expected:\n!{full_tokens}!\n!= Computed\n!{relexed_tokens}!"
+    );
+}
+
+const CONTENT: &str = "int main() {
+    int x = a * b;
+    char *s = \"abc\"
+        \"def\";
+    int y = x \\
+        + 1;
+    return x@;
+}
+";
+
+#[test]
+fn relex_comment_start() {
+    test_relex(CONTENT, 27..27, "/", Options::default());
+}
+
+#[test]
+fn relex_comment_start_whitespace() {
+    test_relex(
+        CONTENT,
+        27..27,
+        "/",
+        Options::default().with_whitespace(true),
+    );
+}
+
+#[test]
+fn relex_merged_string() {
+    test_relex(CONTENT, 61..64, "xyz", Options::default());
+    test_relex(CONTENT, 46..46, "\"a\" ", Options::default());
+}
+
+#[test]
+fn relex_continuation() {
+    test_relex(CONTENT, 91..92, "-", Options::default());
+    test_relex(CONTENT, 81..82, "", Options::default());
+}
+
+#[test]
+fn relex_lines() {
+    test_relex(CONTENT, 12..13, "\nint z;\n", Options::default());
+    test_relex(CONTENT, 13..40, "", Options::default());
+    test_relex(CONTENT, 112..113, "", Options::default());
+    test_relex(CONTENT, 113..113, "int w;", Options::default());
+}
+
+#[test]
+fn relex_directive() {
+    test_relex("#if 1\nint x;\n#endif\n", 3..4, "0", Options::default());
+}
+
+#[test]
+fn relex_invalid_range() {
+    let old = "int abcdef;";
+    // reversed, and out of the file
+    for (start, end) in [(8, 5), (3, 20)] {
+        let range = start..end;
+        let previous = lex_file(old, &mut Location::from(String::new()));
+        let (tokens, errors) = relex_file(
+            previous,
+            old,
+            range.clone(),
+            "",
+            &Location::from(String::new()),
+            Options::default(),
+        )
+        .into_parts();
+        assert!(tokens.is_empty(), "{tokens:?}");
+        let expected = format!(
+            ":1:1: error: Invalid edit: {}..{} is not a valid range of the file.",
+            range.start, range.end
+        );
+        assert!(
+            errors.len() == 1 && errors[0].to_string() == expected,
+            "Mismatch! Expected:\n!{expected}!\n!= Computed\n!{errors:?}!"
+        );
+    }
+}
+
+#[test]
+fn lex_by_lines() {
+    let content = "int x = 1;\n\n  x++; return x;\n";
+    let (lines, errors) = lex_file_by_lines(
+        content,
+        &mut Location::from(String::new()),
+        Options::default(),
+    )
+    .into_parts();
+    assert!(errors.is_empty(), "{errors:?}");
+    let displayed = lines
+        .iter()
+        .map(|line| {
+            let tokens = line
+                .iter()
+                .map(|(token, _)| token.to_owned())
+                .collect::<Vec<_>>();
+            display_tokens(&tokens)
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        displayed
+            == [
+                "[Keyword(int), Ident(x), Assign, 1, SemiColon]",
+                "[]",
+                "[Ident(x), Increment, SemiColon, Keyword(return), Ident(x), SemiColon]"
+            ],
+        "Mismatch! Computed\n{displayed:?}"
+    );
+}
+
+#[test]
+fn lex_by_lines_block_comment() {
+    let content = "int /* a \\\n b */ x;\ny;";
+    let (lines, errors) = lex_file_by_lines(
+        content,
+        &mut Location::from(String::new()),
+        Options::default().with_whitespace(true),
+    )
+    .into_parts();
+    assert!(errors.is_empty(), "{errors:?}");
+    let displayed = lines
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|(token, end_line)| format!("{:?} {end_line}", token.get_value()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        displayed
+            == [
+                vec![
+                    "Keyword(Int) 0",
+                    "Whitespace(\" \") 0",
+                    "Newline 0",
+                    "Comment(\"/* a \\\\ b */\") 1"
+                ],
+                vec![
+                    "Whitespace(\" \") 1",
+                    "Ident(\"x\") 1",
+                    "Symbol(SemiColon) 1",
+                    "Newline 1"
+                ],
+                vec!["Ident(\"y\") 2", "Symbol(SemiColon) 2"],
+            ],
+        "Mismatch! Computed\n{displayed:?}"
+    );
+}
+
+#[test]
+fn lexer_stream() {
+    let content = "int x = 1;\ns = \"a\"\n  \"b\";\n#if 1\nx = @;\n";
+    let options = Options::default().with_whitespace(true);
+    let items = Lexer::new(content, Location::from(String::new()), options).collect::<Vec<_>>();
+    let full = lex_file_with_options(content, &mut Location::from(String::new()), options);
+    let (full_tokens, full_errors) = full.into_parts();
+    let tokens = items
+        .iter()
+        .filter_map(|item| item.as_ref().ok())
+        .cloned()
+        .collect::<Vec<_>>();
+    assert!(
+        format!("{tokens:?}") == format!("{full_tokens:?}"),
+        "Mismatch! Expected:\n!{full_tokens:?}!\n!= Computed\n!{tokens:?}!"
+    );
+    let errors = items
+        .iter()
+        .filter_map(|item| item.as_ref().err())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let expected = full_errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert!(errors.len() == 2, "{errors:?}");
+    assert!(
+        errors == expected,
+        "Mismatch! Expected:\n{expected:?}\n!= Computed\n{errors:?}"
+    );
+    // the error of the last line comes after the tokens of the first lines
+    let first_error = items.iter().position(Result::is_err).unwrap();
+    let merged = items
+        .iter()
+        .position(|item| {
+            item.as_ref()
+                .is_ok_and(|token| token.to_string() == "\"ab\"")
+        })
+        .unwrap();
+    assert!(merged < first_error, "{items:?}");
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "serde")]
+
+use c_parser::*;
+
+#[test]
+fn serialise_tokens() {
+    let content = "x = L'a' + 1.5L;";
+    let options = Options::default().with_lexemes(true);
+    let tokens = lex_file_with_options(content, &mut Location::from("file.c"), options)
+        .unwrap_or_display(&[("file.c".to_owned(), content)], "lexer");
+    let json = serde_json::to_string(&tokens).unwrap();
+    assert!(
+        json.starts_with(
+            r#"[{"extra_lines":0,"lexeme":"x","location":{"col":1,"file":"file.c","length":1,"line":1},"value":{"Ident":"x"}},"#
+        ),
+        "{json}"
+    );
+    let values = serde_json::to_value(&tokens)
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|token| token["value"].to_string())
+        .collect::<Vec<_>>();
+    assert!(
+        values
+            == [
+                r#"{"Ident":"x"}"#,
+                r#"{"Symbol":"Assign"}"#,
+                r#"{"Char":["a","Wide"]}"#,
+                r#"{"Symbol":"Plus"}"#,
+                r#"{"Number":{"LongDouble":"1.5"}}"#,
+                r#"{"Symbol":"SemiColon"}"#,
+            ],
+        "{values:?}"
+    );
+    let deserialised: Vec<Token> = serde_json::from_str(&json).unwrap();
+    assert!(
+        format!("{deserialised:?}") == format!("{tokens:?}"),
+        "Mismatch! Expected:\n{tokens:?}\n!= Computed\n{deserialised:?}"
+    );
+}
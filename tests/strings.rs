@@ -1,4 +1,5 @@
 use std::fs;
+use std::time::Instant;
 
 use c_parser::*;
 
@@ -122,6 +123,31 @@ char_array:
     =>
     "[(((char x)[4]) = {'b', (12 + '5'), '3', '\0'}), \u{2205} ..]"
 
+array_designators:
+    "int a[4] = {1, [2] = 3, 4};"
+    =>
+    "[(((int a)[4]) = {1, ([2] = 3), 4}), \u{2205} ..]"
+
+brace_elision:
+    "int a[2][2] = {1, 2, 3, 4};"
+    =>
+    "[((((int a)[2])[2]) = {{1, 2}, {3, 4}}), \u{2205} ..]"
+
+brace_elision_braced:
+    "int a[2][2] = {{1, 2}, {3, 4}};"
+    =>
+    "[((((int a)[2])[2]) = {{1, 2}, {3, 4}}), \u{2205} ..]"
+
+brace_elision_partial:
+    "int a[][3] = {{1}, 2, 3, 4, 5};"
+    =>
+    "[((((int a)[\u{2205} ])[3]) = {{1}, {2, 3, 4}, {5}}), \u{2205} ..]"
+
+casts:
+    "y = (int*)&x; z = *(char*)p; (void)f(); w = (unsigned long)a[1] + 1;"
+    =>
+    "[(y = ((int *)(&x))), (z = (*((char *)p))), ((void)(f\u{b0}())), (w = (((unsigned long)(a[1])) + 1)), \u{2205} ..]"
+
 nested_block_functions:
         "f(a+b) { g(!x) {     a = 1;     b = 2; } c = 3;
 }
@@ -183,6 +209,292 @@ function_argument_priority:
     =>
     "[(main°((!(f°((x + y), (!u)))), (g°((f°((h°(x, y)), z)), t)), u))..]"
 
+octal_escapes:
+    "a = \"\\377\"; b = \"\\400\"; c = \"\\777\";"
+    =>
+    "[(a = \"\u{ff}\"), (b = \" 0\"), (c = \"?7\"), \u{2205} ..]"
+
+repeated_unary:
+    "a = !!b; c = - -d; e = --f; g = sizeof sizeof x; h = i + -j;"
+    =>
+    "[(a = (!(!b))), (c = (-(-d))), (e = (--f)), (g = (sizeof (sizeof x))), (h = (i + (-j))), \u{2205} ..]"
+
+member_access_dereference:
+    "a = *p.x; b = *p->x; c = (*p).x;"
+    =>
+    "[(a = (*(p . x))), (b = (*(p -> x))), (c = (((*p)) . x)), \u{2205} ..]"
+
+empty_file:
+    ""
+    =>
+    "[..]"
+
+comment_file:
+    "/* comment */\n// line comment\n"
+    =>
+    "[..]"
+
+merged_strings:
+    "s = \"a\\0b\" \"c\"; t = \"\\x41g\";"
+    =>
+    "[(s = \"a\0bc\"), (t = \"Ag\"), \u{2205} ..]"
+
+hexadecimal_escape_separator:
+    "s = \"\\x1'2\";"
+    =>
+    "[(s = \"\u{1}'2\"), \u{2205} ..]"
+
+static_assert_file_scope:
+    "_Static_assert(sizeof(int) == 4, \"int\"); int x;"
+    =>
+    "[(static_assert\u{b0}(((sizeof\u{b0}((int \u{2205} ))) == 4), \"int\")), (int x), \u{2205} ..]"
+
+static_assert_struct:
+    "struct S { int a; static_assert(1, \"b\"); int c; };"
+    =>
+    "[[(struct \u{2205}  \u{2205} )], S, [(int a), (static_assert\u{b0}(1, \"b\")), (int c), \u{2205} ], \u{2205} ..]"
+
+static_assert_function:
+    "int main() { static_assert(1, \"c\"); return 0; }"
+    =>
+    "[((int main)\u{b0}()), [(static_assert\u{b0}(1, \"c\")), \u{2205} , [(return)], 0, \u{2205} ]..]"
+
+alignof_type:
+    "x = alignof(int) + _Alignof(struct S *);"
+    =>
+    "[(x = ((alignof\u{b0}((int \u{2205} ))) + (alignof\u{b0}((struct S * \u{2205} ))))), \u{2205} ..]"
+
+builtins:
+    "x = offsetof(struct S, x); y = va_arg(ap, int); z = __builtin_unknown(int, 2);"
+    =>
+    "[(x = (offsetof\u{b0}((struct S \u{2205} ), x))), (y = (va_arg\u{b0}(ap, (int \u{2205} )))), (z = (__builtin_unknown\u{b0}((int \u{2205} ), 2))), \u{2205} ..]"
+
+builtin_type_names:
+    "x = va_arg(ap, int(*)(void)); y = __builtin_types_compatible_p(int[3], const char *[]);"
+    =>
+    "[(x = (va_arg\u{b0}(ap, ((int \u{2205} ) (*)((void \u{2205} )))))), (y = (__builtin_types_compatible_p\u{b0}(((int \u{2205} )[3]), ((const char * \u{2205} )[\u{2205} ])))), \u{2205} ..]"
+
+enum_implicit_values:
+    "enum E { A, B, C }; enum { D, E = 5, F } x;"
+    =>
+    "[((enum E {A = 0, B = 1, C = 2}) \u{2205} ), ((enum {D = 0, E = 5, F = 6}) x), \u{2205} ..]"
+
+enum_declaration_attributes:
+    "static enum { A } x; const enum E { B } y = B;"
+    =>
+    "[(static (enum {A = 0}) x), ((const (enum E {B = 0}) y) = B), \u{2205} ..]"
+
+enum_previous_enumerators:
+    "enum { A = 1, B = A + 2, C, D = B * C };"
+    =>
+    "[((enum {A = 1, B = 3, C = 4, D = 12}) \u{2205} ), \u{2205} ..]"
+
+enum_duplicate_values:
+    "enum { A = 1, B, C = 1, };"
+    =>
+    "[((enum {A = 1, B = 2, C = 1}) \u{2205} ), \u{2205} ..]"
+
+nested_ternary_failure:
+    "x = a ? b : c ? d : e;"
+    =>
+    "[(x = (a ? b : (c ? d : e))), \u{2205} ..]"
+
+nested_ternary_success:
+    "x = a ? b ? c : d : e; y = a ? b ? c : d : e ? f : g;"
+    =>
+    "[(x = (a ? (b ? c : d) : e)), (y = (a ? (b ? c : d) : (e ? f : g))), \u{2205} ..]"
+
+
+ternary_initialiser:
+    "int x = a?b:c; int y = a ? f(1) : g(2), z = d ? e : f;"
+    =>
+    "[((int x) = (a ? b : c)), (((int y) = (a ? (f\u{b0}(1)) : (g\u{b0}(2)))) , ((int z) = (d ? e : f))), \u{2205} ..]"
+
+ternary_statement:
+    "a ? b() : c();"
+    =>
+    "[(a ? (b\u{b0}()) : (c\u{b0}())), \u{2205} ..]"
+
+switch_case_ternary:
+    "switch (n) { case 1: x?y:z; break; case a ? 2 : 3: f(); default: u ? v : w; }"
+    =>
+    "[[(switch \u{2205}  \u{2205} )], (n), [(case 1: (x ? y : z)), \u{2205} , [(break)], (case (a ? 2 : 3): (f\u{b0}())), (default: (u ? v : w)), \u{2205} ]..]"
+
+
+conditional_if:
+    "
+#if 1+1==2
+int x;
+#else
+int y;
+#endif
+    "
+    =>
+    "[(int x), \u{2205} ..]"
+
+conditional_defined:
+    "
+#define FOO
+#if defined(FOO) && !defined BAR
+int x;
+#endif
+#ifndef FOO
+int y;
+#endif
+    "
+    =>
+    "[(int x), \u{2205} ..]"
+
+conditional_nested_elif:
+    "
+#define VERSION 3
+#if VERSION == 1
+int a;
+#elif VERSION == 2
+int b;
+#elif VERSION >= 3
+#   if VERSION > 3
+int c;
+#   elif defined(VERSION)
+int d;
+#   endif
+#else
+int e;
+#endif
+    "
+    =>
+    "[(int d), \u{2205} ..]"
+
+conditional_unsigned:
+    "
+#if 0u - 1 > 0 && -1 < 0 && (1 ? -1 : 0u) > 0
+int x;
+#endif
+#if 1 << 62 > 0 && 18446744073709551615u / 2 == 9223372036854775807
+int y;
+#endif
+    "
+    =>
+    "[(int x), (int y), \u{2205} ..]"
+
+conditional_variadic:
+    "
+#define FIRST(x, ...) x
+#if FIRST(1)
+int a;
+#endif
+#if FIRST(0, 1, 2)
+int b;
+#endif
+#if FIRST() + 1
+int c;
+#endif
+    "
+    =>
+    "[(int a), (int c), \u{2205} ..]"
+
+conditional_comma_elision:
+    "
+#define THIRD(a, b, c, ...) c
+#define HAS_ARGS(...) THIRD(0, ## __VA_ARGS__, 1, 0)
+#if HAS_ARGS()
+int a;
+#endif
+#if HAS_ARGS(5)
+int b;
+#endif
+    "
+    =>
+    "[(int b), \u{2205} ..]"
+
+array_declarators:
+    "int a[10]; void f(int a[static 1]); void g(int n, int a[*], int b[const n]);"
+    =>
+    "[((int a)[10]), ((void f)\u{b0}(((int a)[static 1]))), ((void g)\u{b0}((int n), ((int a)[*]), ((int b)[const VLA n]))), \u{2205} ..]"
+
+nested_brackets:
+    "{ (a[1]) }"
+    =>
+    "[[((a[1]))]..]"
+
+pointer_qualifiers:
+    "int *restrict p; void (*const fp)(void); int (*fps[3])(int a);"
+    =>
+    "[(int * restrict p), (void \u{b0}((void \u{2205} )) * const fp), ((int \u{b0}((int a)) * fps)[3]), \u{2205} ..]"
+
+standard_attributes:
+    "[[nodiscard, gnu::packed]] int f; [[deprecated(\"old\")]] static int x;"
+    =>
+    "[([[nodiscard]] [[gnu::packed]] int f), ([[deprecated(\"old\")]] static int x), \u{2205} ..]"
+
+attribute_opaque_arguments:
+    "[[deprecated(\"x\")]] int f(void); [[gnu::format(printf, 1, 2), a(b[c], {d})]] int g;"
+    =>
+    "[(([[deprecated(\"x\")]] int f)\u{b0}((void \u{2205} ))), ([[gnu::format(Ident(printf), Comma, 1, Comma, 2)]] [[a(Ident(b), BracketOpen, Ident(c), BracketClose, Comma, BraceOpen, Ident(d), BraceClose)]] int g), \u{2205} ..]"
+
+struct_multiple_declarators:
+    "struct { int a, *b, c[3]; }; static int *p = 0, q;"
+    =>
+    "[[(struct \u{2205}  \u{2205} )], [(((int a) , (int * b)) , ((int c)[3])), \u{2205} ], (((static int * p) = 0) , (static int q)), \u{2205} ..]"
+
+chained_assignments:
+    "a = b = c; a += b -= c;"
+    =>
+    "[(a = (b = c)), (a += (b -= c)), \u{2205} ..]"
+
+for_comma_init:
+    "for(i=0,j=0;;) {}"
+    =>
+    "[[(for \u{2205}  \u{2205} )], ([((i = 0) , (j = 0)), \u{2205} , \u{2205} ..]), []..]"
+
+for_empty_condition:
+    "for(;;) break;"
+    =>
+    "[[(for \u{2205}  \u{2205} )], ([\u{2205} , \u{2205} , \u{2205} ..]), [(break)], \u{2205} ..]"
+
+for_zero_condition:
+    "for(;0;) break;"
+    =>
+    "[[(for \u{2205}  \u{2205} )], ([\u{2205} , 0, \u{2205} ..]), [(break)], \u{2205} ..]"
+
+for_comma_increment:
+    "for(;;i++,j--) {}"
+    =>
+    "[[(for \u{2205}  \u{2205} )], ([\u{2205} , \u{2205} , ((i++) , (j--))..]), []..]"
+
+compound_assign_lvalues:
+    "a[i] += 1; s.x *= 2; p->x -= 3; (*p) |= 4;"
+    =>
+    "[((a[i]) += 1), ((s . x) *= 2), ((p -> x) -= 3), (((*p)) |= 4), \u{2205} ..]"
+
+pointer_to_array_declarator:
+    "int (*a)[10]; int *b[10]; int (*c[4])[2][3];"
+    =>
+    "[(int [10] * a), ((int * b)[10]), ((int [3] [2] * c)[4]), \u{2205} ..]"
+
+thread_local_file_scope:
+    "thread_local int x; _Thread_local static int *p; extern thread_local int y;"
+    =>
+    "[(thread_local int x), (thread_local static int * p), (extern thread_local int y), \u{2205} ..]"
+
+compound_literal_array:
+    "p = (int[]){1, 2, 3}; n = sizeof (int[2]){0};"
+    =>
+    "[(p = ((((int \u{2205} )[\u{2205} ])){1, 2, 3})), (n = (sizeof ((((int \u{2205} )[2])){0}))), \u{2205} ..]"
+
+compound_literal_struct:
+    "p = (struct P){.x = 1}; f((struct P){.x = 1, .y = 2});"
+    =>
+    "[(p = (((struct P \u{2205} )){(.x = 1)})), (f\u{b0}((((struct P \u{2205} )){(.x = 1), (.y = 2)}))), \u{2205} ..]"
+typeof_declaration:
+    "typeof(1+1) x = 2; int a; typeof(a) b; typeof_unqual(const int) *c;"
+    =>
+    "[((typeof((1 + 1)) x) = 2), (int a), (typeof(a) b), (typeof_unqual((const int \u{2205} )) * c), \u{2205} ..]"
+
+postfix_chain:
+    "a.b[c](d)->e; (*fp)(x); f(x)(y)[0];"
+    =>
+    "[((((a . b)[c])\u{b0}(d)) -> e), (((*fp))\u{b0}(x)), (((f\u{b0}(x))\u{b0}(y))[0]), \u{2205} ..]"
 
 );
 
@@ -210,6 +522,30 @@ lengths_literal:
                 ^~~~
 "
 
+lengths_number:
+"x = 'c' 12345;"
+=>
+":1:9: parser error: Found 2 consecutive literals: block [(x = 'c')..] followed by 12345.
+    1 | x = 'c' 12345;
+                ^~~~~
+"
+
+lengths_overflow:
+"x = 99999999999999999999;"
+=>
+":1:5: lexer error: Overflow: 99999999999999999999 doesn't fit in `int`, nor in the bigger types up to `long long`.
+    1 | x = 99999999999999999999;
+            ^~~~~~~~~~~~~~~~~~~~
+"
+
+lengths_overflow_suffix:
+"x = 99999999999999999999ull;"
+=>
+":1:5: lexer error: Overflow: 99999999999999999999ull doesn't fit in `unsigned long long`.
+    1 | x = 99999999999999999999ull;
+            ^~~~~~~~~~~~~~~~~~~~~~~
+"
+
 lengths_symbols:
     "<<="
     =>
@@ -221,41 +557,871 @@ lengths_symbols:
 digraphs:
     "%:include <stdio.h>"
     =>
-":1:1: lexer error: Found invalid character '#', found by replacing digraph '%:'.
+":1:1: lexer error: Found unsupported preprocessor directive '#include'. Please run the preprocessor first (e.g. `cpp` or `gcc -E`).
     1 | %:include <stdio.h>
-        ^~
+        ^
 "
 
-trigraphs:
-    "
-char b??(5??) = ??< 'b', 'l', 'o',??/
-                    'b', '\0' ??>;
-int x = 1 ??' ??- 2 ??! 3;
-" =>
-":2:7: lexer warning: Trigraphs are deprecated in C23. Please remove them: replace '??(' by '['.
-    2 | char b??(5??) = ??< 'b', 'l', 'o',??/
-              ^~~
-:2:11: lexer warning: Trigraphs are deprecated in C23. Please remove them: replace '??)' by ']'.
-    2 | char b??(5??) = ??< 'b', 'l', 'o',??/
-                  ^~~
-:2:17: lexer warning: Trigraphs are deprecated in C23. Please remove them: replace '??<' by '{'.
-    2 | char b??(5??) = ??< 'b', 'l', 'o',??/
-                        ^~~
-:2:35: lexer warning: Trigraphs are deprecated in C23. Please remove them: replace '??/' by '\\'.
-    2 | char b??(5??) = ??< 'b', 'l', 'o',??/
-                                          ^~~
-:3:30: lexer warning: Trigraphs are deprecated in C23. Please remove them: replace '??>' by '}'.
-    3 |                     'b', '\0' ??>;
-                                     ^~~
-:4:11: lexer warning: Trigraphs are deprecated in C23. Please remove them: replace '??'' by '^'.
-    4 | int x = 1 ??' ??- 2 ??! 3;
-                  ^~~
-:4:15: lexer warning: Trigraphs are deprecated in C23. Please remove them: replace '??-' by '~'.
-    4 | int x = 1 ??' ??- 2 ??! 3;
-                      ^~~
-:4:21: lexer warning: Trigraphs are deprecated in C23. Please remove them: replace '??!' by '|'.
-    4 | int x = 1 ??' ??- 2 ??! 3;
-                            ^~~
+octal_escape_overflow:
+    "b = \"\\400\";"
+    =>
+":1:9: lexer warning[E0011]: Octal escape sequence out of range: \\400 is 256, but a char can't exceed 255. It was interpreted as \\40 followed by the character '0'.
+    1 | b = \"\\400\";
+                ^
+"
+
+preprocessor_directive:
+    "#include <stdio.h>\nint x;"
+    =>
+":1:1: lexer error: Found unsupported preprocessor directive '#include'. Please run the preprocessor first (e.g. `cpp` or `gcc -E`).
+    1 | #include <stdio.h>
+        ^
+"
+
+conditional_unterminated:
+    "#if 1\nint x;\n#elif 0\n"
+    =>
+":1:1: lexer error: Found conditional directive without a matching '#endif'.
+    1 | #if 1
+        ^
+"
+
+conditional_else_elif:
+    "#ifdef X\n#else\n#elif 1\n#endif\n"
+    =>
+":3:1: lexer error: Found '#elif' after '#else'.
+    3 | #elif 1
+        ^
+"
+
+conditional_not_constant:
+    "#if 1.5\n#endif\n"
+    =>
+":1:1: lexer error: Expected an integer constant expression after '#if'.
+    1 | #if 1.5
+        ^
+"
+
+conditional_division_by_zero:
+    "#if 1/0\n#endif\n#if 0 && 1 % 0\n#endif\n"
+    =>
+":1:1: lexer error: Found a division by zero in the condition of '#if'.
+    1 | #if 1/0
+        ^
+"
+
+conditional_overflow:
+    "#if 9223372036854775807 + 1\n#elif 1 << 63\n#elif 9223372036854775807u + 1\n#endif\n"
+    =>
+":1:1: lexer error: Found an integer overflow in the condition of '#if': the result doesn't fit in 'intmax_t'.
+    1 | #if 9223372036854775807 + 1
+        ^
+:2:1: lexer error: Found an integer overflow in the condition of '#elif': the result doesn't fit in 'intmax_t'.
+    2 | #elif 1 << 63
+        ^
+"
+
+backslash_eof:
+    "int x = 1;\\"
+    =>
+":1:11: lexer error[E0002]: Found '\\' at end of file: backslash-newline at end of file is illegal, as there is no next line to continue on.
+    1 | int x = 1;\\
+                  ^
+"
+
+empty_file:
+    ""
+    =>
+    ""
+
+whitespace_file:
+    "   \n\t\n  "
+    =>
+    ""
+
+comment_file:
+    "/* comment */\n// line comment\n"
+    =>
+    ""
+
+lengths_merged_strings:
+    "x = 'c' \"a\\0b\" \"c\";"
+    =>
+":1:10: parser error: Found 2 consecutive literals: block [(x = 'c')..] followed by \"a\0bc\".
+    1 | x = 'c' \"a\\0b\" \"c\";
+                 ^~~~~~~~
+"
+
+invalid_suffix:
+    "x = 123abc;"
+    =>
+":1:5: lexer error: Invalid suffix \"abc\" on integer constant.
+    1 | x = 123abc;
+            ^~~~~~
+"
+
+invalid_suffix_identifier:
+    "int 3abc = 1;"
+    =>
+":1:5: lexer error: Invalid suffix \"abc\" on integer constant.
+    1 | int 3abc = 1;
+            ^~~~
+"
+
+enum_empty:
+    "enum E {};"
+    =>
+":1:1: parser error: Found an empty enum, but an enum must contain at least one enumerator.
+    1 | enum E {};
+        ^~~~
+"
+
+enum_duplicate_name:
+    "enum { A = 1, B, A = 1 };"
+    =>
+":1:18: parser error: Found duplicate enumerator 'A' in enum.
+    1 | enum { A = 1, B, A = 1 };
+                         ^
+"
+
+builtin_empty_argument:
+    "__builtin_expect(a,);"
+    =>
+":1:17: parser error: Expected an expression, but found nothing.
+    1 | __builtin_expect(a,);
+                        ^
+"
+
+builtin_type_argument:
+    "va_arg(ap, 3)"
+    =>
+":1:12: parser error: Expected a type name, but found an expression.
+    1 | va_arg(ap, 3)
+                   ^
+"
+
+array_static_outside_parameters:
+    "int a[static 10];"
+    =>
+":1:7: parser error: Found 'static' in array declarator, but it is only allowed in function parameters.
+    1 | int a[static 10];
+              ^~~~~~
+"
+
+universal_char_space:
+    "int a\\u0020b;"
+    =>
+":1:6: lexer error[E0017]: Found a universal character name for U+0020, but this character isn't allowed in an identifier.
+    1 | int a\\u0020b;
+             ^~~~~~
+"
+
+universal_char_basic:
+    "int \\u0041;"
+    =>
+":1:5: lexer error[E0017]: Found a universal character name for 'A', but the characters of the basic character set can't be written with universal character names. Consider writing 'A' instead.
+    1 | int \\u0041;
+            ^~~~~~
+"
+
+universal_char_outside_identifier:
+    "x = a \\ b;"
+    =>
+":1:7: lexer error[E0004]: Escape characters are only authorised in strings or chars, not in 'no context' context.
+    1 | x = a \\ b;
+              ^
+"
+
+universal_char_digit_start:
+    "int \\u0030;"
+    =>
+":1:5: lexer error[E0017]: Found a universal character name for '0', but a digit can't start an identifier.
+    1 | int \\u0030;
+            ^~~~~~
+"
+
+universal_char_end_of_line:
+    "int \\u00"
+    =>
+":1:5: lexer error[E0005]: Invalid escaped short unicode number: must contain 4 digits, but found only 2
+    1 | int \\u00
+            ^~~~
+"
+
+universal_char_next_line:
+    "int a\\u\n;"
+    =>
+":1:6: lexer error[E0005]: Invalid escaped short unicode number: must contain 4 digits, but found only 0
+    1 | int a\\u
+             ^~
+"
+
+mismatched_brace_bracket:
+    "{ a ]"
+    =>
+":1:5: parser error: Mismatched bracket: expected '}' to match '{' at 1:1, found ']'.
+    1 | { a ]
+            ^
+:1:1: parser note: '{' opened here.
+    1 | { a ]
+        ^
+"
+
+mismatched_parenthesis_brace:
+    "( a }"
+    =>
+":1:5: parser error: Mismatched bracket: expected ')' to match '(' at 1:1, found '}'.
+    1 | ( a }
+            ^
+:1:1: parser note: '(' opened here.
+    1 | ( a }
+        ^
+"
+
+restrict_function_pointer:
+    "void restrict (*fp)(void);"
+    =>
+":1:6: parser error: Found 'restrict' applied to a type that isn't a pointer, but 'restrict' can only qualify pointers, like in 'int *restrict p'.
+    1 | void restrict (*fp)(void);
+             ^~~~~~~~
+"
+
+double_dot:
+    "x = a..b;"
+    =>
+":1:6: lexer error: Found unexpected '..', which isn't a valid symbol. Did you mean '...'?
+    1 | x = a..b;
+             ^~
 "
 
+attribute_unbalanced_arguments:
+    "[[a(b[c)]]] int f;"
+    =>
+":1:8: parser error: Mismatched '[' in the arguments of an attribute: expected ']', but found ParenthesisClose.
+    1 | [[a(b[c)]]] int f;
+               ^
+"
+
+namespace_outside_attribute:
+    "a::b;"
+    =>
+":1:2: parser error: Found '::' outside of an attribute, but C doesn't have namespaces: '::' can only be used in attributes, like in '[[gnu::packed]]'.
+    1 | a::b;
+         ^~
+"
+
+compound_assign_not_lvalue:
+    "(a+b) += 1;"
+    =>
+":1:7: parser error: LHS: expected a modifiable lvalue, found binary operator '+'. Only variables, array subscripts, member accesses and dereferences can be assigned.
+    1 | (a+b) += 1;
+              ^~
+"
+
+macro_too_few_arguments:
+    "#define LOG(fmt, level, ...) fmt\n#if LOG(1)\n#endif"
+    =>
+":2:5: lexer error: Macro 'LOG' expects at least 2 arguments, but found 1.
+    2 | #if LOG(1)
+            ^~~
+:2:1: lexer error: Expected a condition after '#if', but found nothing.
+    2 | #if LOG(1)
+        ^
+"
+
+macro_used_in_code:
+    "#define X 1\n#if X\nint y = X;\n#endif"
+    =>
+":3:9: lexer warning: Macros are not expanded in the code: 'X' can only be used in conditional directives.
+    3 | int y = X;
+                ^
+"
+
+token_pasting_outside_macro:
+    "int a ## b;"
+    =>
+":1:7: parser error: Found '##' outside of a macro definition: the token pasting operator can only be used in the value of a '#define'.
+    1 | int a ## b;
+              ^~
+"
+
+stringizing_outside_macro:
+    "int a # b;"
+    =>
+":1:7: parser error: Found '#' outside of a macro definition: the stringizing operator can only be used in the value of a '#define'.
+    1 | int a # b;
+              ^
+"
+
+brace_elision_too_many:
+    "int a[2][2] = {1, 2, 3, 4, 5};"
+    =>
+":1:29: parser error: Found too many elements in the initialiser of the array: expected at most 2.
+    1 | int a[2][2] = {1, 2, 3, 4, 5};
+                                    ^
+"
+
+case_without_value:
+    "switch (n) { case : x; }"
+    =>
+":1:19: parser error: Expected a value after 'case', but found ':'.
+    1 | switch (n) { case : x; }
+                          ^
+"
+
+chained_assignment_rvalue:
+    "a = (b + c) = d;"
+    =>
+":1:13: parser error: LHS: expected a modifiable lvalue, found binary operator '+'. Only variables, array subscripts, member accesses and dereferences can be assigned.
+    1 | a = (b + c) = d;
+                    ^
+"
+
+comparison_chain:
+    "x = a < b < c;"
+    =>
+":1:11: parser suggestion: Found chained comparisons: 'a < b < c' means '(a < b) < c', that compares the result of 'a < b', 0 or 1, with 'c'. Did you mean 'a < b && b < c'? Otherwise, add parenthesis to make it explicit.
+    1 | x = a < b < c;
+                  ^
+"
+
+comparison_chain_explicit:
+    "x = (a < b) < c;"
+    =>
+""
+
+comma_discarded_value:
+    "a, b;"
+    =>
+":1:1: parser suggestion: The left operand of this comma operator has no side effect, so its value is discarded. Perhaps this is unintended?
+    1 | a, b;
+        ^
+"
+
+comma_side_effect:
+    "f(), b;"
+    =>
+""
+
+do_while_zero:
+    "do { x; } while(0);"
+    =>
+""
+
+while_zero_dead_body:
+    "while(0){ x; }"
+    =>
+":1:11: parser suggestion: The condition of this 'while' loop is always false, so its body is never executed. Perhaps this is unintended? To execute a block once, like in a macro, use 'do { ... } while (0);' instead.
+    1 | while(0){ x; }
+                  ^
+"
+
+while_zero_empty_body:
+    "while(0);"
+    =>
+""
+
+ternary_incompatible_types:
+    "int cond; int x = cond ? 1 : \"s\";"
+    =>
+":1:19: parser error: Found branches of incompatible types 'int' and 'char *' in a conditional operator, but they must have a common type.
+    1 | int cond; int x = cond ? 1 : \"s\";
+                          ^~~~
+"
+
+ternary_incompatible_types_constant_first:
+    "int c; int x = 1 + c ? 1 : \"s\";"
+    =>
+":1:20: parser error: Found branches of incompatible types 'int' and 'char *' in a conditional operator, but they must have a common type.
+    1 | int c; int x = 1 + c ? 1 : \"s\";
+                           ^
+"
+
+typeof_expression_type:
+    "int c, *p; typeof(1+1) x = 2; int *y = c ? x : p;"
+    =>
+":1:40: parser error: Found branches of incompatible types 'int' and 'int *' in a conditional operator, but they must have a common type.
+    1 | int c, *p; typeof(1+1) x = 2; int *y = c ? x : p;
+                                               ^
+"
+
+typeof_variable_type:
+    "int c; char *a; typeof(a) b; double d = c ? b : 1.0;"
+    =>
+":1:41: parser error: Found branches of incompatible types 'char *' and 'double' in a conditional operator, but they must have a common type.
+    1 | int c; char *a; typeof(a) b; double d = c ? b : 1.0;
+                                                ^
+"
+
+thread_local_function:
+    "thread_local int f();"
+    =>
+":1:18: parser error: Found 'thread_local' on the function 'f', but only variables can be thread-local.
+    1 | thread_local int f();
+                         ^
+"
+
+thread_local_block_scope:
+    "int main() { static thread_local int x; thread_local int y = x; return y; }"
+    =>
+":1:58: parser error: Found 'thread_local' on the local variable 'y', but it must also be 'static' or 'extern'.
+    1 | int main() { static thread_local int x; thread_local int y = x; return y; }
+                                                                 ^
+"
+
+compound_literal_expression:
+    "x = (a + 1){1};"
+    =>
+":1:5: parser error: Found a list initialiser after an expression in parenthesis, but only a type name can be followed by a list initialiser, like in '(int[]){1, 2, 3}'.
+    1 | x = (a + 1){1};
+            ^
+"
+
+digit_separator_doubled:
+    "x = 1''0;"
+    =>
+":1:5: lexer error: Found a doubled digit separator in the number constant '1''0', but digit separators must be between two digits.
+    1 | x = 1''0;
+            ^~~~
+"
+
+digit_separator_trailing:
+    "x = 10';"
+    =>
+":1:5: lexer error: Found a trailing digit separator in the number constant '10'', but digit separators must be between two digits.
+    1 | x = 10';
+            ^~~
+"
+
+digit_separator_prefix:
+    "x = 0x'FF;"
+    =>
+":1:5: lexer error: Found a digit separator right after the '0x' prefix in the number constant '0x'FF', but digit separators must be between two digits.
+    1 | x = 0x'FF;
+            ^~~~~
+"
+
+digit_separator_suffix:
+    "x = 1'u;"
+    =>
+":1:5: lexer error: Found a digit separator before 'u' in the number constant '1'u', but digit separators must be between two digits.
+    1 | x = 1'u;
+            ^~~
+"
+
+digit_separator_full_stop:
+    "x = 1.'5;"
+    =>
+":1:5: lexer error: Found a digit separator after '.' in the number constant '1.'5', but digit separators must be between two digits.
+    1 | x = 1.'5;
+            ^~~~
+"
+
+float_infinity_suffix:
+    "x = 1.0inf;"
+    =>
+":1:5: lexer error: Invalid suffix \"inf\" on floating constant.
+    1 | x = 1.0inf;
+            ^~~~~~
+"
+
+hex_float_no_exponent:
+    "x = 0x1.8;"
+    =>
+":1:5: lexer error: Invalid number constant type: a hexadecimal float must have a 'p' exponent, like in '0x1.8p0'.
+    1 | x = 0x1.8;
+            ^~~~~
+"
+
+hex_float_underflow:
+    "x = 0x1p-1075;"
+    =>
+":1:5: lexer warning: Underflow: 0x1p-1075 is too small for `double`, and was rounded to 0.
+    1 | x = 0x1p-1075;
+            ^~~~~~~~~
+"
+
+long_double_overflow:
+    "x = 1e5000L;"
+    =>
+":1:5: lexer warning: Overflow: 1e5000L doesn't fit in `long double`.
+    1 | x = 1e5000L;
+            ^~~~~~~
+"
+
+long_double_underflow:
+    "x = 1e-5000L;"
+    =>
+":1:5: lexer warning: Underflow: 1e-5000L is too small for `long double`, and was rounded to 0.
+    1 | x = 1e-5000L;
+            ^~~~~~~~
+"
+
+encoding_prefix_mismatch:
+    "x = u8\"a\" L\"b\";"
+    =>
+":1:13: lexer error: Found adjacent string literals with different prefixes 'u8' and 'L': they can't be merged. Consider using the same prefix.
+    1 | x = u8\"a\" L\"b\";
+                    ^
+"
+
+encoding_prefix_char_too_big:
+    "x = u8'\\u00e9';"
+    =>
+":1:8: lexer error: Found '\u{e9}' in a char with the prefix 'u8', but it doesn't fit in a single code unit. Consider using a string literal instead.
+    1 | x = u8'\\u00e9';
+               ^~~~~~
+"
+
+binary_no_digits:
+    "x = 0b;"
+    =>
+":1:5: lexer error: Invalid number constant type: no digits found after 0b prefix. Please add at least one '0' or '1'.
+    1 | x = 0b;
+            ^~
+"
+
+binary_invalid_digit:
+    "x = 0b102;"
+    =>
+":1:5: lexer error: Invalid number constant type: found invalid character '2' in binary base.
+    1 | x = 0b102;
+            ^~~~~
+"
+
+typedef_redefinition:
+    "typedef int T; typedef long T;"
+    =>
+":1:29: parser error: Found a redefinition of the typedef 'T' as 'long', but it was already defined as 'int'.
+    1 | typedef int T; typedef long T;
+                                    ^
+:1:13: parser note: 'T' was first defined here.
+    1 | typedef int T; typedef long T;
+                    ^
+"
+
+typedef_identical_redefinition:
+    "typedef int T; typedef int T;"
+    =>
+""
+
 );
+
+#[test]
+fn modernise_deprecated_keywords() {
+    let content = "_Bool b; _Static_assert(1, \"\"); _Noreturn void f();";
+    let files = &[(String::new(), content)];
+    let mut tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    modernise_keywords(&mut tokens);
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed
+            == "[Keyword(bool), Ident(b), SemiColon, Keyword(static_assert), ParenthesisOpen, 1, Comma, \"\", ParenthesisClose, SemiColon, Keyword(_Noreturn), Keyword(void), Ident(f), ParenthesisOpen, ParenthesisClose, SemiColon]",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn identifier_after_string() {
+    let content = "x = L\"a\" u; y = u\"b\"; z = u \"c\";";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed
+            == "[Ident(x), Assign, L\"a\", Ident(u), SemiColon, Ident(y), Assign, u\"b\", SemiColon, Ident(z), Assign, Ident(u), \"c\", SemiColon]",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn encoding_prefixes() {
+    let content = "L\"a\" \"b\" ; U\"c\" U\"d\" ; \"e\" L\"f\" ; L'g' u'h' U'i' u8'j' Lk'l'";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed
+            == "[L\"ab\", SemiColon, U\"cd\", SemiColon, L\"ef\", SemiColon, L'g', u'h', U'i', u8'j', Ident(Lk), 'l']",
+        "Mismatch! Computed\n{displayed}"
+    );
+    let source = tokens
+        .iter()
+        .map(|token| token.get_value().to_source())
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert!(
+        source == "L\"ab\" ; U\"cd\" ; L\"ef\" ; L'g' u'h' U'i' u8'j' Lk 'l'",
+        "Mismatch! Computed\n{source}"
+    );
+}
+
+#[test]
+fn clang_dump() {
+    let content = "x = f(a, 2) * -b[1];";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let node = parse_tokens(tokens).unwrap_or_display(files, "parser");
+    let dumped = node.dump();
+    let expected = "\
+CompoundStmt
+`-BinaryOperator '='
+  |-DeclRefExpr 'x'
+  `-BinaryOperator '*'
+    |-CallExpr
+    | |-DeclRefExpr 'f'
+    | |-DeclRefExpr 'a'
+    | `-IntegerLiteral 'int' 2
+    `-UnaryOperator prefix '-'
+      `-ArraySubscriptExpr
+        |-DeclRefExpr 'b'
+        `-IntegerLiteral 'int' 1
+";
+    assert!(
+        dumped == expected,
+        "Mismatch! Expected:\n{expected}\n!= Computed\n{dumped}"
+    );
+}
+
+#[test]
+fn clone_tokens_and_ast() {
+    let content = "int f(int x) { return x ? (int)x[1] : -1; }";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let cloned = tokens.clone();
+    assert!(
+        format!("{tokens:?}") == format!("{cloned:?}"),
+        "Mismatch! Expected:\n{tokens:?}\n!= Computed\n{cloned:?}"
+    );
+    let node = parse_tokens(tokens).unwrap_or_display(files, "parser");
+    let cloned_node = parse_tokens(cloned).unwrap_or_display(files, "parser");
+    assert!(
+        node == cloned_node.clone(),
+        "Mismatch! Expected:\n{node}\n!= Computed\n{cloned_node}"
+    );
+}
+
+#[test]
+fn ellipsis() {
+    let content = "a.b; f(a, ...); a....b";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed
+            == "[Ident(a), Dot, Ident(b), SemiColon, Ident(f), ParenthesisOpen, Ident(a), Comma, Ellipsis, ParenthesisClose, SemiColon, Ident(a), Ellipsis, Dot, Ident(b)]",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn tokens_to_source() {
+    let content = "const foo = \"a\\\"b\" + 'c' <<= 1.5f;";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let displayed = tokens[1].to_string();
+    assert!(displayed == "Ident(foo)", "Mismatch! Computed\n{displayed}");
+    let plain = tokens[1].get_value().to_source();
+    assert!(plain == "foo", "Mismatch! Computed\n{plain}");
+    let source = tokens
+        .iter()
+        .map(|token| token.get_value().to_source())
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert!(
+        source == "const foo = \"a\\\"b\" + 'c' <<= 1.5f ;",
+        "Mismatch! Computed\n{source}"
+    );
+}
+
+#[test]
+fn digraph_symbols() {
+    let content = "<% :> <: %> %: %:define X 1";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed
+            == "[BraceOpen, BracketClose, BracketOpen, BraceClose, Hash, Hash, Ident(define), Ident(X), 1]",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn digraph_braces() {
+    let lex = |content: &str| {
+        let tokens = lex_file(content, &mut Location::from(String::new()))
+            .unwrap_or_display(&[(String::new(), content)], "lexer");
+        display_tokens(&tokens)
+    };
+    let digraphs = lex("<% int x; %>");
+    let braces = lex("{ int x; }");
+    assert!(
+        digraphs == braces,
+        "Mismatch! Computed\n{digraphs}\ninstead of\n{braces}"
+    );
+    let digraphs = lex("x<::><%%>%:");
+    assert!(
+        digraphs == "[Ident(x), BracketOpen, BracketClose, BraceOpen, BraceClose, Hash]",
+        "Mismatch! Computed\n{digraphs}"
+    );
+}
+
+#[test]
+fn identifiers() {
+    let content = "_a1 = a_1 + __1;";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed == "[Ident(_a1), Assign, Ident(a_1), Plus, Ident(__1), SemiColon]",
+        "Mismatch! Computed\n{displayed}"
+    );
+}
+
+#[test]
+fn universal_char_names() {
+    let content = "int \\u00C0 = 1; \\U000000C0x = a\\u00C1;";
+    let files = &[(String::new(), content)];
+    let options = Options::default().with_lexemes(true);
+    let tokens = lex_file_with_options(content, &mut Location::from(String::new()), options)
+        .unwrap_or_display(files, "lexer");
+    let displayed = display_tokens(&tokens);
+    assert!(
+        displayed
+            == "[Keyword(int), Ident(\u{c0}), Assign, 1, SemiColon, Ident(\u{c0}x), Assign, Ident(a\u{c1}), SemiColon]",
+        "Mismatch! Computed\n{displayed}"
+    );
+    let lexemes = tokens
+        .iter()
+        .filter_map(Token::get_lexeme)
+        .collect::<Vec<_>>();
+    assert!(
+        lexemes[1] == "\\u00C0" && lexemes[5] == "\\U000000C0x" && lexemes[7] == "a\\u00C1",
+        "{lexemes:?}"
+    );
+    let node = parse_tokens(tokens).unwrap_or_display(files, "parser");
+    assert!(
+        node.to_string() == "[((int \u{c0}) = 1), (\u{c0}x = a\u{c1}), \u{2205} ..]",
+        "{node}"
+    );
+}
+
+#[test]
+fn lower_arithmetic() {
+    let content = "x = a + b * c; y -= -(x % 2);";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let node = parse_tokens(tokens).unwrap_or_display(files, "parser");
+    let instructions = node.lower().unwrap();
+    let lowered = instructions
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ");
+    assert!(
+        lowered == "t0 = b * c; t1 = a + t0; x = t1; t2 = x % 2; t3 = -t2; y = y - t3",
+        "Mismatch! Computed\n{lowered}"
+    );
+    assert!(matches!(
+        instructions[0],
+        Instruction::Binary {
+            op: BinaryOperator::Multiply,
+            ..
+        }
+    ));
+    assert!(matches!(
+        instructions[4],
+        Instruction::Unary {
+            op: UnaryOperator::Minus,
+            ..
+        }
+    ));
+    for (content, expected) in [
+        (
+            "f(x);",
+            "a.c:1:1: error: Found (f\u{b0}(x)), but only the arithmetic expressions and the assignments can be lowered for now.",
+        ),
+        (
+            "y = 1;\nx = 2 && 3;",
+            "a.c:2:1: error: Found (2 && 3), but only the arithmetic expressions and the assignments can be lowered for now.",
+        ),
+        (
+            "y = 1;\n{ 2 && 3; }",
+            "a.c:1:1: error: Found (2 && 3), but only the arithmetic expressions and the assignments can be lowered for now.",
+        ),
+    ] {
+        let files = &[("a.c".to_owned(), content)];
+        let tokens =
+            lex_file(content, &mut Location::from("a.c")).unwrap_or_display(files, "lexer");
+        let node = parse_tokens(tokens).unwrap_or_display(files, "parser");
+        let error = node.lower().unwrap_err().to_string();
+        assert!(error == expected, "Mismatch! Computed\n{error}");
+    }
+}
+
+#[test]
+fn ternary_common_type() {
+    let content = "int cond, *p; double d = cond ? 1 : 2.0; int *q = cond ? p : NULL;";
+    let files = &[(String::new(), content)];
+    let tokens =
+        lex_file(content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let node = parse_tokens(tokens).unwrap_or_display(files, "parser");
+    let dumped = node.dump();
+    let expected = "\
+CompoundStmt
+|-BinaryOperator ','
+| |-VarDecl cond 'int'
+| `-VarDecl p 'int *'
+|-BinaryOperator '='
+| |-VarDecl d 'double'
+| `-ConditionalOperator 'double'
+|   |-DeclRefExpr 'cond'
+|   |-IntegerLiteral 'int' 1
+|   `-FloatingLiteral 'double' 2
+`-BinaryOperator '='
+  |-VarDecl q 'int *'
+  `-ConditionalOperator 'int *'
+    |-DeclRefExpr 'cond'
+    |-DeclRefExpr 'p'
+    `-CXXNullPtrLiteralExpr
+";
+    assert!(
+        dumped == expected,
+        "Mismatch! Expected:\n{expected}\n!= Computed\n{dumped}"
+    );
+}
+
+#[test]
+fn many_adjacent_strings() {
+    let content = "x = ".to_owned() + &"\"a\" ".repeat(10_000) + ";";
+    let files = &[(String::new(), content.as_str())];
+    let start = Instant::now();
+    let tokens =
+        lex_file(&content, &mut Location::from(String::new())).unwrap_or_display(files, "lexer");
+    let elapsed = start.elapsed();
+    assert!(
+        tokens.len() == 4,
+        "Strings weren't merged: {} tokens",
+        tokens.len()
+    );
+    let merged = tokens[2].get_value().to_source();
+    assert!(
+        merged == format!("\"{}\"", "a".repeat(10_000)),
+        "Mismatch! Computed\n{merged}"
+    );
+    assert!(
+        elapsed.as_secs() < 1,
+        "Merging the strings took {elapsed:?}"
+    );
+}